@@ -285,6 +285,7 @@ fn main() {
                     .set_viewport(0, [viewport.clone()])
                     // Draw our buffer
                     .bind_pipeline_graphics(pipeline.clone())
+                    .unwrap()
                     .bind_vertex_buffers(0, buffer)
                     .draw(num_vertices, 1, 0, 0)
                     .unwrap()
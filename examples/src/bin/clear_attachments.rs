@@ -216,6 +216,7 @@ fn main() {
                 )
                 .unwrap()
                 .bind_pipeline_graphics(pipeline.clone())
+                .unwrap()
                 // Clear attachments with clear values and rects information, all the rects will be cleared by the same value
                 // Note that the ClearRect offsets and extents are not affected by the viewport,
                 // they are directly applied to the rendering image
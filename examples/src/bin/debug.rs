@@ -101,7 +101,7 @@ fn main() {
 
         println!(
             "{} {} {}: {}",
-            msg.layer_prefix.unwrap_or("unknown"),
+            msg.message_id_name.unwrap_or("unknown"),
             ty,
             severity,
             msg.description
@@ -148,12 +148,13 @@ impl DirectionalLightingSystem {
             self.gfx_queue.device().clone(),
             self.gfx_queue.family(),
             CommandBufferUsage::MultipleSubmit,
-            self.pipeline.subpass().clone(),
+            self.pipeline.subpass().unwrap().clone(),
         )
         .unwrap();
         builder
             .set_viewport(0, [viewport.clone()])
             .bind_pipeline_graphics(self.pipeline.clone())
+            .unwrap()
             .bind_descriptor_sets(
                 PipelineBindPoint::Graphics,
                 self.pipeline.layout().clone(),
@@ -161,6 +162,7 @@ impl DirectionalLightingSystem {
                 descriptor_set,
             )
             .push_constants(self.pipeline.layout().clone(), 0, push_constants)
+            .unwrap()
             .bind_vertex_buffers(0, self.vertex_buffer.clone())
             .draw(self.vertex_buffer.len() as u32, 1, 0, 0)
             .unwrap();
@@ -160,12 +160,13 @@ impl PointLightingSystem {
             self.gfx_queue.device().clone(),
             self.gfx_queue.family(),
             CommandBufferUsage::MultipleSubmit,
-            self.pipeline.subpass().clone(),
+            self.pipeline.subpass().unwrap().clone(),
         )
         .unwrap();
         builder
             .set_viewport(0, [viewport.clone()])
             .bind_pipeline_graphics(self.pipeline.clone())
+            .unwrap()
             .bind_descriptor_sets(
                 PipelineBindPoint::Graphics,
                 self.pipeline.layout().clone(),
@@ -173,6 +174,7 @@ impl PointLightingSystem {
                 descriptor_set,
             )
             .push_constants(self.pipeline.layout().clone(), 0, push_constants)
+            .unwrap()
             .bind_vertex_buffers(0, self.vertex_buffer.clone())
             .draw(self.vertex_buffer.len() as u32, 1, 0, 0)
             .unwrap();
@@ -80,7 +80,7 @@ impl TriangleDrawSystem {
             self.gfx_queue.device().clone(),
             self.gfx_queue.family(),
             CommandBufferUsage::MultipleSubmit,
-            self.pipeline.subpass().clone(),
+            self.pipeline.subpass().unwrap().clone(),
         )
         .unwrap();
         builder
@@ -93,6 +93,7 @@ impl TriangleDrawSystem {
                 }],
             )
             .bind_pipeline_graphics(self.pipeline.clone())
+            .unwrap()
             .bind_vertex_buffers(0, self.vertex_buffer.clone())
             .draw(self.vertex_buffer.len() as u32, 1, 0, 0)
             .unwrap();
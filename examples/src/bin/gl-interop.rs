@@ -281,6 +281,7 @@ fn main() {
                     .unwrap()
                     .set_viewport(0, [viewport.clone()])
                     .bind_pipeline_graphics(pipeline.clone())
+                    .unwrap()
                     .bind_descriptor_sets(
                         PipelineBindPoint::Graphics,
                         pipeline.layout().clone(),
@@ -371,7 +372,7 @@ fn vk_setup(
     let _debug_callback = DebugCallback::errors_and_warnings(&instance, |msg| {
         println!(
             "{} {:?} {:?}: {}",
-            msg.layer_prefix.unwrap_or("unknown"),
+            msg.message_id_name.unwrap_or("unknown"),
             msg.ty,
             msg.severity,
             msg.description
@@ -368,6 +368,7 @@ fn main() {
                     // contain the arguments when the draw is executed on the GPU
                     .set_viewport(0, [viewport.clone()])
                     .bind_pipeline_graphics(render_pipeline.clone())
+                    .unwrap()
                     .bind_vertex_buffers(0, vertices.clone())
                     .draw_indirect(indirect_args.clone())
                     .unwrap()
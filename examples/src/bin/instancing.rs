@@ -326,6 +326,7 @@ fn main() {
                     .unwrap()
                     .set_viewport(0, [viewport.clone()])
                     .bind_pipeline_graphics(pipeline.clone())
+                    .unwrap()
                     // We pass both our lists of vertices here.
                     .bind_vertex_buffers(
                         0,
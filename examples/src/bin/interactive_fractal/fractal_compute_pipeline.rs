@@ -127,6 +127,7 @@ impl FractalComputePipeline {
             .bind_pipeline_compute(self.pipeline.clone())
             .bind_descriptor_sets(PipelineBindPoint::Compute, pipeline_layout.clone(), 0, set)
             .push_constants(pipeline_layout.clone(), 0, push_constants)
+            .unwrap()
             .dispatch([img_dims[0] / 8, img_dims[1] / 8, 1])
             .unwrap();
         let command_buffer = builder.build().unwrap();
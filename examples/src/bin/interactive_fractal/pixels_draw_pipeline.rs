@@ -140,7 +140,7 @@ impl PixelsDrawPipeline {
             self.gfx_queue.device().clone(),
             self.gfx_queue.family(),
             CommandBufferUsage::MultipleSubmit,
-            self.pipeline.subpass().clone(),
+            self.pipeline.subpass().unwrap().clone(),
         )
         .unwrap();
         let desc_set = self.create_descriptor_set(image);
@@ -154,6 +154,7 @@ impl PixelsDrawPipeline {
                 }],
             )
             .bind_pipeline_graphics(self.pipeline.clone())
+            .unwrap()
             .bind_descriptor_sets(
                 PipelineBindPoint::Graphics,
                 self.pipeline.layout().clone(),
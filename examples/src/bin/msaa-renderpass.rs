@@ -304,6 +304,7 @@ fn main() {
         .unwrap()
         .set_viewport(0, [viewport.clone()])
         .bind_pipeline_graphics(pipeline.clone())
+        .unwrap()
         .bind_vertex_buffers(0, vertex_buffer.clone())
         .draw(vertex_buffer.len() as u32, 1, 0, 0)
         .unwrap()
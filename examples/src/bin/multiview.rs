@@ -284,6 +284,7 @@ fn main() {
         .begin_render_pass(framebuffer.clone(), SubpassContents::Inline, clear_values)
         .unwrap()
         .bind_pipeline_graphics(pipeline.clone())
+        .unwrap()
         .bind_vertex_buffers(0, vertex_buffer.clone())
         .draw(vertex_buffer.len() as u32, 1, 0, 0)
         .unwrap()
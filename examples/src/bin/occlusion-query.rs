@@ -325,6 +325,7 @@ fn main() {
                     .unwrap()
                     .set_viewport(0, [viewport.clone()])
                     .bind_pipeline_graphics(pipeline.clone())
+                    .unwrap()
                     .begin_render_pass(
                         framebuffers[image_num].clone(),
                         SubpassContents::Inline,
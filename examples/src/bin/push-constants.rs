@@ -139,6 +139,7 @@ fn main() {
             set.clone(),
         )
         .push_constants(pipeline.layout().clone(), 0, push_constants)
+        .unwrap()
         .dispatch([1024, 1, 1])
         .unwrap();
     let command_buffer = builder.build().unwrap();
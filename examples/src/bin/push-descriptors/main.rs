@@ -267,6 +267,7 @@ fn main() {
                 .unwrap()
                 .set_viewport(0, [viewport.clone()])
                 .bind_pipeline_graphics(pipeline.clone())
+                .unwrap()
                 .push_descriptor_set(
                     PipelineBindPoint::Graphics,
                     pipeline.layout().clone(),
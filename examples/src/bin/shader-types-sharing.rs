@@ -193,6 +193,7 @@ fn main() {
                 set.clone(),
             )
             .push_constants(pipeline.layout().clone(), 0, parameters)
+            .unwrap()
             .dispatch([1024, 1, 1])
             .unwrap();
         let command_buffer = builder.build().unwrap();
@@ -0,0 +1,414 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Meta, NestedMeta, Result};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    Std140,
+    Std430,
+}
+
+impl Layout {
+    fn name(self) -> &'static str {
+        match self {
+            Layout::Std140 => "std140",
+            Layout::Std430 => "std430",
+        }
+    }
+
+    fn alignment_const(self) -> TokenStream {
+        match self {
+            Layout::Std140 => quote! { STD140_ALIGNMENT },
+            Layout::Std430 => quote! { STD430_ALIGNMENT },
+        }
+    }
+
+    // The minimum alignment of a struct with this layout.
+    fn struct_alignment(self) -> usize {
+        match self {
+            Layout::Std140 => 16,
+            Layout::Std430 => 1,
+        }
+    }
+}
+
+struct Field {
+    name: String,
+    ty: syn::Type,
+    padding: bool,
+}
+
+/// Implements the `BufferContents` derive.
+///
+/// The structs that match their GLSL layout are tested in `tests/buffer_contents.rs`. The layout
+/// checks of the others fail during const evaluation:
+///
+/// A field whose GLSL offset is larger than its Rust offset, because a `vec3` is aligned to 16:
+///
+/// ```compile_fail,E0080
+/// # use nalgebra::Vector3;
+/// # use vulkano_shaders::BufferContents;
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std430)]
+/// #[repr(C)]
+/// struct Misplaced {
+///     weight: f32,
+///     position: Vector3<f32>,
+/// }
+/// ```
+///
+/// An array of scalars with the `std140` layout, whose elements are padded to 16 bytes in GLSL:
+///
+/// ```compile_fail,E0080
+/// # use vulkano_shaders::BufferContents;
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std140)]
+/// #[repr(C)]
+/// struct ScalarArray {
+///     values: [f32; 4],
+/// }
+/// ```
+///
+/// A struct whose size isn't a multiple of its GLSL alignment, used as a field of another struct
+/// or in an array:
+///
+/// ```compile_fail,E0080
+/// # use vulkano_shaders::BufferContents;
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std140)]
+/// #[repr(C)]
+/// struct Unpadded {
+///     intensity: f32,
+///     range: f32,
+/// }
+///
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std140)]
+/// #[repr(C)]
+/// struct Outer {
+///     inner: Unpadded,
+/// }
+/// ```
+///
+/// ```compile_fail,E0080
+/// # use vulkano_shaders::BufferContents;
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std140)]
+/// #[repr(C)]
+/// struct Unpadded {
+///     intensity: f32,
+///     range: f32,
+/// }
+///
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std140)]
+/// #[repr(C)]
+/// struct Outer {
+///     inner: [Unpadded; 4],
+/// }
+/// ```
+///
+/// A `mat3`, whose columns are padded to 16 bytes in GLSL:
+///
+/// ```compile_fail,E0080
+/// # use nalgebra::Matrix3;
+/// # use vulkano_shaders::BufferContents;
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std430)]
+/// #[repr(C)]
+/// struct Transform {
+///     rotation: Matrix3<f32>,
+/// }
+/// ```
+///
+/// The derive itself fails for structs whose layout isn't computed from the alignment of their
+/// fields, with `#[repr(packed)]` or `#[repr(align(N))]`:
+///
+/// ```compile_fail
+/// # use vulkano_shaders::BufferContents;
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std430)]
+/// #[repr(C, packed)]
+/// struct Packed {
+///     weight: f32,
+///     offset: f64,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use vulkano_shaders::BufferContents;
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std430)]
+/// #[repr(C, align(16))]
+/// struct Aligned {
+///     weight: f32,
+/// }
+/// ```
+pub(super) fn derive_buffer_contents(input: DeriveInput) -> Result<TokenStream> {
+    let struct_name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "`BufferContents` can't be derived for generic types",
+        ));
+    }
+
+    check_repr(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(_) | Fields::Unnamed(_) => data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    Ok(Field {
+                        name: field
+                            .ident
+                            .as_ref()
+                            .map_or_else(|| index.to_string(), ToString::to_string),
+                        ty: field.ty.clone(),
+                        padding: parse_field_attrs(&field.attrs)?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Fields::Unit => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "`BufferContents` can't be derived for structs without fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`BufferContents` can only be derived for structs",
+            ))
+        }
+    };
+
+    let layouts = parse_struct_attrs(&input.attrs)?;
+
+    if layouts.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "the layout must be specified with `#[buffer_contents(std140)]`, \
+            `#[buffer_contents(std430)]` or both",
+        ));
+    }
+
+    let alignments = [Layout::Std140, Layout::Std430].map(|layout| {
+        let alignment_const = layout.alignment_const();
+
+        if !layouts.contains(&layout) {
+            return quote! { const #alignment_const: Option<usize> = None; };
+        }
+
+        let struct_alignment = layout.struct_alignment();
+        let field_alignments = fields.iter().filter(|field| !field.padding).map(|field| {
+            let ty = &field.ty;
+            quote! {
+                if let Some(field_alignment) =
+                    <#ty as ::vulkano::buffer::BufferContents>::#alignment_const
+                {
+                    if field_alignment > alignment {
+                        alignment = field_alignment;
+                    }
+                }
+            }
+        });
+
+        // GLSL rounds the size of a struct up to its alignment, so the size of the Rust struct
+        // must already be a multiple of it for the struct to be usable as a member of another.
+        quote! {
+            const #alignment_const: Option<usize> = {
+                let mut alignment = #struct_alignment;
+                #(#field_alignments)*
+
+                if ::std::mem::size_of::<Self>() % alignment == 0 {
+                    Some(alignment)
+                } else {
+                    None
+                }
+            };
+        }
+    });
+
+    let checks = layouts.iter().map(|&layout| {
+        let alignment_const = layout.alignment_const();
+        let field_checks = fields.iter().map(|field| {
+            let ty = &field.ty;
+
+            if field.padding {
+                return quote! {
+                    rust_offset = round_up(rust_offset, ::std::mem::align_of::<#ty>())
+                        + ::std::mem::size_of::<#ty>();
+                    glsl_offset = rust_offset;
+                };
+            }
+
+            let unsupported_message = format!(
+                "the type of field `{}` of `{}` can't be used with the {} layout",
+                field.name,
+                struct_name,
+                layout.name(),
+            );
+            let offset_message = format!(
+                "the offset of field `{}` of `{}` doesn't match its offset with the {} layout; \
+                add a padding field before it",
+                field.name,
+                struct_name,
+                layout.name(),
+            );
+
+            quote! {
+                let alignment = match <#ty as ::vulkano::buffer::BufferContents>::#alignment_const {
+                    Some(alignment) => alignment,
+                    None => panic!(#unsupported_message),
+                };
+                rust_offset = round_up(rust_offset, ::std::mem::align_of::<#ty>());
+                glsl_offset = round_up(glsl_offset, alignment);
+
+                if rust_offset != glsl_offset {
+                    panic!(#offset_message);
+                }
+
+                rust_offset += ::std::mem::size_of::<#ty>();
+                glsl_offset += ::std::mem::size_of::<#ty>();
+            };
+        });
+
+        quote! {
+            const _: () = {
+                const fn round_up(value: usize, alignment: usize) -> usize {
+                    (value + alignment - 1) / alignment * alignment
+                }
+
+                let mut rust_offset = 0usize;
+                let mut glsl_offset = 0usize;
+                #(#field_checks)*
+                let _ = (rust_offset, glsl_offset);
+            };
+        }
+    });
+
+    Ok(quote! {
+        unsafe impl ::vulkano::buffer::BufferContents for #struct_name {
+            #(#alignments)*
+        }
+
+        #(#checks)*
+    })
+}
+
+// Checks that the struct has `#[repr(C)]`, and no `packed` or `align` representation. The
+// offsets are computed from the alignment of the field types, which these would change.
+fn check_repr(input: &DeriveInput) -> Result<()> {
+    let mut repr_c = false;
+
+    for meta in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| attr.parse_meta().ok())
+    {
+        if let Meta::List(list) = meta {
+            for nested in &list.nested {
+                let path = match nested {
+                    NestedMeta::Meta(nested) => nested.path(),
+                    NestedMeta::Lit(_) => continue,
+                };
+
+                if path.is_ident("C") {
+                    repr_c = true;
+                } else if path.is_ident("packed") || path.is_ident("align") {
+                    return Err(Error::new(
+                        nested.span(),
+                        "`BufferContents` can't be derived for types with `#[repr(packed)]` or \
+                        `#[repr(align(N))]`",
+                    ));
+                }
+            }
+        }
+    }
+
+    if !repr_c {
+        return Err(Error::new(
+            input.ident.span(),
+            "`BufferContents` can only be derived for types with `#[repr(C)]`",
+        ));
+    }
+
+    Ok(())
+}
+
+fn buffer_contents_attr_idents(attrs: &[Attribute]) -> Result<Vec<syn::Path>> {
+    let mut idents = Vec::new();
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("buffer_contents"))
+    {
+        match attr.parse_meta()? {
+            Meta::List(list) => {
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(Meta::Path(path)) => idents.push(path),
+                        nested => return Err(Error::new(nested.span(), "expected an identifier")),
+                    }
+                }
+            }
+            meta => return Err(Error::new(meta.span(), "expected `buffer_contents(...)`")),
+        }
+    }
+
+    Ok(idents)
+}
+
+fn parse_struct_attrs(attrs: &[Attribute]) -> Result<Vec<Layout>> {
+    let mut layouts = Vec::new();
+
+    for path in buffer_contents_attr_idents(attrs)? {
+        let layout = if path.is_ident("std140") {
+            Layout::Std140
+        } else if path.is_ident("std430") {
+            Layout::Std430
+        } else {
+            return Err(Error::new(
+                path.span(),
+                "expected `std140` or `std430` as the layout",
+            ));
+        };
+
+        if !layouts.contains(&layout) {
+            layouts.push(layout);
+        }
+    }
+
+    Ok(layouts)
+}
+
+// Returns whether the field is marked as padding.
+fn parse_field_attrs(attrs: &[Attribute]) -> Result<bool> {
+    let mut padding = false;
+
+    for path in buffer_contents_attr_idents(attrs)? {
+        if path.is_ident("padding") {
+            padding = true;
+        } else {
+            return Err(Error::new(path.span(), "expected `padding`"));
+        }
+    }
+
+    Ok(padding)
+}
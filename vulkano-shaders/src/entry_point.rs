@@ -71,6 +71,16 @@ fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
         }
         ShaderExecution::Fragment => quote! { ::vulkano::shader::ShaderExecution::Fragment },
         ShaderExecution::Compute => quote! { ::vulkano::shader::ShaderExecution::Compute },
+        ShaderExecution::RayGeneration => {
+            quote! { ::vulkano::shader::ShaderExecution::RayGeneration }
+        }
+        ShaderExecution::AnyHit => quote! { ::vulkano::shader::ShaderExecution::AnyHit },
+        ShaderExecution::ClosestHit => quote! { ::vulkano::shader::ShaderExecution::ClosestHit },
+        ShaderExecution::Miss => quote! { ::vulkano::shader::ShaderExecution::Miss },
+        ShaderExecution::Intersection => {
+            quote! { ::vulkano::shader::ShaderExecution::Intersection }
+        }
+        ShaderExecution::Callable => quote! { ::vulkano::shader::ShaderExecution::Callable },
     }
 }
 
@@ -86,6 +96,7 @@ fn write_descriptor_requirements(
             image_multisampled,
             image_scalar_type,
             image_view_type,
+            input_attachment_index,
             sampler_compare,
             sampler_no_unnormalized_coordinates,
             sampler_no_ycbcr_conversion,
@@ -121,6 +132,10 @@ fn write_descriptor_requirements(
             }
             None => quote! { None },
         };
+        let input_attachment_index = match input_attachment_index {
+            Some(input_attachment_index) => quote! { Some(#input_attachment_index) },
+            None => quote! { None },
+        };
         let sampler_compare = sampler_compare.iter();
         let sampler_no_unnormalized_coordinates = sampler_no_unnormalized_coordinates.iter();
         let sampler_no_ycbcr_conversion = sampler_no_ycbcr_conversion.iter();
@@ -196,6 +211,7 @@ fn write_descriptor_requirements(
                     image_multisampled: #image_multisampled,
                     image_scalar_type: #image_scalar_type,
                     image_view_type: #image_view_type,
+                    input_attachment_index: #input_attachment_index,
                     sampler_compare: [#(#sampler_compare),*].into_iter().collect(),
                     sampler_no_unnormalized_coordinates: [#(#sampler_no_unnormalized_coordinates),*].into_iter().collect(),
                     sampler_no_ycbcr_conversion: [#(#sampler_no_ycbcr_conversion),*].into_iter().collect(),
@@ -104,6 +104,14 @@
 //! * `tess_ctrl`
 //! * `tess_eval`
 //! * `compute`
+//! * `raygen`
+//! * `any_hit`
+//! * `closest_hit`
+//! * `miss`
+//! * `intersection`
+//! * `callable`
+//!
+//! The ray tracing shader types require `vulkan_version: "1.2"` and `spirv_version: "1.4"`.
 //!
 //! For details on what these shader types mean, [see Vulkano's documentation][pipeline].
 //!
@@ -238,6 +246,7 @@ use syn::{
     Ident, ItemUse, LitBool, LitStr, Meta, MetaList, NestedMeta, Path as SynPath, TypeImplTrait,
 };
 
+mod buffer_contents;
 mod codegen;
 mod entry_point;
 mod structs;
@@ -396,7 +405,13 @@ impl Parse for MacroInput {
                         "tess_ctrl" => ShaderKind::TessControl,
                         "tess_eval" => ShaderKind::TessEvaluation,
                         "compute" => ShaderKind::Compute,
-                        _ => panic!("Unexpected shader type, valid values: vertex, fragment, geometry, tess_ctrl, tess_eval, compute")
+                        "raygen" => ShaderKind::RayGeneration,
+                        "any_hit" => ShaderKind::AnyHit,
+                        "closest_hit" => ShaderKind::ClosestHit,
+                        "miss" => ShaderKind::Miss,
+                        "intersection" => ShaderKind::Intersection,
+                        "callable" => ShaderKind::Callable,
+                        _ => panic!("Unexpected shader type, valid values: vertex, fragment, geometry, tess_ctrl, tess_eval, compute, raygen, any_hit, closest_hit, miss, intersection, callable")
                     };
 
                     output.0 = Some(ty);
@@ -915,3 +930,61 @@ pub fn shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
     proc_macro::TokenStream::from(result)
 }
+
+/// Derives `vulkano::buffer::BufferContents` for a struct, checking at compile time that its
+/// layout matches the layout of the equivalent GLSL struct in a uniform or storage buffer.
+///
+/// The struct must be `#[repr(C)]`, and the GLSL layout rules that it follows must be given with
+/// `#[buffer_contents(std140)]`, `#[buffer_contents(std430)]` or `#[buffer_contents(std140,
+/// std430)]`. Each field must have a type that implements `BufferContents`, such as a scalar, an
+/// array, or another struct that derives it, and must be at the same offset as in GLSL. Fields
+/// that only exist to add the padding that GLSL inserts, and have no equivalent in the GLSL
+/// struct, must be marked with `#[buffer_contents(padding)]`.
+///
+/// If a field is at the wrong offset, or has a type that can't be used with one of the layouts,
+/// the crate fails to compile with a message naming the field.
+///
+/// ```
+/// use vulkano_shaders::BufferContents;
+///
+/// // Matches the following GLSL:
+/// //
+/// // struct Light {
+/// //     float intensity;
+/// //     float range;
+/// // };
+/// //
+/// // layout(std140, set = 0, binding = 0) uniform Lights {
+/// //     uint count;
+/// //     Light lights[8];
+/// // };
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std140)]
+/// #[repr(C)]
+/// struct Light {
+///     intensity: f32,
+///     range: f32,
+///     // With std140, the size of a struct is rounded up to a multiple of 16.
+///     #[buffer_contents(padding)]
+///     _pad: [u32; 2],
+/// }
+///
+/// #[derive(Clone, Copy, BufferContents)]
+/// #[buffer_contents(std140)]
+/// #[repr(C)]
+/// struct Lights {
+///     count: u32,
+///     // With std140, arrays of structs are aligned to 16.
+///     #[buffer_contents(padding)]
+///     _pad: [u32; 3],
+///     lights: [Light; 8],
+/// }
+/// ```
+#[proc_macro_derive(BufferContents, attributes(buffer_contents))]
+pub fn derive_buffer_contents(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+
+    buffer_contents::derive_buffer_contents(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
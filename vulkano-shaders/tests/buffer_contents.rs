@@ -0,0 +1,98 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Tests of the `BufferContents` derive whose structs match their GLSL layout. The structs that
+//! don't match it are tested as `compile_fail` doctests in `src/buffer_contents.rs`.
+
+use nalgebra::{Matrix4, Vector3};
+use std::mem;
+use vulkano::buffer::BufferContents;
+use vulkano_shaders::BufferContents;
+
+#[derive(Clone, Copy, BufferContents)]
+#[buffer_contents(std140)]
+#[repr(C)]
+struct Light {
+    intensity: f32,
+    range: f32,
+    #[buffer_contents(padding)]
+    _pad: [u32; 2],
+}
+
+#[derive(Clone, Copy, BufferContents)]
+#[buffer_contents(std140)]
+#[repr(C)]
+struct Lights {
+    count: u32,
+    #[buffer_contents(padding)]
+    _pad: [u32; 3],
+    lights: [Light; 8],
+}
+
+#[derive(Clone, Copy, BufferContents)]
+#[buffer_contents(std140, std430)]
+#[repr(C)]
+struct Vertex {
+    // A `float` fits at the end of a `vec3`.
+    position: Vector3<f32>,
+    weight: f32,
+}
+
+#[derive(Clone, Copy, BufferContents)]
+#[buffer_contents(std140, std430)]
+#[repr(C)]
+struct Mesh {
+    count: u32,
+    #[buffer_contents(padding)]
+    _pad: [u32; 3],
+    vertices: [Vertex; 4],
+}
+
+#[derive(Clone, Copy, BufferContents)]
+#[buffer_contents(std140, std430)]
+#[repr(C)]
+struct Camera {
+    view: Matrix4<f32>,
+    position: Vector3<f32>,
+    near: f32,
+}
+
+#[test]
+fn nested_struct() {
+    assert_eq!(Light::STD140_ALIGNMENT, Some(16));
+    assert_eq!(Light::STD430_ALIGNMENT, None);
+    assert_eq!(Lights::STD140_ALIGNMENT, Some(16));
+    assert_eq!(Lights::STD430_ALIGNMENT, None);
+    assert_eq!(mem::size_of::<Lights>(), 16 + 8 * 16);
+}
+
+#[test]
+fn struct_array_stride() {
+    assert_eq!(<[Light; 8]>::STD140_ALIGNMENT, Some(16));
+    assert_eq!(mem::size_of::<[Light; 8]>(), 8 * 16);
+
+    assert_eq!(Vertex::STD140_ALIGNMENT, Some(16));
+    assert_eq!(Vertex::STD430_ALIGNMENT, Some(16));
+    assert_eq!(<[Vertex; 4]>::STD430_ALIGNMENT, Some(16));
+    assert_eq!(mem::size_of::<[Vertex; 4]>(), 4 * 16);
+}
+
+#[test]
+fn both_layouts() {
+    assert_eq!(Mesh::STD140_ALIGNMENT, Some(16));
+    assert_eq!(Mesh::STD430_ALIGNMENT, Some(16));
+    assert_eq!(mem::size_of::<Mesh>(), 16 + 4 * 16);
+}
+
+#[test]
+fn matrix_field() {
+    assert_eq!(Camera::STD140_ALIGNMENT, Some(16));
+    assert_eq!(Camera::STD430_ALIGNMENT, Some(16));
+    assert_eq!(mem::size_of::<Camera>(), 64 + 16);
+}
@@ -80,19 +80,15 @@ fn fns_output(extension_members: &[FnsMember], fns_level: &str) -> TokenStream {
 fn extension_fns_members(ty: &str, extensions: &IndexMap<&str, &Extension>) -> Vec<FnsMember> {
     extensions
         .values()
-        .filter(|ext| ext.ext_type.as_ref().unwrap() == ty)
-        // Filter only extensions that have functions
+        // Device extensions can also have commands that are dispatched through a physical
+        // device. These must be loaded with the instance functions.
         .filter(|ext| {
-            ext.children.iter().any(|ch| {
-                if let ExtensionChild::Require { items, .. } = ch {
-                    items
-                        .iter()
-                        .any(|i| matches!(i, InterfaceItem::Command { .. }))
-                } else {
-                    false
-                }
-            })
+            ext.ext_type.as_ref().unwrap() == ty
+                || ty == "instance"
+                    && has_command(ext, |name| name.starts_with("vkGetPhysicalDevice"))
         })
+        // Filter only extensions that have functions
+        .filter(|ext| has_command(ext, |_| true))
         .map(|ext| {
             let base = ext.name.strip_prefix("VK_").unwrap().to_snake_case();
             let name = format_ident!("{}", base);
@@ -101,3 +97,15 @@ fn extension_fns_members(ty: &str, extensions: &IndexMap<&str, &Extension>) -> V
         })
         .collect()
 }
+
+fn has_command(ext: &Extension, mut filter: impl FnMut(&str) -> bool) -> bool {
+    ext.children.iter().any(|ch| {
+        if let ExtensionChild::Require { items, .. } = ch {
+            items
+                .iter()
+                .any(|i| matches!(i, InterfaceItem::Command { name, .. } if filter(name)))
+        } else {
+            false
+        }
+    })
+}
@@ -0,0 +1,1558 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Acceleration structures for ray tracing.
+//!
+//! An acceleration structure is an opaque data structure, stored in a buffer, that allows the
+//! implementation to quickly find the geometry that a ray intersects with. There are two levels
+//! of acceleration structures:
+//!
+//! - A bottom-level acceleration structure contains the actual geometry, either as triangles or
+//!   as axis-aligned bounding boxes.
+//! - A top-level acceleration structure contains instances of bottom-level acceleration
+//!   structures, each with its own transformation.
+//!
+//! Creating an `AccelerationStructure` only reserves space for it in a buffer. Its contents are
+//! built on the device by recording
+//! [`build_acceleration_structure`](crate::command_buffer::AutoCommandBufferBuilder::build_acceleration_structure)
+//! into a command buffer. The size of the buffers that are needed for the build can be queried
+//! beforehand with [`AccelerationStructure::build_sizes`].
+//!
+//! Using acceleration structures requires the
+//! [`khr_acceleration_structure`](crate::device::DeviceExtensions::khr_acceleration_structure)
+//! extension and the
+//! [`acceleration_structure`](crate::device::Features::acceleration_structure) feature.
+
+use crate::buffer::BufferAccess;
+use crate::deferred::DeferredOperation;
+use crate::deferred::DeferredOperationError;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::format::Format;
+use crate::pipeline::graphics::input_assembly::IndexType;
+use crate::DeviceSize;
+use crate::Error;
+use crate::OomError;
+use crate::Success;
+use crate::VulkanObject;
+use std::error;
+use std::ffi::c_void;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+
+/// An acceleration structure, stored in a buffer.
+pub struct AccelerationStructure {
+    handle: ash::vk::AccelerationStructureKHR,
+    device: Arc<Device>,
+    buffer: Arc<dyn BufferAccess>,
+    ty: AccelerationStructureType,
+}
+
+impl AccelerationStructure {
+    /// Creates a new `AccelerationStructure` of type `ty`, that occupies the whole of `buffer`.
+    ///
+    /// The contents of the acceleration structure are undefined until it has been built. The
+    /// size that `buffer` needs to have can be queried with
+    /// [`build_sizes`](AccelerationStructure::build_sizes).
+    pub fn new(
+        buffer: Arc<dyn BufferAccess>,
+        ty: AccelerationStructureType,
+    ) -> Result<Arc<AccelerationStructure>, AccelerationStructureCreationError> {
+        let device = buffer.device().clone();
+        check_enabled(&device)?;
+
+        let inner = buffer.inner();
+
+        if !inner.buffer.usage().acceleration_structure_storage {
+            return Err(AccelerationStructureCreationError::BufferMissingUsage);
+        }
+
+        if inner.offset % 256 != 0 {
+            return Err(AccelerationStructureCreationError::OffsetNotAligned);
+        }
+
+        let handle = unsafe {
+            let infos = ash::vk::AccelerationStructureCreateInfoKHR {
+                buffer: inner.buffer.internal_object(),
+                offset: inner.offset,
+                size: buffer.size(),
+                ty: ty.into(),
+                ..Default::default()
+            };
+
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            device.check_errors(
+                fns.khr_acceleration_structure
+                    .create_acceleration_structure_khr(
+                        device.internal_object(),
+                        &infos,
+                        ptr::null(),
+                        output.as_mut_ptr(),
+                    ),
+            )?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(AccelerationStructure {
+            handle,
+            device,
+            buffer,
+            ty,
+        }))
+    }
+
+    /// Returns the sizes of the acceleration structure and the scratch buffers that are needed to
+    /// build an acceleration structure from `info`.
+    ///
+    /// Only the type and the number of the geometries, their flags, vertex format, maximum vertex
+    /// and primitive count, and whether they have index and transform data are taken into
+    /// account. The contents of the buffers are not accessed, but like for the build, the buffers
+    /// must have the `device_address` usage.
+    pub fn build_sizes(
+        device: &Arc<Device>,
+        info: &AccelerationStructureBuildGeometryInfo,
+    ) -> Result<AccelerationStructureBuildSizes, AccelerationStructureCreationError> {
+        check_enabled(device)?;
+
+        let (geometries, ranges) = info.geometries.to_vulkan().map_err(|geometry_index| {
+            AccelerationStructureCreationError::GeometryBufferMissingUsage { geometry_index }
+        })?;
+
+        Ok(unsafe {
+            Self::build_sizes_unchecked(
+                device,
+                ash::vk::AccelerationStructureBuildTypeKHR::DEVICE,
+                info.geometries.ty(),
+                info.flags,
+                &geometries,
+                &ranges,
+            )
+        })
+    }
+
+    /// Returns the sizes of the acceleration structure and the scratch memory that are needed to
+    /// build an acceleration structure from `info` on the host, with
+    /// [`build_on_host`](AccelerationStructure::build_on_host).
+    ///
+    /// The sizes can be different from the ones that are returned by
+    /// [`build_sizes`](AccelerationStructure::build_sizes) for the same geometries.
+    pub fn host_build_sizes(
+        device: &Arc<Device>,
+        info: &AccelerationStructureHostBuildGeometryInfo,
+    ) -> Result<AccelerationStructureBuildSizes, AccelerationStructureHostBuildError> {
+        check_host_enabled(device)?;
+        check_host_geometries(device, &info.geometries)?;
+
+        let (geometries, ranges) = info.geometries.to_vulkan();
+
+        Ok(unsafe {
+            Self::build_sizes_unchecked(
+                device,
+                ash::vk::AccelerationStructureBuildTypeKHR::HOST,
+                info.geometries.ty(),
+                info.flags,
+                &geometries,
+                &ranges,
+            )
+        })
+    }
+
+    // Queries the build sizes of geometries that have already been converted to their Vulkan
+    // form.
+    //
+    // The `khr_acceleration_structure` extension and the `acceleration_structure` feature must be
+    // enabled on `device`.
+    pub(crate) unsafe fn build_sizes_unchecked(
+        device: &Device,
+        build_type: ash::vk::AccelerationStructureBuildTypeKHR,
+        ty: AccelerationStructureType,
+        flags: BuildAccelerationStructureFlags,
+        geometries: &[ash::vk::AccelerationStructureGeometryKHR],
+        ranges: &[ash::vk::AccelerationStructureBuildRangeInfoKHR],
+    ) -> AccelerationStructureBuildSizes {
+        let max_primitive_counts: Vec<u32> =
+            ranges.iter().map(|range| range.primitive_count).collect();
+        let build_info = ash::vk::AccelerationStructureBuildGeometryInfoKHR {
+            ty: ty.into(),
+            flags: flags.into(),
+            mode: ash::vk::BuildAccelerationStructureModeKHR::BUILD,
+            geometry_count: geometries.len() as u32,
+            p_geometries: geometries.as_ptr(),
+            ..Default::default()
+        };
+
+        let fns = device.fns();
+        let mut sizes = ash::vk::AccelerationStructureBuildSizesInfoKHR::default();
+        fns.khr_acceleration_structure
+            .get_acceleration_structure_build_sizes_khr(
+                device.internal_object(),
+                build_type,
+                &build_info,
+                max_primitive_counts.as_ptr(),
+                &mut sizes,
+            );
+
+        AccelerationStructureBuildSizes {
+            acceleration_structure_size: sizes.acceleration_structure_size,
+            update_scratch_size: sizes.update_scratch_size,
+            build_scratch_size: sizes.build_scratch_size,
+        }
+    }
+
+    /// Returns the buffer that stores the acceleration structure.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<dyn BufferAccess> {
+        &self.buffer
+    }
+
+    /// Returns the type of the acceleration structure.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        self.ty
+    }
+
+    /// Returns the device address of the acceleration structure.
+    ///
+    /// This is the value that must be written to
+    /// [`AccelerationStructureInstance::acceleration_structure_reference`] to refer to a
+    /// bottom-level acceleration structure from a top-level one.
+    #[inline]
+    pub fn device_address(&self) -> u64 {
+        unsafe {
+            let info = ash::vk::AccelerationStructureDeviceAddressInfoKHR {
+                acceleration_structure: self.handle,
+                ..Default::default()
+            };
+
+            let fns = self.device.fns();
+            fns.khr_acceleration_structure
+                .get_acceleration_structure_device_address_khr(self.device.internal_object(), &info)
+        }
+    }
+
+    /// Builds the acceleration structure on the host, from geometry in host memory.
+    ///
+    /// This requires the
+    /// [`acceleration_structure_host_commands`](crate::device::Features::acceleration_structure_host_commands)
+    /// feature. The buffer of the acceleration structure must be at least as large as the
+    /// size returned by [`host_build_sizes`](AccelerationStructure::host_build_sizes) for `info`.
+    ///
+    /// If `deferred_operation` is `Some`, the implementation may defer the build to it. The build
+    /// is then spread over all the threads that call [`join`](DeferredOperation::join) on the
+    /// operation while it is in progress. The current thread joins the operation as well, and
+    /// this function only returns once the build has completed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `deferred_operation` was not created with the device of the acceleration
+    ///   structure.
+    ///
+    /// # Safety
+    ///
+    /// - The buffer of the acceleration structure must be bound to host-visible memory.
+    /// - The acceleration structure must not be in use by the device.
+    /// - If `info` contains instances, the instances must refer to bottom-level acceleration
+    ///   structures that have been built, and that are kept alive and not rebuilt for as long as
+    ///   the acceleration structure is used.
+    /// - If `info` contains triangles with indices, the indices must not be greater than the
+    ///   `max_vertex` of the geometry.
+    pub unsafe fn build_on_host(
+        &self,
+        info: &AccelerationStructureHostBuildGeometryInfo,
+        deferred_operation: Option<Arc<DeferredOperation>>,
+    ) -> Result<(), AccelerationStructureHostBuildError> {
+        if let Some(deferred_operation) = &deferred_operation {
+            assert_eq!(
+                deferred_operation.device().internal_object(),
+                self.device.internal_object()
+            );
+        }
+
+        check_host_enabled(&self.device)?;
+
+        if self.ty != info.geometries.ty() {
+            return Err(
+                AccelerationStructureHostBuildError::DestinationTypeMismatch {
+                    destination: self.ty,
+                    geometries: info.geometries.ty(),
+                },
+            );
+        }
+
+        check_host_geometries(&self.device, &info.geometries)?;
+
+        let (geometries, ranges) = info.geometries.to_vulkan();
+        let sizes = Self::build_sizes_unchecked(
+            &self.device,
+            ash::vk::AccelerationStructureBuildTypeKHR::HOST,
+            self.ty,
+            info.flags,
+            &geometries,
+            &ranges,
+        );
+
+        if self.buffer.size() < sizes.acceleration_structure_size {
+            return Err(AccelerationStructureHostBuildError::DestinationTooSmall {
+                required: sizes.acceleration_structure_size,
+                obtained: self.buffer.size(),
+            });
+        }
+
+        let mut scratch = vec![0u8; sizes.build_scratch_size as usize];
+        let build_info = ash::vk::AccelerationStructureBuildGeometryInfoKHR {
+            ty: self.ty.into(),
+            flags: info.flags.into(),
+            mode: ash::vk::BuildAccelerationStructureModeKHR::BUILD,
+            dst_acceleration_structure: self.handle,
+            geometry_count: geometries.len() as u32,
+            p_geometries: geometries.as_ptr(),
+            scratch_data: ash::vk::DeviceOrHostAddressKHR {
+                host_address: scratch.as_mut_ptr() as *mut c_void,
+            },
+            ..Default::default()
+        };
+        let deferred_operation_handle = match deferred_operation {
+            Some(ref deferred_operation) => deferred_operation.internal_object(),
+            None => ash::vk::DeferredOperationKHR::null(),
+        };
+
+        let fns = self.device.fns();
+        let result = self.device.check_errors(
+            fns.khr_acceleration_structure
+                .build_acceleration_structures_khr(
+                    self.device.internal_object(),
+                    deferred_operation_handle,
+                    1,
+                    &build_info,
+                    &ranges.as_ptr(),
+                ),
+        )?;
+
+        // The geometries and the scratch memory must stay alive until a deferred build has
+        // completed.
+        if let (Success::OperationDeferred, Some(deferred_operation)) =
+            (result, &deferred_operation)
+        {
+            deferred_operation.wait()?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_enabled(device: &Device) -> Result<(), AccelerationStructureCreationError> {
+    if !device.enabled_extensions().khr_acceleration_structure {
+        return Err(AccelerationStructureCreationError::ExtensionNotEnabled {
+            extension: "khr_acceleration_structure",
+            reason: "tried to use an acceleration structure",
+        });
+    }
+
+    if !device.enabled_features().acceleration_structure {
+        return Err(AccelerationStructureCreationError::FeatureNotEnabled {
+            feature: "acceleration_structure",
+            reason: "tried to use an acceleration structure",
+        });
+    }
+
+    Ok(())
+}
+
+fn check_host_enabled(device: &Device) -> Result<(), AccelerationStructureHostBuildError> {
+    if !device.enabled_extensions().khr_acceleration_structure {
+        return Err(AccelerationStructureHostBuildError::ExtensionNotEnabled {
+            extension: "khr_acceleration_structure",
+            reason: "tried to build an acceleration structure on the host",
+        });
+    }
+
+    if !device.enabled_features().acceleration_structure {
+        return Err(AccelerationStructureHostBuildError::FeatureNotEnabled {
+            feature: "acceleration_structure",
+            reason: "tried to build an acceleration structure on the host",
+        });
+    }
+
+    if !device
+        .enabled_features()
+        .acceleration_structure_host_commands
+    {
+        return Err(AccelerationStructureHostBuildError::FeatureNotEnabled {
+            feature: "acceleration_structure_host_commands",
+            reason: "tried to build an acceleration structure on the host",
+        });
+    }
+
+    Ok(())
+}
+
+fn check_host_geometries(
+    device: &Device,
+    geometries: &AccelerationStructureHostGeometries,
+) -> Result<(), AccelerationStructureHostBuildError> {
+    fn check_alignment(
+        geometry_index: usize,
+        data: *const u8,
+        alignment: usize,
+    ) -> Result<(), AccelerationStructureHostBuildError> {
+        if data as usize % alignment != 0 {
+            return Err(
+                AccelerationStructureHostBuildError::GeometryDataNotAligned {
+                    geometry_index,
+                    alignment,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    match geometries {
+        AccelerationStructureHostGeometries::Triangles(triangles) => {
+            for (geometry_index, data) in triangles.iter().enumerate() {
+                if !device
+                    .physical_device()
+                    .format_properties(data.vertex_format)
+                    .buffer_features
+                    .acceleration_structure_vertex_buffer
+                {
+                    return Err(
+                        AccelerationStructureHostBuildError::UnsupportedVertexFormat {
+                            geometry_index,
+                            format: data.vertex_format,
+                        },
+                    );
+                }
+
+                // Formats that support the buffer feature are not block-compressed, so they have
+                // a block size.
+                let vertex_size = data.vertex_format.block_size().unwrap_or(0);
+                if (data.vertex_data.len() as DeviceSize)
+                    < data.max_vertex as DeviceSize * data.vertex_stride + vertex_size
+                {
+                    return Err(AccelerationStructureHostBuildError::GeometryDataTooSmall {
+                        geometry_index,
+                    });
+                }
+
+                if let Some((indices, index_type)) = data.index_data {
+                    if index_type == IndexType::U8 {
+                        return Err(AccelerationStructureHostBuildError::UnsupportedIndexType {
+                            geometry_index,
+                        });
+                    }
+
+                    if (indices.len() as DeviceSize)
+                        < data.primitive_count as DeviceSize * 3 * index_type.size()
+                    {
+                        return Err(AccelerationStructureHostBuildError::GeometryDataTooSmall {
+                            geometry_index,
+                        });
+                    }
+                }
+
+                if let Some(transform) = data.transform_data {
+                    check_alignment(geometry_index, transform.as_ptr() as *const u8, 16)?;
+                }
+            }
+        }
+        AccelerationStructureHostGeometries::Aabbs(aabbs) => {
+            for (geometry_index, data) in aabbs.iter().enumerate() {
+                if data.primitive_count != 0
+                    && (data.data.len() as DeviceSize)
+                        < (data.primitive_count - 1) as DeviceSize * data.stride + 24
+                {
+                    return Err(AccelerationStructureHostBuildError::GeometryDataTooSmall {
+                        geometry_index,
+                    });
+                }
+
+                check_alignment(geometry_index, data.data.as_ptr(), 8)?;
+            }
+        }
+        AccelerationStructureHostGeometries::Instances(instances) => {
+            check_alignment(0, instances.instances.as_ptr() as *const u8, 16)?;
+        }
+    }
+
+    Ok(())
+}
+
+unsafe impl DeviceOwned for AccelerationStructure {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for AccelerationStructure {
+    type Object = ash::vk::AccelerationStructureKHR;
+
+    #[inline]
+    fn internal_object(&self) -> ash::vk::AccelerationStructureKHR {
+        self.handle
+    }
+}
+
+impl fmt::Debug for AccelerationStructure {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan acceleration structure {:?}>", self.handle)
+    }
+}
+
+impl PartialEq for AccelerationStructure {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle && self.device() == other.device()
+    }
+}
+
+impl Eq for AccelerationStructure {}
+
+impl Hash for AccelerationStructure {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state);
+        self.device().hash(state);
+    }
+}
+
+impl Drop for AccelerationStructure {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            fns.khr_acceleration_structure
+                .destroy_acceleration_structure_khr(
+                    self.device.internal_object(),
+                    self.handle,
+                    ptr::null(),
+                );
+        }
+    }
+}
+
+/// The level of an acceleration structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum AccelerationStructureType {
+    /// Contains instances of bottom-level acceleration structures.
+    TopLevel = ash::vk::AccelerationStructureTypeKHR::TOP_LEVEL.as_raw(),
+    /// Contains triangles or axis-aligned bounding boxes.
+    BottomLevel = ash::vk::AccelerationStructureTypeKHR::BOTTOM_LEVEL.as_raw(),
+}
+
+impl From<AccelerationStructureType> for ash::vk::AccelerationStructureTypeKHR {
+    #[inline]
+    fn from(val: AccelerationStructureType) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+/// The sizes that are needed to build an acceleration structure, as returned by
+/// [`AccelerationStructure::build_sizes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccelerationStructureBuildSizes {
+    /// The size of the buffer that stores the acceleration structure.
+    pub acceleration_structure_size: DeviceSize,
+    /// The size of the scratch buffer that is needed to update the acceleration structure.
+    pub update_scratch_size: DeviceSize,
+    /// The size of the scratch buffer that is needed to build the acceleration structure.
+    pub build_scratch_size: DeviceSize,
+}
+
+/// Describes the geometry to build an acceleration structure from.
+#[derive(Clone)]
+pub struct AccelerationStructureBuildGeometryInfo {
+    /// Hints for how the acceleration structure should be built.
+    pub flags: BuildAccelerationStructureFlags,
+    /// The geometries to build the acceleration structure from.
+    pub geometries: AccelerationStructureGeometries,
+}
+
+/// Hints for how an acceleration structure should be built.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BuildAccelerationStructureFlags {
+    /// Allows the acceleration structure to be compacted after it has been built.
+    pub allow_compaction: bool,
+    /// Favors fast tracing over fast building. Can't be combined with `prefer_fast_build`.
+    pub prefer_fast_trace: bool,
+    /// Favors fast building over fast tracing. Can't be combined with `prefer_fast_trace`.
+    pub prefer_fast_build: bool,
+    /// Minimizes the size of the acceleration structure and of the scratch buffer, possibly at
+    /// the cost of build and trace performance.
+    pub low_memory: bool,
+}
+
+impl From<BuildAccelerationStructureFlags> for ash::vk::BuildAccelerationStructureFlagsKHR {
+    #[inline]
+    fn from(val: BuildAccelerationStructureFlags) -> Self {
+        let mut result = ash::vk::BuildAccelerationStructureFlagsKHR::empty();
+        if val.allow_compaction {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::ALLOW_COMPACTION;
+        }
+        if val.prefer_fast_trace {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_TRACE;
+        }
+        if val.prefer_fast_build {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_BUILD;
+        }
+        if val.low_memory {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::LOW_MEMORY;
+        }
+        result
+    }
+}
+
+/// The geometries of an acceleration structure.
+///
+/// Triangles and axis-aligned bounding boxes result in a bottom-level acceleration structure,
+/// instances result in a top-level acceleration structure.
+#[derive(Clone)]
+pub enum AccelerationStructureGeometries {
+    /// Geometries made of triangles.
+    Triangles(Vec<AccelerationStructureGeometryTrianglesData>),
+    /// Geometries made of axis-aligned bounding boxes, that are intersected by an intersection
+    /// shader.
+    Aabbs(Vec<AccelerationStructureGeometryAabbsData>),
+    /// Instances of bottom-level acceleration structures.
+    Instances(AccelerationStructureGeometryInstancesData),
+}
+
+impl AccelerationStructureGeometries {
+    /// Returns the type of acceleration structure that is built from these geometries.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        match self {
+            Self::Triangles(_) | Self::Aabbs(_) => AccelerationStructureType::BottomLevel,
+            Self::Instances(_) => AccelerationStructureType::TopLevel,
+        }
+    }
+
+    /// Returns the buffers that are read while building, with the index of the geometry they
+    /// belong to.
+    pub(crate) fn buffers(&self) -> Vec<(usize, &Arc<dyn BufferAccess>)> {
+        match self {
+            Self::Triangles(triangles) => triangles
+                .iter()
+                .enumerate()
+                .flat_map(|(index, data)| {
+                    std::iter::once(&data.vertex_data)
+                        .chain(data.index_data.as_ref().map(|(buffer, _)| buffer))
+                        .chain(data.transform_data.as_ref())
+                        .map(move |buffer| (index, buffer))
+                })
+                .collect(),
+            Self::Aabbs(aabbs) => aabbs
+                .iter()
+                .enumerate()
+                .map(|(index, data)| (index, &data.data))
+                .collect(),
+            Self::Instances(instances) => vec![(0, &instances.data)],
+        }
+    }
+
+    /// Returns the Vulkan geometries and their build ranges.
+    ///
+    /// Returns the index of the geometry as an error if one of its buffers doesn't have the
+    /// `device_address` usage.
+    pub(crate) fn to_vulkan(
+        &self,
+    ) -> Result<
+        (
+            Vec<ash::vk::AccelerationStructureGeometryKHR>,
+            Vec<ash::vk::AccelerationStructureBuildRangeInfoKHR>,
+        ),
+        usize,
+    > {
+        fn address(
+            geometry_index: usize,
+            buffer: &Arc<dyn BufferAccess>,
+        ) -> Result<ash::vk::DeviceOrHostAddressConstKHR, usize> {
+            match buffer.raw_device_address() {
+                Ok(address) => Ok(ash::vk::DeviceOrHostAddressConstKHR {
+                    device_address: address.get(),
+                }),
+                Err(_) => Err(geometry_index),
+            }
+        }
+
+        match self {
+            Self::Triangles(triangles) => triangles
+                .iter()
+                .enumerate()
+                .map(|(geometry_index, data)| {
+                    let (index_type, index_data) = match &data.index_data {
+                        Some((buffer, index_type)) => {
+                            ((*index_type).into(), address(geometry_index, buffer)?)
+                        }
+                        None => (ash::vk::IndexType::NONE_KHR, Default::default()),
+                    };
+                    let transform_data = match &data.transform_data {
+                        Some(buffer) => address(geometry_index, buffer)?,
+                        None => Default::default(),
+                    };
+
+                    Ok((
+                        ash::vk::AccelerationStructureGeometryKHR {
+                            geometry_type: ash::vk::GeometryTypeKHR::TRIANGLES,
+                            geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                                triangles: ash::vk::AccelerationStructureGeometryTrianglesDataKHR {
+                                    vertex_format: data.vertex_format.into(),
+                                    vertex_data: address(geometry_index, &data.vertex_data)?,
+                                    vertex_stride: data.vertex_stride,
+                                    max_vertex: data.max_vertex,
+                                    index_type,
+                                    index_data,
+                                    transform_data,
+                                    ..Default::default()
+                                },
+                            },
+                            flags: data.flags.into(),
+                            ..Default::default()
+                        },
+                        ash::vk::AccelerationStructureBuildRangeInfoKHR {
+                            primitive_count: data.primitive_count,
+                            ..Default::default()
+                        },
+                    ))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|geometries| geometries.into_iter().unzip()),
+            Self::Aabbs(aabbs) => aabbs
+                .iter()
+                .enumerate()
+                .map(|(geometry_index, data)| {
+                    Ok((
+                        ash::vk::AccelerationStructureGeometryKHR {
+                            geometry_type: ash::vk::GeometryTypeKHR::AABBS,
+                            geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                                aabbs: ash::vk::AccelerationStructureGeometryAabbsDataKHR {
+                                    data: address(geometry_index, &data.data)?,
+                                    stride: data.stride,
+                                    ..Default::default()
+                                },
+                            },
+                            flags: data.flags.into(),
+                            ..Default::default()
+                        },
+                        ash::vk::AccelerationStructureBuildRangeInfoKHR {
+                            primitive_count: data.primitive_count,
+                            ..Default::default()
+                        },
+                    ))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|geometries| geometries.into_iter().unzip()),
+            Self::Instances(data) => Ok((
+                vec![ash::vk::AccelerationStructureGeometryKHR {
+                    geometry_type: ash::vk::GeometryTypeKHR::INSTANCES,
+                    geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                        instances: ash::vk::AccelerationStructureGeometryInstancesDataKHR {
+                            array_of_pointers: ash::vk::FALSE,
+                            data: address(0, &data.data)?,
+                            ..Default::default()
+                        },
+                    },
+                    flags: data.flags.into(),
+                    ..Default::default()
+                }],
+                vec![ash::vk::AccelerationStructureBuildRangeInfoKHR {
+                    primitive_count: data.primitive_count,
+                    ..Default::default()
+                }],
+            )),
+        }
+    }
+}
+
+/// A geometry made of triangles, for a bottom-level acceleration structure.
+#[derive(Clone)]
+pub struct AccelerationStructureGeometryTrianglesData {
+    /// Flags that affect how the triangles are traced.
+    pub flags: GeometryFlags,
+    /// The format of the vertex positions. The format must support the
+    /// [`acceleration_structure_vertex_buffer`](crate::device::physical::FormatFeatures::acceleration_structure_vertex_buffer)
+    /// buffer feature.
+    pub vertex_format: Format,
+    /// The buffer that contains the vertex positions.
+    pub vertex_data: Arc<dyn BufferAccess>,
+    /// The number of bytes between the start of two consecutive vertices.
+    pub vertex_stride: DeviceSize,
+    /// The highest index of a vertex that is used by the geometry.
+    pub max_vertex: u32,
+    /// The buffer that contains the indices, and their type. If `None`, the vertices are not
+    /// indexed.
+    pub index_data: Option<(Arc<dyn BufferAccess>, IndexType)>,
+    /// A buffer that contains a 3x4 row-major matrix of `f32`, that transforms the vertices.
+    pub transform_data: Option<Arc<dyn BufferAccess>>,
+    /// The number of triangles.
+    pub primitive_count: u32,
+}
+
+/// A geometry made of axis-aligned bounding boxes, for a bottom-level acceleration structure.
+#[derive(Clone)]
+pub struct AccelerationStructureGeometryAabbsData {
+    /// Flags that affect how the bounding boxes are traced.
+    pub flags: GeometryFlags,
+    /// The buffer that contains the bounding boxes, each made of six `f32`: the minimum `x`, `y`
+    /// and `z` followed by the maximum `x`, `y` and `z`.
+    pub data: Arc<dyn BufferAccess>,
+    /// The number of bytes between the start of two consecutive bounding boxes.
+    pub stride: DeviceSize,
+    /// The number of bounding boxes.
+    pub primitive_count: u32,
+}
+
+/// Instances of bottom-level acceleration structures, for a top-level acceleration structure.
+#[derive(Clone)]
+pub struct AccelerationStructureGeometryInstancesData {
+    /// Flags that affect how the instances are traced.
+    pub flags: GeometryFlags,
+    /// The buffer that contains the instances, as tightly packed
+    /// [`AccelerationStructureInstance`] values.
+    pub data: Arc<dyn BufferAccess>,
+    /// The number of instances.
+    pub primitive_count: u32,
+}
+
+/// Describes geometry in host memory, to build an acceleration structure from on the host with
+/// [`AccelerationStructure::build_on_host`].
+#[derive(Clone)]
+pub struct AccelerationStructureHostBuildGeometryInfo<'a> {
+    /// Hints for how the acceleration structure should be built.
+    pub flags: BuildAccelerationStructureFlags,
+    /// The geometries to build the acceleration structure from.
+    pub geometries: AccelerationStructureHostGeometries<'a>,
+}
+
+/// The geometries of an acceleration structure, in host memory.
+///
+/// Triangles and axis-aligned bounding boxes result in a bottom-level acceleration structure,
+/// instances result in a top-level acceleration structure.
+#[derive(Clone)]
+pub enum AccelerationStructureHostGeometries<'a> {
+    /// Geometries made of triangles.
+    Triangles(Vec<AccelerationStructureHostTrianglesData<'a>>),
+    /// Geometries made of axis-aligned bounding boxes, that are intersected by an intersection
+    /// shader.
+    Aabbs(Vec<AccelerationStructureHostAabbsData<'a>>),
+    /// Instances of bottom-level acceleration structures.
+    Instances(AccelerationStructureHostInstancesData<'a>),
+}
+
+impl<'a> AccelerationStructureHostGeometries<'a> {
+    /// Returns the type of acceleration structure that is built from these geometries.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        match self {
+            Self::Triangles(_) | Self::Aabbs(_) => AccelerationStructureType::BottomLevel,
+            Self::Instances(_) => AccelerationStructureType::TopLevel,
+        }
+    }
+
+    fn to_vulkan(
+        &self,
+    ) -> (
+        Vec<ash::vk::AccelerationStructureGeometryKHR>,
+        Vec<ash::vk::AccelerationStructureBuildRangeInfoKHR>,
+    ) {
+        fn address<T>(data: &[T]) -> ash::vk::DeviceOrHostAddressConstKHR {
+            ash::vk::DeviceOrHostAddressConstKHR {
+                host_address: data.as_ptr() as *const c_void,
+            }
+        }
+
+        match self {
+            Self::Triangles(triangles) => triangles
+                .iter()
+                .map(|data| {
+                    let (index_type, index_data) = match data.index_data {
+                        Some((indices, index_type)) => (index_type.into(), address(indices)),
+                        None => (ash::vk::IndexType::NONE_KHR, Default::default()),
+                    };
+
+                    (
+                        ash::vk::AccelerationStructureGeometryKHR {
+                            geometry_type: ash::vk::GeometryTypeKHR::TRIANGLES,
+                            geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                                triangles: ash::vk::AccelerationStructureGeometryTrianglesDataKHR {
+                                    vertex_format: data.vertex_format.into(),
+                                    vertex_data: address(data.vertex_data),
+                                    vertex_stride: data.vertex_stride,
+                                    max_vertex: data.max_vertex,
+                                    index_type,
+                                    index_data,
+                                    transform_data: data
+                                        .transform_data
+                                        .map_or(Default::default(), |transform| address(transform)),
+                                    ..Default::default()
+                                },
+                            },
+                            flags: data.flags.into(),
+                            ..Default::default()
+                        },
+                        ash::vk::AccelerationStructureBuildRangeInfoKHR {
+                            primitive_count: data.primitive_count,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .unzip(),
+            Self::Aabbs(aabbs) => aabbs
+                .iter()
+                .map(|data| {
+                    (
+                        ash::vk::AccelerationStructureGeometryKHR {
+                            geometry_type: ash::vk::GeometryTypeKHR::AABBS,
+                            geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                                aabbs: ash::vk::AccelerationStructureGeometryAabbsDataKHR {
+                                    data: address(data.data),
+                                    stride: data.stride,
+                                    ..Default::default()
+                                },
+                            },
+                            flags: data.flags.into(),
+                            ..Default::default()
+                        },
+                        ash::vk::AccelerationStructureBuildRangeInfoKHR {
+                            primitive_count: data.primitive_count,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .unzip(),
+            Self::Instances(data) => (
+                vec![ash::vk::AccelerationStructureGeometryKHR {
+                    geometry_type: ash::vk::GeometryTypeKHR::INSTANCES,
+                    geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                        instances: ash::vk::AccelerationStructureGeometryInstancesDataKHR {
+                            array_of_pointers: ash::vk::FALSE,
+                            data: address(data.instances),
+                            ..Default::default()
+                        },
+                    },
+                    flags: data.flags.into(),
+                    ..Default::default()
+                }],
+                vec![ash::vk::AccelerationStructureBuildRangeInfoKHR {
+                    primitive_count: data.instances.len() as u32,
+                    ..Default::default()
+                }],
+            ),
+        }
+    }
+}
+
+/// A geometry made of triangles in host memory, for a bottom-level acceleration structure.
+#[derive(Clone)]
+pub struct AccelerationStructureHostTrianglesData<'a> {
+    /// Flags that affect how the triangles are traced.
+    pub flags: GeometryFlags,
+    /// The format of the vertex positions. The format must support the
+    /// [`acceleration_structure_vertex_buffer`](crate::device::physical::FormatFeatures::acceleration_structure_vertex_buffer)
+    /// buffer feature.
+    pub vertex_format: Format,
+    /// The vertex positions.
+    pub vertex_data: &'a [u8],
+    /// The number of bytes between the start of two consecutive vertices.
+    pub vertex_stride: DeviceSize,
+    /// The highest index of a vertex that is used by the geometry.
+    pub max_vertex: u32,
+    /// The indices, and their type. If `None`, the vertices are not indexed.
+    pub index_data: Option<(&'a [u8], IndexType)>,
+    /// A 3x4 row-major matrix that transforms the vertices. Must be aligned to 16 bytes.
+    pub transform_data: Option<&'a [[f32; 4]; 3]>,
+    /// The number of triangles.
+    pub primitive_count: u32,
+}
+
+/// A geometry made of axis-aligned bounding boxes in host memory, for a bottom-level
+/// acceleration structure.
+#[derive(Clone)]
+pub struct AccelerationStructureHostAabbsData<'a> {
+    /// Flags that affect how the bounding boxes are traced.
+    pub flags: GeometryFlags,
+    /// The bounding boxes, each made of six `f32`: the minimum `x`, `y` and `z` followed by the
+    /// maximum `x`, `y` and `z`. Must be aligned to 8 bytes.
+    pub data: &'a [u8],
+    /// The number of bytes between the start of two consecutive bounding boxes.
+    pub stride: DeviceSize,
+    /// The number of bounding boxes.
+    pub primitive_count: u32,
+}
+
+/// Instances of bottom-level acceleration structures in host memory, for a top-level
+/// acceleration structure.
+#[derive(Clone)]
+pub struct AccelerationStructureHostInstancesData<'a> {
+    /// Flags that affect how the instances are traced.
+    pub flags: GeometryFlags,
+    /// The instances. Must be aligned to 16 bytes.
+    pub instances: &'a [AccelerationStructureInstance],
+}
+
+/// Flags that affect how a geometry is traced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GeometryFlags {
+    /// The any-hit shader is not invoked for this geometry.
+    pub opaque: bool,
+    /// The any-hit shader is invoked at most once for each primitive in this geometry.
+    pub no_duplicate_any_hit_invocation: bool,
+}
+
+impl From<GeometryFlags> for ash::vk::GeometryFlagsKHR {
+    #[inline]
+    fn from(val: GeometryFlags) -> Self {
+        let mut result = ash::vk::GeometryFlagsKHR::empty();
+        if val.opaque {
+            result |= ash::vk::GeometryFlagsKHR::OPAQUE;
+        }
+        if val.no_duplicate_any_hit_invocation {
+            result |= ash::vk::GeometryFlagsKHR::NO_DUPLICATE_ANY_HIT_INVOCATION;
+        }
+        result
+    }
+}
+
+/// An instance of a bottom-level acceleration structure, as it is stored in the buffer of
+/// [`AccelerationStructureGeometryInstancesData`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+pub struct AccelerationStructureInstance {
+    /// A 3x4 row-major matrix that transforms the bottom-level acceleration structure.
+    pub transform: [[f32; 4]; 3],
+    /// The custom index in the lower 24 bits, and the visibility mask in the upper 8 bits.
+    pub instance_custom_index_and_mask: u32,
+    /// The offset into the hit group records of the shader binding table in the lower 24 bits,
+    /// and the [`GeometryInstanceFlags`] in the upper 8 bits.
+    pub instance_shader_binding_table_record_offset_and_flags: u32,
+    /// The device address of the bottom-level acceleration structure, as returned by
+    /// [`AccelerationStructure::device_address`].
+    pub acceleration_structure_reference: u64,
+}
+
+impl AccelerationStructureInstance {
+    /// Creates a new instance of `bottom_level` with the given transformation, that is visible to
+    /// all rays and uses the first hit group record.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `bottom_level` is not a bottom-level acceleration structure.
+    #[inline]
+    pub fn new(
+        bottom_level: &AccelerationStructure,
+        transform: [[f32; 4]; 3],
+    ) -> AccelerationStructureInstance {
+        assert_eq!(bottom_level.ty(), AccelerationStructureType::BottomLevel);
+
+        AccelerationStructureInstance {
+            transform,
+            instance_custom_index_and_mask: 0xff << 24,
+            instance_shader_binding_table_record_offset_and_flags: 0,
+            acceleration_structure_reference: bottom_level.device_address(),
+        }
+    }
+
+    /// Sets the custom index, that is available in shaders as `gl_InstanceCustomIndexEXT`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `index` does not fit in 24 bits.
+    #[inline]
+    pub fn with_custom_index(mut self, index: u32) -> Self {
+        assert!(index < 1 << 24);
+        self.instance_custom_index_and_mask =
+            (self.instance_custom_index_and_mask & 0xff00_0000) | index;
+        self
+    }
+
+    /// Sets the visibility mask. The instance is only hit by rays whose cull mask has a bit in
+    /// common with `mask`.
+    #[inline]
+    pub fn with_mask(mut self, mask: u8) -> Self {
+        self.instance_custom_index_and_mask =
+            (self.instance_custom_index_and_mask & 0x00ff_ffff) | (mask as u32) << 24;
+        self
+    }
+
+    /// Sets the offset into the hit group records of the shader binding table.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `offset` does not fit in 24 bits.
+    #[inline]
+    pub fn with_shader_binding_table_record_offset(mut self, offset: u32) -> Self {
+        assert!(offset < 1 << 24);
+        self.instance_shader_binding_table_record_offset_and_flags =
+            (self.instance_shader_binding_table_record_offset_and_flags & 0xff00_0000) | offset;
+        self
+    }
+
+    /// Sets the flags of the instance.
+    #[inline]
+    pub fn with_flags(mut self, flags: GeometryInstanceFlags) -> Self {
+        let flags = ash::vk::GeometryInstanceFlagsKHR::from(flags).as_raw();
+        self.instance_shader_binding_table_record_offset_and_flags =
+            (self.instance_shader_binding_table_record_offset_and_flags & 0x00ff_ffff)
+                | flags << 24;
+        self
+    }
+}
+
+/// Flags that affect how an instance of a bottom-level acceleration structure is traced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GeometryInstanceFlags {
+    /// Disables face culling for this instance.
+    pub triangle_facing_cull_disable: bool,
+    /// Considers triangles with a clockwise winding to be front-facing, instead of
+    /// counter-clockwise.
+    pub triangle_flip_facing: bool,
+    /// Treats all geometries in this instance as opaque.
+    pub force_opaque: bool,
+    /// Treats all geometries in this instance as non-opaque.
+    pub force_no_opaque: bool,
+}
+
+impl From<GeometryInstanceFlags> for ash::vk::GeometryInstanceFlagsKHR {
+    #[inline]
+    fn from(val: GeometryInstanceFlags) -> Self {
+        let mut result = ash::vk::GeometryInstanceFlagsKHR::empty();
+        if val.triangle_facing_cull_disable {
+            result |= ash::vk::GeometryInstanceFlagsKHR::TRIANGLE_FACING_CULL_DISABLE;
+        }
+        if val.triangle_flip_facing {
+            result |= ash::vk::GeometryInstanceFlagsKHR::TRIANGLE_FLIP_FACING;
+        }
+        if val.force_opaque {
+            result |= ash::vk::GeometryInstanceFlagsKHR::FORCE_OPAQUE;
+        }
+        if val.force_no_opaque {
+            result |= ash::vk::GeometryInstanceFlagsKHR::FORCE_NO_OPAQUE;
+        }
+        result
+    }
+}
+
+/// Error that can happen when creating an acceleration structure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccelerationStructureCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation returned an error other than running out of memory.
+    CreationFailed,
+
+    /// An extension required for acceleration structures wasn't enabled.
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    /// A feature required for acceleration structures wasn't enabled.
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The buffer was not created with the `acceleration_structure_storage` usage.
+    BufferMissingUsage,
+
+    /// A buffer of a geometry was not created with the `device_address` usage.
+    GeometryBufferMissingUsage { geometry_index: usize },
+
+    /// The offset of the buffer is not a multiple of 256.
+    OffsetNotAligned,
+}
+
+impl error::Error for AccelerationStructureCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AccelerationStructureCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::CreationFailed => write!(
+                fmt,
+                "the implementation failed to create the acceleration structure"
+            ),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::BufferMissingUsage => write!(
+                fmt,
+                "the buffer was not created with the acceleration_structure_storage usage"
+            ),
+            Self::GeometryBufferMissingUsage { geometry_index } => write!(
+                fmt,
+                "a buffer of geometry {} was not created with the device_address usage",
+                geometry_index
+            ),
+            Self::OffsetNotAligned => {
+                write!(fmt, "the offset of the buffer is not a multiple of 256")
+            }
+        }
+    }
+}
+
+impl From<OomError> for AccelerationStructureCreationError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<Error> for AccelerationStructureCreationError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => Self::CreationFailed,
+        }
+    }
+}
+
+/// Error that can happen when building an acceleration structure on the host.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccelerationStructureHostBuildError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation returned an error other than running out of memory.
+    BuildFailed,
+
+    /// The deferred operation that the build was deferred to failed.
+    DeferredOperationError(DeferredOperationError),
+
+    /// An extension required for host builds wasn't enabled.
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    /// A feature required for host builds wasn't enabled.
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The type of the destination doesn't match the type of the geometries.
+    DestinationTypeMismatch {
+        /// The type of the destination.
+        destination: AccelerationStructureType,
+        /// The type of acceleration structure that the geometries build.
+        geometries: AccelerationStructureType,
+    },
+
+    /// The buffer of the destination is too small for the geometries.
+    DestinationTooSmall {
+        /// The size that the geometries require.
+        required: DeviceSize,
+        /// The size of the buffer of the destination.
+        obtained: DeviceSize,
+    },
+
+    /// The data of a geometry is smaller than what its vertex, index or primitive count require.
+    GeometryDataTooSmall {
+        /// The index of the geometry.
+        geometry_index: usize,
+    },
+
+    /// The data of a geometry is not aligned as required by its type.
+    GeometryDataNotAligned {
+        /// The index of the geometry.
+        geometry_index: usize,
+        /// The required alignment.
+        alignment: usize,
+    },
+
+    /// The vertex format of a geometry doesn't support the `acceleration_structure_vertex_buffer`
+    /// buffer feature.
+    UnsupportedVertexFormat {
+        /// The index of the geometry.
+        geometry_index: usize,
+        /// The vertex format of the geometry.
+        format: Format,
+    },
+
+    /// The indices of a geometry are of type `U8`, which is not supported for acceleration
+    /// structure builds.
+    UnsupportedIndexType {
+        /// The index of the geometry.
+        geometry_index: usize,
+    },
+}
+
+impl error::Error for AccelerationStructureHostBuildError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            Self::DeferredOperationError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AccelerationStructureHostBuildError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::BuildFailed => write!(
+                fmt,
+                "the implementation failed to build the acceleration structure"
+            ),
+            Self::DeferredOperationError(_) => {
+                write!(fmt, "the deferred operation of the build failed")
+            }
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::DestinationTypeMismatch { .. } => write!(
+                fmt,
+                "the type of the destination doesn't match the type of the geometries"
+            ),
+            Self::DestinationTooSmall { .. } => write!(
+                fmt,
+                "the buffer of the destination is too small for the geometries"
+            ),
+            Self::GeometryDataTooSmall { geometry_index } => write!(
+                fmt,
+                "the data of geometry {} is too small for its vertex, index or primitive count",
+                geometry_index
+            ),
+            Self::GeometryDataNotAligned {
+                geometry_index,
+                alignment,
+            } => write!(
+                fmt,
+                "the data of geometry {} is not aligned to {} bytes",
+                geometry_index, alignment
+            ),
+            Self::UnsupportedVertexFormat { .. } => write!(
+                fmt,
+                "the vertex format of a geometry is not supported for acceleration structure builds",
+            ),
+            Self::UnsupportedIndexType { .. } => write!(
+                fmt,
+                "the index type of a geometry is not supported for acceleration structure builds",
+            ),
+        }
+    }
+}
+
+impl From<OomError> for AccelerationStructureHostBuildError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<DeferredOperationError> for AccelerationStructureHostBuildError {
+    #[inline]
+    fn from(err: DeferredOperationError) -> Self {
+        Self::DeferredOperationError(err)
+    }
+}
+
+impl From<Error> for AccelerationStructureHostBuildError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => Self::BuildFailed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AccelerationStructure;
+    use super::AccelerationStructureBuildGeometryInfo;
+    use super::AccelerationStructureCreationError;
+    use super::AccelerationStructureGeometries;
+    use super::AccelerationStructureGeometryAabbsData;
+    use super::AccelerationStructureHostAabbsData;
+    use super::AccelerationStructureHostBuildError;
+    use super::AccelerationStructureHostBuildGeometryInfo;
+    use super::AccelerationStructureHostGeometries;
+    use super::AccelerationStructureInstance;
+    use super::AccelerationStructureType;
+    use super::BuildAccelerationStructureFlags;
+    use super::GeometryFlags;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use std::mem;
+
+    #[test]
+    fn instance_layout() {
+        assert_eq!(mem::size_of::<AccelerationStructureInstance>(), 64);
+    }
+
+    #[test]
+    fn missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage::all(),
+            false,
+            (0..256).map(|_| 0u8),
+        )
+        .unwrap();
+
+        match AccelerationStructure::new(buffer, AccelerationStructureType::BottomLevel) {
+            Err(AccelerationStructureCreationError::ExtensionNotEnabled {
+                extension: "khr_acceleration_structure",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn build_sizes_geometry_buffer_missing_usage() {
+        let (device, _) = gfx_dev_and_queue_with!(
+            [
+                khr_acceleration_structure,
+                khr_buffer_device_address,
+                khr_deferred_host_operations,
+                ext_descriptor_indexing
+            ],
+            [acceleration_structure, buffer_device_address]
+        );
+
+        let data = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage {
+                acceleration_structure_build_input_read_only: true,
+                ..BufferUsage::none()
+            },
+            false,
+            (0..24).map(|_| 0u8),
+        )
+        .unwrap();
+        let info = AccelerationStructureBuildGeometryInfo {
+            flags: BuildAccelerationStructureFlags::default(),
+            geometries: AccelerationStructureGeometries::Aabbs(vec![
+                AccelerationStructureGeometryAabbsData {
+                    flags: GeometryFlags::default(),
+                    data,
+                    stride: 24,
+                    primitive_count: 1,
+                },
+            ]),
+        };
+
+        match AccelerationStructure::build_sizes(&device, &info) {
+            Err(AccelerationStructureCreationError::GeometryBufferMissingUsage {
+                geometry_index: 0,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn host_build_missing_feature() {
+        let (device, _) = gfx_dev_and_queue_with!(
+            [
+                khr_acceleration_structure,
+                khr_buffer_device_address,
+                khr_deferred_host_operations,
+                ext_descriptor_indexing
+            ],
+            [acceleration_structure, buffer_device_address]
+        );
+
+        let data = [0u64; 3];
+        let info = AccelerationStructureHostBuildGeometryInfo {
+            flags: BuildAccelerationStructureFlags::default(),
+            geometries: AccelerationStructureHostGeometries::Aabbs(vec![
+                AccelerationStructureHostAabbsData {
+                    flags: GeometryFlags::default(),
+                    data: unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, 24) },
+                    stride: 24,
+                    primitive_count: 1,
+                },
+            ]),
+        };
+
+        match AccelerationStructure::host_build_sizes(&device, &info) {
+            Err(AccelerationStructureHostBuildError::FeatureNotEnabled {
+                feature: "acceleration_structure_host_commands",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn host_build_data_too_small() {
+        let (device, _) = gfx_dev_and_queue_with!(
+            [
+                khr_acceleration_structure,
+                khr_buffer_device_address,
+                khr_deferred_host_operations,
+                ext_descriptor_indexing
+            ],
+            [
+                acceleration_structure,
+                acceleration_structure_host_commands,
+                buffer_device_address
+            ]
+        );
+
+        let data = [0u64; 3];
+        let info = AccelerationStructureHostBuildGeometryInfo {
+            flags: BuildAccelerationStructureFlags::default(),
+            geometries: AccelerationStructureHostGeometries::Aabbs(vec![
+                AccelerationStructureHostAabbsData {
+                    flags: GeometryFlags::default(),
+                    data: unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, 24) },
+                    stride: 24,
+                    primitive_count: 2,
+                },
+            ]),
+        };
+
+        match AccelerationStructure::host_build_sizes(&device, &info) {
+            Err(AccelerationStructureHostBuildError::GeometryDataTooSmall {
+                geometry_index: 0,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
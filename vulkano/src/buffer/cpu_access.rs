@@ -37,6 +37,7 @@ use crate::memory::Content;
 use crate::memory::CpuAccess as MemCpuAccess;
 use crate::memory::DedicatedAlloc;
 use crate::memory::DeviceMemoryAllocError;
+use crate::memory::ExternalMemoryHandleTypes;
 use crate::sync::AccessError;
 use crate::sync::Sharing;
 use crate::DeviceSize;
@@ -53,6 +54,7 @@ use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::ops::Range;
 use std::ptr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
@@ -234,7 +236,14 @@ impl<T: ?Sized> CpuAccessibleBuffer<T> {
                 Sharing::Exclusive
             };
 
-            match UnsafeBuffer::new(device.clone(), size, usage, sharing, None) {
+            match UnsafeBuffer::new(
+                device.clone(),
+                size,
+                usage,
+                sharing,
+                None,
+                ExternalMemoryHandleTypes::none(),
+            ) {
                 Ok(b) => b,
                 Err(BufferCreationError::AllocError(err)) => return Err(err),
                 Err(_) => unreachable!(), // We don't use sparse binding, therefore the other
@@ -313,18 +322,7 @@ where
     /// accesses (ie. reads).
     #[inline]
     pub fn read(&self) -> Result<ReadLock<T>, ReadLockError> {
-        let lock = match self.access.try_read() {
-            Some(l) => l,
-            // TODO: if a user simultaneously calls .write(), and write() is currently finding out
-            //       that the buffer is in fact GPU locked, then we will return a CpuWriteLocked
-            //       error instead of a GpuWriteLocked ; is this a problem? how do we fix this?
-            None => return Err(ReadLockError::CpuWriteLocked),
-        };
-
-        if let CurrentGpuAccess::Exclusive { .. } = *lock {
-            return Err(ReadLockError::GpuWriteLocked);
-        }
-
+        let lock = self.read_lock()?;
         let offset = self.memory.offset();
         let range = offset..offset + self.inner.size();
 
@@ -344,6 +342,35 @@ where
     /// that uses it and any attempt to call `read()` will return an error.
     #[inline]
     pub fn write(&self) -> Result<WriteLock<T>, WriteLockError> {
+        let lock = self.write_lock()?;
+        let offset = self.memory.offset();
+        let range = offset..offset + self.inner.size();
+
+        Ok(WriteLock {
+            inner: unsafe { self.memory.mapped_memory().unwrap().read_write(range) },
+            lock: lock,
+        })
+    }
+
+    #[inline]
+    fn read_lock(&self) -> Result<RwLockReadGuard<CurrentGpuAccess>, ReadLockError> {
+        let lock = match self.access.try_read() {
+            Some(l) => l,
+            // TODO: if a user simultaneously calls .write(), and write() is currently finding out
+            //       that the buffer is in fact GPU locked, then we will return a CpuWriteLocked
+            //       error instead of a GpuWriteLocked ; is this a problem? how do we fix this?
+            None => return Err(ReadLockError::CpuWriteLocked),
+        };
+
+        if let CurrentGpuAccess::Exclusive { .. } = *lock {
+            return Err(ReadLockError::GpuWriteLocked);
+        }
+
+        Ok(lock)
+    }
+
+    #[inline]
+    fn write_lock(&self) -> Result<RwLockWriteGuard<CurrentGpuAccess>, WriteLockError> {
         let lock = match self.access.try_write() {
             Some(l) => l,
             // TODO: if a user simultaneously calls .read() or .write(), and the function is
@@ -358,14 +385,67 @@ where
             _ => return Err(WriteLockError::GpuLocked),
         }
 
-        let offset = self.memory.offset();
-        let range = offset..offset + self.inner.size();
+        Ok(lock)
+    }
+}
+
+impl<T, A> CpuAccessibleBuffer<[T], A>
+where
+    T: 'static,
+    A: MemoryPoolAlloc,
+{
+    /// Locks the buffer in order to read the elements in `range` from the CPU.
+    ///
+    /// This behaves like [`read`](Self::read), but only the given range of elements is accessed.
+    /// For memory that is not host-coherent, only that range is invalidated, which is cheaper for
+    /// large buffers. The range is extended as needed to satisfy the `non_coherent_atom_size` of
+    /// the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `range` is out of bounds of the buffer.
+    #[inline]
+    pub fn read_range(&self, range: Range<DeviceSize>) -> Result<ReadLock<[T]>, ReadLockError> {
+        let byte_range = self.byte_range(range);
+        let lock = self.read_lock()?;
+
+        Ok(ReadLock {
+            inner: unsafe { self.memory.mapped_memory().unwrap().read_write(byte_range) },
+            lock: lock,
+        })
+    }
+
+    /// Locks the buffer in order to write the elements in `range` from the CPU.
+    ///
+    /// This behaves like [`write`](Self::write), but only the given range of elements is
+    /// accessed. For memory that is not host-coherent, only that range is flushed when the lock is
+    /// dropped, which is cheaper for large buffers. The range is extended as needed to satisfy the
+    /// `non_coherent_atom_size` of the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `range` is out of bounds of the buffer.
+    #[inline]
+    pub fn write_range(&self, range: Range<DeviceSize>) -> Result<WriteLock<[T]>, WriteLockError> {
+        let byte_range = self.byte_range(range);
+        let lock = self.write_lock()?;
 
         Ok(WriteLock {
-            inner: unsafe { self.memory.mapped_memory().unwrap().read_write(range) },
+            inner: unsafe { self.memory.mapped_memory().unwrap().read_write(byte_range) },
             lock: lock,
         })
     }
+
+    // Converts a range of elements into a range of bytes in the memory.
+    #[inline]
+    fn byte_range(&self, range: Range<DeviceSize>) -> Range<DeviceSize> {
+        let element_size = mem::size_of::<T>() as DeviceSize;
+        assert!(range.start <= range.end);
+        assert!(range.end * element_size <= self.inner.size());
+
+        let offset = self.memory.offset();
+        offset + range.start * element_size..offset + range.end * element_size
+    }
 }
 
 unsafe impl<T: ?Sized, A> BufferAccess for CpuAccessibleBuffer<T, A>
@@ -668,4 +748,19 @@ mod tests {
         let _ = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, EMPTY);
         let _ = CpuAccessibleBuffer::from_iter(device, BufferUsage::all(), false, EMPTY.iter());
     }
+
+    #[test]
+    fn read_write_range() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_iter(device, BufferUsage::all(), false, 0..16u32).unwrap();
+
+        buffer
+            .write_range(4..8)
+            .unwrap()
+            .copy_from_slice(&[40, 50, 60, 70]);
+        assert_eq!(&*buffer.read_range(3..9).unwrap(), &[3, 40, 50, 60, 70, 8]);
+        assert_eq!(buffer.read().unwrap()[15], 15);
+    }
 }
@@ -25,6 +25,7 @@ use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPool;
 use crate::memory::DedicatedAlloc;
 use crate::memory::DeviceMemoryAllocError;
+use crate::memory::ExternalMemoryHandleTypes;
 use crate::sync::AccessError;
 use crate::sync::Sharing;
 use crate::DeviceSize;
@@ -383,6 +384,7 @@ where
                     self.usage,
                     Sharing::Exclusive::<iter::Empty<_>>,
                     None,
+                    ExternalMemoryHandleTypes::none(),
                 ) {
                     Ok(b) => b,
                     Err(BufferCreationError::AllocError(err)) => return Err(err),
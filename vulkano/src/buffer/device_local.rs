@@ -39,17 +39,12 @@ use crate::memory::pool::MemoryPoolAlloc;
 use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPoolAlloc;
 use crate::memory::DeviceMemoryAllocError;
+use crate::memory::ExternalMemoryHandleTypes;
 use crate::memory::{DedicatedAlloc, MemoryRequirements};
 use crate::sync::AccessError;
 use crate::sync::Sharing;
 use crate::DeviceSize;
 use smallvec::SmallVec;
-use std::hash::Hash;
-use std::hash::Hasher;
-use std::marker::PhantomData;
-use std::mem;
-use std::sync::Arc;
-use std::sync::Mutex;
 #[cfg(any(
     target_os = "linux",
     target_os = "dragonflybsd",
@@ -57,7 +52,13 @@ use std::sync::Mutex;
     target_os = "netbsd",
     target_os = "openbsd"
 ))]
-use {crate::memory::ExternalMemoryHandleTypes, std::fs::File};
+use std::fs::File;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker::PhantomData;
+use std::mem;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 /// Buffer whose content is in device-local memory.
 ///
@@ -160,7 +161,13 @@ impl<T: ?Sized> DeviceLocalBuffer<T> {
             .map(|f| f.id())
             .collect::<SmallVec<[u32; 4]>>();
 
-        let (buffer, mem_reqs) = Self::build_buffer(&device, size, usage, &queue_families)?;
+        let (buffer, mem_reqs) = Self::build_buffer(
+            &device,
+            size,
+            usage,
+            &queue_families,
+            ExternalMemoryHandleTypes::none(),
+        )?;
 
         let mem = MemoryPool::alloc_from_requirements(
             &Device::standard_pool(&device),
@@ -213,7 +220,13 @@ impl<T: ?Sized> DeviceLocalBuffer<T> {
             .map(|f| f.id())
             .collect::<SmallVec<[u32; 4]>>();
 
-        let (buffer, mem_reqs) = Self::build_buffer(&device, size, usage, &queue_families)?;
+        let (buffer, mem_reqs) = Self::build_buffer(
+            &device,
+            size,
+            usage,
+            &queue_families,
+            ExternalMemoryHandleTypes::posix(),
+        )?;
 
         let mem = alloc_dedicated_with_exportable_fd(
             device.clone(),
@@ -247,6 +260,7 @@ impl<T: ?Sized> DeviceLocalBuffer<T> {
         size: DeviceSize,
         usage: BufferUsage,
         queue_families: &SmallVec<[u32; 4]>,
+        external_memory_handle_types: ExternalMemoryHandleTypes,
     ) -> Result<(UnsafeBuffer, MemoryRequirements), DeviceMemoryAllocError> {
         let (buffer, mem_reqs) = {
             let sharing = if queue_families.len() >= 2 {
@@ -255,11 +269,27 @@ impl<T: ?Sized> DeviceLocalBuffer<T> {
                 Sharing::Exclusive
             };
 
-            match UnsafeBuffer::new(device.clone(), size, usage, sharing, None) {
+            match UnsafeBuffer::new(
+                device.clone(),
+                size,
+                usage,
+                sharing,
+                None,
+                external_memory_handle_types,
+            ) {
                 Ok(b) => b,
                 Err(BufferCreationError::AllocError(err)) => return Err(err),
-                Err(_) => unreachable!(), // We don't use sparse binding, therefore the other
-                                          // errors can't happen
+                Err(BufferCreationError::ExtensionNotEnabled { extension, .. }) => {
+                    return Err(DeviceMemoryAllocError::MissingExtension(extension))
+                }
+                Err(BufferCreationError::DeviceAddressFeatureNotEnabled) => {
+                    return Err(DeviceMemoryAllocError::ImplicitSpecViolation(
+                        "the buffer_device_address feature must be enabled to use the \
+                        device_address usage",
+                    ))
+                }
+                // We don't use sparse binding, therefore the other errors can't happen.
+                Err(_) => unreachable!(),
             }
         };
         Ok((buffer, mem_reqs))
@@ -433,3 +463,25 @@ where
         self.size().hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DeviceLocalBuffer;
+    use crate::buffer::BufferUsage;
+    use crate::memory::DeviceMemoryAllocError;
+
+    #[test]
+    fn missing_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let usage = BufferUsage {
+            conditional_rendering: true,
+            ..BufferUsage::none()
+        };
+
+        match DeviceLocalBuffer::<u32>::new(device, usage, Some(queue.family())) {
+            Err(DeviceMemoryAllocError::MissingExtension("ext_conditional_rendering")) => (),
+            _ => panic!(),
+        }
+    }
+}
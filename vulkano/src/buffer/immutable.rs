@@ -43,6 +43,7 @@ use crate::memory::pool::PotentialDedicatedAllocation;
 use crate::memory::pool::StdMemoryPoolAlloc;
 use crate::memory::DedicatedAlloc;
 use crate::memory::DeviceMemoryAllocError;
+use crate::memory::ExternalMemoryHandleTypes;
 use crate::sync::AccessError;
 use crate::sync::NowFuture;
 use crate::sync::Sharing;
@@ -305,7 +306,14 @@ impl<T: ?Sized> ImmutableBuffer<T> {
                 Sharing::Exclusive
             };
 
-            match UnsafeBuffer::new(device.clone(), size, usage, sharing, None) {
+            match UnsafeBuffer::new(
+                device.clone(),
+                size,
+                usage,
+                sharing,
+                None,
+                ExternalMemoryHandleTypes::none(),
+            ) {
                 Ok(b) => b,
                 Err(BufferCreationError::AllocError(err)) => return Err(err),
                 Err(_) => unreachable!(), // We don't use sparse binding, therefore the other
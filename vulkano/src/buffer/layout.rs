@@ -0,0 +1,177 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::mem;
+
+/// A type whose layout in memory matches the layout of an equivalent GLSL type, following the
+/// `std140` and/or `std430` layout rules of uniform and storage buffers.
+///
+/// This trait is implemented for the 32- and 64-bit scalar types, for arrays of types that
+/// implement it, and for the `nalgebra` vector and matrix types if the `nalgebra` feature is
+/// enabled. For
+/// structs, it should be derived with the `BufferContents` derive macro of `vulkano-shaders`,
+/// which checks at compile time that the offset of every field matches the offset that GLSL
+/// gives to it.
+///
+/// Note that a Rust array is laid out like a GLSL array, not like a GLSL vector. In particular,
+/// arrays of scalars can't be used with the `std140` layout, because each element of such an
+/// array is padded to 16 bytes.
+///
+/// # Safety
+///
+/// - If `STD140_ALIGNMENT` is `Some`, then the type must have the same size and layout as the
+///   equivalent GLSL type with the `std140` layout rules, and the value must be the base
+///   alignment of the GLSL type with these rules.
+/// - The same applies to `STD430_ALIGNMENT` with the `std430` layout rules.
+pub unsafe trait BufferContents {
+    /// The base alignment of the type with the `std140` layout rules, or `None` if the type can't
+    /// be used with these rules.
+    const STD140_ALIGNMENT: Option<usize>;
+
+    /// The base alignment of the type with the `std430` layout rules, or `None` if the type can't
+    /// be used with these rules.
+    const STD430_ALIGNMENT: Option<usize>;
+}
+
+macro_rules! impl_buffer_contents_scalar {
+    ($($ty:ty),+) => {
+        $(
+            unsafe impl BufferContents for $ty {
+                const STD140_ALIGNMENT: Option<usize> = Some(mem::size_of::<$ty>());
+                const STD430_ALIGNMENT: Option<usize> = Some(mem::size_of::<$ty>());
+            }
+        )+
+    };
+}
+
+impl_buffer_contents_scalar!(i32, u32, f32, i64, u64, f64);
+
+unsafe impl<T, const N: usize> BufferContents for [T; N]
+where
+    T: BufferContents,
+{
+    // With `std140`, the alignment of an array is rounded up to that of a `vec4`.
+    const STD140_ALIGNMENT: Option<usize> = match T::STD140_ALIGNMENT {
+        Some(alignment) => array_alignment(round_up(alignment, 16), mem::size_of::<T>()),
+        None => None,
+    };
+
+    const STD430_ALIGNMENT: Option<usize> = match T::STD430_ALIGNMENT {
+        Some(alignment) => array_alignment(alignment, mem::size_of::<T>()),
+        None => None,
+    };
+}
+
+// The elements of a Rust array are `element_size` bytes apart, while GLSL rounds the stride up to
+// the alignment of the array. The layouts only match if no rounding is needed.
+const fn array_alignment(alignment: usize, element_size: usize) -> Option<usize> {
+    if element_size % alignment == 0 {
+        Some(alignment)
+    } else {
+        None
+    }
+}
+
+const fn round_up(value: usize, alignment: usize) -> usize {
+    (value + alignment - 1) / alignment * alignment
+}
+
+macro_rules! impl_buffer_contents_nalgebra_vector {
+    ($ty:ident, $num:expr) => {
+        #[cfg(feature = "nalgebra")]
+        unsafe impl<T> BufferContents for nalgebra::$ty<T>
+        where
+            T: BufferContents + nalgebra::Scalar,
+        {
+            const STD140_ALIGNMENT: Option<usize> = match T::STD140_ALIGNMENT {
+                Some(alignment) => Some(alignment * $num),
+                None => None,
+            };
+
+            const STD430_ALIGNMENT: Option<usize> = match T::STD430_ALIGNMENT {
+                Some(alignment) => Some(alignment * $num),
+                None => None,
+            };
+        }
+    };
+}
+
+// A `vec3` has the same alignment as a `vec4`.
+impl_buffer_contents_nalgebra_vector!(Vector2, 2);
+impl_buffer_contents_nalgebra_vector!(Vector3, 4);
+impl_buffer_contents_nalgebra_vector!(Vector4, 4);
+
+// A GLSL matrix is laid out like an array of its column vectors, and an `nalgebra` matrix stores
+// its columns one after the other, so they only match when the columns need no padding.
+macro_rules! impl_buffer_contents_nalgebra_matrix {
+    ($ty:ident, $rows:expr, $num:expr) => {
+        #[cfg(feature = "nalgebra")]
+        unsafe impl<T> BufferContents for nalgebra::$ty<T>
+        where
+            T: BufferContents + nalgebra::Scalar,
+        {
+            const STD140_ALIGNMENT: Option<usize> = match T::STD140_ALIGNMENT {
+                Some(alignment) => {
+                    array_alignment(round_up(alignment * $num, 16), mem::size_of::<T>() * $rows)
+                }
+                None => None,
+            };
+
+            const STD430_ALIGNMENT: Option<usize> = match T::STD430_ALIGNMENT {
+                Some(alignment) => array_alignment(alignment * $num, mem::size_of::<T>() * $rows),
+                None => None,
+            };
+        }
+    };
+}
+
+// The first number is the number of rows, which is the size of a column vector, and the second
+// is the alignment of that vector in scalars.
+impl_buffer_contents_nalgebra_matrix!(Matrix2, 2, 2);
+impl_buffer_contents_nalgebra_matrix!(Matrix2x3, 2, 2);
+impl_buffer_contents_nalgebra_matrix!(Matrix2x4, 2, 2);
+impl_buffer_contents_nalgebra_matrix!(Matrix3x2, 3, 4);
+impl_buffer_contents_nalgebra_matrix!(Matrix3, 3, 4);
+impl_buffer_contents_nalgebra_matrix!(Matrix3x4, 3, 4);
+impl_buffer_contents_nalgebra_matrix!(Matrix4x2, 4, 4);
+impl_buffer_contents_nalgebra_matrix!(Matrix4x3, 4, 4);
+impl_buffer_contents_nalgebra_matrix!(Matrix4, 4, 4);
+
+#[cfg(test)]
+mod tests {
+    use super::BufferContents;
+
+    #[test]
+    fn arrays() {
+        assert_eq!(<[f32; 4]>::STD140_ALIGNMENT, None);
+        assert_eq!(<[f32; 4]>::STD430_ALIGNMENT, Some(4));
+        assert_eq!(<[f64; 2]>::STD430_ALIGNMENT, Some(8));
+        assert_eq!(<[[f32; 3]; 2]>::STD430_ALIGNMENT, Some(4));
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_matrices() {
+        use nalgebra::{Matrix2, Matrix3, Matrix4, Matrix4x3};
+
+        assert_eq!(Matrix4::<f32>::STD140_ALIGNMENT, Some(16));
+        assert_eq!(Matrix4::<f32>::STD430_ALIGNMENT, Some(16));
+        assert_eq!(Matrix4x3::<f32>::STD140_ALIGNMENT, Some(16));
+        assert_eq!(Matrix4::<f64>::STD430_ALIGNMENT, Some(32));
+
+        // The columns of a `mat2` are padded to 16 bytes with `std140`.
+        assert_eq!(Matrix2::<f32>::STD140_ALIGNMENT, None);
+        assert_eq!(Matrix2::<f32>::STD430_ALIGNMENT, Some(8));
+        assert_eq!(Matrix2::<f64>::STD140_ALIGNMENT, Some(16));
+
+        // The columns of a `mat3` are padded to 16 bytes with both layouts.
+        assert_eq!(Matrix3::<f32>::STD140_ALIGNMENT, None);
+        assert_eq!(Matrix3::<f32>::STD430_ALIGNMENT, None);
+    }
+}
@@ -87,10 +87,12 @@ pub use self::cpu_access::CpuAccessibleBuffer;
 pub use self::cpu_pool::CpuBufferPool;
 pub use self::device_local::DeviceLocalBuffer;
 pub use self::immutable::ImmutableBuffer;
+pub use self::layout::BufferContents;
 pub use self::slice::BufferSlice;
 pub use self::sys::BufferCreationError;
 pub use self::traits::BufferAccess;
 pub use self::traits::BufferInner;
+pub use self::traits::DeviceAddress;
 pub use self::traits::TypedBufferAccess;
 pub use self::usage::BufferUsage;
 pub use self::view::BufferView;
@@ -103,6 +105,7 @@ pub mod immutable;
 pub mod sys;
 pub mod view;
 
+mod layout;
 mod slice;
 mod traits;
 mod usage;
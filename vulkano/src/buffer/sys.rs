@@ -29,6 +29,7 @@ use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocError;
+use crate::memory::ExternalMemoryHandleTypes;
 use crate::memory::MemoryRequirements;
 use crate::sync::Sharing;
 use crate::DeviceSize;
@@ -59,6 +60,9 @@ impl UnsafeBuffer {
     ///
     /// See the module's documentation for information about safety.
     ///
+    /// If `external_memory_handle_types` is not empty, the buffer can be bound to memory that is
+    /// exported to or imported from those types of external handles.
+    ///
     /// # Panic
     ///
     /// - Panics if `sparse.sparse` is false and `sparse.sparse_residency` or `sparse.sparse_aliased` is true.
@@ -70,6 +74,7 @@ impl UnsafeBuffer {
         mut usage: BufferUsage,
         sharing: Sharing<I>,
         sparse: Option<SparseLevel>,
+        external_memory_handle_types: ExternalMemoryHandleTypes,
     ) -> Result<(UnsafeBuffer, MemoryRequirements), BufferCreationError>
     where
         I: IntoIterator<Item = u32>,
@@ -114,6 +119,39 @@ impl UnsafeBuffer {
             }
         }
 
+        if usage.conditional_rendering && !device.enabled_extensions().ext_conditional_rendering {
+            return Err(BufferCreationError::ExtensionNotEnabled {
+                extension: "ext_conditional_rendering",
+                reason: "usage.conditional_rendering was set",
+            });
+        }
+
+        if (usage.transform_feedback_buffer || usage.transform_feedback_counter_buffer)
+            && !device.enabled_extensions().ext_transform_feedback
+        {
+            return Err(BufferCreationError::ExtensionNotEnabled {
+                extension: "ext_transform_feedback",
+                reason: "usage.transform_feedback_buffer or usage.transform_feedback_counter_buffer was set",
+            });
+        }
+
+        if (usage.acceleration_structure_build_input_read_only
+            || usage.acceleration_structure_storage)
+            && !device.enabled_extensions().khr_acceleration_structure
+        {
+            return Err(BufferCreationError::ExtensionNotEnabled {
+                extension: "khr_acceleration_structure",
+                reason: "usage.acceleration_structure_build_input_read_only or usage.acceleration_structure_storage was set",
+            });
+        }
+
+        if usage.shader_binding_table && !device.enabled_extensions().khr_ray_tracing_pipeline {
+            return Err(BufferCreationError::ExtensionNotEnabled {
+                extension: "khr_ray_tracing_pipeline",
+                reason: "usage.shader_binding_table was set",
+            });
+        }
+
         let usage_bits = ash::vk::BufferUsageFlags::from(usage);
         // Checking for empty BufferUsage.
         assert!(
@@ -121,6 +159,24 @@ impl UnsafeBuffer {
             "Can't create buffer with empty BufferUsage"
         );
 
+        let external_memory_buffer_create_info = if !external_memory_handle_types.is_empty() {
+            if !(device.api_version() >= Version::V1_1
+                || device.enabled_extensions().khr_external_memory)
+            {
+                return Err(BufferCreationError::ExtensionNotEnabled {
+                    extension: "khr_external_memory",
+                    reason: "one or more fields of external_memory_handle_types were set",
+                });
+            }
+
+            Some(ash::vk::ExternalMemoryBufferCreateInfo {
+                handle_types: external_memory_handle_types.into(),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
         let buffer = {
             let (sh_mode, sh_indices) = match sharing {
                 Sharing::Exclusive => {
@@ -132,6 +188,9 @@ impl UnsafeBuffer {
             };
 
             let infos = ash::vk::BufferCreateInfo {
+                p_next: external_memory_buffer_create_info
+                    .as_ref()
+                    .map_or(ptr::null(), |info| info as *const _ as *const _),
                 flags,
                 size,
                 usage: usage_bits,
@@ -201,8 +260,8 @@ impl UnsafeBuffer {
 
                 let mut out = MemoryRequirements::from(output.memory_requirements);
                 if let Some(output2) = output2 {
-                    debug_assert_eq!(output2.requires_dedicated_allocation, 0);
                     out.prefer_dedicated = output2.prefers_dedicated_allocation != 0;
+                    out.require_dedicated = output2.requires_dedicated_allocation != 0;
                 }
                 out
             } else {
@@ -425,6 +484,11 @@ pub enum BufferCreationError {
     SparseResidencyAliasedFeatureNotEnabled,
     /// Device address was requested but the corresponding feature wasn't enabled.
     DeviceAddressFeatureNotEnabled,
+    /// An extension required for the requested buffer wasn't enabled.
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
 }
 
 impl error::Error for BufferCreationError {
@@ -440,25 +504,30 @@ impl error::Error for BufferCreationError {
 impl fmt::Display for BufferCreationError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(
-            fmt,
-            "{}",
-            match *self {
-                BufferCreationError::AllocError(_) => "allocating memory failed",
-                BufferCreationError::SparseBindingFeatureNotEnabled => {
-                    "sparse binding was requested but the corresponding feature wasn't enabled"
-                }
-                BufferCreationError::SparseResidencyBufferFeatureNotEnabled => {
-                    "sparse residency was requested but the corresponding feature wasn't enabled"
-                }
-                BufferCreationError::SparseResidencyAliasedFeatureNotEnabled => {
-                    "sparse aliasing was requested but the corresponding feature wasn't enabled"
-                }
-                BufferCreationError::DeviceAddressFeatureNotEnabled => {
-                    "device address was requested but the corresponding feature wasn't enabled"
-                }
-            }
-        )
+        match *self {
+            BufferCreationError::AllocError(_) => write!(fmt, "allocating memory failed"),
+            BufferCreationError::SparseBindingFeatureNotEnabled => write!(
+                fmt,
+                "sparse binding was requested but the corresponding feature wasn't enabled"
+            ),
+            BufferCreationError::SparseResidencyBufferFeatureNotEnabled => write!(
+                fmt,
+                "sparse residency was requested but the corresponding feature wasn't enabled"
+            ),
+            BufferCreationError::SparseResidencyAliasedFeatureNotEnabled => write!(
+                fmt,
+                "sparse aliasing was requested but the corresponding feature wasn't enabled"
+            ),
+            BufferCreationError::DeviceAddressFeatureNotEnabled => write!(
+                fmt,
+                "device address was requested but the corresponding feature wasn't enabled"
+            ),
+            BufferCreationError::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+        }
     }
 }
 
@@ -495,6 +564,7 @@ mod tests {
 
     use crate::device::Device;
     use crate::device::DeviceOwned;
+    use crate::memory::ExternalMemoryHandleTypes;
     use crate::sync::Sharing;
 
     #[test]
@@ -507,6 +577,7 @@ mod tests {
                 BufferUsage::all(),
                 Sharing::Exclusive::<Empty<_>>,
                 None,
+                ExternalMemoryHandleTypes::none(),
             )
         }
         .unwrap();
@@ -527,6 +598,7 @@ mod tests {
                 BufferUsage::all(),
                 Sharing::Exclusive::<Empty<_>>,
                 sparse,
+                ExternalMemoryHandleTypes::none(),
             ) {
                 Err(BufferCreationError::SparseBindingFeatureNotEnabled) => (),
                 _ => panic!(),
@@ -548,6 +620,7 @@ mod tests {
                 BufferUsage::all(),
                 Sharing::Exclusive::<Empty<_>>,
                 sparse,
+                ExternalMemoryHandleTypes::none(),
             ) {
                 Err(BufferCreationError::SparseResidencyBufferFeatureNotEnabled) => (),
                 _ => panic!(),
@@ -569,6 +642,7 @@ mod tests {
                 BufferUsage::all(),
                 Sharing::Exclusive::<Empty<_>>,
                 sparse,
+                ExternalMemoryHandleTypes::none(),
             ) {
                 Err(BufferCreationError::SparseResidencyAliasedFeatureNotEnabled) => (),
                 _ => panic!(),
@@ -587,6 +661,7 @@ mod tests {
                 BufferUsage::all(),
                 Sharing::Exclusive::<Empty<_>>,
                 None,
+                ExternalMemoryHandleTypes::none(),
             );
         };
     }
@@ -15,6 +15,7 @@ use crate::memory::Content;
 use crate::sync::AccessError;
 use crate::DeviceSize;
 use crate::SafeDeref;
+use crate::Version;
 use crate::VulkanObject;
 use std::hash::Hash;
 use std::hash::Hasher;
@@ -126,18 +127,69 @@ pub unsafe trait BufferAccess: DeviceOwned + Send + Sync {
                 buffer: inner.buffer.internal_object(),
                 ..Default::default()
             };
-            let ptr = dev
-                .fns()
-                .ext_buffer_device_address
-                .get_buffer_device_address_ext(dev.internal_object(), &info);
+            let fns = dev.fns();
+            let ptr = if dev.api_version() >= Version::V1_2 {
+                fns.v1_2
+                    .get_buffer_device_address(dev.internal_object(), &info)
+            } else if dev.enabled_extensions().khr_buffer_device_address {
+                fns.khr_buffer_device_address
+                    .get_buffer_device_address_khr(dev.internal_object(), &info)
+            } else {
+                fns.ext_buffer_device_address
+                    .get_buffer_device_address_ext(dev.internal_object(), &info)
+            };
 
             if ptr == 0 {
-                panic!("got null ptr from a valid GetBufferDeviceAddressEXT call");
+                panic!("got null ptr from a valid GetBufferDeviceAddress call");
             }
 
             Ok(NonZeroU64::new_unchecked(ptr + inner.offset))
         }
     }
+
+    /// Returns the address of this buffer on the device, which can be stored in other buffers
+    /// and dereferenced in shaders.
+    ///
+    /// The buffer must have been created with the `device_address` usage, which requires the
+    /// `buffer_device_address` feature to be enabled on the device.
+    #[inline]
+    fn device_address(&self) -> Result<DeviceAddress, DeviceAddressUsageNotEnabledError> {
+        self.raw_device_address().map(DeviceAddress::from)
+    }
+}
+
+/// The address of a buffer on the device, as returned by [`BufferAccess::device_address`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct DeviceAddress(u64);
+
+impl DeviceAddress {
+    /// Returns the address as a plain integer.
+    #[inline]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<NonZeroU64> for DeviceAddress {
+    #[inline]
+    fn from(val: NonZeroU64) -> Self {
+        DeviceAddress(val.get())
+    }
+}
+
+impl From<u64> for DeviceAddress {
+    #[inline]
+    fn from(val: u64) -> Self {
+        DeviceAddress(val)
+    }
+}
+
+impl From<DeviceAddress> for u64 {
+    #[inline]
+    fn from(val: DeviceAddress) -> Self {
+        val.0
+    }
 }
 
 /// Inner information about a buffer.
@@ -29,6 +29,24 @@ pub struct BufferUsage {
     /// Requires the `buffer_device_address` feature. If that feature is not enabled, this will
     /// be silently ignored.
     pub device_address: bool,
+    /// Allows the buffer to be used as the predicate for conditional rendering. Requires the
+    /// `ext_conditional_rendering` extension.
+    pub conditional_rendering: bool,
+    /// Allows the buffer to be used as a transform feedback buffer. Requires the
+    /// `ext_transform_feedback` extension.
+    pub transform_feedback_buffer: bool,
+    /// Allows the buffer to be used as a transform feedback counter buffer. Requires the
+    /// `ext_transform_feedback` extension.
+    pub transform_feedback_counter_buffer: bool,
+    /// Allows the buffer to be used as read-only input to an acceleration structure build.
+    /// Requires the `khr_acceleration_structure` extension.
+    pub acceleration_structure_build_input_read_only: bool,
+    /// Allows the buffer to be used as the storage of an acceleration structure. Requires the
+    /// `khr_acceleration_structure` extension.
+    pub acceleration_structure_storage: bool,
+    /// Allows the buffer to be used as a shader binding table. Requires the
+    /// `khr_ray_tracing_pipeline` extension.
+    pub shader_binding_table: bool,
 }
 
 impl BufferUsage {
@@ -46,10 +64,17 @@ impl BufferUsage {
             vertex_buffer: false,
             indirect_buffer: false,
             device_address: false,
+            conditional_rendering: false,
+            transform_feedback_buffer: false,
+            transform_feedback_counter_buffer: false,
+            acceleration_structure_build_input_read_only: false,
+            acceleration_structure_storage: false,
+            shader_binding_table: false,
         }
     }
 
-    /// Builds a `BufferUsage` with all values set to true. Can be used for quick prototyping.
+    /// Builds a `BufferUsage` with all values set to true, except for the ones that require an
+    /// extension. Can be used for quick prototyping.
     #[inline]
     pub const fn all() -> BufferUsage {
         BufferUsage {
@@ -63,6 +88,12 @@ impl BufferUsage {
             vertex_buffer: true,
             indirect_buffer: true,
             device_address: true,
+            conditional_rendering: false,
+            transform_feedback_buffer: false,
+            transform_feedback_counter_buffer: false,
+            acceleration_structure_build_input_read_only: false,
+            acceleration_structure_storage: false,
+            shader_binding_table: false,
         }
     }
 
@@ -215,6 +246,24 @@ impl From<BufferUsage> for ash::vk::BufferUsageFlags {
         if val.device_address {
             result |= ash::vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
         }
+        if val.conditional_rendering {
+            result |= ash::vk::BufferUsageFlags::CONDITIONAL_RENDERING_EXT;
+        }
+        if val.transform_feedback_buffer {
+            result |= ash::vk::BufferUsageFlags::TRANSFORM_FEEDBACK_BUFFER_EXT;
+        }
+        if val.transform_feedback_counter_buffer {
+            result |= ash::vk::BufferUsageFlags::TRANSFORM_FEEDBACK_COUNTER_BUFFER_EXT;
+        }
+        if val.acceleration_structure_build_input_read_only {
+            result |= ash::vk::BufferUsageFlags::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR;
+        }
+        if val.acceleration_structure_storage {
+            result |= ash::vk::BufferUsageFlags::ACCELERATION_STRUCTURE_STORAGE_KHR;
+        }
+        if val.shader_binding_table {
+            result |= ash::vk::BufferUsageFlags::SHADER_BINDING_TABLE_KHR;
+        }
         result
     }
 }
@@ -235,6 +284,17 @@ impl BitOr for BufferUsage {
             vertex_buffer: self.vertex_buffer || rhs.vertex_buffer,
             indirect_buffer: self.indirect_buffer || rhs.indirect_buffer,
             device_address: self.device_address || rhs.device_address,
+            conditional_rendering: self.conditional_rendering || rhs.conditional_rendering,
+            transform_feedback_buffer: self.transform_feedback_buffer
+                || rhs.transform_feedback_buffer,
+            transform_feedback_counter_buffer: self.transform_feedback_counter_buffer
+                || rhs.transform_feedback_counter_buffer,
+            acceleration_structure_build_input_read_only: self
+                .acceleration_structure_build_input_read_only
+                || rhs.acceleration_structure_build_input_read_only,
+            acceleration_structure_storage: self.acceleration_structure_storage
+                || rhs.acceleration_structure_storage,
+            shader_binding_table: self.shader_binding_table || rhs.shader_binding_table,
         }
     }
 }
@@ -7,6 +7,8 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use crate::acceleration_structure::AccelerationStructure;
+use crate::acceleration_structure::AccelerationStructureBuildGeometryInfo;
 use crate::buffer::BufferAccess;
 use crate::buffer::TypedBufferAccess;
 use crate::command_buffer::pool::standard::StandardCommandPoolAlloc;
@@ -14,6 +16,8 @@ use crate::command_buffer::pool::standard::StandardCommandPoolBuilder;
 use crate::command_buffer::pool::CommandPool;
 use crate::command_buffer::pool::CommandPoolAlloc;
 use crate::command_buffer::pool::CommandPoolBuilderAlloc;
+use crate::command_buffer::pool::CommandPoolCreationError;
+use crate::command_buffer::pool::StandardCommandPool;
 use crate::command_buffer::synced::CommandBufferState;
 use crate::command_buffer::synced::SyncCommandBuffer;
 use crate::command_buffer::synced::SyncCommandBufferBuilder;
@@ -35,6 +39,7 @@ use crate::command_buffer::DrawIndexedIndirectCommand;
 use crate::command_buffer::DrawIndirectCommand;
 use crate::command_buffer::ImageUninitializedSafe;
 use crate::command_buffer::PrimaryCommandBuffer;
+use crate::command_buffer::RenderingInfo;
 use crate::command_buffer::SecondaryCommandBuffer;
 use crate::command_buffer::SubpassContents;
 use crate::descriptor_set::{check_descriptor_write, DescriptorSetsCollection, WriteDescriptorSet};
@@ -43,32 +48,41 @@ use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::device::Queue;
 use crate::format::ClearValue;
+use crate::format::Format;
 use crate::format::NumericType;
 use crate::format::Pixel;
 use crate::image::attachment::ClearAttachment;
 use crate::image::attachment::ClearRect;
+use crate::image::immutable::SubImage;
 use crate::image::ImageAccess;
 use crate::image::ImageAspect;
 use crate::image::ImageAspects;
 use crate::image::ImageLayout;
+use crate::image::ImageViewAbstract;
+use crate::image::SampleCount;
 use crate::pipeline::graphics::color_blend::LogicOp;
 use crate::pipeline::graphics::depth_stencil::CompareOp;
 use crate::pipeline::graphics::depth_stencil::StencilFaces;
 use crate::pipeline::graphics::depth_stencil::StencilOp;
+use crate::pipeline::graphics::fragment_shading_rate::FragmentShadingRateCombinerOp;
 use crate::pipeline::graphics::input_assembly::Index;
 use crate::pipeline::graphics::input_assembly::IndexType;
 use crate::pipeline::graphics::input_assembly::PrimitiveTopology;
 use crate::pipeline::graphics::rasterization::CullMode;
 use crate::pipeline::graphics::rasterization::FrontFace;
+use crate::pipeline::graphics::render_pass::PipelineRenderPassType;
+use crate::pipeline::graphics::render_pass::PipelineRenderingCreateInfo;
 use crate::pipeline::graphics::vertex_input::VertexBuffersCollection;
 use crate::pipeline::graphics::viewport::Scissor;
 use crate::pipeline::graphics::viewport::Viewport;
 use crate::pipeline::layout::PipelineLayout;
+use crate::pipeline::ray_tracing::ShaderBindingTable;
 use crate::pipeline::ComputePipeline;
 use crate::pipeline::DynamicState;
 use crate::pipeline::GraphicsPipeline;
 use crate::pipeline::Pipeline;
 use crate::pipeline::PipelineBindPoint;
+use crate::pipeline::RayTracingPipeline;
 use crate::query::QueryControlFlags;
 use crate::query::QueryPipelineStatisticFlags;
 use crate::query::QueryPool;
@@ -82,6 +96,8 @@ use crate::sampler::Filter;
 use crate::shader::ShaderStages;
 use crate::sync::AccessCheckError;
 use crate::sync::AccessFlags;
+use crate::sync::DependencyInfo;
+use crate::sync::Event;
 use crate::sync::GpuFuture;
 use crate::sync::PipelineMemoryAccess;
 use crate::sync::PipelineStage;
@@ -94,6 +110,7 @@ use smallvec::SmallVec;
 use std::cmp;
 use std::error;
 use std::ffi::CStr;
+use std::ffi::CString;
 use std::fmt;
 use std::iter;
 use std::marker::PhantomData;
@@ -128,16 +145,52 @@ pub struct AutoCommandBufferBuilder<L, P = StandardCommandPoolBuilder> {
     // If any queries are active, this hashmap contains their state.
     query_state: FnvHashMap<ash::vk::QueryType, QueryState>,
 
+    // If conditional rendering is active, contains its state.
+    conditional_rendering_state: Option<ConditionalRenderingState>,
+
+    // True if transform feedback is active.
+    transform_feedback_active: bool,
+
+    // The number of debug label regions that have been opened but not yet closed.
+    debug_utils_label_depth: u32,
+
     _data: PhantomData<L>,
 }
 
 // The state of the current render pass, specifying the pass, subpass index and its intended contents.
 struct RenderPassState {
-    subpass: Subpass,
+    render_pass: PipelineRenderPassType, // The attachment formats when using dynamic rendering
     contents: SubpassContents,
     attached_layers_ranges: SmallVec<[Range<u32>; 4]>,
     dimensions: [u32; 3],
     framebuffer: ash::vk::Framebuffer, // Always null for secondary command buffers
+    // None if the framebuffer is unknown
+    attachments: Option<SmallVec<[Arc<dyn ImageViewAbstract>; 4]>>,
+}
+
+// Returns the image views of all the attachments of a framebuffer.
+fn framebuffer_attachments(framebuffer: &Framebuffer) -> SmallVec<[Arc<dyn ImageViewAbstract>; 4]> {
+    (0..framebuffer.render_pass().desc().attachments().len())
+        .map(|index| framebuffer.attached_image_view(index).unwrap().clone())
+        .collect()
+}
+
+// Returns whether `clear_value` is of the kind that clears a color attachment with `format`.
+fn color_clear_value_matches(format: Format, clear_value: &ClearValue) -> bool {
+    match format.type_color() {
+        Some(
+            NumericType::SFLOAT
+            | NumericType::UFLOAT
+            | NumericType::SNORM
+            | NumericType::UNORM
+            | NumericType::SSCALED
+            | NumericType::USCALED
+            | NumericType::SRGB,
+        ) => matches!(clear_value, ClearValue::Float(_)),
+        Some(NumericType::SINT) => matches!(clear_value, ClearValue::Int(_)),
+        Some(NumericType::UINT) => matches!(clear_value, ClearValue::Uint(_)),
+        None => false,
+    }
 }
 
 // The state of an active query.
@@ -149,6 +202,11 @@ struct QueryState {
     in_subpass: bool,
 }
 
+// The state of active conditional rendering.
+struct ConditionalRenderingState {
+    in_subpass: bool,
+}
+
 impl AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandPoolBuilder> {
     /// Starts building a primary command buffer.
     #[inline]
@@ -167,6 +225,41 @@ impl AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandPoolBuild
             CommandBufferLevel::primary(),
         )
     }
+
+    /// Starts building a protected primary command buffer.
+    ///
+    /// The command buffer is allocated from
+    /// [`Device::standard_protected_command_pool`], and can only be executed on a protected
+    /// queue (see [`Queue::is_protected`](crate::device::Queue::is_protected)). Executing it
+    /// through [`PrimaryCommandBuffer::execute`] performs a protected submission.
+    ///
+    /// The [`protected_memory`](crate::device::Features::protected_memory) feature must be
+    /// enabled on the device.
+    #[inline]
+    pub fn primary_protected(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        usage: CommandBufferUsage,
+    ) -> Result<
+        AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandPoolBuilder>,
+        BeginError,
+    > {
+        let pool = match Device::standard_protected_command_pool(&device, queue_family) {
+            Ok(pool) => pool,
+            Err(CommandPoolCreationError::OomError(err)) => return Err(err.into()),
+            Err(CommandPoolCreationError::FeatureNotEnabled { .. }) => {
+                return Err(BeginError::ProtectedMemoryFeatureNotEnabled)
+            }
+        };
+
+        Ok(AutoCommandBufferBuilder::with_pool(
+            device,
+            queue_family,
+            pool,
+            usage,
+            CommandBufferLevel::primary(),
+        )?)
+    }
 }
 
 impl AutoCommandBufferBuilder<SecondaryAutoCommandBuffer, StandardCommandPoolBuilder> {
@@ -286,6 +379,17 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
         queue_family: QueueFamily,
         usage: CommandBufferUsage,
         level: CommandBufferLevel,
+    ) -> Result<AutoCommandBufferBuilder<L, StandardCommandPoolBuilder>, OomError> {
+        let pool = Device::standard_command_pool(&device, queue_family);
+        AutoCommandBufferBuilder::with_pool(device, queue_family, pool, usage, level)
+    }
+
+    fn with_pool(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        pool: Arc<StandardCommandPool>,
+        usage: CommandBufferUsage,
+        level: CommandBufferLevel,
     ) -> Result<AutoCommandBufferBuilder<L, StandardCommandPoolBuilder>, OomError> {
         let (inheritance, render_pass_state) = match &level {
             CommandBufferLevel::Primary => (None, None),
@@ -295,7 +399,7 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
                          subpass,
                          framebuffer,
                      }| RenderPassState {
-                        subpass: subpass.clone(),
+                        render_pass: subpass.clone().into(),
                         contents: SubpassContents::Inline,
                         dimensions: framebuffer
                             .as_ref()
@@ -306,6 +410,7 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
                             .map(|f| f.attached_layers_ranges())
                             .unwrap_or_default(),
                         framebuffer: ash::vk::Framebuffer::null(), // Only needed for primary command buffers
+                        attachments: framebuffer.as_ref().map(|f| framebuffer_attachments(f)),
                     },
                 );
 
@@ -321,7 +426,6 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
         };
 
         unsafe {
-            let pool = Device::standard_command_pool(&device, queue_family);
             let pool_builder_alloc = pool
                 .alloc(!matches!(level, CommandBufferLevel::Primary), 1)?
                 .next()
@@ -334,6 +438,9 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
                 queue_family_id: queue_family.id(),
                 render_pass_state,
                 query_state: FnvHashMap::default(),
+                conditional_rendering_state: None,
+                transform_feedback_active: false,
+                debug_utils_label_depth: 0,
                 inheritance,
                 usage,
                 _data: PhantomData,
@@ -350,6 +457,8 @@ pub enum BeginError {
     OomError(OomError),
     /// Pipeline statistics query inheritance was requested, but the `pipeline_statistics_query` feature was not enabled.
     PipelineStatisticsQueryFeatureNotEnabled,
+    /// A protected command buffer was requested, but the `protected_memory` feature was not enabled.
+    ProtectedMemoryFeatureNotEnabled,
 }
 
 impl error::Error for BeginError {
@@ -378,6 +487,10 @@ impl fmt::Display for BeginError {
                     "pipeline statistics query inheritance was requested but the corresponding \
                  feature wasn't enabled"
                 }
+                Self::ProtectedMemoryFeatureNotEnabled => {
+                    "a protected command buffer was requested but the protected_memory feature \
+                 wasn't enabled"
+                }
             }
         )
     }
@@ -405,6 +518,18 @@ where
             return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
         }
 
+        if self.conditional_rendering_state.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive.into());
+        }
+
+        if self.transform_feedback_active {
+            return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive.into());
+        }
+
+        if self.debug_utils_label_depth != 0 {
+            return Err(AutoCommandBufferBuilderContextError::DebugUtilsLabelIsActive.into());
+        }
+
         let submit_state = match self.usage {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -434,6 +559,18 @@ where
             return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
         }
 
+        if self.conditional_rendering_state.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive.into());
+        }
+
+        if self.transform_feedback_active {
+            return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive.into());
+        }
+
+        if self.debug_utils_label_depth != 0 {
+            return Err(AutoCommandBufferBuilderContextError::DebugUtilsLabelIsActive.into());
+        }
+
         let submit_state = match self.usage {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -478,24 +615,86 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             return Err(AutoCommandBufferBuilderContextError::WrongSubpassType);
         }
 
-        // Subpasses must be the same.
-        if pipeline.subpass().index() != render_pass_state.subpass.index() {
-            return Err(AutoCommandBufferBuilderContextError::WrongSubpassIndex);
-        }
+        match (pipeline.render_pass(), &render_pass_state.render_pass) {
+            (
+                PipelineRenderPassType::BeginRenderPass(pipeline_subpass),
+                PipelineRenderPassType::BeginRenderPass(current_subpass),
+            ) => {
+                // Subpasses must be the same.
+                if pipeline_subpass.index() != current_subpass.index() {
+                    return Err(AutoCommandBufferBuilderContextError::WrongSubpassIndex);
+                }
 
-        // Render passes must be compatible.
-        if !pipeline
-            .subpass()
-            .render_pass()
-            .desc()
-            .is_compatible_with_desc(&render_pass_state.subpass.render_pass().desc())
-        {
-            return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass);
+                // Render passes must be compatible.
+                if !pipeline_subpass
+                    .render_pass()
+                    .desc()
+                    .is_compatible_with_desc(&current_subpass.render_pass().desc())
+                {
+                    return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass);
+                }
+            }
+            (
+                PipelineRenderPassType::BeginRendering(pipeline_info),
+                PipelineRenderPassType::BeginRendering(current_info),
+            ) => {
+                // The formats of the pipeline must match those of the attachments that are
+                // present.
+                let formats_match =
+                    |pipeline_format: &Option<Format>, current_format: &Option<Format>| {
+                        current_format.is_none() || pipeline_format == current_format
+                    };
+
+                if pipeline_info.view_mask != current_info.view_mask
+                    || pipeline_info.color_attachment_formats.len()
+                        != current_info.color_attachment_formats.len()
+                    || !pipeline_info
+                        .color_attachment_formats
+                        .iter()
+                        .zip(&current_info.color_attachment_formats)
+                        .all(|(pipeline_format, current_format)| {
+                            formats_match(pipeline_format, current_format)
+                        })
+                    || !formats_match(
+                        &pipeline_info.depth_attachment_format,
+                        &current_info.depth_attachment_format,
+                    )
+                    || !formats_match(
+                        &pipeline_info.stencil_attachment_format,
+                        &current_info.stencil_attachment_format,
+                    )
+                {
+                    return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass);
+                }
+            }
+            _ => return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass),
         }
 
         Ok(())
     }
 
+    // Returns the image views that are used as input attachments by the current subpass, in the
+    // order of their input attachment index, or `None` if they are not known.
+    fn current_input_attachments(&self) -> Option<SmallVec<[Arc<dyn ImageViewAbstract>; 4]>> {
+        let render_pass_state = self.render_pass_state.as_ref()?;
+        let attachments = render_pass_state.attachments.as_ref()?;
+
+        // Dynamic rendering has no input attachments.
+        let subpass = match &render_pass_state.render_pass {
+            PipelineRenderPassType::BeginRenderPass(subpass) => subpass,
+            PipelineRenderPassType::BeginRendering(_) => return Some(SmallVec::new()),
+        };
+
+        Some(
+            subpass
+                .subpass_desc()
+                .input_attachments
+                .iter()
+                .map(|&(attachment, _)| attachments[attachment].clone())
+                .collect(),
+        )
+    }
+
     #[inline]
     fn queue_family(&self) -> QueueFamily {
         self.device()
@@ -537,6 +736,10 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 self.queue_family().supports_graphics(),
                 "the queue family of the command buffer must support graphics operations"
             ),
+            PipelineBindPoint::RayTracing => assert!(
+                self.queue_family().supports_compute(),
+                "the queue family of the command buffer must support compute operations"
+            ),
         }
 
         let descriptor_sets = descriptor_sets.into_vec();
@@ -667,7 +870,10 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     ///
     /// - Panics if the queue family of the command buffer does not support graphics operations.
     /// - Panics if `self` and `pipeline` do not belong to the same device.
-    pub fn bind_pipeline_graphics(&mut self, pipeline: Arc<GraphicsPipeline>) -> &mut Self {
+    pub fn bind_pipeline_graphics(
+        &mut self,
+        pipeline: Arc<GraphicsPipeline>,
+    ) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
         assert!(
             self.queue_family().supports_graphics(),
             "the queue family of the command buffer must support graphics operations"
@@ -678,6 +884,10 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             self.device().internal_object()
         );
 
+        if self.transform_feedback_active {
+            return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive);
+        }
+
         // TODO:
         // If the variable multisample rate feature is not supported, pipeline is a graphics
         // pipeline, the current subpass uses no attachments, and this is not the first call to
@@ -695,9 +905,6 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         // sampleLocationsInfo member of that element must match the sampleLocationsInfo specified
         // in VkPipelineSampleLocationsStateCreateInfoEXT when the pipeline was created
 
-        // TODO:
-        // This command must not be recorded when transform feedback is active
-
         // TODO:
         // pipeline must not have been created with VK_PIPELINE_CREATE_LIBRARY_BIT_KHR set
 
@@ -720,6 +927,30 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             self.inner.bind_pipeline_graphics(pipeline);
         }
 
+        Ok(self)
+    }
+
+    /// Binds a ray tracing pipeline for future trace rays calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support compute operations.
+    /// - Panics if `self` and `pipeline` do not belong to the same device.
+    pub fn bind_pipeline_ray_tracing(&mut self, pipeline: Arc<RayTracingPipeline>) -> &mut Self {
+        assert!(
+            self.queue_family().supports_compute(),
+            "the queue family of the command buffer must support compute operations"
+        );
+
+        assert_eq!(
+            pipeline.device().internal_object(),
+            self.device().internal_object()
+        );
+
+        unsafe {
+            self.inner.bind_pipeline_ray_tracing(pipeline);
+        }
+
         self
     }
 
@@ -908,6 +1139,120 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         }
     }
 
+    /// Adds commands that fill all the mipmap levels of an image, by successively blitting each
+    /// level into the next one with a linear filter. Level 0 must already contain the image data.
+    ///
+    /// The image must have been created with both the `transfer_source` and
+    /// `transfer_destination` usages, and its format must support blit operations and linear
+    /// filtering. An error is returned otherwise. Images with only one level are left unchanged.
+    ///
+    /// As with other commands, layout transitions are handled automatically: when the command
+    /// buffer finishes, the image is transitioned to the layout that it requires for its
+    /// following uses, such as `ShaderReadOnlyOptimal` for sampled images.
+    pub fn generate_mipmaps(
+        &mut self,
+        image: Arc<dyn ImageAccess>,
+    ) -> Result<&mut Self, BlitImageError> {
+        {
+            let format_features = image.inner().image.format_features();
+
+            if !format_features.blit_src {
+                return Err(CheckBlitImageError::SourceFormatNotSupported.into());
+            }
+
+            if !format_features.blit_dst {
+                return Err(CheckBlitImageError::DestinationFormatNotSupported.into());
+            }
+
+            if !format_features.sampled_image_filter_linear {
+                return Err(CheckBlitImageError::FilterFormatNotSupported.into());
+            }
+        }
+
+        let dimensions = image.dimensions();
+        let layout = image.initial_layout_requirement();
+
+        for level in 1..image.mip_levels() {
+            let [xs, ys, ds] = dimensions
+                .mip_level_dimensions(level - 1)
+                .unwrap()
+                .width_height_depth();
+            let [xd, yd, dd] = dimensions
+                .mip_level_dimensions(level)
+                .unwrap()
+                .width_height_depth();
+
+            for layer in 0..dimensions.array_layers() {
+                // Each blit only accesses the two levels involved, so that the source and the
+                // destination don't conflict with each other.
+                let source = SubImage::new(image.clone(), level - 1, 1, layer, 1, layout);
+                let destination = SubImage::new(image.clone(), level, 1, layer, 1, layout);
+
+                self.blit_image(
+                    source,
+                    [0, 0, 0],
+                    [xs as i32, ys as i32, ds as i32],
+                    layer,
+                    level - 1,
+                    destination,
+                    [0, 0, 0],
+                    [xd as i32, yd as i32, dd as i32],
+                    layer,
+                    level,
+                    1,
+                    Filter::Linear,
+                )?;
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Adds a command that builds an acceleration structure from `info` into `destination`.
+    ///
+    /// The buffer of `destination` and `scratch_buffer` must be at least as large as the sizes
+    /// returned by [`AccelerationStructure::build_sizes`] for `info`. The geometry buffers must
+    /// have the `acceleration_structure_build_input_read_only` and `device_address` usages, and
+    /// the scratch buffer must have the `storage_buffer` and `device_address` usages.
+    ///
+    /// Builds that are recorded in a command buffer can't be deferred. To spread a build over
+    /// several threads with a [`DeferredOperation`](crate::deferred::DeferredOperation), build on
+    /// the host with [`AccelerationStructure::build_on_host`] instead.
+    ///
+    /// # Safety
+    ///
+    /// - If `info` contains instances, the buffer of instances must only contain valid
+    ///   [`AccelerationStructureInstance`](crate::acceleration_structure::AccelerationStructureInstance)
+    ///   values, whose `acceleration_structure_reference` is the device address of a bottom-level
+    ///   acceleration structure that has been built.
+    /// - The bottom-level acceleration structures that are referenced by the instances must be
+    ///   kept alive, and must not be rebuilt, for as long as `destination` is used.
+    /// - If `info` contains triangles with indices, the indices must not be greater than the
+    ///   `max_vertex` of the geometry.
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: AccelerationStructureBuildGeometryInfo,
+        destination: Arc<AccelerationStructure>,
+        scratch_buffer: Arc<dyn BufferAccess>,
+    ) -> Result<&mut Self, BuildAccelerationStructureError> {
+        if !self.queue_family().supports_compute() {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
+
+        self.ensure_outside_render_pass()?;
+        check_build_acceleration_structure(
+            self.device(),
+            &info,
+            &destination,
+            scratch_buffer.as_ref(),
+        )?;
+
+        self.inner
+            .build_acceleration_structure(info, destination, scratch_buffer)?;
+
+        Ok(self)
+    }
+
     /// Adds a command that clears specific regions of specific attachments of the framebuffer.
     ///
     /// `attachments` specify the types of attachments and their clear values.
@@ -933,10 +1278,15 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         self.ensure_inside_render_pass_inline(pipeline)?;
 
         let render_pass_state = self.render_pass_state.as_ref().unwrap();
-        let subpass = &render_pass_state.subpass;
-        let multiview = subpass.render_pass().desc().multiview().is_some();
-        let has_depth_stencil_attachment = subpass.has_depth_stencil_attachment();
-        let num_color_attachments = subpass.num_color_attachments();
+        let multiview = match &render_pass_state.render_pass {
+            PipelineRenderPassType::BeginRenderPass(subpass) => {
+                subpass.render_pass().desc().multiview().is_some()
+            }
+            PipelineRenderPassType::BeginRendering(info) => info.view_mask != 0,
+        };
+        let has_depth_stencil_attachment =
+            render_pass_state.render_pass.has_depth_stencil_attachment();
+        let num_color_attachments = render_pass_state.render_pass.num_color_attachments();
         let dimensions = render_pass_state.dimensions;
         let attached_layers_ranges = &render_pass_state.attached_layers_ranges;
 
@@ -1225,6 +1575,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     }
 
     /// Adds a command that copies from a buffer to an image.
+    ///
+    /// For images with a block-compressed format, `offset` must be a multiple of the
+    /// [block extent](crate::format::Format::block_extent) of the format, and each component of
+    /// `size` must either be a multiple of it or reach the edge of the mip level. The buffer holds
+    /// tightly packed blocks, with the number of blocks in each dimension rounded up.
+    ///
+    /// For depth/stencil images, the depth aspect is copied if the format has one, and the
+    /// stencil aspect otherwise.
     pub fn copy_buffer_to_image_dimensions<S, Px>(
         &mut self,
         source: Arc<S>,
@@ -1242,6 +1600,16 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         unsafe {
             self.ensure_outside_render_pass()?;
 
+            let destination_aspects = destination.format().aspects();
+            // TODO: Allow the user to choose aspect
+            let image_aspect = if destination_aspects.depth {
+                ImageAspect::Depth
+            } else if destination_aspects.stencil {
+                ImageAspect::Stencil
+            } else {
+                ImageAspect::Color
+            };
+
             check_copy_buffer_image(
                 self.device(),
                 source.as_ref(),
@@ -1252,17 +1620,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 base_array_layer,
                 layer_count,
                 mip_level,
+                image_aspect,
             )?;
 
             let copy = UnsafeCommandBufferBuilderBufferImageCopy {
                 buffer_offset: 0,
                 buffer_row_length: 0,
                 buffer_image_height: 0,
-                image_aspect: if destination.format().aspects().color {
-                    ImageAspect::Color
-                } else {
-                    unimplemented!()
-                },
+                image_aspect,
                 image_mip_level: mip_level,
                 image_base_array_layer: base_array_layer,
                 image_layer_count: layer_count,
@@ -1400,6 +1765,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     }
 
     /// Adds a command that copies from an image to a buffer.
+    ///
+    /// For images with a block-compressed format, `offset` must be a multiple of the
+    /// [block extent](crate::format::Format::block_extent) of the format, and each component of
+    /// `size` must either be a multiple of it or reach the edge of the mip level. The buffer holds
+    /// tightly packed blocks, with the number of blocks in each dimension rounded up.
+    ///
+    /// For depth/stencil images, the depth aspect is copied if the format has one, and the
+    /// stencil aspect otherwise.
     pub fn copy_image_to_buffer_dimensions<D, Px>(
         &mut self,
         source: Arc<dyn ImageAccess>,
@@ -1410,6 +1783,45 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         layer_count: u32,
         mip_level: u32,
     ) -> Result<&mut Self, CopyBufferImageError>
+    where
+        D: TypedBufferAccess<Content = [Px]> + 'static,
+        Px: Pixel,
+    {
+        let source_aspects = source.format().aspects();
+        // TODO: Allow the user to choose aspect
+        let image_aspect = if source_aspects.depth {
+            ImageAspect::Depth
+        } else if source_aspects.stencil {
+            ImageAspect::Stencil
+        } else {
+            ImageAspect::Color
+        };
+
+        self.copy_image_aspect_to_buffer_dimensions(
+            source,
+            destination,
+            offset,
+            size,
+            base_array_layer,
+            layer_count,
+            mip_level,
+            image_aspect,
+        )
+    }
+
+    // Same as `copy_image_to_buffer_dimensions`, but copies `image_aspect` of the image. This
+    // allows copying the planes of a multi-planar image one by one.
+    pub(crate) fn copy_image_aspect_to_buffer_dimensions<D, Px>(
+        &mut self,
+        source: Arc<dyn ImageAccess>,
+        destination: Arc<D>,
+        offset: [u32; 3],
+        size: [u32; 3],
+        base_array_layer: u32,
+        layer_count: u32,
+        mip_level: u32,
+        image_aspect: ImageAspect,
+    ) -> Result<&mut Self, CopyBufferImageError>
     where
         D: TypedBufferAccess<Content = [Px]> + 'static,
         Px: Pixel,
@@ -1427,23 +1839,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 base_array_layer,
                 layer_count,
                 mip_level,
+                image_aspect,
             )?;
 
-            let source_aspects = source.format().aspects();
             let copy = UnsafeCommandBufferBuilderBufferImageCopy {
                 buffer_offset: 0,
                 buffer_row_length: 0,
                 buffer_image_height: 0,
-                // TODO: Allow the user to choose aspect
-                image_aspect: if source_aspects.color {
-                    ImageAspect::Color
-                } else if source_aspects.depth {
-                    ImageAspect::Depth
-                } else if source_aspects.stencil {
-                    ImageAspect::Stencil
-                } else {
-                    unimplemented!()
-                },
+                image_aspect,
                 image_mip_level: mip_level,
                 image_base_array_layer: base_array_layer,
                 image_layer_count: layer_count,
@@ -1461,69 +1864,143 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         }
     }
 
-    /// Open a command buffer debug label region.
+    /// Opens a command buffer debug label region.
     ///
-    /// Note: you need to enable `VK_EXT_debug_utils` extension when creating an instance.
+    /// The region must be closed with [`end_debug_utils_label`](Self::end_debug_utils_label)
+    /// before the command buffer is built. Regions can be nested. If `color` is `None`, the
+    /// region has no color.
+    ///
+    /// The `ext_debug_utils` extension must be enabled on the instance.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `name` contains a null character.
     #[inline]
-    pub fn debug_marker_begin(
+    pub fn begin_debug_utils_label(
         &mut self,
-        name: &'static CStr,
-        color: [f32; 4],
+        name: &str,
+        color: Option<[f32; 4]>,
     ) -> Result<&mut Self, DebugMarkerError> {
-        if !self.queue_family().supports_graphics() && self.queue_family().supports_compute() {
-            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
-        }
+        let name = CString::new(name).expect("debug label name contains a null character");
+        self.debug_utils_label_begin(name, color.unwrap_or_default())
+    }
 
-        check_debug_marker_color(color)?;
+    /// Closes the command buffer debug label region that was most recently opened with
+    /// [`begin_debug_utils_label`](Self::begin_debug_utils_label).
+    ///
+    /// The `ext_debug_utils` extension must be enabled on the instance.
+    #[inline]
+    pub fn end_debug_utils_label(&mut self) -> Result<&mut Self, DebugMarkerError> {
+        self.ensure_debug_utils_label_supported()?;
+
+        if self.debug_utils_label_depth == 0 {
+            return Err(AutoCommandBufferBuilderContextError::DebugUtilsLabelNotActive.into());
+        }
 
         unsafe {
-            self.inner.debug_marker_begin(name.into(), color);
+            self.inner.debug_marker_end();
         }
+        self.debug_utils_label_depth -= 1;
 
         Ok(self)
     }
 
+    /// Inserts a single debug label into the command buffer. If `color` is `None`, the label has
+    /// no color.
+    ///
+    /// The `ext_debug_utils` extension must be enabled on the instance.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `name` contains a null character.
+    #[inline]
+    pub fn insert_debug_utils_label(
+        &mut self,
+        name: &str,
+        color: Option<[f32; 4]>,
+    ) -> Result<&mut Self, DebugMarkerError> {
+        let name = CString::new(name).expect("debug label name contains a null character");
+        self.debug_utils_label_insert(name, color.unwrap_or_default())
+    }
+
+    /// Open a command buffer debug label region.
+    ///
+    /// Note: you need to enable `VK_EXT_debug_utils` extension when creating an instance.
+    #[deprecated(since = "0.28", note = "Use `begin_debug_utils_label` instead")]
+    #[inline]
+    pub fn debug_marker_begin(
+        &mut self,
+        name: &'static CStr,
+        color: [f32; 4],
+    ) -> Result<&mut Self, DebugMarkerError> {
+        self.debug_utils_label_begin(name.into(), color)
+    }
+
     /// Close a command buffer label region.
     ///
     /// Note: you need to open a command buffer label region first with `debug_marker_begin`.
     /// Note: you need to enable `VK_EXT_debug_utils` extension when creating an instance.
+    #[deprecated(since = "0.28", note = "Use `end_debug_utils_label` instead")]
     #[inline]
     pub fn debug_marker_end(&mut self) -> Result<&mut Self, DebugMarkerError> {
-        if !self.queue_family().supports_graphics() && self.queue_family().supports_compute() {
-            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
-        }
-
-        // TODO: validate that debug_marker_begin with same name was sent earlier
-
-        unsafe {
-            self.inner.debug_marker_end();
-        }
-
-        Ok(self)
+        self.end_debug_utils_label()
     }
 
     /// Insert a label into a command buffer.
     ///
     /// Note: you need to enable `VK_EXT_debug_utils` extension when creating an instance.
+    #[deprecated(since = "0.28", note = "Use `insert_debug_utils_label` instead")]
     #[inline]
     pub fn debug_marker_insert(
         &mut self,
         name: &'static CStr,
         color: [f32; 4],
     ) -> Result<&mut Self, DebugMarkerError> {
-        if !self.queue_family().supports_graphics() && self.queue_family().supports_compute() {
-            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        self.debug_utils_label_insert(name.into(), color)
+    }
+
+    fn debug_utils_label_begin(
+        &mut self,
+        name: CString,
+        color: [f32; 4],
+    ) -> Result<&mut Self, DebugMarkerError> {
+        self.ensure_debug_utils_label_supported()?;
+        check_debug_marker_color(color)?;
+
+        unsafe {
+            self.inner.debug_marker_begin(name, color);
         }
+        self.debug_utils_label_depth += 1;
 
+        Ok(self)
+    }
+
+    fn debug_utils_label_insert(
+        &mut self,
+        name: CString,
+        color: [f32; 4],
+    ) -> Result<&mut Self, DebugMarkerError> {
+        self.ensure_debug_utils_label_supported()?;
         check_debug_marker_color(color)?;
 
         unsafe {
-            self.inner.debug_marker_insert(name.into(), color);
+            self.inner.debug_marker_insert(name, color);
         }
 
         Ok(self)
     }
 
+    #[inline]
+    fn ensure_debug_utils_label_supported(
+        &self,
+    ) -> Result<(), AutoCommandBufferBuilderContextError> {
+        if !self.queue_family().supports_graphics() && !self.queue_family().supports_compute() {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily);
+        }
+
+        Ok(())
+    }
+
     /// Perform a single compute operation using a compute pipeline.
     ///
     /// A compute pipeline must have been bound using
@@ -1537,9 +2014,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
         let pipeline = check_pipeline_compute(self.state())?;
         self.ensure_outside_render_pass()?;
-        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
-        check_push_constants_validity(self.state(), pipeline.layout())?;
-        check_dispatch(self.device(), group_counts)?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            None,
+        )?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+        check_dispatch(self.device(), group_counts)?;
 
         unsafe {
             self.inner.dispatch(group_counts);
@@ -1548,8 +2030,9 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         Ok(self)
     }
 
-    /// Perform multiple compute operations using a compute pipeline. One dispatch is performed for
-    /// each [`DispatchIndirectCommand`] struct in `indirect_buffer`.
+    /// Perform a single compute operation using a compute pipeline, reading the group counts from
+    /// the first [`DispatchIndirectCommand`] struct in `indirect_buffer`. The buffer must have the
+    /// `indirect_buffer` usage and must start at an offset that is a multiple of 4.
     ///
     /// A compute pipeline must have been bound using
     /// [`bind_pipeline_compute`](Self::bind_pipeline_compute). Any resources used by the compute
@@ -1568,9 +2051,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
         let pipeline = check_pipeline_compute(self.state())?;
         self.ensure_outside_render_pass()?;
-        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            None,
+        )?;
         check_push_constants_validity(self.state(), pipeline.layout())?;
-        check_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
+        check_dispatch_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
 
         unsafe {
             self.inner.dispatch_indirect(indirect_buffer)?;
@@ -1601,7 +2089,12 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let pipeline = check_pipeline_graphics(self.state())?;
         self.ensure_inside_render_pass_inline(pipeline)?;
         check_dynamic_state_validity(self.state(), pipeline)?;
-        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            self.current_input_attachments().as_deref(),
+        )?;
         check_push_constants_validity(self.state(), pipeline.layout())?;
         check_vertex_buffers(
             self.state(),
@@ -1623,9 +2116,9 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     /// One draw is performed for each [`DrawIndirectCommand`] struct in `indirect_buffer`.
     /// The maximum number of draw commands in the buffer is limited by the
     /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
-    /// This limit is 1 unless the
-    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature has been
-    /// enabled.
+    /// If there is more than one draw command, the
+    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature must be
+    /// enabled on the device.
     ///
     /// A graphics pipeline must have been bound using
     /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
@@ -1644,28 +2137,74 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let pipeline = check_pipeline_graphics(self.state())?;
         self.ensure_inside_render_pass_inline(pipeline)?;
         check_dynamic_state_validity(self.state(), pipeline)?;
-        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            self.current_input_attachments().as_deref(),
+        )?;
         check_push_constants_validity(self.state(), pipeline.layout())?;
         check_vertex_buffers(self.state(), pipeline, None, None)?;
         check_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
 
         let requested = indirect_buffer.len() as u32;
-        let limit = self
-            .device()
-            .physical_device()
-            .properties()
-            .max_draw_indirect_count;
+        check_draw_indirect_draw_count(self.device(), requested)?;
 
-        if requested > limit {
-            return Err(
-                CheckIndirectBufferError::MaxDrawIndirectCountLimitExceeded { limit, requested }
-                    .into(),
-            );
+        unsafe {
+            self.inner.draw_indirect(
+                indirect_buffer,
+                requested,
+                mem::size_of::<DrawIndirectCommand>() as u32,
+            )?;
         }
 
+        Ok(self)
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, with the number of draws read
+    /// from a buffer.
+    ///
+    /// This is the same as [`draw_indirect`](Self::draw_indirect), except that the number of
+    /// draws is read from `count_buffer` when the command is executed, and is capped to the
+    /// number of [`DrawIndirectCommand`] structs in `indirect_buffer`.
+    ///
+    /// The [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature or the
+    /// [`khr_draw_indirect_count`](crate::device::DeviceExtensions::khr_draw_indirect_count)
+    /// extension must be enabled on the device. Unlike `draw_indirect`, the
+    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature is not needed
+    /// to perform more than one draw.
+    #[inline]
+    pub fn draw_indirect_count<Inb, Cb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+        count_buffer: Arc<Cb>,
+    ) -> Result<&mut Self, DrawIndirectError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawIndirectCommand]> + Send + Sync + 'static,
+        Cb: TypedBufferAccess<Content = u32> + Send + Sync + 'static,
+    {
+        let pipeline = check_pipeline_graphics(self.state())?;
+        self.ensure_inside_render_pass_inline(pipeline)?;
+        check_dynamic_state_validity(self.state(), pipeline)?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            self.current_input_attachments().as_deref(),
+        )?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+        check_vertex_buffers(self.state(), pipeline, None, None)?;
+        check_draw_indirect_count(self.device())?;
+        check_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
+        check_indirect_buffer(self.device(), count_buffer.as_ref())?;
+
+        let requested = indirect_buffer.len() as u32;
+        check_max_draw_indirect_count(self.device(), requested)?;
+
         unsafe {
-            self.inner.draw_indirect(
+            self.inner.draw_indirect_count(
                 indirect_buffer,
+                count_buffer,
                 requested,
                 mem::size_of::<DrawIndirectCommand>() as u32,
             )?;
@@ -1705,7 +2244,12 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let pipeline = check_pipeline_graphics(self.state())?;
         self.ensure_inside_render_pass_inline(pipeline)?;
         check_dynamic_state_validity(self.state(), pipeline)?;
-        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            self.current_input_attachments().as_deref(),
+        )?;
         check_push_constants_validity(self.state(), pipeline.layout())?;
         check_vertex_buffers(
             self.state(),
@@ -1733,9 +2277,9 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`.
     /// The maximum number of draw commands in the buffer is limited by the
     /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
-    /// This limit is 1 unless the
-    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature has been
-    /// enabled.
+    /// If there is more than one draw command, the
+    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature must be
+    /// enabled on the device.
     ///
     /// An index buffer must have been bound using
     /// [`bind_index_buffer`](Self::bind_index_buffer), and the index ranges of each
@@ -1759,29 +2303,76 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let pipeline = check_pipeline_graphics(self.state())?;
         self.ensure_inside_render_pass_inline(pipeline)?;
         check_dynamic_state_validity(self.state(), pipeline)?;
-        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            self.current_input_attachments().as_deref(),
+        )?;
         check_push_constants_validity(self.state(), pipeline.layout())?;
         check_vertex_buffers(self.state(), pipeline, None, None)?;
         check_index_buffer(self.state(), None)?;
         check_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
 
         let requested = indirect_buffer.len() as u32;
-        let limit = self
-            .device()
-            .physical_device()
-            .properties()
-            .max_draw_indirect_count;
+        check_draw_indirect_draw_count(self.device(), requested)?;
 
-        if requested > limit {
-            return Err(
-                CheckIndirectBufferError::MaxDrawIndirectCountLimitExceeded { limit, requested }
-                    .into(),
-            );
+        unsafe {
+            self.inner.draw_indexed_indirect(
+                indirect_buffer,
+                requested,
+                mem::size_of::<DrawIndexedIndirectCommand>() as u32,
+            )?;
         }
 
+        Ok(self)
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer, with
+    /// the number of draws read from a buffer.
+    ///
+    /// This is the same as [`draw_indexed_indirect`](Self::draw_indexed_indirect), except that
+    /// the number of draws is read from `count_buffer` when the command is executed, and is
+    /// capped to the number of [`DrawIndexedIndirectCommand`] structs in `indirect_buffer`.
+    ///
+    /// The [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature or the
+    /// [`khr_draw_indirect_count`](crate::device::DeviceExtensions::khr_draw_indirect_count)
+    /// extension must be enabled on the device. Unlike `draw_indexed_indirect`, the
+    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature is not needed
+    /// to perform more than one draw.
+    #[inline]
+    pub fn draw_indexed_indirect_count<Inb, Cb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+        count_buffer: Arc<Cb>,
+    ) -> Result<&mut Self, DrawIndexedIndirectError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+        Cb: TypedBufferAccess<Content = u32> + 'static,
+    {
+        let pipeline = check_pipeline_graphics(self.state())?;
+        self.ensure_inside_render_pass_inline(pipeline)?;
+        check_dynamic_state_validity(self.state(), pipeline)?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            self.current_input_attachments().as_deref(),
+        )?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+        check_vertex_buffers(self.state(), pipeline, None, None)?;
+        check_index_buffer(self.state(), None)?;
+        check_draw_indirect_count(self.device())?;
+        check_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
+        check_indirect_buffer(self.device(), count_buffer.as_ref())?;
+
+        let requested = indirect_buffer.len() as u32;
+        check_max_draw_indirect_count(self.device(), requested)?;
+
         unsafe {
-            self.inner.draw_indexed_indirect(
+            self.inner.draw_indexed_indirect_count(
                 indirect_buffer,
+                count_buffer,
                 requested,
                 mem::size_of::<DrawIndexedIndirectCommand>() as u32,
             )?;
@@ -1816,29 +2407,32 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
     /// Sets push constants for future dispatch or draw calls.
     ///
-    /// # Panics
+    /// The bytes of `push_constants` are written to the push constant block starting at `offset`,
+    /// so a part of a larger block can be updated by passing a smaller type. The shader stages to
+    /// update are determined from the push constant ranges of `pipeline_layout`.
     ///
-    /// - Panics if `offset` is not a multiple of 4.
-    /// - Panics if the size of `push_constants` is not a multiple of 4.
-    /// - Panics if any of the bytes in `push_constants` do not fall within any of the pipeline
-    ///   layout's push constant ranges.
+    /// An error is returned if `offset` or the size of `push_constants` is not a multiple of 4, or
+    /// if any of the bytes being set don't fall within the push constant ranges of
+    /// `pipeline_layout`.
     pub fn push_constants<Pc>(
         &mut self,
         pipeline_layout: Arc<PipelineLayout>,
         offset: u32,
         push_constants: Pc,
-    ) -> &mut Self {
+    ) -> Result<&mut Self, PushConstantsError> {
         let size = mem::size_of::<Pc>() as u32;
 
         if size == 0 {
-            return self;
+            return Ok(self);
         }
 
-        assert!(offset % 4 == 0, "the offset must be a multiple of 4");
-        assert!(
-            size % 4 == 0,
-            "the size of push_constants must be a multiple of 4"
-        );
+        if offset % 4 != 0 {
+            return Err(PushConstantsError::OffsetNotMultipleOf4 { offset });
+        }
+
+        if size % 4 != 0 {
+            return Err(PushConstantsError::SizeNotMultipleOf4 { size });
+        }
 
         // Figure out which shader stages in the pipeline layout overlap this byte range.
         // Also check that none of the bytes being set are outside all push constant ranges.
@@ -1866,15 +2460,11 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     Ok(shader_stages)
                 }
             })
-            .expect(
-                "not all bytes in push_constants fall within the pipeline layout's push constant ranges",
-            );
+            .map_err(|_| PushConstantsError::RangeOutOfBounds { offset, size })?;
 
         unsafe {
-            let data = slice::from_raw_parts(
-                (&push_constants as *const Pc as *const u8).offset(offset as isize),
-                size as usize,
-            );
+            let data =
+                slice::from_raw_parts(&push_constants as *const Pc as *const u8, size as usize);
 
             self.inner.push_constants::<[u8]>(
                 pipeline_layout.clone(),
@@ -1885,7 +2475,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             );
         }
 
-        self
+        Ok(self)
     }
 
     /// Pushes descriptor data directly into the command buffer for future dispatch or draw calls.
@@ -1914,6 +2504,10 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 self.queue_family().supports_graphics(),
                 "the queue family of the command buffer must support graphics operations"
             ),
+            PipelineBindPoint::RayTracing => assert!(
+                self.queue_family().supports_compute(),
+                "the queue family of the command buffer must support compute operations"
+            ),
         }
 
         assert!(
@@ -2327,6 +2921,91 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         self
     }
 
+    /// Sets the dynamic fragment shading rate for future draw calls.
+    ///
+    /// Each component of `fragment_size` must be 1, 2 or 4. `combiner_ops` are the operations
+    /// that combine the rate with the rate of the primitive, and then with the rate of the
+    /// shading rate attachment.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the [`khr_fragment_shading_rate`](crate::device::DeviceExtensions::khr_fragment_shading_rate)
+    ///   extension is not enabled on the device.
+    /// - Panics if the
+    ///   [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if a component of `fragment_size` is not 1, 2 or 4.
+    /// - Panics if `combiner_ops[0]` is not `Keep` and the
+    ///   [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    ///   feature is not enabled on the device.
+    /// - Panics if `combiner_ops[1]` is not `Keep` and the
+    ///   [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    ///   feature is not enabled on the device.
+    /// - Panics if `combiner_ops` contains `Min`, `Max` or `Mul` and the
+    ///   `fragment_shading_rate_non_trivial_combiner_ops` device property is not supported.
+    pub fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        assert!(
+            self.queue_family().supports_graphics(),
+            "the queue family of the command buffer must support graphics operations"
+        );
+        assert!(
+            self.device().enabled_extensions().khr_fragment_shading_rate,
+            "the khr_fragment_shading_rate extension must be enabled on the device"
+        );
+        assert!(
+            self.device()
+                .enabled_features()
+                .pipeline_fragment_shading_rate,
+            "the pipeline_fragment_shading_rate feature must be enabled on the device"
+        );
+        assert!(
+            !self.has_fixed_state(DynamicState::FragmentShadingRate),
+            "the currently bound graphics pipeline must not contain this state internally"
+        );
+        assert!(
+            fragment_size.iter().all(|&size| matches!(size, 1 | 2 | 4)),
+            "each component of fragment_size must be 1, 2 or 4"
+        );
+
+        if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep {
+            assert!(
+                self.device().enabled_features().primitive_fragment_shading_rate,
+                "if combiner_ops[0] is not Keep, the primitive_fragment_shading_rate feature must be enabled on the device"
+            );
+        }
+
+        if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep {
+            assert!(
+                self.device().enabled_features().attachment_fragment_shading_rate,
+                "if combiner_ops[1] is not Keep, the attachment_fragment_shading_rate feature must be enabled on the device"
+            );
+        }
+
+        if combiner_ops.iter().any(|op| op.is_non_trivial()) {
+            assert!(
+                self.device()
+                    .physical_device()
+                    .properties()
+                    .fragment_shading_rate_non_trivial_combiner_ops
+                    .unwrap_or(false),
+                "the fragment_shading_rate_non_trivial_combiner_ops property must be supported to use the Min, Max or Mul combiner operations"
+            );
+        }
+
+        unsafe {
+            self.inner
+                .set_fragment_shading_rate(fragment_size, combiner_ops);
+        }
+
+        self
+    }
+
     /// Sets the dynamic front face for future draw calls.
     ///
     /// # Panics
@@ -2971,6 +3650,41 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         self
     }
 
+    /// Traces rays using a ray tracing pipeline, with one ray generation shader invocation for
+    /// each element of `dimensions`.
+    ///
+    /// A ray tracing pipeline must have been bound using
+    /// [`bind_pipeline_ray_tracing`](Self::bind_pipeline_ray_tracing), and `shader_binding_table`
+    /// must have been created for that pipeline. Any resources used by the pipeline, such as
+    /// descriptor sets, must have been set beforehand.
+    #[inline]
+    pub fn trace_rays(
+        &mut self,
+        shader_binding_table: Arc<ShaderBindingTable>,
+        dimensions: [u32; 3],
+    ) -> Result<&mut Self, TraceRaysError> {
+        if !self.queue_family().supports_compute() {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
+
+        let pipeline = check_pipeline_ray_tracing(self.state())?;
+        self.ensure_outside_render_pass()?;
+        check_descriptor_sets_validity(
+            self.state(),
+            pipeline,
+            pipeline.descriptor_requirements(),
+            None,
+        )?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+        check_trace_rays(self.device(), &shader_binding_table, pipeline, dimensions)?;
+
+        unsafe {
+            self.inner.trace_rays(shader_binding_table, dimensions)?;
+        }
+
+        Ok(self)
+    }
+
     /// Adds a command that writes data to a buffer.
     ///
     /// If `data` is larger than the buffer, only the part of `data` that fits is written. If the
@@ -3002,95 +3716,285 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         }
     }
 
-    /// Adds a command that begins a query.
+    /// Adds a command that begins conditional rendering.
     ///
-    /// The query will be active until [`end_query`](Self::end_query) is called for the same query.
+    /// Draw and dispatch commands recorded until
+    /// [`end_conditional_rendering`](Self::end_conditional_rendering) is called are only executed
+    /// if the 32-bit value in `buffer` is non-zero at the time they are executed. If `inverted` is
+    /// true, they are only executed if the value is zero instead.
     ///
-    /// # Safety
-    /// The query must be unavailable, ensured by calling [`reset_query_pool`](Self::reset_query_pool).
-    pub unsafe fn begin_query(
+    /// If conditional rendering is begun inside a subpass, it must be ended inside the same
+    /// subpass. If it is begun outside a render pass, it must be ended outside a render pass.
+    ///
+    /// The [`ext_conditional_rendering`](crate::device::DeviceExtensions::ext_conditional_rendering)
+    /// extension and the
+    /// [`conditional_rendering`](crate::device::Features::conditional_rendering) feature must be
+    /// enabled on the device.
+    pub fn begin_conditional_rendering<B>(
         &mut self,
-        query_pool: Arc<QueryPool>,
-        query: u32,
-        flags: QueryControlFlags,
-    ) -> Result<&mut Self, BeginQueryError> {
-        check_begin_query(self.device(), &query_pool, query, flags)?;
-
-        match query_pool.ty() {
-            QueryType::Occlusion => {
-                if !self.queue_family().supports_graphics() {
-                    return Err(
-                        AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into(),
-                    );
-                }
+        buffer: Arc<B>,
+        inverted: bool,
+    ) -> Result<&mut Self, BeginConditionalRenderingError>
+    where
+        B: TypedBufferAccess<Content = u32> + 'static,
+    {
+        unsafe {
+            if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute())
+            {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
             }
-            QueryType::PipelineStatistics(flags) => {
-                if flags.is_compute() && !self.queue_family().supports_compute()
-                    || flags.is_graphics() && !self.queue_family().supports_graphics()
-                {
-                    return Err(
-                        AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into(),
-                    );
-                }
+
+            if self.conditional_rendering_state.is_some() {
+                return Err(
+                    AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive.into(),
+                );
             }
-            QueryType::Timestamp => unreachable!(),
-        }
 
-        let ty = query_pool.ty();
-        let raw_ty = ty.into();
-        let raw_query_pool = query_pool.internal_object();
-        if self.query_state.contains_key(&raw_ty) {
-            return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
-        }
+            check_begin_conditional_rendering(self.device(), buffer.as_ref())?;
 
-        // TODO: validity checks
-        self.inner.begin_query(query_pool, query, flags);
-        self.query_state.insert(
-            raw_ty,
-            QueryState {
-                query_pool: raw_query_pool,
-                query,
-                ty,
-                flags,
+            self.inner.begin_conditional_rendering(buffer, inverted)?;
+            self.conditional_rendering_state = Some(ConditionalRenderingState {
                 in_subpass: self.render_pass_state.is_some(),
-            },
-        );
+            });
+        }
 
         Ok(self)
     }
 
-    /// Adds a command that ends an active query.
-    pub fn end_query(
+    /// Adds a command that ends conditional rendering.
+    pub fn end_conditional_rendering(
         &mut self,
-        query_pool: Arc<QueryPool>,
-        query: u32,
-    ) -> Result<&mut Self, EndQueryError> {
+    ) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
         unsafe {
-            check_end_query(self.device(), &query_pool, query)?;
+            let state = self
+                .conditional_rendering_state
+                .as_ref()
+                .ok_or(AutoCommandBufferBuilderContextError::ConditionalRenderingNotActive)?;
 
-            let raw_ty = query_pool.ty().into();
-            let raw_query_pool = query_pool.internal_object();
-            if !self.query_state.get(&raw_ty).map_or(false, |state| {
-                state.query_pool == raw_query_pool && state.query == query
-            }) {
-                return Err(AutoCommandBufferBuilderContextError::QueryNotActive.into());
+            if !state.in_subpass && self.render_pass_state.is_some() {
+                return Err(AutoCommandBufferBuilderContextError::ForbiddenInsideRenderPass);
             }
 
-            self.inner.end_query(query_pool, query);
-            self.query_state.remove(&raw_ty);
+            self.inner.end_conditional_rendering();
+            self.conditional_rendering_state = None;
         }
 
         Ok(self)
     }
 
-    /// Adds a command that writes a timestamp to a timestamp query.
+    /// Binds buffers that transform feedback will write the captured vertex data to.
     ///
-    /// # Safety
-    /// The query must be unavailable, ensured by calling [`reset_query_pool`](Self::reset_query_pool).
-    pub unsafe fn write_timestamp(
+    /// The buffers are bound to consecutive bindings, starting with `first_binding`. Each
+    /// buffer must have been created with the `transform_feedback_buffer` usage, and its offset
+    /// must be a multiple of 4.
+    ///
+    /// The [`ext_transform_feedback`](crate::device::DeviceExtensions::ext_transform_feedback)
+    /// extension and the [`transform_feedback`](crate::device::Features::transform_feedback)
+    /// feature must be enabled on the device.
+    pub fn bind_transform_feedback_buffers<I>(
         &mut self,
-        query_pool: Arc<QueryPool>,
-        query: u32,
+        first_binding: u32,
+        buffers: I,
+    ) -> Result<&mut Self, BindTransformFeedbackBuffersError>
+    where
+        I: IntoIterator<Item = Arc<dyn BufferAccess>>,
+    {
+        unsafe {
+            if !self.queue_family().supports_graphics() {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            if self.transform_feedback_active {
+                return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive.into());
+            }
+
+            let buffers: SmallVec<[_; 4]> = buffers.into_iter().collect();
+            check_bind_transform_feedback_buffers(self.device(), first_binding, &buffers)?;
+
+            self.inner
+                .bind_transform_feedback_buffers(first_binding, buffers)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Adds a command that begins transform feedback.
+    ///
+    /// Until [`end_transform_feedback`](Self::end_transform_feedback) is called, the outputs of
+    /// the last vertex processing stage of draw commands are written to the buffers bound with
+    /// [`bind_transform_feedback_buffers`](Self::bind_transform_feedback_buffers). Set
+    /// `rasterizer_discard_enable` in the rasterization state of the graphics pipeline to capture
+    /// the vertices without rasterizing them.
+    ///
+    /// To resume a previous capture, pass the counter buffers that were written by
+    /// `end_transform_feedback` as `counter_buffers`. They are read for consecutive transform
+    /// feedback buffer bindings, starting with `first_counter_buffer`. If `counter_buffers` is
+    /// empty, capture starts at the beginning of each transform feedback buffer.
+    ///
+    /// Transform feedback must be begun and ended inside the same subpass.
+    pub fn begin_transform_feedback<I>(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: I,
+    ) -> Result<&mut Self, BeginTransformFeedbackError>
+    where
+        I: IntoIterator<Item = Arc<dyn BufferAccess>>,
+    {
+        unsafe {
+            if !self.queue_family().supports_graphics() {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            if self.render_pass_state.is_none() {
+                return Err(
+                    AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass.into(),
+                );
+            }
+
+            if self.transform_feedback_active {
+                return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive.into());
+            }
+
+            let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+            check_transform_feedback_counter_buffers(
+                self.device(),
+                first_counter_buffer,
+                &counter_buffers,
+            )?;
+
+            self.inner
+                .begin_transform_feedback(first_counter_buffer, counter_buffers)?;
+            self.transform_feedback_active = true;
+        }
+
+        Ok(self)
+    }
+
+    /// Adds a command that ends transform feedback.
+    ///
+    /// The number of bytes written to each transform feedback buffer is stored in
+    /// `counter_buffers`, for consecutive bindings starting with `first_counter_buffer`. These can
+    /// be passed to [`begin_transform_feedback`](Self::begin_transform_feedback) later to resume
+    /// the capture.
+    pub fn end_transform_feedback<I>(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: I,
+    ) -> Result<&mut Self, EndTransformFeedbackError>
+    where
+        I: IntoIterator<Item = Arc<dyn BufferAccess>>,
+    {
+        unsafe {
+            if !self.transform_feedback_active {
+                return Err(
+                    AutoCommandBufferBuilderContextError::TransformFeedbackNotActive.into(),
+                );
+            }
+
+            let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+            check_transform_feedback_counter_buffers(
+                self.device(),
+                first_counter_buffer,
+                &counter_buffers,
+            )?;
+
+            self.inner
+                .end_transform_feedback(first_counter_buffer, counter_buffers)?;
+            self.transform_feedback_active = false;
+        }
+
+        Ok(self)
+    }
+
+    /// Adds a command that begins a query.
+    ///
+    /// The query will be active until [`end_query`](Self::end_query) is called for the same query.
+    ///
+    /// # Safety
+    /// The query must be unavailable, ensured by calling [`reset_query_pool`](Self::reset_query_pool).
+    pub unsafe fn begin_query(
+        &mut self,
+        query_pool: Arc<QueryPool>,
+        query: u32,
+        flags: QueryControlFlags,
+    ) -> Result<&mut Self, BeginQueryError> {
+        check_begin_query(self.device(), &query_pool, query, flags)?;
+
+        match query_pool.ty() {
+            QueryType::Occlusion => {
+                if !self.queue_family().supports_graphics() {
+                    return Err(
+                        AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into(),
+                    );
+                }
+            }
+            QueryType::PipelineStatistics(flags) => {
+                if flags.is_compute() && !self.queue_family().supports_compute()
+                    || flags.is_graphics() && !self.queue_family().supports_graphics()
+                {
+                    return Err(
+                        AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into(),
+                    );
+                }
+            }
+            QueryType::Timestamp => unreachable!(),
+        }
+
+        let ty = query_pool.ty();
+        let raw_ty = ty.into();
+        let raw_query_pool = query_pool.internal_object();
+        if self.query_state.contains_key(&raw_ty) {
+            return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
+        }
+
+        // TODO: validity checks
+        self.inner.begin_query(query_pool, query, flags);
+        self.query_state.insert(
+            raw_ty,
+            QueryState {
+                query_pool: raw_query_pool,
+                query,
+                ty,
+                flags,
+                in_subpass: self.render_pass_state.is_some(),
+            },
+        );
+
+        Ok(self)
+    }
+
+    /// Adds a command that ends an active query.
+    pub fn end_query(
+        &mut self,
+        query_pool: Arc<QueryPool>,
+        query: u32,
+    ) -> Result<&mut Self, EndQueryError> {
+        unsafe {
+            check_end_query(self.device(), &query_pool, query)?;
+
+            let raw_ty = query_pool.ty().into();
+            let raw_query_pool = query_pool.internal_object();
+            if !self.query_state.get(&raw_ty).map_or(false, |state| {
+                state.query_pool == raw_query_pool && state.query == query
+            }) {
+                return Err(AutoCommandBufferBuilderContextError::QueryNotActive.into());
+            }
+
+            self.inner.end_query(query_pool, query);
+            self.query_state.remove(&raw_ty);
+        }
+
+        Ok(self)
+    }
+
+    /// Adds a command that writes a timestamp to a timestamp query.
+    ///
+    /// # Safety
+    /// The query must be unavailable, ensured by calling [`reset_query_pool`](Self::reset_query_pool).
+    pub unsafe fn write_timestamp(
+        &mut self,
+        query_pool: Arc<QueryPool>,
+        query: u32,
         stage: PipelineStage,
     ) -> Result<&mut Self, WriteTimestampError> {
         check_write_timestamp(
@@ -3179,107 +4083,367 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
         Ok(self)
     }
-}
 
-/// Commands that can only be executed on primary command buffers
-impl<P> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<P::Alloc>, P>
-where
-    P: CommandPoolBuilderAlloc,
-{
-    /// Adds a command that enters a render pass.
+    /// Adds a command that sets an event to the signaled state, once all the previous commands
+    /// have completed the stages in `stages`.
     ///
-    /// If `contents` is `SubpassContents::SecondaryCommandBuffers`, then you will only be able to
-    /// add secondary command buffers while you're inside the first subpass of the render pass.
-    /// If it is `SubpassContents::Inline`, you will only be able to add inline draw commands and
-    /// not secondary command buffers.
+    /// # Safety
     ///
-    /// C must contain exactly one clear value for each attachment in the framebuffer.
+    /// - The event must not be waited upon in another command buffer that is executing at the
+    ///   same time.
+    /// - The event must not be set or reset from the host while the command is pending.
     ///
-    /// You must call this before you can add draw commands.
-    #[inline]
-    pub fn begin_render_pass<I>(
+    /// # Panics
+    ///
+    /// - Panics if `stages` is empty or contains `host`.
+    pub unsafe fn set_event(
         &mut self,
-        framebuffer: Arc<Framebuffer>,
-        contents: SubpassContents,
-        clear_values: I,
-    ) -> Result<&mut Self, BeginRenderPassError>
-    where
-        I: IntoIterator<Item = ClearValue>,
-    {
-        unsafe {
-            if !self.queue_family().supports_graphics() {
-                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
-            }
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
+        assert_ne!(stages, PipelineStages::none());
+        assert!(!stages.host);
+        self.ensure_outside_render_pass()?;
 
-            self.ensure_outside_render_pass()?;
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily);
+        }
 
-            let clear_values = framebuffer
-                .render_pass()
-                .desc()
-                .convert_clear_values(clear_values);
-            let clear_values = clear_values.collect::<Vec<_>>().into_iter(); // TODO: necessary for Send + Sync ; needs an API rework of convert_clear_values
-            let mut clear_values_copy = clear_values.clone().enumerate(); // TODO: Proper errors for clear value errors instead of panics
+        self.inner.set_event(event, stages);
+        Ok(self)
+    }
 
-            for (atch_i, atch_desc) in framebuffer
-                .render_pass()
-                .desc()
-                .attachments()
-                .into_iter()
-                .enumerate()
-            {
-                match clear_values_copy.next() {
-                    Some((clear_i, clear_value)) => {
-                        if atch_desc.load == LoadOp::Clear {
-                            let aspects = atch_desc.format.aspects();
+    /// Adds a command that resets an event to the unsignaled state, once all the previous
+    /// commands have completed the stages in `stages`.
+    ///
+    /// # Safety
+    ///
+    /// - Same as [`set_event`](Self::set_event).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `stages` is empty or contains `host`.
+    pub unsafe fn reset_event(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
+        assert_ne!(stages, PipelineStages::none());
+        assert!(!stages.host);
+        self.ensure_outside_render_pass()?;
 
-                            if aspects.depth && aspects.stencil {
-                                assert!(
-                                    matches!(clear_value, ClearValue::DepthStencil(_)),
-                                    "Bad ClearValue! index: {}, attachment index: {}, expected: DepthStencil, got: {:?}",
-                                    clear_i,
-                                    atch_i,
-                                    clear_value,
-                                );
-                            } else if aspects.depth {
-                                assert!(
-                                    matches!(clear_value, ClearValue::Depth(_)),
-                                    "Bad ClearValue! index: {}, attachment index: {}, expected: Depth, got: {:?}",
-                                    clear_i,
-                                    atch_i,
-                                    clear_value,
-                                );
-                            } else if aspects.depth {
-                                assert!(
-                                    matches!(clear_value, ClearValue::Stencil(_)),
-                                    "Bad ClearValue! index: {}, attachment index: {}, expected: Stencil, got: {:?}",
-                                    clear_i,
-                                    atch_i,
-                                    clear_value,
-                                );
-                            } else if let Some(numeric_type) = atch_desc.format.type_color() {
-                                match numeric_type {
-                                    NumericType::SFLOAT
-                                    | NumericType::UFLOAT
-                                    | NumericType::SNORM
-                                    | NumericType::UNORM
-                                    | NumericType::SSCALED
-                                    | NumericType::USCALED
-                                    | NumericType::SRGB => {
-                                        assert!(
-                                            matches!(clear_value, ClearValue::Float(_)),
-                                            "Bad ClearValue! index: {}, attachment index: {}, expected: Float, got: {:?}",
-                                            clear_i,
-                                            atch_i,
-                                            clear_value,
-                                        );
-                                    }
-                                    NumericType::SINT => {
-                                        assert!(
-                                            matches!(clear_value, ClearValue::Int(_)),
-                                            "Bad ClearValue! index: {}, attachment index: {}, expected: Int, got: {:?}",
-                                            clear_i,
-                                            atch_i,
-                                            clear_value,
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily);
+        }
+
+        self.inner.reset_event(event, stages);
+        Ok(self)
+    }
+
+    /// Adds a command that waits until all of `events` are signaled, before the stages in
+    /// `destination_stages` of the following commands can start.
+    ///
+    /// Memory accesses in `source_access` performed by `source_stages` before the events were set
+    /// are made visible to the accesses in `destination_access` of `destination_stages`.
+    ///
+    /// # Safety
+    ///
+    /// - The events must be set by a previous command in this command buffer, by a command that
+    ///   has already been submitted to the same queue, or from the host.
+    /// - Vulkano does not take the wait into account when inserting its own pipeline barriers;
+    ///   the synchronization between commands in this command buffer is still done automatically.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `source_stages` or `destination_stages` is empty.
+    /// - Panics if `source_access` is not compatible with `source_stages`, or
+    ///   `destination_access` with `destination_stages`.
+    pub unsafe fn wait_events(
+        &mut self,
+        events: impl IntoIterator<Item = Arc<Event>>,
+        source_stages: PipelineStages,
+        source_access: AccessFlags,
+        destination_stages: PipelineStages,
+        destination_access: AccessFlags,
+    ) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
+        assert_ne!(source_stages, PipelineStages::none());
+        assert_ne!(destination_stages, PipelineStages::none());
+        assert!(source_access.is_compatible_with(&source_stages));
+        assert!(destination_access.is_compatible_with(&destination_stages));
+
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily);
+        }
+
+        self.inner.wait_events(
+            events,
+            source_stages,
+            source_access,
+            destination_stages,
+            destination_access,
+        );
+        Ok(self)
+    }
+
+    /// Adds a pipeline barrier, using the per-barrier stages and accesses of the
+    /// [`synchronization2`](crate::device::Features::synchronization2) feature.
+    ///
+    /// Vulkano already inserts the barriers that are needed between the commands of this command
+    /// buffer, so this is only needed to synchronize with operations that vulkano doesn't know
+    /// about.
+    ///
+    /// The `synchronization2` feature must be enabled on the device. Pipeline barriers inside a
+    /// render pass are not supported yet.
+    ///
+    /// # Safety
+    ///
+    /// - The accesses of each barrier must be compatible with its stages, and the stages must be
+    ///   supported by the queue family of the command buffer.
+    /// - The `old_layout` of each image barrier must be the layout that the image is in at this
+    ///   point of the command buffer.
+    /// - Queue ownership transfers must be correct.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if a buffer or image of `dependency_info` was not created with the same device
+    ///   as the command buffer.
+    pub unsafe fn pipeline_barrier2(
+        &mut self,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, PipelineBarrierError> {
+        self.check_dependency_info(&dependency_info, "called pipeline_barrier2")?;
+        self.ensure_outside_render_pass()?;
+
+        self.inner.pipeline_barrier2(dependency_info);
+        Ok(self)
+    }
+
+    /// Adds a command that sets an event to the signaled state, using the per-barrier stages
+    /// and accesses of the [`synchronization2`](crate::device::Features::synchronization2)
+    /// feature.
+    ///
+    /// The event is signaled once all the previous commands have completed the source stages
+    /// of the barriers in `dependency_info`. The same dependency info must be given to
+    /// [`wait_events2`](Self::wait_events2) when waiting for the event.
+    ///
+    /// # Safety
+    ///
+    /// - Same as [`set_event`](Self::set_event).
+    /// - Same as [`pipeline_barrier2`](Self::pipeline_barrier2).
+    /// - The source stages of the barriers must not contain `host`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if a buffer or image of `dependency_info` was not created with the same device
+    ///   as the command buffer.
+    pub unsafe fn set_event2(
+        &mut self,
+        event: Arc<Event>,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, PipelineBarrierError> {
+        self.check_dependency_info(&dependency_info, "called set_event2")?;
+        self.ensure_outside_render_pass()?;
+
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
+
+        self.inner.set_event2(event, dependency_info);
+        Ok(self)
+    }
+
+    /// Adds a command that waits until all of `events` are signaled, using the per-barrier
+    /// stages and accesses of the [`synchronization2`](crate::device::Features::synchronization2)
+    /// feature.
+    ///
+    /// Each event is given with the dependency info that was passed to
+    /// [`set_event2`](Self::set_event2) when setting it.
+    ///
+    /// # Safety
+    ///
+    /// - Same as [`wait_events`](Self::wait_events).
+    /// - Same as [`pipeline_barrier2`](Self::pipeline_barrier2).
+    /// - The events must have been set with `set_event2`, using the same dependency info.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if a buffer or image of the dependency infos was not created with the same
+    ///   device as the command buffer.
+    pub unsafe fn wait_events2(
+        &mut self,
+        events: impl IntoIterator<Item = (Arc<Event>, DependencyInfo)>,
+    ) -> Result<&mut Self, PipelineBarrierError> {
+        let events: SmallVec<[_; 4]> = events.into_iter().collect();
+
+        for (_, dependency_info) in &events {
+            self.check_dependency_info(dependency_info, "called wait_events2")?;
+        }
+
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
+
+        self.inner.wait_events2(events);
+        Ok(self)
+    }
+
+    // Checks the parts of a `DependencyInfo` that are common to all the `synchronization2`
+    // commands.
+    fn check_dependency_info(
+        &self,
+        dependency_info: &DependencyInfo,
+        reason: &'static str,
+    ) -> Result<(), PipelineBarrierError> {
+        let device = self.device();
+
+        if !device.enabled_features().synchronization2 {
+            return Err(PipelineBarrierError::FeatureNotEnabled {
+                feature: "synchronization2",
+                reason,
+            });
+        }
+
+        for (barrier_index, barrier) in dependency_info.buffer_memory_barriers.iter().enumerate() {
+            assert_eq!(
+                barrier.buffer.device().internal_object(),
+                device.internal_object()
+            );
+
+            if barrier.range.start >= barrier.range.end || barrier.range.end > barrier.buffer.size()
+            {
+                return Err(PipelineBarrierError::BufferRangeOutOfBounds {
+                    barrier_index: barrier_index as u32,
+                });
+            }
+        }
+
+        for (barrier_index, barrier) in dependency_info.image_memory_barriers.iter().enumerate() {
+            assert_eq!(
+                barrier.image.inner().image.device().internal_object(),
+                device.internal_object()
+            );
+
+            // Vulkano keeps track of the layouts of the images itself.
+            if barrier.old_layout != barrier.new_layout {
+                return Err(PipelineBarrierError::ImageLayoutTransitionNotSupported {
+                    barrier_index: barrier_index as u32,
+                });
+            }
+
+            if barrier.mip_levels.start >= barrier.mip_levels.end
+                || barrier.mip_levels.end > barrier.image.mip_levels()
+                || barrier.array_layers.start >= barrier.array_layers.end
+                || barrier.array_layers.end > barrier.image.dimensions().array_layers()
+            {
+                return Err(PipelineBarrierError::ImageSubresourceRangeOutOfBounds {
+                    barrier_index: barrier_index as u32,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Commands that can only be executed on primary command buffers
+impl<P> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<P::Alloc>, P>
+where
+    P: CommandPoolBuilderAlloc,
+{
+    /// Adds a command that enters a render pass.
+    ///
+    /// If `contents` is `SubpassContents::SecondaryCommandBuffers`, then you will only be able to
+    /// add secondary command buffers while you're inside the first subpass of the render pass.
+    /// If it is `SubpassContents::Inline`, you will only be able to add inline draw commands and
+    /// not secondary command buffers.
+    ///
+    /// C must contain exactly one clear value for each attachment in the framebuffer.
+    ///
+    /// You must call this before you can add draw commands.
+    #[inline]
+    pub fn begin_render_pass<I>(
+        &mut self,
+        framebuffer: Arc<Framebuffer>,
+        contents: SubpassContents,
+        clear_values: I,
+    ) -> Result<&mut Self, BeginRenderPassError>
+    where
+        I: IntoIterator<Item = ClearValue>,
+    {
+        unsafe {
+            if !self.queue_family().supports_graphics() {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            self.ensure_outside_render_pass()?;
+
+            let clear_values = framebuffer
+                .render_pass()
+                .desc()
+                .convert_clear_values(clear_values);
+            let clear_values = clear_values.collect::<Vec<_>>().into_iter(); // TODO: necessary for Send + Sync ; needs an API rework of convert_clear_values
+            let mut clear_values_copy = clear_values.clone().enumerate(); // TODO: Proper errors for clear value errors instead of panics
+
+            for (atch_i, atch_desc) in framebuffer
+                .render_pass()
+                .desc()
+                .attachments()
+                .into_iter()
+                .enumerate()
+            {
+                match clear_values_copy.next() {
+                    Some((clear_i, clear_value)) => {
+                        if atch_desc.load == LoadOp::Clear {
+                            let aspects = atch_desc.format.aspects();
+
+                            if aspects.depth && aspects.stencil {
+                                assert!(
+                                    matches!(clear_value, ClearValue::DepthStencil(_)),
+                                    "Bad ClearValue! index: {}, attachment index: {}, expected: DepthStencil, got: {:?}",
+                                    clear_i,
+                                    atch_i,
+                                    clear_value,
+                                );
+                            } else if aspects.depth {
+                                assert!(
+                                    matches!(clear_value, ClearValue::Depth(_)),
+                                    "Bad ClearValue! index: {}, attachment index: {}, expected: Depth, got: {:?}",
+                                    clear_i,
+                                    atch_i,
+                                    clear_value,
+                                );
+                            } else if aspects.depth {
+                                assert!(
+                                    matches!(clear_value, ClearValue::Stencil(_)),
+                                    "Bad ClearValue! index: {}, attachment index: {}, expected: Stencil, got: {:?}",
+                                    clear_i,
+                                    atch_i,
+                                    clear_value,
+                                );
+                            } else if let Some(numeric_type) = atch_desc.format.type_color() {
+                                match numeric_type {
+                                    NumericType::SFLOAT
+                                    | NumericType::UFLOAT
+                                    | NumericType::SNORM
+                                    | NumericType::UNORM
+                                    | NumericType::SSCALED
+                                    | NumericType::USCALED
+                                    | NumericType::SRGB => {
+                                        assert!(
+                                            matches!(clear_value, ClearValue::Float(_)),
+                                            "Bad ClearValue! index: {}, attachment index: {}, expected: Float, got: {:?}",
+                                            clear_i,
+                                            atch_i,
+                                            clear_value,
+                                        );
+                                    }
+                                    NumericType::SINT => {
+                                        assert!(
+                                            matches!(clear_value, ClearValue::Int(_)),
+                                            "Bad ClearValue! index: {}, attachment index: {}, expected: Int, got: {:?}",
+                                            clear_i,
+                                            atch_i,
+                                            clear_value,
                                         );
                                     }
                                     NumericType::UINT => {
@@ -3333,11 +4497,12 @@ where
             self.inner
                 .begin_render_pass(framebuffer.clone(), contents, clear_values)?;
             self.render_pass_state = Some(RenderPassState {
-                subpass: framebuffer.render_pass().clone().first_subpass(),
+                render_pass: framebuffer.render_pass().clone().first_subpass().into(),
                 dimensions: framebuffer.dimensions(),
                 attached_layers_ranges: framebuffer.attached_layers_ranges(),
                 contents,
                 framebuffer: framebuffer_object,
+                attachments: Some(framebuffer_attachments(&framebuffer)),
             });
             Ok(self)
         }
@@ -3350,26 +4515,43 @@ where
     #[inline]
     pub fn end_render_pass(&mut self) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
         unsafe {
-            if let Some(render_pass_state) = self.render_pass_state.as_ref() {
-                if !render_pass_state.subpass.is_last_subpass() {
-                    return Err(AutoCommandBufferBuilderContextError::NumSubpassesMismatch {
-                        actual: render_pass_state
-                            .subpass
-                            .render_pass()
-                            .desc()
-                            .subpasses()
-                            .len() as u32,
-                        current: render_pass_state.subpass.index(),
-                    });
+            match self
+                .render_pass_state
+                .as_ref()
+                .map(|state| &state.render_pass)
+            {
+                Some(PipelineRenderPassType::BeginRenderPass(subpass)) => {
+                    if !subpass.is_last_subpass() {
+                        return Err(AutoCommandBufferBuilderContextError::NumSubpassesMismatch {
+                            actual: subpass.render_pass().desc().subpasses().len() as u32,
+                            current: subpass.index(),
+                        });
+                    }
+                }
+                Some(PipelineRenderPassType::BeginRendering(_)) => {
+                    return Err(AutoCommandBufferBuilderContextError::ForbiddenWithBeginRendering);
+                }
+                None => {
+                    return Err(AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass);
                 }
-            } else {
-                return Err(AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass);
             }
 
             if self.query_state.values().any(|state| state.in_subpass) {
                 return Err(AutoCommandBufferBuilderContextError::QueryIsActive);
             }
 
+            if self
+                .conditional_rendering_state
+                .as_ref()
+                .map_or(false, |state| state.in_subpass)
+            {
+                return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive);
+            }
+
+            if self.transform_feedback_active {
+                return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive);
+            }
+
             debug_assert!(self.queue_family().supports_graphics());
 
             self.inner.end_render_pass();
@@ -3378,57 +4560,365 @@ where
         }
     }
 
-    /// Adds a command that executes a secondary command buffer.
+    /// Adds a command that begins a render pass instance with dynamic rendering.
     ///
-    /// If the `flags` that `command_buffer` was created with are more restrictive than those of
-    /// `self`, then `self` will be restricted to match. E.g. executing a secondary command buffer
-    /// with `Flags::OneTimeSubmit` will set `self`'s flags to `Flags::OneTimeSubmit` also.
-    pub fn execute_commands<C>(
+    /// Unlike [`begin_render_pass`](Self::begin_render_pass), this doesn't need a render pass or
+    /// framebuffer object: the attachments are given directly in `rendering_info`. Until
+    /// [`end_rendering`](Self::end_rendering) is called, only graphics pipelines that were created
+    /// for dynamic rendering can be used, and the formats that they were created with must match
+    /// those of the attachments.
+    ///
+    /// The [`dynamic_rendering`](crate::device::Features::dynamic_rendering) feature must be
+    /// enabled on the device.
+    ///
+    /// Only single-sampled attachments are currently supported, and executing secondary command
+    /// buffers inside the render pass instance is not supported yet.
+    pub fn begin_rendering(
         &mut self,
-        command_buffer: C,
-    ) -> Result<&mut Self, ExecuteCommandsError>
-    where
-        C: SecondaryCommandBuffer + 'static,
-    {
-        self.check_command_buffer(&command_buffer)?;
-        let secondary_usage = command_buffer.inner().usage();
+        mut rendering_info: RenderingInfo,
+    ) -> Result<&mut Self, BeginRenderingError> {
+        let device = self.device();
+
+        if !device.enabled_features().dynamic_rendering {
+            return Err(BeginRenderingError::FeatureNotEnabled {
+                feature: "dynamic_rendering",
+                reason: "called begin_rendering",
+            });
+        }
 
-        unsafe {
-            let mut builder = self.inner.execute_commands();
-            builder.add(command_buffer);
-            builder.submit()?;
+        if rendering_info.view_mask != 0 && !device.enabled_features().multiview {
+            return Err(BeginRenderingError::FeatureNotEnabled {
+                feature: "multiview",
+                reason: "RenderingInfo::view_mask was not 0",
+            });
         }
 
-        // Secondary command buffer could leave the primary in any state.
-        self.inner.reset_state();
+        if rendering_info.view_mask != 0 && rendering_info.layer_count != 1 {
+            return Err(BeginRenderingError::MultiviewLayerCountNotOne);
+        }
 
-        // If the secondary is non-concurrent or one-time use, that restricts the primary as well.
-        self.usage = std::cmp::min(self.usage, secondary_usage);
+        if !self.queue_family().supports_graphics() {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
 
-        Ok(self)
-    }
+        self.ensure_outside_render_pass()?;
 
-    /// Adds a command that multiple secondary command buffers in a vector.
-    ///
-    /// This requires that the secondary command buffers do not have resource conflicts; an error
-    /// will be returned if there are any. Use `execute_commands` if you want to ensure that
-    /// resource conflicts are automatically resolved.
-    // TODO ^ would be nice if this just worked without errors
-    pub fn execute_commands_from_vec<C>(
-        &mut self,
-        command_buffers: Vec<C>,
-    ) -> Result<&mut Self, ExecuteCommandsError>
-    where
-        C: SecondaryCommandBuffer + 'static,
-    {
-        for command_buffer in &command_buffers {
-            self.check_command_buffer(command_buffer)?;
+        let max_color_attachments = device.physical_device().properties().max_color_attachments;
+
+        if rendering_info.color_attachments.len() as u32 > max_color_attachments {
+            return Err(BeginRenderingError::MaxColorAttachmentsExceeded {
+                max: max_color_attachments,
+                obtained: rendering_info.color_attachments.len() as u32,
+            });
         }
 
-        let mut secondary_usage = CommandBufferUsage::SimultaneousUse; // Most permissive usage
-        unsafe {
-            let mut builder = self.inner.execute_commands();
-            for command_buffer in command_buffers {
+        for (attachment_index, attachment_info) in
+            rendering_info.color_attachments.iter().enumerate()
+        {
+            if let Some(attachment_info) = attachment_info {
+                if !attachment_info.image_view.usage().color_attachment {
+                    return Err(BeginRenderingError::ColorAttachmentUsageNotSupported {
+                        attachment_index: attachment_index as u32,
+                    });
+                }
+
+                if !matches!(
+                    attachment_info.image_layout,
+                    ImageLayout::General | ImageLayout::ColorAttachmentOptimal
+                ) {
+                    return Err(BeginRenderingError::ColorAttachmentLayoutNotSupported {
+                        attachment_index: attachment_index as u32,
+                        layout: attachment_info.image_layout,
+                    });
+                }
+
+                if attachment_info.load_op == LoadOp::Clear
+                    && !color_clear_value_matches(
+                        attachment_info.image_view.format(),
+                        &attachment_info.clear_value,
+                    )
+                {
+                    return Err(BeginRenderingError::ColorAttachmentClearValueMismatch {
+                        attachment_index: attachment_index as u32,
+                        clear_value: attachment_info.clear_value,
+                    });
+                }
+            }
+        }
+
+        // The layouts that a depth or stencil attachment can be in during rendering.
+        let depth_stencil_layout_supported = |layout: ImageLayout| {
+            matches!(
+                layout,
+                ImageLayout::General
+                    | ImageLayout::DepthStencilAttachmentOptimal
+                    | ImageLayout::DepthStencilReadOnlyOptimal
+            )
+        };
+
+        if let Some(attachment_info) = &rendering_info.depth_attachment {
+            if !attachment_info.image_view.format().aspects().depth
+                || !attachment_info.image_view.usage().depth_stencil_attachment
+            {
+                return Err(BeginRenderingError::DepthAttachmentUsageNotSupported);
+            }
+
+            if !depth_stencil_layout_supported(attachment_info.image_layout) {
+                return Err(BeginRenderingError::DepthAttachmentLayoutNotSupported {
+                    layout: attachment_info.image_layout,
+                });
+            }
+
+            if attachment_info.load_op == LoadOp::Clear
+                && !matches!(
+                    attachment_info.clear_value,
+                    ClearValue::Depth(_) | ClearValue::DepthStencil(_)
+                )
+            {
+                return Err(BeginRenderingError::DepthAttachmentClearValueMismatch {
+                    clear_value: attachment_info.clear_value,
+                });
+            }
+        }
+
+        if let Some(attachment_info) = &rendering_info.stencil_attachment {
+            if !attachment_info.image_view.format().aspects().stencil
+                || !attachment_info.image_view.usage().depth_stencil_attachment
+            {
+                return Err(BeginRenderingError::StencilAttachmentUsageNotSupported);
+            }
+
+            if !depth_stencil_layout_supported(attachment_info.image_layout) {
+                return Err(BeginRenderingError::StencilAttachmentLayoutNotSupported {
+                    layout: attachment_info.image_layout,
+                });
+            }
+
+            if attachment_info.load_op == LoadOp::Clear
+                && !matches!(
+                    attachment_info.clear_value,
+                    ClearValue::Stencil(_) | ClearValue::DepthStencil(_)
+                )
+            {
+                return Err(BeginRenderingError::StencilAttachmentClearValueMismatch {
+                    clear_value: attachment_info.clear_value,
+                });
+            }
+        }
+
+        if let (Some(depth_info), Some(stencil_info)) = (
+            &rendering_info.depth_attachment,
+            &rendering_info.stencil_attachment,
+        ) {
+            if depth_info.image_view.internal_object() != stencil_info.image_view.internal_object()
+            {
+                return Err(BeginRenderingError::DepthStencilAttachmentImageViewMismatch);
+            }
+        }
+
+        // The smallest size and number of layers among all the attachments.
+        let mut max_extent = [u32::MAX; 2];
+        let mut max_layers = u32::MAX;
+        let mut attached_layers_ranges = SmallVec::new();
+        let mut attachments = SmallVec::new();
+
+        for attachment_info in rendering_info
+            .color_attachments
+            .iter()
+            .flatten()
+            .chain(rendering_info.depth_attachment.iter())
+            .chain(rendering_info.stencil_attachment.iter())
+        {
+            let image_view = &attachment_info.image_view;
+            let image = image_view.image();
+
+            if image.samples() != SampleCount::Sample1 {
+                return Err(BeginRenderingError::MultisampledAttachmentNotSupported);
+            }
+
+            let [width, height, _] = image
+                .dimensions()
+                .mip_level_dimensions(image_view.mip_levels().start)
+                .unwrap()
+                .width_height_depth();
+            let array_layers = image_view.array_layers();
+            max_extent = [max_extent[0].min(width), max_extent[1].min(height)];
+            max_layers = max_layers.min(array_layers.end - array_layers.start);
+            attached_layers_ranges.push(array_layers);
+            attachments.push(image_view.clone());
+        }
+
+        let [offset_x, offset_y] = rendering_info.render_area_offset;
+
+        if rendering_info.render_area_extent == [0, 0] {
+            if attachments.is_empty() {
+                return Err(BeginRenderingError::RenderAreaExtentZero);
+            }
+
+            rendering_info.render_area_extent = [
+                max_extent[0].saturating_sub(offset_x),
+                max_extent[1].saturating_sub(offset_y),
+            ];
+        }
+
+        let [extent_x, extent_y] = rendering_info.render_area_extent;
+
+        if extent_x == 0 || extent_y == 0 {
+            return Err(BeginRenderingError::RenderAreaExtentZero);
+        }
+
+        if offset_x as u64 + extent_x as u64 > max_extent[0] as u64
+            || offset_y as u64 + extent_y as u64 > max_extent[1] as u64
+        {
+            return Err(BeginRenderingError::RenderAreaOutOfBounds);
+        }
+
+        let layers_needed = if rendering_info.view_mask != 0 {
+            u32::BITS - rendering_info.view_mask.leading_zeros()
+        } else {
+            rendering_info.layer_count
+        };
+
+        if layers_needed == 0 || layers_needed > max_layers {
+            return Err(BeginRenderingError::LayerCountOutOfBounds);
+        }
+
+        let render_pass = PipelineRenderingCreateInfo {
+            view_mask: rendering_info.view_mask,
+            color_attachment_formats: rendering_info
+                .color_attachments
+                .iter()
+                .map(|attachment_info| {
+                    attachment_info
+                        .as_ref()
+                        .map(|attachment_info| attachment_info.image_view.format())
+                })
+                .collect(),
+            depth_attachment_format: rendering_info
+                .depth_attachment
+                .as_ref()
+                .map(|attachment_info| attachment_info.image_view.format()),
+            stencil_attachment_format: rendering_info
+                .stencil_attachment
+                .as_ref()
+                .map(|attachment_info| attachment_info.image_view.format()),
+        };
+        let dimensions = [offset_x + extent_x, offset_y + extent_y, layers_needed];
+
+        if rendering_info.view_mask != 0 {
+            // When multiview is enabled, at the beginning of each subpass all non-render pass state is undefined
+            self.inner.reset_state();
+        }
+
+        unsafe {
+            self.inner.begin_rendering(rendering_info)?;
+        }
+
+        self.render_pass_state = Some(RenderPassState {
+            render_pass: render_pass.into(),
+            contents: SubpassContents::Inline,
+            attached_layers_ranges,
+            dimensions,
+            framebuffer: ash::vk::Framebuffer::null(),
+            attachments: Some(attachments),
+        });
+        Ok(self)
+    }
+
+    /// Adds a command that ends the current render pass instance, that was begun with
+    /// [`begin_rendering`](Self::begin_rendering).
+    #[inline]
+    pub fn end_rendering(&mut self) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
+        unsafe {
+            match self
+                .render_pass_state
+                .as_ref()
+                .map(|state| &state.render_pass)
+            {
+                Some(PipelineRenderPassType::BeginRendering(_)) => (),
+                Some(PipelineRenderPassType::BeginRenderPass(_)) => {
+                    return Err(AutoCommandBufferBuilderContextError::ForbiddenWithBeginRenderPass);
+                }
+                None => {
+                    return Err(AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass);
+                }
+            }
+
+            if self.query_state.values().any(|state| state.in_subpass) {
+                return Err(AutoCommandBufferBuilderContextError::QueryIsActive);
+            }
+
+            if self
+                .conditional_rendering_state
+                .as_ref()
+                .map_or(false, |state| state.in_subpass)
+            {
+                return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive);
+            }
+
+            if self.transform_feedback_active {
+                return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive);
+            }
+
+            debug_assert!(self.queue_family().supports_graphics());
+
+            self.inner.end_rendering();
+            self.render_pass_state = None;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that executes a secondary command buffer.
+    ///
+    /// If the `flags` that `command_buffer` was created with are more restrictive than those of
+    /// `self`, then `self` will be restricted to match. E.g. executing a secondary command buffer
+    /// with `Flags::OneTimeSubmit` will set `self`'s flags to `Flags::OneTimeSubmit` also.
+    pub fn execute_commands<C>(
+        &mut self,
+        command_buffer: C,
+    ) -> Result<&mut Self, ExecuteCommandsError>
+    where
+        C: SecondaryCommandBuffer + 'static,
+    {
+        self.check_command_buffer(&command_buffer)?;
+        let secondary_usage = command_buffer.inner().usage();
+
+        unsafe {
+            let mut builder = self.inner.execute_commands();
+            builder.add(command_buffer);
+            builder.submit()?;
+        }
+
+        // Secondary command buffer could leave the primary in any state.
+        self.inner.reset_state();
+
+        // If the secondary is non-concurrent or one-time use, that restricts the primary as well.
+        self.usage = std::cmp::min(self.usage, secondary_usage);
+
+        Ok(self)
+    }
+
+    /// Adds a command that multiple secondary command buffers in a vector.
+    ///
+    /// This requires that the secondary command buffers do not have resource conflicts; an error
+    /// will be returned if there are any. Use `execute_commands` if you want to ensure that
+    /// resource conflicts are automatically resolved.
+    // TODO ^ would be nice if this just worked without errors
+    pub fn execute_commands_from_vec<C>(
+        &mut self,
+        command_buffers: Vec<C>,
+    ) -> Result<&mut Self, ExecuteCommandsError>
+    where
+        C: SecondaryCommandBuffer + 'static,
+    {
+        for command_buffer in &command_buffers {
+            self.check_command_buffer(command_buffer)?;
+        }
+
+        let mut secondary_usage = CommandBufferUsage::SimultaneousUse; // Most permissive usage
+        unsafe {
+            let mut builder = self.inner.execute_commands();
+            for command_buffer in command_buffers {
                 secondary_usage = std::cmp::min(secondary_usage, command_buffer.inner().usage());
                 builder.add(command_buffer);
             }
@@ -3458,6 +4948,25 @@ where
             self.ensure_outside_render_pass()?;
         }
 
+        if self.conditional_rendering_state.is_some()
+            && !self
+                .device()
+                .enabled_features()
+                .inherited_conditional_rendering
+        {
+            return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive);
+        }
+
+        if self.transform_feedback_active {
+            return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive);
+        }
+
+        // VUID-vkCmdExecuteCommands-commandBuffer-01820
+        // VUID-vkCmdExecuteCommands-commandBuffer-01821
+        if command_buffer.inner().is_protected() != self.pool_builder_alloc.inner().is_protected() {
+            return Err(AutoCommandBufferBuilderContextError::ProtectedMismatch);
+        }
+
         for state in self.query_state.values() {
             match state.ty {
                 QueryType::Occlusion => match command_buffer.inheritance().occlusion_query {
@@ -3498,12 +5007,20 @@ where
             .as_ref()
             .ok_or(AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass)?;
 
+        // Secondary command buffers can't inherit dynamic rendering yet.
+        let current_subpass = match &render_pass_state.render_pass {
+            PipelineRenderPassType::BeginRenderPass(subpass) => subpass,
+            PipelineRenderPassType::BeginRendering(_) => {
+                return Err(AutoCommandBufferBuilderContextError::ForbiddenWithBeginRendering);
+            }
+        };
+
         if render_pass_state.contents != SubpassContents::SecondaryCommandBuffers {
             return Err(AutoCommandBufferBuilderContextError::WrongSubpassType);
         }
 
         // Subpasses must be the same.
-        if render_pass.subpass.index() != render_pass_state.subpass.index() {
+        if render_pass.subpass.index() != current_subpass.index() {
             return Err(AutoCommandBufferBuilderContextError::WrongSubpassIndex);
         }
 
@@ -3512,7 +5029,7 @@ where
             .subpass
             .render_pass()
             .desc()
-            .is_compatible_with_desc(render_pass_state.subpass.render_pass().desc())
+            .is_compatible_with_desc(current_subpass.render_pass().desc())
         {
             return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass);
         }
@@ -3536,22 +5053,25 @@ where
     ) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
         unsafe {
             if let Some(render_pass_state) = self.render_pass_state.as_mut() {
-                if render_pass_state.subpass.try_next_subpass() {
+                let subpass = match &mut render_pass_state.render_pass {
+                    PipelineRenderPassType::BeginRenderPass(subpass) => subpass,
+                    PipelineRenderPassType::BeginRendering(_) => {
+                        return Err(
+                            AutoCommandBufferBuilderContextError::ForbiddenWithBeginRendering,
+                        );
+                    }
+                };
+
+                if subpass.try_next_subpass() {
                     render_pass_state.contents = contents;
                 } else {
                     return Err(AutoCommandBufferBuilderContextError::NumSubpassesMismatch {
-                        actual: render_pass_state
-                            .subpass
-                            .render_pass()
-                            .desc()
-                            .subpasses()
-                            .len() as u32,
-                        current: render_pass_state.subpass.index(),
+                        actual: subpass.render_pass().desc().subpasses().len() as u32,
+                        current: subpass.index(),
                     });
                 }
 
-                if let Some(multiview) = render_pass_state.subpass.render_pass().desc().multiview()
-                {
+                if let Some(multiview) = subpass.render_pass().desc().multiview() {
                     // When multiview is enabled, at the beginning of each subpass all non-render pass state is undefined
                     self.inner.reset_state();
                 }
@@ -3563,6 +5083,18 @@ where
                 return Err(AutoCommandBufferBuilderContextError::QueryIsActive);
             }
 
+            if self
+                .conditional_rendering_state
+                .as_ref()
+                .map_or(false, |state| state.in_subpass)
+            {
+                return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive);
+            }
+
+            if self.transform_feedback_active {
+                return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive);
+            }
+
             debug_assert!(self.queue_family().supports_graphics());
 
             self.inner.next_subpass(contents);
@@ -3863,6 +5395,12 @@ err_gen!(BuildError {
     OomError,
 });
 
+err_gen!(BuildAccelerationStructureError {
+    AutoCommandBufferBuilderContextError,
+    CheckBuildAccelerationStructureError,
+    SyncCommandBufferBuilderError,
+});
+
 err_gen!(BeginRenderPassError {
     AutoCommandBufferBuilderContextError,
     SyncCommandBufferBuilderError,
@@ -3988,6 +5526,30 @@ err_gen!(ExecuteCommandsError {
     SyncCommandBufferBuilderError,
 });
 
+err_gen!(BeginConditionalRenderingError {
+    AutoCommandBufferBuilderContextError,
+    CheckBeginConditionalRenderingError,
+    SyncCommandBufferBuilderError,
+});
+
+err_gen!(BindTransformFeedbackBuffersError {
+    AutoCommandBufferBuilderContextError,
+    CheckTransformFeedbackError,
+    SyncCommandBufferBuilderError,
+});
+
+err_gen!(BeginTransformFeedbackError {
+    AutoCommandBufferBuilderContextError,
+    CheckTransformFeedbackError,
+    SyncCommandBufferBuilderError,
+});
+
+err_gen!(EndTransformFeedbackError {
+    AutoCommandBufferBuilderContextError,
+    CheckTransformFeedbackError,
+    SyncCommandBufferBuilderError,
+});
+
 err_gen!(BeginQueryError {
     AutoCommandBufferBuilderContextError,
     CheckBeginQueryError,
@@ -4008,6 +5570,15 @@ err_gen!(ResetQueryPoolError {
     CheckResetQueryPoolError,
 });
 
+err_gen!(TraceRaysError {
+    AutoCommandBufferBuilderContextError,
+    CheckPipelineError,
+    CheckPushConstantsValidityError,
+    CheckDescriptorSetsValidityError,
+    CheckTraceRaysError,
+    SyncCommandBufferBuilderError,
+});
+
 err_gen!(UpdateBufferError {
     AutoCommandBufferBuilderContextError,
     CheckUpdateBufferError,
@@ -4087,57 +5658,425 @@ impl From<CheckPipelineError> for ClearAttachmentsError {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub enum AutoCommandBufferBuilderContextError {
-    /// Operation forbidden inside of a render pass.
-    ForbiddenInsideRenderPass,
-    /// Operation forbidden outside of a render pass.
-    ForbiddenOutsideRenderPass,
-    /// Tried to use a secondary command buffer with a specified framebuffer that is
-    /// incompatible with the current framebuffer.
-    IncompatibleFramebuffer,
-    /// Tried to use a graphics pipeline or secondary command buffer whose render pass
-    /// is incompatible with the current render pass.
-    IncompatibleRenderPass,
-    /// The queue family doesn't allow this operation.
-    NotSupportedByQueueFamily,
-    /// Tried to end a render pass with subpasses remaining, or tried to go to next subpass with no
-    /// subpass remaining.
-    NumSubpassesMismatch {
-        /// Actual number of subpasses in the current render pass.
-        actual: u32,
-        /// Current subpass index before the failing command.
-        current: u32,
+/// Error that can happen when calling [`begin_rendering`](AutoCommandBufferBuilder::begin_rendering).
+#[derive(Debug, Clone)]
+pub enum BeginRenderingError {
+    /// AutoCommandBufferBuilderContextError
+    AutoCommandBufferBuilderContextError(AutoCommandBufferBuilderContextError),
+    /// SyncCommandBufferBuilderError
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    /// The load operation of a color attachment is `LoadOp::Clear`, but its clear value doesn't
+    /// match the numeric type of its format.
+    ColorAttachmentClearValueMismatch {
+        /// The index of the attachment.
+        attachment_index: u32,
+        /// The clear value that was provided.
+        clear_value: ClearValue,
     },
-    /// A query is active that conflicts with the current operation.
-    QueryIsActive,
-    /// This query was not active.
-    QueryNotActive,
-    /// A query is active that is not included in the `inheritance` of the secondary command buffer.
-    QueryNotInherited,
-    /// Tried to use a graphics pipeline or secondary command buffer whose subpass index
-    /// didn't match the current subpass index.
-    WrongSubpassIndex,
-    /// Tried to execute a secondary command buffer inside a subpass that only allows inline
-    /// commands, or a draw command in a subpass that only allows secondary command buffers.
-    WrongSubpassType,
+    /// The image layout of a color attachment is not `General` or `ColorAttachmentOptimal`.
+    ColorAttachmentLayoutNotSupported {
+        /// The index of the attachment.
+        attachment_index: u32,
+        /// The layout that was provided.
+        layout: ImageLayout,
+    },
+    /// The image view of a color attachment was not created with the `color_attachment` usage.
+    ColorAttachmentUsageNotSupported {
+        /// The index of the attachment.
+        attachment_index: u32,
+    },
+    /// The load operation of the depth attachment is `LoadOp::Clear`, but its clear value is not
+    /// `ClearValue::Depth` or `ClearValue::DepthStencil`.
+    DepthAttachmentClearValueMismatch {
+        /// The clear value that was provided.
+        clear_value: ClearValue,
+    },
+    /// The image layout of the depth attachment is not `General`, `DepthStencilAttachmentOptimal`
+    /// or `DepthStencilReadOnlyOptimal`.
+    DepthAttachmentLayoutNotSupported {
+        /// The layout that was provided.
+        layout: ImageLayout,
+    },
+    /// The image view of the depth attachment has no depth aspect, or was not created with the
+    /// `depth_stencil_attachment` usage.
+    DepthAttachmentUsageNotSupported,
+    /// The depth and stencil attachments are both present, but use different image views.
+    DepthStencilAttachmentImageViewMismatch,
+    /// A feature needed for the operation is not enabled.
+    FeatureNotEnabled {
+        /// The name of the feature.
+        feature: &'static str,
+        /// Why the feature is needed.
+        reason: &'static str,
+    },
+    /// The number of layers that are rendered to is `0`, or exceeds the number of layers of an
+    /// attachment.
+    LayerCountOutOfBounds,
+    /// The number of color attachments exceeds the `max_color_attachments` limit.
+    MaxColorAttachmentsExceeded {
+        /// The maximum that is allowed.
+        max: u32,
+        /// The number that was provided.
+        obtained: u32,
+    },
+    /// An attachment has more than one sample, which is not supported yet.
+    MultisampledAttachmentNotSupported,
+    /// `view_mask` is not `0`, but `layer_count` is not `1`.
+    MultiviewLayerCountNotOne,
+    /// The size of the render area is `0` in at least one dimension.
+    RenderAreaExtentZero,
+    /// The render area is not inside the bounds of all the attachments.
+    RenderAreaOutOfBounds,
+    /// The load operation of the stencil attachment is `LoadOp::Clear`, but its clear value is
+    /// not `ClearValue::Stencil` or `ClearValue::DepthStencil`.
+    StencilAttachmentClearValueMismatch {
+        /// The clear value that was provided.
+        clear_value: ClearValue,
+    },
+    /// The image layout of the stencil attachment is not `General`,
+    /// `DepthStencilAttachmentOptimal` or `DepthStencilReadOnlyOptimal`.
+    StencilAttachmentLayoutNotSupported {
+        /// The layout that was provided.
+        layout: ImageLayout,
+    },
+    /// The image view of the stencil attachment has no stencil aspect, or was not created with
+    /// the `depth_stencil_attachment` usage.
+    StencilAttachmentUsageNotSupported,
 }
 
-impl error::Error for AutoCommandBufferBuilderContextError {}
+impl error::Error for BeginRenderingError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::AutoCommandBufferBuilderContextError(ref err) => Some(err),
+            Self::SyncCommandBufferBuilderError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
-impl fmt::Display for AutoCommandBufferBuilderContextError {
+impl fmt::Display for BeginRenderingError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::AutoCommandBufferBuilderContextError(_) => write!(fmt, "a context error"),
+            Self::SyncCommandBufferBuilderError(_) => write!(fmt, "a SyncCommandBufferBuilderError"),
+            Self::ColorAttachmentClearValueMismatch {
+                attachment_index,
+                clear_value,
+            } => write!(
+                fmt,
+                "the clear value {:?} of color attachment {} doesn't match the numeric type of its format",
+                clear_value, attachment_index,
+            ),
+            Self::ColorAttachmentLayoutNotSupported {
+                attachment_index,
+                layout,
+            } => write!(
+                fmt,
+                "the image layout {:?} of color attachment {} is not supported for rendering",
+                layout, attachment_index,
+            ),
+            Self::ColorAttachmentUsageNotSupported { attachment_index } => write!(
+                fmt,
+                "the image view of color attachment {} was not created with the `color_attachment` usage",
+                attachment_index,
+            ),
+            Self::DepthAttachmentClearValueMismatch { clear_value } => write!(
+                fmt,
+                "the clear value {:?} of the depth attachment is not a depth value",
+                clear_value,
+            ),
+            Self::DepthAttachmentLayoutNotSupported { layout } => write!(
+                fmt,
+                "the image layout {:?} of the depth attachment is not supported for rendering",
+                layout,
+            ),
+            Self::DepthAttachmentUsageNotSupported => write!(
+                fmt,
+                "the image view of the depth attachment has no depth aspect, or was not created with the `depth_stencil_attachment` usage",
+            ),
+            Self::DepthStencilAttachmentImageViewMismatch => write!(
+                fmt,
+                "the depth and stencil attachments are both present, but use different image views",
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::LayerCountOutOfBounds => write!(
+                fmt,
+                "the number of layers that are rendered to is 0, or exceeds the number of layers of an attachment",
+            ),
+            Self::MaxColorAttachmentsExceeded { max, obtained } => write!(
+                fmt,
+                "the number of color attachments ({}) exceeds the `max_color_attachments` limit ({})",
+                obtained, max,
+            ),
+            Self::MultisampledAttachmentNotSupported => write!(
+                fmt,
+                "an attachment has more than one sample, which is not supported yet",
+            ),
+            Self::MultiviewLayerCountNotOne => write!(
+                fmt,
+                "the view mask is not 0, but the layer count is not 1",
+            ),
+            Self::RenderAreaExtentZero => write!(
+                fmt,
+                "the size of the render area is 0 in at least one dimension",
+            ),
+            Self::RenderAreaOutOfBounds => write!(
+                fmt,
+                "the render area is not inside the bounds of all the attachments",
+            ),
+            Self::StencilAttachmentClearValueMismatch { clear_value } => write!(
+                fmt,
+                "the clear value {:?} of the stencil attachment is not a stencil value",
+                clear_value,
+            ),
+            Self::StencilAttachmentLayoutNotSupported { layout } => write!(
+                fmt,
+                "the image layout {:?} of the stencil attachment is not supported for rendering",
+                layout,
+            ),
+            Self::StencilAttachmentUsageNotSupported => write!(
+                fmt,
+                "the image view of the stencil attachment has no stencil aspect, or was not created with the `depth_stencil_attachment` usage",
+            ),
+        }
+    }
+}
+
+impl From<AutoCommandBufferBuilderContextError> for BeginRenderingError {
+    #[inline]
+    fn from(err: AutoCommandBufferBuilderContextError) -> Self {
+        Self::AutoCommandBufferBuilderContextError(err)
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for BeginRenderingError {
+    #[inline]
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+/// Error that can happen when calling [`pipeline_barrier2`](AutoCommandBufferBuilder::pipeline_barrier2),
+/// [`set_event2`](AutoCommandBufferBuilder::set_event2) or
+/// [`wait_events2`](AutoCommandBufferBuilder::wait_events2).
+#[derive(Debug, Clone)]
+pub enum PipelineBarrierError {
+    /// AutoCommandBufferBuilderContextError
+    AutoCommandBufferBuilderContextError(AutoCommandBufferBuilderContextError),
+
+    /// The range of a buffer memory barrier is empty, or exceeds the size of the buffer.
+    BufferRangeOutOfBounds {
+        /// The index of the barrier.
+        barrier_index: u32,
+    },
+    /// A feature needed for the operation is not enabled.
+    FeatureNotEnabled {
+        /// The name of the feature.
+        feature: &'static str,
+        /// Why the feature is needed.
+        reason: &'static str,
+    },
+    /// The `old_layout` and `new_layout` of an image memory barrier are different. Layout
+    /// transitions are done automatically by vulkano.
+    ImageLayoutTransitionNotSupported {
+        /// The index of the barrier.
+        barrier_index: u32,
+    },
+    /// The mip levels or array layers of an image memory barrier are empty, or exceed those of
+    /// the image.
+    ImageSubresourceRangeOutOfBounds {
+        /// The index of the barrier.
+        barrier_index: u32,
+    },
+}
+
+impl error::Error for PipelineBarrierError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::AutoCommandBufferBuilderContextError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PipelineBarrierError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::AutoCommandBufferBuilderContextError(_) => write!(fmt, "a context error"),
+            Self::BufferRangeOutOfBounds { barrier_index } => write!(
+                fmt,
+                "the range of buffer memory barrier {} is empty, or exceeds the size of the buffer",
+                barrier_index,
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::ImageLayoutTransitionNotSupported { barrier_index } => write!(
+                fmt,
+                "the old and new layouts of image memory barrier {} are different",
+                barrier_index,
+            ),
+            Self::ImageSubresourceRangeOutOfBounds { barrier_index } => write!(
+                fmt,
+                "the mip levels or array layers of image memory barrier {} are empty, or exceed those of the image",
+                barrier_index,
+            ),
+        }
+    }
+}
+
+impl From<AutoCommandBufferBuilderContextError> for PipelineBarrierError {
+    #[inline]
+    fn from(err: AutoCommandBufferBuilderContextError) -> Self {
+        Self::AutoCommandBufferBuilderContextError(err)
+    }
+}
+
+/// Error that can happen when calling
+/// [`push_constants`](AutoCommandBufferBuilder::push_constants).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PushConstantsError {
+    /// The offset is not a multiple of 4.
+    OffsetNotMultipleOf4 {
+        /// The offset that was provided.
+        offset: u32,
+    },
+    /// Some of the bytes being set are not within any of the push constant ranges of the
+    /// pipeline layout.
+    RangeOutOfBounds {
+        /// The offset that was provided.
+        offset: u32,
+        /// The size of the push constants that were provided.
+        size: u32,
+    },
+    /// The size of the push constants is not a multiple of 4.
+    SizeNotMultipleOf4 {
+        /// The size of the push constants that were provided.
+        size: u32,
+    },
+}
+
+impl error::Error for PushConstantsError {}
+
+impl fmt::Display for PushConstantsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OffsetNotMultipleOf4 { offset } => {
+                write!(fmt, "the offset {} is not a multiple of 4", offset)
+            }
+            Self::RangeOutOfBounds { offset, size } => write!(
+                fmt,
+                "the {} bytes at offset {} are not all within the push constant ranges of the pipeline layout",
+                size, offset,
+            ),
+            Self::SizeNotMultipleOf4 { size } => write!(
+                fmt,
+                "the size of the push constants, {}, is not a multiple of 4",
+                size,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum AutoCommandBufferBuilderContextError {
+    /// Conditional rendering is active, which conflicts with the current operation.
+    ConditionalRenderingIsActive,
+    /// Tried to end conditional rendering, but it was not active.
+    ConditionalRenderingNotActive,
+    /// A debug label region was opened but not closed.
+    DebugUtilsLabelIsActive,
+    /// Tried to close a debug label region, but none was open.
+    DebugUtilsLabelNotActive,
+    /// Operation forbidden inside of a render pass.
+    ForbiddenInsideRenderPass,
+    /// Operation forbidden outside of a render pass.
+    ForbiddenOutsideRenderPass,
+    /// Operation forbidden inside a render pass instance that was begun with
+    /// `begin_render_pass`.
+    ForbiddenWithBeginRenderPass,
+    /// Operation forbidden inside a render pass instance that was begun with `begin_rendering`.
+    ForbiddenWithBeginRendering,
+    /// Tried to use a secondary command buffer with a specified framebuffer that is
+    /// incompatible with the current framebuffer.
+    IncompatibleFramebuffer,
+    /// Tried to use a graphics pipeline or secondary command buffer whose render pass
+    /// is incompatible with the current render pass.
+    IncompatibleRenderPass,
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+    /// Tried to execute an unprotected secondary command buffer in a protected primary command
+    /// buffer, or the other way around.
+    ProtectedMismatch,
+    /// Tried to end a render pass with subpasses remaining, or tried to go to next subpass with no
+    /// subpass remaining.
+    NumSubpassesMismatch {
+        /// Actual number of subpasses in the current render pass.
+        actual: u32,
+        /// Current subpass index before the failing command.
+        current: u32,
+    },
+    /// A query is active that conflicts with the current operation.
+    QueryIsActive,
+    /// This query was not active.
+    QueryNotActive,
+    /// A query is active that is not included in the `inheritance` of the secondary command buffer.
+    QueryNotInherited,
+    /// Transform feedback is active, which conflicts with the current operation.
+    TransformFeedbackIsActive,
+    /// Tried to end transform feedback, but it was not active.
+    TransformFeedbackNotActive,
+    /// Tried to use a graphics pipeline or secondary command buffer whose subpass index
+    /// didn't match the current subpass index.
+    WrongSubpassIndex,
+    /// Tried to execute a secondary command buffer inside a subpass that only allows inline
+    /// commands, or a draw command in a subpass that only allows secondary command buffers.
+    WrongSubpassType,
+}
+
+impl error::Error for AutoCommandBufferBuilderContextError {}
+
+impl fmt::Display for AutoCommandBufferBuilderContextError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(
             fmt,
             "{}",
             match *self {
+                AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive => {
+                    "conditional rendering is active, which conflicts with the current operation"
+                }
+                AutoCommandBufferBuilderContextError::ConditionalRenderingNotActive => {
+                    "tried to end conditional rendering, but it was not active"
+                }
+                AutoCommandBufferBuilderContextError::DebugUtilsLabelIsActive => {
+                    "a debug label region was opened but not closed"
+                }
+                AutoCommandBufferBuilderContextError::DebugUtilsLabelNotActive => {
+                    "tried to close a debug label region, but none was open"
+                }
                 AutoCommandBufferBuilderContextError::ForbiddenInsideRenderPass => {
                     "operation forbidden inside of a render pass"
                 }
                 AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass => {
                     "operation forbidden outside of a render pass"
                 }
+                AutoCommandBufferBuilderContextError::ForbiddenWithBeginRenderPass => {
+                    "operation forbidden inside a render pass instance that was begun with \
+                 `begin_render_pass`"
+                }
+                AutoCommandBufferBuilderContextError::ForbiddenWithBeginRendering => {
+                    "operation forbidden inside a render pass instance that was begun with \
+                 `begin_rendering`"
+                }
                 AutoCommandBufferBuilderContextError::IncompatibleFramebuffer => {
                     "tried to use a secondary command buffer with a specified framebuffer that is \
                  incompatible with the current framebuffer"
@@ -4149,6 +6088,10 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
                 AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily => {
                     "the queue family doesn't allow this operation"
                 }
+                AutoCommandBufferBuilderContextError::ProtectedMismatch => {
+                    "tried to execute an unprotected secondary command buffer in a protected \
+                 primary command buffer, or the other way around"
+                }
                 AutoCommandBufferBuilderContextError::NumSubpassesMismatch { .. } => {
                     "tried to end a render pass with subpasses remaining, or tried to go to next \
                  subpass with no subpass remaining"
@@ -4162,6 +6105,12 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
                 AutoCommandBufferBuilderContextError::QueryNotInherited => {
                     "a query is active that is not included in the inheritance of the secondary command buffer"
                 }
+                AutoCommandBufferBuilderContextError::TransformFeedbackIsActive => {
+                    "transform feedback is active, which conflicts with the current operation"
+                }
+                AutoCommandBufferBuilderContextError::TransformFeedbackNotActive => {
+                    "tried to end transform feedback, but it was not active"
+                }
                 AutoCommandBufferBuilderContextError::WrongSubpassIndex => {
                     "tried to use a graphics pipeline whose subpass index didn't match the current \
                  subpass index"
@@ -4178,23 +6127,275 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
 
 #[cfg(test)]
 mod tests {
+    use super::AutoCommandBufferBuilderContextError;
+    use super::BeginError;
+    use super::BeginRenderingError;
     use super::CopyBufferError;
+    use super::DebugMarkerError;
+    use super::DrawError;
+    use super::PipelineBarrierError;
+    use super::PrimaryAutoCommandBuffer;
+    use super::PushConstantsError;
     use crate::buffer::BufferUsage;
     use crate::buffer::CpuAccessibleBuffer;
     use crate::command_buffer::synced::SyncCommandBufferBuilderError;
     use crate::command_buffer::validity::CheckCopyBufferError;
+    use crate::command_buffer::validity::CheckDescriptorSetsValidityError;
+    use crate::command_buffer::validity::InvalidDescriptorResource;
     use crate::command_buffer::AutoCommandBufferBuilder;
     use crate::command_buffer::CommandBufferExecError;
     use crate::command_buffer::CommandBufferUsage;
+    use crate::command_buffer::DrawIndirectCommand;
     use crate::command_buffer::ExecuteCommandsError;
     use crate::command_buffer::PrimaryCommandBuffer;
+    use crate::command_buffer::RenderingAttachmentInfo;
+    use crate::command_buffer::RenderingInfo;
+    use crate::command_buffer::SubpassContents;
+    use crate::descriptor_set::PersistentDescriptorSet;
+    use crate::descriptor_set::WriteDescriptorSet;
     use crate::device::physical::PhysicalDevice;
     use crate::device::Device;
     use crate::device::DeviceExtensions;
     use crate::device::Features;
+    use crate::device::Queue;
+    use crate::format::ClearValue;
+    use crate::format::Format;
+    use crate::image::view::ImageView;
+    use crate::image::AttachmentImage;
+    use crate::image::ImageAccess;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageLayout;
+    use crate::image::ImageUsage;
+    use crate::image::ImmutableImage;
+    use crate::image::MipmapsCount;
+    use crate::pipeline::graphics::render_pass::PipelineRenderPassType;
+    use crate::pipeline::graphics::render_pass::PipelineRenderingCreateInfo;
+    use crate::pipeline::graphics::viewport::Viewport;
+    use crate::pipeline::graphics::viewport::ViewportState;
+    use crate::pipeline::ComputePipeline;
+    use crate::pipeline::GraphicsPipeline;
+    use crate::pipeline::Pipeline;
+    use crate::pipeline::PipelineBindPoint;
+    use crate::render_pass::Framebuffer;
+    use crate::render_pass::LoadOp;
+    use crate::render_pass::RenderPass;
+    use crate::render_pass::Subpass;
+    use crate::shader::ShaderModule;
+    use crate::sync::AccessFlags;
+    use crate::sync::AccessFlags2;
+    use crate::sync::BufferMemoryBarrier2;
+    use crate::sync::DependencyInfo;
+    use crate::sync::Event;
     use crate::sync::GpuFuture;
+    use crate::sync::PipelineStages;
+    use crate::sync::PipelineStages2;
     use std::sync::Arc;
 
+    /// Same as `gfx_dev_and_queue_with!`, with the `dynamic_rendering` feature and the given
+    /// features enabled.
+    macro_rules! dynamic_rendering_dev_and_queue {
+        ($($feature:ident),*) => {
+            gfx_dev_and_queue_with!(
+                [
+                    khr_create_renderpass2,
+                    khr_depth_stencil_resolve,
+                    khr_dynamic_rendering,
+                    khr_maintenance2,
+                    khr_multiview
+                ],
+                [dynamic_rendering $(, $feature)*]
+            )
+        };
+    }
+
+    // Builds a pipeline without any vertex input or fragment output, for the given render pass.
+    fn empty_graphics_pipeline(
+        device: Arc<Device>,
+        render_pass: impl Into<PipelineRenderPassType>,
+    ) -> Arc<GraphicsPipeline> {
+        /*
+         * #version 450
+         * void main() {
+         * }
+         */
+        const FS: [u8; 180] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 10, 0, 8, 0, 6, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0, 0, 0, 0,
+            14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 4, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105,
+            110, 0, 0, 0, 0, 16, 0, 3, 0, 4, 0, 0, 0, 7, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1,
+            0, 0, 5, 0, 4, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0,
+            33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0,
+            3, 0, 0, 0, 248, 0, 2, 0, 5, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
+        ];
+
+        graphics_pipeline_with_fragment_shader(device, render_pass, &FS)
+    }
+
+    // Builds a pipeline without any vertex input, with the given fragment shader, for the given
+    // render pass.
+    fn graphics_pipeline_with_fragment_shader(
+        device: Arc<Device>,
+        render_pass: impl Into<PipelineRenderPassType>,
+        fs: &[u8],
+    ) -> Arc<GraphicsPipeline> {
+        /*
+         * #version 450
+         * void main() {
+         * }
+         */
+        const VS: [u8; 168] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 10, 0, 8, 0, 6, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0, 0, 0, 0,
+            14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 0, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105,
+            110, 0, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1, 0, 0, 5, 0, 4, 0, 4, 0, 0, 0, 109, 97,
+            105, 110, 0, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0, 54,
+            0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2, 0, 5, 0, 0, 0, 253,
+            0, 1, 0, 56, 0, 1, 0,
+        ];
+        let vs = unsafe { ShaderModule::from_bytes(device.clone(), &VS).unwrap() };
+        let fs = unsafe { ShaderModule::from_bytes(device.clone(), fs).unwrap() };
+
+        GraphicsPipeline::start()
+            .vertex_shader(vs.entry_point("main").unwrap(), ())
+            .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant([
+                Viewport {
+                    origin: [0.0, 0.0],
+                    dimensions: [64.0, 64.0],
+                    depth_range: 0.0..1.0,
+                },
+            ]))
+            .fragment_shader(fs.entry_point("main").unwrap(), ())
+            .render_pass(render_pass)
+            .build(device)
+            .unwrap()
+    }
+
+    #[test]
+    fn input_attachment_index() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        /*
+         * #version 450
+         * layout(input_attachment_index = 1, set = 0, binding = 0) uniform subpassInput u_input;
+         * void main() {
+         *     vec4 value = subpassLoad(u_input);
+         * }
+         */
+        const FS: [u8; 396] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 0, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            17, 0, 2, 0, 40, 0, 0, 0, 14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 4, 0, 0, 0,
+            3, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 16, 0, 3, 0, 3, 0, 0, 0, 7, 0, 0, 0, 3, 0,
+            3, 0, 2, 0, 0, 0, 194, 1, 0, 0, 71, 0, 4, 0, 8, 0, 0, 0, 34, 0, 0, 0, 0, 0, 0, 0, 71,
+            0, 4, 0, 8, 0, 0, 0, 33, 0, 0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 8, 0, 0, 0, 43, 0, 0, 0, 1,
+            0, 0, 0, 19, 0, 2, 0, 1, 0, 0, 0, 33, 0, 3, 0, 2, 0, 0, 0, 1, 0, 0, 0, 22, 0, 3, 0, 5,
+            0, 0, 0, 32, 0, 0, 0, 25, 0, 9, 0, 6, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 32, 0, 4, 0, 7, 0, 0, 0, 0, 0, 0, 0, 6, 0,
+            0, 0, 59, 0, 4, 0, 7, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 21, 0, 4, 0, 10, 0, 0, 0, 32, 0,
+            0, 0, 1, 0, 0, 0, 23, 0, 4, 0, 11, 0, 0, 0, 10, 0, 0, 0, 2, 0, 0, 0, 43, 0, 4, 0, 10,
+            0, 0, 0, 12, 0, 0, 0, 0, 0, 0, 0, 44, 0, 5, 0, 11, 0, 0, 0, 13, 0, 0, 0, 12, 0, 0, 0,
+            12, 0, 0, 0, 23, 0, 4, 0, 14, 0, 0, 0, 5, 0, 0, 0, 4, 0, 0, 0, 54, 0, 5, 0, 1, 0, 0, 0,
+            3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 248, 0, 2, 0, 4, 0, 0, 0, 61, 0, 4, 0, 6, 0, 0, 0,
+            9, 0, 0, 0, 8, 0, 0, 0, 98, 0, 5, 0, 14, 0, 0, 0, 15, 0, 0, 0, 9, 0, 0, 0, 13, 0, 0, 0,
+            253, 0, 1, 0, 56, 0, 1, 0,
+        ];
+
+        let render_pass = crate::ordered_passes_renderpass!(
+            device.clone(),
+            attachments: {
+                first: {
+                    load: Load,
+                    store: DontCare,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                },
+                second: {
+                    load: Load,
+                    store: DontCare,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            passes: [
+                {
+                    color: [],
+                    depth_stencil: {},
+                    input: [first, second]
+                }
+            ]
+        )
+        .unwrap();
+        let pipeline = graphics_pipeline_with_fragment_shader(
+            device.clone(),
+            Subpass::from(render_pass.clone(), 0).unwrap(),
+            &FS,
+        );
+
+        let [first, second] = [(); 2].map(|_| {
+            ImageView::new(
+                AttachmentImage::input_attachment(device.clone(), [64, 64], Format::R8G8B8A8_UNORM)
+                    .unwrap(),
+            )
+            .unwrap()
+        });
+        let framebuffer = Framebuffer::start(render_pass)
+            .add(first.clone())
+            .unwrap()
+            .add(second.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_render_pass(
+                framebuffer,
+                SubpassContents::Inline,
+                [ClearValue::None, ClearValue::None],
+            )
+            .unwrap()
+            .bind_pipeline_graphics(pipeline.clone())
+            .unwrap();
+
+        // The shader reads input attachment 1, so binding the first attachment is an error even
+        // though it is an input attachment of the subpass too.
+        for (view, valid) in [(first, false), (second, true)] {
+            let set = PersistentDescriptorSet::new(
+                pipeline.layout().descriptor_set_layouts()[0].clone(),
+                [WriteDescriptorSet::image_view(0, view)],
+            )
+            .unwrap();
+            builder.bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                pipeline.layout().clone(),
+                0,
+                set,
+            );
+
+            let result = builder.draw(3, 1, 0, 0);
+
+            if valid {
+                result.unwrap();
+            } else {
+                assert!(matches!(
+                    result,
+                    Err(DrawError::CheckDescriptorSetsValidityError(
+                        CheckDescriptorSetsValidityError::InvalidDescriptorResource {
+                            error: InvalidDescriptorResource::InputAttachmentNotInSubpass,
+                            ..
+                        }
+                    ))
+                ));
+            }
+        }
+    }
+
     #[test]
     fn copy_buffer_dimensions() {
         let instance = instance!();
@@ -4259,6 +6460,47 @@ mod tests {
         assert_eq!(*result, [0_u32, 1, 2, 3, 4]);
     }
 
+    #[test]
+    fn copy_buffer_to_depth_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let source = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            [0_u16; 16 * 16].iter().copied(),
+        )
+        .unwrap();
+        let destination = AttachmentImage::with_usage(
+            device.clone(),
+            [16, 16],
+            Format::D16_UNORM,
+            ImageUsage {
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+        )
+        .unwrap();
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        cbb.copy_buffer_to_image(source, destination).unwrap();
+
+        let future = cbb
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+    }
+
     #[test]
     fn secondary_nonconcurrent_conflict() {
         let (device, queue) = gfx_dev_and_queue!();
@@ -4393,4 +6635,979 @@ mod tests {
             ))
         ));
     }
+
+    #[test]
+    fn debug_utils_label_end_without_begin() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.end_debug_utils_label(),
+            Err(DebugMarkerError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::DebugUtilsLabelNotActive
+            ))
+        ));
+    }
+
+    #[test]
+    fn generate_mipmaps() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let format = Format::R8G8B8A8_UNORM;
+        let format_features = device
+            .physical_device()
+            .format_properties(format)
+            .optimal_tiling_features;
+
+        if !(format_features.blit_src
+            && format_features.blit_dst
+            && format_features.sampled_image_filter_linear)
+        {
+            return;
+        }
+
+        let (image, _) = ImmutableImage::uninitialized(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 64,
+                height: 64,
+                array_layers: 2,
+            },
+            format,
+            MipmapsCount::Log2,
+            ImageUsage {
+                transfer_source: true,
+                transfer_destination: true,
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            ImageLayout::ShaderReadOnlyOptimal,
+            device.active_queue_families(),
+        )
+        .unwrap();
+        assert_eq!(image.mip_levels(), 7);
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // Successive levels of the same image must not conflict with each other.
+        builder.generate_mipmaps(image).unwrap();
+        builder.build().unwrap();
+    }
+
+    #[test]
+    fn begin_rendering_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.begin_rendering(RenderingInfo {
+                render_area_extent: [64, 64],
+                ..Default::default()
+            }),
+            Err(BeginRenderingError::FeatureNotEnabled {
+                feature: "dynamic_rendering",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn begin_rendering_render_area_out_of_bounds() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        let view = ImageView::new(
+            AttachmentImage::new(device.clone(), [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.begin_rendering(RenderingInfo {
+                render_area_offset: [32, 0],
+                render_area_extent: [64, 64],
+                color_attachments: vec![Some(RenderingAttachmentInfo::image_view(view))],
+                ..Default::default()
+            }),
+            Err(BeginRenderingError::RenderAreaOutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn begin_rendering_layer_count_out_of_bounds() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        let view = ImageView::new(
+            AttachmentImage::new(device.clone(), [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.begin_rendering(RenderingInfo {
+                layer_count: 2,
+                color_attachments: vec![Some(RenderingAttachmentInfo::image_view(view))],
+                ..Default::default()
+            }),
+            Err(BeginRenderingError::LayerCountOutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn begin_rendering_multiview_layer_count_not_one() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!(multiview);
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.begin_rendering(RenderingInfo {
+                render_area_extent: [64, 64],
+                layer_count: 2,
+                view_mask: 0b11,
+                ..Default::default()
+            }),
+            Err(BeginRenderingError::MultiviewLayerCountNotOne)
+        ));
+    }
+
+    #[test]
+    fn begin_rendering_depth_stencil_image_view_mismatch() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        let format = match [Format::D24_UNORM_S8_UINT, Format::D32_SFLOAT_S8_UINT]
+            .into_iter()
+            .find(|&format| {
+                device
+                    .physical_device()
+                    .format_properties(format)
+                    .optimal_tiling_features
+                    .depth_stencil_attachment
+            }) {
+            Some(format) => format,
+            None => return,
+        };
+
+        let depth_view =
+            ImageView::new(AttachmentImage::new(device.clone(), [64, 64], format).unwrap())
+                .unwrap();
+        let stencil_view =
+            ImageView::new(AttachmentImage::new(device.clone(), [64, 64], format).unwrap())
+                .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.begin_rendering(RenderingInfo {
+                depth_attachment: Some(RenderingAttachmentInfo::image_view(depth_view)),
+                stencil_attachment: Some(RenderingAttachmentInfo::image_view(stencil_view)),
+                ..Default::default()
+            }),
+            Err(BeginRenderingError::DepthStencilAttachmentImageViewMismatch)
+        ));
+    }
+
+    #[test]
+    fn begin_rendering_color_attachment_layout_not_supported() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        let view = ImageView::new(
+            AttachmentImage::new(device.clone(), [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        for layout in [
+            ImageLayout::Undefined,
+            ImageLayout::Preinitialized,
+            ImageLayout::PresentSrc,
+            ImageLayout::DepthStencilAttachmentOptimal,
+        ] {
+            assert!(matches!(
+                builder.begin_rendering(RenderingInfo {
+                    color_attachments: vec![Some(RenderingAttachmentInfo {
+                        image_layout: layout,
+                        ..RenderingAttachmentInfo::image_view(view.clone())
+                    })],
+                    ..Default::default()
+                }),
+                Err(BeginRenderingError::ColorAttachmentLayoutNotSupported {
+                    attachment_index: 0,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn begin_rendering_depth_attachment_layout_not_supported() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        // `D16_UNORM` must support being used as a depth attachment.
+        let view = ImageView::new(
+            AttachmentImage::new(device.clone(), [64, 64], Format::D16_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.begin_rendering(RenderingInfo {
+                depth_attachment: Some(RenderingAttachmentInfo {
+                    image_layout: ImageLayout::ColorAttachmentOptimal,
+                    ..RenderingAttachmentInfo::image_view(view)
+                }),
+                ..Default::default()
+            }),
+            Err(BeginRenderingError::DepthAttachmentLayoutNotSupported {
+                layout: ImageLayout::ColorAttachmentOptimal,
+            })
+        ));
+    }
+
+    #[test]
+    fn begin_rendering_clear_value_mismatch() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        let color_view = ImageView::new(
+            AttachmentImage::new(device.clone(), [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+        let depth_view = ImageView::new(
+            AttachmentImage::new(device.clone(), [64, 64], Format::D16_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        for clear_value in [ClearValue::None, ClearValue::Uint([0; 4])] {
+            assert!(matches!(
+                builder.begin_rendering(RenderingInfo {
+                    color_attachments: vec![Some(RenderingAttachmentInfo {
+                        load_op: LoadOp::Clear,
+                        clear_value,
+                        ..RenderingAttachmentInfo::image_view(color_view.clone())
+                    })],
+                    ..Default::default()
+                }),
+                Err(BeginRenderingError::ColorAttachmentClearValueMismatch {
+                    attachment_index: 0,
+                    ..
+                })
+            ));
+        }
+
+        assert!(matches!(
+            builder.begin_rendering(RenderingInfo {
+                depth_attachment: Some(RenderingAttachmentInfo {
+                    load_op: LoadOp::Clear,
+                    clear_value: ClearValue::Float([0.0; 4]),
+                    ..RenderingAttachmentInfo::image_view(depth_view.clone())
+                }),
+                ..Default::default()
+            }),
+            Err(BeginRenderingError::DepthAttachmentClearValueMismatch { .. })
+        ));
+
+        // A clear value of the right kind is accepted.
+        builder
+            .begin_rendering(RenderingInfo {
+                color_attachments: vec![Some(RenderingAttachmentInfo {
+                    load_op: LoadOp::Clear,
+                    clear_value: ClearValue::Float([0.0; 4]),
+                    ..RenderingAttachmentInfo::image_view(color_view)
+                })],
+                depth_attachment: Some(RenderingAttachmentInfo {
+                    load_op: LoadOp::Clear,
+                    clear_value: ClearValue::Depth(1.0),
+                    ..RenderingAttachmentInfo::image_view(depth_view)
+                }),
+                ..Default::default()
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn render_pass_pipeline_inside_begin_rendering() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        let render_pass = RenderPass::empty_single_pass(device.clone()).unwrap();
+        let pipeline =
+            empty_graphics_pipeline(device.clone(), Subpass::from(render_pass, 0).unwrap());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_rendering(RenderingInfo {
+                render_area_extent: [64, 64],
+                ..Default::default()
+            })
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap();
+
+        assert!(matches!(
+            builder.draw(3, 1, 0, 0),
+            Err(DrawError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::IncompatibleRenderPass
+            ))
+        ));
+    }
+
+    #[test]
+    fn begin_rendering_pipeline_inside_render_pass() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        let pipeline =
+            empty_graphics_pipeline(device.clone(), PipelineRenderingCreateInfo::default());
+        let render_pass = RenderPass::empty_single_pass(device.clone()).unwrap();
+        let framebuffer = Framebuffer::with_dimensions(render_pass, [64, 64, 1])
+            .build()
+            .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_render_pass(framebuffer, SubpassContents::Inline, [])
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap();
+
+        assert!(matches!(
+            builder.draw(3, 1, 0, 0),
+            Err(DrawError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::IncompatibleRenderPass
+            ))
+        ));
+    }
+
+    #[test]
+    fn render_pass_commands_inside_begin_rendering() {
+        let (device, queue) = dynamic_rendering_dev_and_queue!();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_rendering(RenderingInfo {
+                render_area_extent: [64, 64],
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert!(matches!(
+            builder.next_subpass(SubpassContents::Inline),
+            Err(AutoCommandBufferBuilderContextError::ForbiddenWithBeginRendering)
+        ));
+        assert!(matches!(
+            builder.end_render_pass(),
+            Err(AutoCommandBufferBuilderContextError::ForbiddenWithBeginRendering)
+        ));
+
+        builder.end_rendering().unwrap();
+        builder.build().unwrap();
+    }
+
+    #[test]
+    fn pipeline_barrier2_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            [0_u32, 1, 2, 3].iter().copied(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            assert!(matches!(
+                builder.pipeline_barrier2(DependencyInfo {
+                    buffer_memory_barriers: vec![BufferMemoryBarrier2::buffer(buffer)],
+                    ..Default::default()
+                }),
+                Err(PipelineBarrierError::FeatureNotEnabled {
+                    feature: "synchronization2",
+                    ..
+                })
+            ));
+        }
+    }
+
+    // Copies `source` to `intermediate` and then to `destination`, which needs a barrier between
+    // the two copies.
+    fn copy_through_buffer(device: Arc<Device>, queue: Arc<Queue>, synchronization2: bool) {
+        let source = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            [1_u32, 2, 3, 4].iter().copied(),
+        )
+        .unwrap();
+        let intermediate = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            [0_u32; 4].iter().copied(),
+        )
+        .unwrap();
+        let destination = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            [0_u32; 4].iter().copied(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .copy_buffer(source.clone(), intermediate.clone())
+            .unwrap();
+
+        if synchronization2 {
+            unsafe {
+                builder
+                    .pipeline_barrier2(DependencyInfo {
+                        buffer_memory_barriers: vec![BufferMemoryBarrier2 {
+                            source_stages: PipelineStages2 {
+                                copy: true,
+                                ..PipelineStages2::none()
+                            },
+                            source_access: AccessFlags2 {
+                                transfer_write: true,
+                                ..AccessFlags2::none()
+                            },
+                            destination_stages: PipelineStages2 {
+                                copy: true,
+                                ..PipelineStages2::none()
+                            },
+                            destination_access: AccessFlags2 {
+                                transfer_read: true,
+                                ..AccessFlags2::none()
+                            },
+                            ..BufferMemoryBarrier2::buffer(intermediate.clone())
+                        }],
+                        ..Default::default()
+                    })
+                    .unwrap();
+            }
+        }
+
+        builder
+            .copy_buffer(intermediate.clone(), destination.clone())
+            .unwrap();
+
+        let future = builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        assert_eq!(*destination.read().unwrap(), [1_u32, 2, 3, 4]);
+    }
+
+    #[test]
+    fn barriers_without_synchronization2() {
+        let (device, queue) = gfx_dev_and_queue!();
+        copy_through_buffer(device, queue, false);
+    }
+
+    #[test]
+    fn barriers_with_synchronization2() {
+        let (device, queue) = gfx_dev_and_queue_with!([khr_synchronization2], [synchronization2]);
+        copy_through_buffer(device, queue, true);
+    }
+
+    fn execute_and_wait(
+        builder: AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        queue: Arc<Queue>,
+    ) {
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn set_event() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let event = Arc::new(Event::alloc(device.clone()).unwrap());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            builder
+                .set_event(
+                    event.clone(),
+                    PipelineStages {
+                        all_commands: true,
+                        ..PipelineStages::none()
+                    },
+                )
+                .unwrap();
+        }
+
+        execute_and_wait(builder, queue);
+        assert!(event.signaled().unwrap());
+    }
+
+    #[test]
+    fn reset_event() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let mut event = Event::alloc(device.clone()).unwrap();
+        event.set();
+        let event = Arc::new(event);
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            builder
+                .reset_event(
+                    event.clone(),
+                    PipelineStages {
+                        all_commands: true,
+                        ..PipelineStages::none()
+                    },
+                )
+                .unwrap();
+        }
+
+        execute_and_wait(builder, queue);
+        assert!(!event.signaled().unwrap());
+    }
+
+    #[test]
+    fn wait_events() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let event = Arc::new(Event::alloc(device.clone()).unwrap());
+
+        let source = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            [1_u32, 2, 3, 4].iter().copied(),
+        )
+        .unwrap();
+        let destination = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            [0_u32; 4].iter().copied(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let transfer = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+
+        unsafe {
+            builder
+                .set_event(event.clone(), transfer)
+                .unwrap()
+                .wait_events(
+                    [event.clone()],
+                    transfer,
+                    AccessFlags::none(),
+                    transfer,
+                    AccessFlags {
+                        transfer_write: true,
+                        ..AccessFlags::none()
+                    },
+                )
+                .unwrap();
+        }
+
+        builder.copy_buffer(source, destination.clone()).unwrap();
+
+        execute_and_wait(builder, queue);
+        assert!(event.signaled().unwrap());
+        assert_eq!(*destination.read().unwrap(), [1_u32, 2, 3, 4]);
+    }
+
+    /// Creates a compute pipeline that copies a push constant block of four `u32` into the
+    /// storage buffer at set 0, binding 0.
+    fn push_constants_pipeline(device: Arc<Device>) -> Arc<ComputePipeline> {
+        /*
+        #version 450
+
+        layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+        layout(push_constant) uniform PushConstants {
+            uint values[4];
+        } pc;
+
+        layout(set = 0, binding = 0) buffer Output {
+            uint values[4];
+        } data;
+
+        void main() {
+            data.values[0] = pc.values[0];
+            data.values[1] = pc.values[1];
+            data.values[2] = pc.values[2];
+            data.values[3] = pc.values[3];
+        }
+        */
+        const MODULE: [u8; 788] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 0, 0, 0, 0, 32, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 5, 0, 0, 0, 1, 0, 0, 0, 109, 97, 105,
+            110, 0, 0, 0, 0, 16, 0, 6, 0, 1, 0, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0,
+            0, 71, 0, 4, 0, 6, 0, 0, 0, 6, 0, 0, 0, 4, 0, 0, 0, 72, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0,
+            0, 35, 0, 0, 0, 0, 0, 0, 0, 71, 0, 3, 0, 7, 0, 0, 0, 2, 0, 0, 0, 72, 0, 5, 0, 10, 0, 0,
+            0, 0, 0, 0, 0, 35, 0, 0, 0, 0, 0, 0, 0, 71, 0, 3, 0, 10, 0, 0, 0, 3, 0, 0, 0, 71, 0, 4,
+            0, 12, 0, 0, 0, 34, 0, 0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 12, 0, 0, 0, 33, 0, 0, 0, 0, 0,
+            0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0, 21, 0, 4, 0, 4, 0,
+            0, 0, 32, 0, 0, 0, 0, 0, 0, 0, 43, 0, 4, 0, 4, 0, 0, 0, 5, 0, 0, 0, 4, 0, 0, 0, 28, 0,
+            4, 0, 6, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 30, 0, 3, 0, 7, 0, 0, 0, 6, 0, 0, 0, 32, 0,
+            4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 7, 0, 0, 0, 59, 0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 9, 0, 0,
+            0, 30, 0, 3, 0, 10, 0, 0, 0, 6, 0, 0, 0, 32, 0, 4, 0, 11, 0, 0, 0, 2, 0, 0, 0, 10, 0,
+            0, 0, 59, 0, 4, 0, 11, 0, 0, 0, 12, 0, 0, 0, 2, 0, 0, 0, 43, 0, 4, 0, 4, 0, 0, 0, 13,
+            0, 0, 0, 0, 0, 0, 0, 43, 0, 4, 0, 4, 0, 0, 0, 14, 0, 0, 0, 1, 0, 0, 0, 43, 0, 4, 0, 4,
+            0, 0, 0, 15, 0, 0, 0, 2, 0, 0, 0, 43, 0, 4, 0, 4, 0, 0, 0, 16, 0, 0, 0, 3, 0, 0, 0, 32,
+            0, 4, 0, 17, 0, 0, 0, 9, 0, 0, 0, 4, 0, 0, 0, 32, 0, 4, 0, 18, 0, 0, 0, 2, 0, 0, 0, 4,
+            0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2, 0, 19,
+            0, 0, 0, 65, 0, 6, 0, 17, 0, 0, 0, 20, 0, 0, 0, 9, 0, 0, 0, 13, 0, 0, 0, 13, 0, 0, 0,
+            61, 0, 4, 0, 4, 0, 0, 0, 21, 0, 0, 0, 20, 0, 0, 0, 65, 0, 6, 0, 18, 0, 0, 0, 22, 0, 0,
+            0, 12, 0, 0, 0, 13, 0, 0, 0, 13, 0, 0, 0, 62, 0, 3, 0, 22, 0, 0, 0, 21, 0, 0, 0, 65, 0,
+            6, 0, 17, 0, 0, 0, 23, 0, 0, 0, 9, 0, 0, 0, 13, 0, 0, 0, 14, 0, 0, 0, 61, 0, 4, 0, 4,
+            0, 0, 0, 24, 0, 0, 0, 23, 0, 0, 0, 65, 0, 6, 0, 18, 0, 0, 0, 25, 0, 0, 0, 12, 0, 0, 0,
+            13, 0, 0, 0, 14, 0, 0, 0, 62, 0, 3, 0, 25, 0, 0, 0, 24, 0, 0, 0, 65, 0, 6, 0, 17, 0, 0,
+            0, 26, 0, 0, 0, 9, 0, 0, 0, 13, 0, 0, 0, 15, 0, 0, 0, 61, 0, 4, 0, 4, 0, 0, 0, 27, 0,
+            0, 0, 26, 0, 0, 0, 65, 0, 6, 0, 18, 0, 0, 0, 28, 0, 0, 0, 12, 0, 0, 0, 13, 0, 0, 0, 15,
+            0, 0, 0, 62, 0, 3, 0, 28, 0, 0, 0, 27, 0, 0, 0, 65, 0, 6, 0, 17, 0, 0, 0, 29, 0, 0, 0,
+            9, 0, 0, 0, 13, 0, 0, 0, 16, 0, 0, 0, 61, 0, 4, 0, 4, 0, 0, 0, 30, 0, 0, 0, 29, 0, 0,
+            0, 65, 0, 6, 0, 18, 0, 0, 0, 31, 0, 0, 0, 12, 0, 0, 0, 13, 0, 0, 0, 16, 0, 0, 0, 62, 0,
+            3, 0, 31, 0, 0, 0, 30, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
+        ];
+        let module = unsafe { ShaderModule::from_bytes(device.clone(), &MODULE).unwrap() };
+
+        ComputePipeline::new(
+            device,
+            module.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn push_constants_partial_update() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let pipeline = push_constants_pipeline(device.clone());
+
+        let data_buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, [0_u32; 4])
+                .unwrap();
+        let set = PersistentDescriptorSet::new(
+            pipeline.layout().descriptor_set_layouts()[0].clone(),
+            [WriteDescriptorSet::buffer(0, data_buffer.clone())],
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // Overwrite the last two values of the block, without touching the first two.
+        builder
+            .push_constants(pipeline.layout().clone(), 0, [1_u32, 2, 3, 4])
+            .unwrap()
+            .push_constants(pipeline.layout().clone(), 8, [30_u32, 40])
+            .unwrap()
+            .bind_pipeline_compute(pipeline.clone())
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                set,
+            )
+            .dispatch([1, 1, 1])
+            .unwrap();
+
+        let future = builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        assert_eq!(*data_buffer.read().unwrap(), [1_u32, 2, 30, 40]);
+    }
+
+    #[test]
+    fn push_constants_out_of_range() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let pipeline = push_constants_pipeline(device.clone());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert_eq!(
+            builder
+                .push_constants(pipeline.layout().clone(), 2, 0_u32)
+                .err(),
+            Some(PushConstantsError::OffsetNotMultipleOf4 { offset: 2 }),
+        );
+        assert_eq!(
+            builder
+                .push_constants(pipeline.layout().clone(), 0, [0_u16; 3])
+                .err(),
+            Some(PushConstantsError::SizeNotMultipleOf4 { size: 6 }),
+        );
+        assert_eq!(
+            builder
+                .push_constants(pipeline.layout().clone(), 8, [0_u32; 4])
+                .err(),
+            Some(PushConstantsError::RangeOutOfBounds {
+                offset: 8,
+                size: 16
+            }),
+        );
+    }
+
+    #[test]
+    fn draw_indirect_count_without_multi_draw_indirect() {
+        let (device, queue) = gfx_dev_and_queue_with!([khr_draw_indirect_count], []);
+
+        if device
+            .physical_device()
+            .properties()
+            .max_draw_indirect_count
+            < 2
+        {
+            return;
+        }
+
+        let render_pass = RenderPass::empty_single_pass(device.clone()).unwrap();
+        let pipeline = empty_graphics_pipeline(
+            device.clone(),
+            Subpass::from(render_pass.clone(), 0).unwrap(),
+        );
+        let framebuffer = Framebuffer::with_dimensions(render_pass, [64, 64, 1])
+            .build()
+            .unwrap();
+
+        let command = DrawIndirectCommand {
+            vertex_count: 3,
+            instance_count: 1,
+            first_vertex: 0,
+            first_instance: 0,
+        };
+        let indirect_buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::indirect_buffer(),
+            false,
+            [command, command],
+        )
+        .unwrap();
+        let count_buffer = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage::indirect_buffer(),
+            false,
+            2u32,
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_render_pass(framebuffer, SubpassContents::Inline, [])
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap();
+
+        // Only `draw_indirect` and `draw_indexed_indirect` need `multi_draw_indirect` for more
+        // than one draw.
+        assert!(builder
+            .draw_indirect_count(indirect_buffer, count_buffer)
+            .is_ok());
+    }
+
+    #[test]
+    fn bind_pipeline_graphics_transform_feedback_active() {
+        let (device, queue) =
+            gfx_dev_and_queue_with!([ext_transform_feedback], [transform_feedback]);
+
+        let render_pass = RenderPass::empty_single_pass(device.clone()).unwrap();
+        let pipeline = empty_graphics_pipeline(
+            device.clone(),
+            Subpass::from(render_pass.clone(), 0).unwrap(),
+        );
+        let framebuffer = Framebuffer::with_dimensions(render_pass, [64, 64, 1])
+            .build()
+            .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_render_pass(framebuffer, SubpassContents::Inline, [])
+            .unwrap()
+            .begin_transform_feedback(0, [])
+            .unwrap();
+
+        assert!(matches!(
+            builder.bind_pipeline_graphics(pipeline),
+            Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive)
+        ));
+    }
+
+    #[test]
+    fn primary_protected_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        assert!(matches!(
+            AutoCommandBufferBuilder::primary_protected(
+                device,
+                queue.family(),
+                CommandBufferUsage::OneTimeSubmit,
+            ),
+            Err(BeginError::ProtectedMemoryFeatureNotEnabled)
+        ));
+    }
+
+    #[test]
+    fn primary_protected_unprotected_queue() {
+        let (device, queue) = gfx_dev_and_queue!(protected_memory);
+        assert!(!queue.is_protected());
+
+        let command_buffer = AutoCommandBufferBuilder::primary_protected(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        assert!(matches!(
+            command_buffer.execute(queue),
+            Err(CommandBufferExecError::ProtectedQueueRequired)
+        ));
+    }
 }
@@ -78,10 +78,15 @@
 
 pub use self::auto::AutoCommandBufferBuilder;
 pub use self::auto::AutoCommandBufferBuilderContextError;
+pub use self::auto::BeginConditionalRenderingError;
 pub use self::auto::BeginError;
 pub use self::auto::BeginQueryError;
 pub use self::auto::BeginRenderPassError;
+pub use self::auto::BeginRenderingError;
+pub use self::auto::BeginTransformFeedbackError;
+pub use self::auto::BindTransformFeedbackBuffersError;
 pub use self::auto::BlitImageError;
+pub use self::auto::BuildAccelerationStructureError;
 pub use self::auto::BuildError;
 pub use self::auto::ClearColorImageError;
 pub use self::auto::CopyBufferError;
@@ -96,20 +101,29 @@ pub use self::auto::DrawIndexedError;
 pub use self::auto::DrawIndexedIndirectError;
 pub use self::auto::DrawIndirectError;
 pub use self::auto::EndQueryError;
+pub use self::auto::EndTransformFeedbackError;
 pub use self::auto::ExecuteCommandsError;
 pub use self::auto::FillBufferError;
+pub use self::auto::PipelineBarrierError;
 pub use self::auto::PrimaryAutoCommandBuffer;
+pub use self::auto::PushConstantsError;
 pub use self::auto::ResetQueryPoolError;
 pub use self::auto::SecondaryAutoCommandBuffer;
+pub use self::auto::TraceRaysError;
 pub use self::auto::UpdateBufferError;
 pub use self::auto::WriteTimestampError;
 pub use self::traits::CommandBufferExecError;
 pub use self::traits::CommandBufferExecFuture;
 pub use self::traits::PrimaryCommandBuffer;
 pub use self::traits::SecondaryCommandBuffer;
+use crate::format::ClearValue;
+use crate::image::view::ImageViewAbstract;
+use crate::image::ImageLayout;
 use crate::query::QueryControlFlags;
 use crate::query::QueryPipelineStatisticFlags;
 use crate::render_pass::Framebuffer;
+use crate::render_pass::LoadOp;
+use crate::render_pass::StoreOp;
 use crate::render_pass::Subpass;
 use std::sync::Arc;
 
@@ -230,6 +244,128 @@ pub struct CommandBufferInheritanceRenderPass {
     pub framebuffer: Option<Arc<Framebuffer>>,
 }
 
+/// Parameters to begin a render pass instance with dynamic rendering, using
+/// [`begin_rendering`](AutoCommandBufferBuilder::begin_rendering).
+///
+/// Only single-sampled attachments are currently supported, and attachments can't be resolved.
+#[derive(Clone)]
+pub struct RenderingInfo {
+    /// The offset of the area that is rendered to, in pixels.
+    ///
+    /// The default value is `[0, 0]`.
+    pub render_area_offset: [u32; 2],
+
+    /// The size of the area that is rendered to, in pixels.
+    ///
+    /// If set to `[0, 0]`, the smallest size among the attachments minus `render_area_offset` is
+    /// used.
+    ///
+    /// The default value is `[0, 0]`.
+    pub render_area_extent: [u32; 2],
+
+    /// The number of layers of the attachments that are rendered to. Must be `1` if `view_mask`
+    /// is not `0`.
+    ///
+    /// The default value is `1`.
+    pub layer_count: u32,
+
+    /// If not `0`, multiview rendering is used, and the bits that are set indicate the views that
+    /// are rendered to. It must match the `view_mask` of the pipelines that are used.
+    ///
+    /// If not `0`, the [`multiview`](crate::device::Features::multiview) feature must be enabled
+    /// on the device.
+    ///
+    /// The default value is `0`.
+    pub view_mask: u32,
+
+    /// The color attachments, one for each attachment index. `None` indicates that no attachment
+    /// is bound to that index.
+    ///
+    /// The default value is empty.
+    pub color_attachments: Vec<Option<RenderingAttachmentInfo>>,
+
+    /// The depth attachment, or `None` if there is no depth attachment.
+    ///
+    /// The default value is `None`.
+    pub depth_attachment: Option<RenderingAttachmentInfo>,
+
+    /// The stencil attachment, or `None` if there is no stencil attachment.
+    ///
+    /// If both this and `depth_attachment` are `Some`, they must use the same image view.
+    ///
+    /// The default value is `None`.
+    pub stencil_attachment: Option<RenderingAttachmentInfo>,
+}
+
+impl Default for RenderingInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            render_area_offset: [0, 0],
+            render_area_extent: [0, 0],
+            layer_count: 1,
+            view_mask: 0,
+            color_attachments: Vec::new(),
+            depth_attachment: None,
+            stencil_attachment: None,
+        }
+    }
+}
+
+/// An attachment that is used with dynamic rendering.
+#[derive(Clone)]
+pub struct RenderingAttachmentInfo {
+    /// The image view to render to.
+    pub image_view: Arc<dyn ImageViewAbstract>,
+
+    /// The layout that the image is in during rendering. The image is transitioned to this
+    /// layout automatically.
+    ///
+    /// For a color attachment, this must be `General` or `ColorAttachmentOptimal`. For a depth or
+    /// stencil attachment, it must be `General`, `DepthStencilAttachmentOptimal` or
+    /// `DepthStencilReadOnlyOptimal`.
+    pub image_layout: ImageLayout,
+
+    /// What to do with the attachment at the start of rendering.
+    pub load_op: LoadOp,
+
+    /// What to do with the attachment at the end of rendering.
+    pub store_op: StoreOp,
+
+    /// The value to clear the attachment with, if `load_op` is `LoadOp::Clear`. Ignored
+    /// otherwise.
+    ///
+    /// For a color attachment, the value must match the numeric type of the format: `Float`,
+    /// `Int` or `Uint`. For a depth attachment, it must be `Depth` or `DepthStencil`, and for a
+    /// stencil attachment `Stencil` or `DepthStencil`.
+    pub clear_value: ClearValue,
+}
+
+impl RenderingAttachmentInfo {
+    /// Returns a `RenderingAttachmentInfo` for `image_view`, which loads and stores the existing
+    /// content of the attachment.
+    ///
+    /// The layout is `DepthStencilAttachmentOptimal` if the format of the image view has a depth
+    /// or stencil aspect, and `ColorAttachmentOptimal` otherwise.
+    #[inline]
+    pub fn image_view(image_view: Arc<dyn ImageViewAbstract>) -> Self {
+        let aspects = image_view.format().aspects();
+        let image_layout = if aspects.depth || aspects.stencil {
+            ImageLayout::DepthStencilAttachmentOptimal
+        } else {
+            ImageLayout::ColorAttachmentOptimal
+        };
+
+        Self {
+            image_view,
+            image_layout,
+            load_op: LoadOp::Load,
+            store_op: StoreOp::Store,
+            clear_value: ClearValue::None,
+        }
+    }
+}
+
 impl CommandBufferLevel {
     /// Equivalent to `Kind::Primary`.
     ///
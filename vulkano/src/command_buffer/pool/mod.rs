@@ -17,6 +17,7 @@
 //! this trait yourself by wrapping around the `UnsafeCommandPool` type.
 
 pub use self::standard::StandardCommandPool;
+pub use self::sys::CommandPoolCreationError;
 pub use self::sys::CommandPoolTrimError;
 pub use self::sys::UnsafeCommandPool;
 pub use self::sys::UnsafeCommandPoolAlloc;
@@ -21,6 +21,7 @@ use std::vec::IntoIter as VecIntoIter;
 use crate::command_buffer::pool::CommandPool;
 use crate::command_buffer::pool::CommandPoolAlloc;
 use crate::command_buffer::pool::CommandPoolBuilderAlloc;
+use crate::command_buffer::pool::CommandPoolCreationError;
 use crate::command_buffer::pool::UnsafeCommandPool;
 use crate::command_buffer::pool::UnsafeCommandPoolAlloc;
 use crate::device::physical::QueueFamily;
@@ -45,6 +46,9 @@ pub struct StandardCommandPool {
     // Identifier of the queue family.
     queue_family: u32,
 
+    // Whether the Vulkan pools are created with the `PROTECTED` flag.
+    protected: bool,
+
     // For each thread, we store thread-specific info.
     per_thread: Mutex<FnvHashMap<thread::ThreadId, Weak<StandardCommandPoolPerThread>>>,
 }
@@ -77,9 +81,44 @@ impl StandardCommandPool {
         StandardCommandPool {
             device: device,
             queue_family: queue_family.id(),
+            protected: false,
             per_thread: Mutex::new(Default::default()),
         }
     }
+
+    /// Builds a new pool whose command buffers are protected.
+    ///
+    /// The command buffers allocated from this pool can only be submitted to protected queues.
+    /// The [`protected_memory`](crate::device::Features::protected_memory) feature must be
+    /// enabled on the device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    ///
+    pub fn new_protected(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+    ) -> Result<StandardCommandPool, CommandPoolCreationError> {
+        // VUID-VkCommandPoolCreateInfo-flags-02860
+        if !device.enabled_features().protected_memory {
+            return Err(CommandPoolCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                reason: "tried to create a protected command pool",
+            });
+        }
+
+        Ok(StandardCommandPool {
+            protected: true,
+            ..StandardCommandPool::new(device, queue_family)
+        })
+    }
+
+    /// Returns true if the command buffers allocated from this pool are protected.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl CommandPool for Arc<StandardCommandPool> {
@@ -99,8 +138,13 @@ unsafe impl CommandPool for Arc<StandardCommandPool> {
         let per_thread = if let Some(entry) = hashmap.get(&this_thread).and_then(Weak::upgrade) {
             entry
         } else {
-            let new_pool =
-                UnsafeCommandPool::new(self.device.clone(), self.queue_family(), false, true)?;
+            let new_pool = UnsafeCommandPool::new_inner(
+                self.device.clone(),
+                self.queue_family(),
+                false,
+                true,
+                self.protected,
+            )?;
             let pt = Arc::new(StandardCommandPoolPerThread {
                 pool: Mutex::new(new_pool),
                 available_primary_command_buffers: SegQueue::new(),
@@ -274,6 +318,7 @@ impl Drop for StandardCommandPoolAlloc {
 mod tests {
     use crate::command_buffer::pool::CommandPool;
     use crate::command_buffer::pool::CommandPoolBuilderAlloc;
+    use crate::command_buffer::pool::CommandPoolCreationError;
     use crate::command_buffer::pool::StandardCommandPool;
     use crate::device::Device;
     use crate::VulkanObject;
@@ -310,4 +355,28 @@ mod tests {
         drop(cb);
         assert!(pool_weak.upgrade().is_none());
     }
+
+    #[test]
+    fn protected_missing_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        match StandardCommandPool::new_protected(device, queue.family()) {
+            Err(CommandPoolCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn protected_command_buffers() {
+        let (device, queue) = gfx_dev_and_queue!(protected_memory);
+
+        let pool = Arc::new(StandardCommandPool::new_protected(device, queue.family()).unwrap());
+        assert!(pool.is_protected());
+
+        let cb = pool.alloc(false, 1).unwrap().next().unwrap();
+        assert!(cb.inner().is_protected());
+    }
 }
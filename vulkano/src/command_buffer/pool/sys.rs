@@ -38,6 +38,9 @@ pub struct UnsafeCommandPool {
     // Index of the associated queue family in the physical device.
     queue_family_index: u32,
 
+    // Whether the pool was created with the `PROTECTED` flag.
+    protected: bool,
+
     // We don't want `UnsafeCommandPool` to implement Sync.
     // This marker unimplements both Send and Sync, but we reimplement Send manually right under.
     dummy_avoid_sync: PhantomData<*const u8>,
@@ -64,6 +67,53 @@ impl UnsafeCommandPool {
         queue_family: QueueFamily,
         transient: bool,
         reset_cb: bool,
+    ) -> Result<UnsafeCommandPool, OomError> {
+        UnsafeCommandPool::new_inner(device, queue_family, transient, reset_cb, false)
+    }
+
+    /// Creates a new protected pool.
+    ///
+    /// The command buffers created with this pool can only be executed on protected queues of the
+    /// given family, with a protected submission (see
+    /// [`SubmitCommandBufferBuilder::set_protected`](crate::command_buffer::submit::SubmitCommandBufferBuilder::set_protected)).
+    /// The [`protected_memory`](crate::device::Features::protected_memory) feature must be
+    /// enabled on the device.
+    ///
+    /// `transient` and `reset_cb` have the same meaning as in [`new`](UnsafeCommandPool::new).
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue family doesn't belong to the same physical device as `device`.
+    ///
+    pub fn new_protected(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        transient: bool,
+        reset_cb: bool,
+    ) -> Result<UnsafeCommandPool, CommandPoolCreationError> {
+        // VUID-VkCommandPoolCreateInfo-flags-02860
+        if !device.enabled_features().protected_memory {
+            return Err(CommandPoolCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                reason: "tried to create a protected command pool",
+            });
+        }
+
+        Ok(UnsafeCommandPool::new_inner(
+            device,
+            queue_family,
+            transient,
+            reset_cb,
+            true,
+        )?)
+    }
+
+    pub(super) fn new_inner(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        transient: bool,
+        reset_cb: bool,
+        protected: bool,
     ) -> Result<UnsafeCommandPool, OomError> {
         assert_eq!(
             device.physical_device().internal_object(),
@@ -84,7 +134,12 @@ impl UnsafeCommandPool {
             } else {
                 ash::vk::CommandPoolCreateFlags::empty()
             };
-            flag1 | flag2
+            let flag3 = if protected {
+                ash::vk::CommandPoolCreateFlags::PROTECTED
+            } else {
+                ash::vk::CommandPoolCreateFlags::empty()
+            };
+            flag1 | flag2 | flag3
         };
 
         let pool = unsafe {
@@ -108,6 +163,7 @@ impl UnsafeCommandPool {
             pool: pool,
             device: device.clone(),
             queue_family_index: queue_family.id(),
+            protected,
             dummy_avoid_sync: PhantomData,
         })
     }
@@ -211,12 +267,14 @@ impl UnsafeCommandPool {
         };
 
         let device = self.device.clone();
+        let protected = self.protected;
 
         Ok(out
             .into_iter()
             .map(move |command_buffer| UnsafeCommandPoolAlloc {
                 command_buffer,
                 device: device.clone(),
+                protected,
             }))
     }
 
@@ -251,6 +309,13 @@ impl UnsafeCommandPool {
             .queue_family_by_id(self.queue_family_index)
             .unwrap()
     }
+
+    /// Returns true if this is a protected pool, whose command buffers can only be executed with
+    /// a protected submission.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandPool {
@@ -284,6 +349,15 @@ impl Drop for UnsafeCommandPool {
 pub struct UnsafeCommandPoolAlloc {
     command_buffer: ash::vk::CommandBuffer,
     device: Arc<Device>,
+    protected: bool,
+}
+
+impl UnsafeCommandPoolAlloc {
+    /// Returns true if the command buffer was allocated from a protected pool.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandPoolAlloc {
@@ -302,6 +376,47 @@ unsafe impl VulkanObject for UnsafeCommandPoolAlloc {
     }
 }
 
+/// Error that can happen when creating a protected command pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CommandPoolCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for CommandPoolCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            CommandPoolCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CommandPoolCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            CommandPoolCreationError::OomError(_) => write!(fmt, "not enough memory available"),
+            CommandPoolCreationError::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+        }
+    }
+}
+
+impl From<OomError> for CommandPoolCreationError {
+    #[inline]
+    fn from(err: OomError) -> CommandPoolCreationError {
+        CommandPoolCreationError::OomError(err)
+    }
+}
+
 /// Error that can happen when trimming command pools.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum CommandPoolTrimError {
@@ -335,6 +450,7 @@ impl From<Error> for CommandPoolTrimError {
 
 #[cfg(test)]
 mod tests {
+    use crate::command_buffer::pool::CommandPoolCreationError;
     use crate::command_buffer::pool::CommandPoolTrimError;
     use crate::command_buffer::pool::UnsafeCommandPool;
     use crate::Version;
@@ -345,6 +461,19 @@ mod tests {
         let _ = UnsafeCommandPool::new(device, queue.family(), false, false).unwrap();
     }
 
+    #[test]
+    fn protected_missing_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        match UnsafeCommandPool::new_protected(device, queue.family(), false, false) {
+            Err(CommandPoolCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn queue_family_getter() {
         let (device, queue) = gfx_dev_and_queue!();
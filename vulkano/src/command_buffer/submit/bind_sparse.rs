@@ -8,9 +8,9 @@
 // according to those terms.
 
 use crate::buffer::sys::UnsafeBuffer;
-use crate::check_errors;
 use crate::device::Queue;
 use crate::image::sys::UnsafeImage;
+use crate::image::ImageAspect;
 use crate::memory::DeviceMemory;
 use crate::sync::Fence;
 use crate::sync::Semaphore;
@@ -140,7 +140,8 @@ impl<'a> SubmitBindSparseBuilder<'a> {
         unsafe {
             debug_assert!(queue.family().supports_sparse_binding());
 
-            let fns = queue.device().fns();
+            let device = queue.device();
+            let fns = device.fns();
             let queue = queue.internal_object_guard();
 
             // We start by storing all the `VkSparseBufferMemoryBindInfo`s of the whole command
@@ -239,7 +240,7 @@ impl<'a> SubmitBindSparseBuilder<'a> {
             };
 
             // Finally executing the command.
-            check_errors(fns.v1_0.queue_bind_sparse(
+            device.check_errors(fns.v1_0.queue_bind_sparse(
                 *queue,
                 bs_infos.len() as u32,
                 bs_infos.as_ptr(),
@@ -460,7 +461,72 @@ impl<'a> SubmitBindSparseImageBindBuilder<'a> {
         }
     }
 
-    // TODO: finish
+    /// Binds a region of `memory` to a region of a subresource of the image.
+    ///
+    /// `offset` and `extent` are in texels, and must be multiples of the sparse image block size
+    /// of the image's format, unless the region reaches the edge of the subresource.
+    pub unsafe fn add_bind(
+        &mut self,
+        aspect: ImageAspect,
+        mip_level: u32,
+        array_layer: u32,
+        offset: [i32; 3],
+        extent: [u32; 3],
+        memory: &DeviceMemory,
+        memory_offset: DeviceSize,
+    ) {
+        self.binds.push(ash::vk::SparseImageMemoryBind {
+            subresource: ash::vk::ImageSubresource {
+                aspect_mask: aspect.into(),
+                mip_level,
+                array_layer,
+            },
+            offset: ash::vk::Offset3D {
+                x: offset[0],
+                y: offset[1],
+                z: offset[2],
+            },
+            extent: ash::vk::Extent3D {
+                width: extent[0],
+                height: extent[1],
+                depth: extent[2],
+            },
+            memory: memory.internal_object(),
+            memory_offset,
+            flags: ash::vk::SparseMemoryBindFlags::empty(),
+        });
+    }
+
+    /// Unbinds the memory of a region of a subresource of the image.
+    pub unsafe fn add_unbind(
+        &mut self,
+        aspect: ImageAspect,
+        mip_level: u32,
+        array_layer: u32,
+        offset: [i32; 3],
+        extent: [u32; 3],
+    ) {
+        self.binds.push(ash::vk::SparseImageMemoryBind {
+            subresource: ash::vk::ImageSubresource {
+                aspect_mask: aspect.into(),
+                mip_level,
+                array_layer,
+            },
+            offset: ash::vk::Offset3D {
+                x: offset[0],
+                y: offset[1],
+                z: offset[2],
+            },
+            extent: ash::vk::Extent3D {
+                width: extent[0],
+                height: extent[1],
+                depth: extent[2],
+            },
+            memory: ash::vk::DeviceMemory::null(),
+            memory_offset: 0,
+            flags: ash::vk::SparseMemoryBindFlags::empty(),
+        });
+    }
 }
 
 /// Error that can happen when submitting the present prototype.
@@ -19,7 +19,6 @@ use crate::swapchain::PresentRegion;
 use crate::swapchain::Swapchain;
 use crate::sync::Semaphore;
 
-use crate::check_errors;
 use crate::Error;
 use crate::OomError;
 use crate::SynchronizedVulkanObject;
@@ -160,7 +159,8 @@ impl<'a> SubmitPresentBuilder<'a> {
 
             let mut results = vec![ash::vk::Result::SUCCESS; self.swapchains.len()];
 
-            let fns = queue.device().fns();
+            let device = queue.device();
+            let fns = device.fns();
             let queue = queue.internal_object_guard();
 
             let infos = ash::vk::PresentInfoKHR {
@@ -177,10 +177,10 @@ impl<'a> SubmitPresentBuilder<'a> {
                 ..Default::default()
             };
 
-            check_errors(fns.khr_swapchain.queue_present_khr(*queue, &infos))?;
+            device.check_errors(fns.khr_swapchain.queue_present_khr(*queue, &infos))?;
 
             for result in results {
-                check_errors(result)?;
+                device.check_errors(result)?;
             }
 
             Ok(())
@@ -205,7 +205,8 @@ pub enum SubmitPresentError {
     /// Not enough memory.
     OomError(OomError),
 
-    /// The connection to the device has been lost.
+    /// The connection to the device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
     DeviceLost,
 
     /// The surface is no longer accessible and must be recreated.
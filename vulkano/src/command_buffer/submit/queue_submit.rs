@@ -7,12 +7,12 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use crate::check_errors;
 use crate::command_buffer::sys::UnsafeCommandBuffer;
 use crate::device::Queue;
 use crate::sync::Fence;
 use crate::sync::PipelineStages;
 use crate::sync::Semaphore;
+use crate::sync::TimelineSemaphore;
 use crate::Error;
 use crate::OomError;
 use crate::SynchronizedVulkanObject;
@@ -21,15 +21,20 @@ use smallvec::SmallVec;
 use std::error;
 use std::fmt;
 use std::marker::PhantomData;
+use std::ptr;
 
 /// Prototype for a submission that executes command buffers.
 // TODO: example here
 #[derive(Debug)]
 pub struct SubmitCommandBufferBuilder<'a> {
     wait_semaphores: SmallVec<[ash::vk::Semaphore; 16]>,
+    wait_values: SmallVec<[u64; 16]>,
     destination_stages: SmallVec<[ash::vk::PipelineStageFlags; 8]>,
     signal_semaphores: SmallVec<[ash::vk::Semaphore; 16]>,
+    signal_values: SmallVec<[u64; 16]>,
+    has_timeline_semaphores: bool,
     command_buffers: SmallVec<[ash::vk::CommandBuffer; 4]>,
+    protected: bool,
     fence: ash::vk::Fence,
     marker: PhantomData<&'a ()>,
 }
@@ -40,9 +45,13 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     pub fn new() -> SubmitCommandBufferBuilder<'a> {
         SubmitCommandBufferBuilder {
             wait_semaphores: SmallVec::new(),
+            wait_values: SmallVec::new(),
             destination_stages: SmallVec::new(),
             signal_semaphores: SmallVec::new(),
+            signal_values: SmallVec::new(),
+            has_timeline_semaphores: false,
             command_buffers: SmallVec::new(),
+            protected: false,
             fence: ash::vk::Fence::null(),
             marker: PhantomData,
         }
@@ -140,9 +149,42 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         debug_assert!(!ash::vk::PipelineStageFlags::from(stages).is_empty());
         // TODO: debug assert that the device supports the stages
         self.wait_semaphores.push(semaphore.internal_object());
+        self.wait_values.push(0);
         self.destination_stages.push(stages.into());
     }
 
+    /// Adds a timeline semaphore to be waited upon before the command buffers are executed. The
+    /// wait completes once the counter of the semaphore is greater than or equal to `value`.
+    ///
+    /// Only the given `stages` of the command buffers added afterwards will wait upon
+    /// the semaphore. Other stages not included in `stages` can execute before waiting.
+    ///
+    /// # Safety
+    ///
+    /// - The stages must be supported by the device.
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has at least started executing the command buffers.
+    ///
+    /// - If you submit this builder, the semaphore must reach `value` eventually, either from the
+    ///   CPU or from another submission. In other words, you must not block the queue forever.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_wait_timeline_semaphore(
+        &mut self,
+        semaphore: &'a TimelineSemaphore,
+        value: u64,
+        stages: PipelineStages,
+    ) {
+        debug_assert!(!ash::vk::PipelineStageFlags::from(stages).is_empty());
+        self.wait_semaphores.push(semaphore.internal_object());
+        self.wait_values.push(value);
+        self.destination_stages.push(stages.into());
+        self.has_timeline_semaphores = true;
+    }
+
     /// Adds a command buffer that is executed as part of this command.
     ///
     /// The command buffers are submitted in the order in which they are added.
@@ -166,6 +208,38 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         self.command_buffers.push(command_buffer.internal_object());
     }
 
+    /// Sets whether the submission is a protected submission.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Safety
+    ///
+    /// - If `protected` is `true`, the queue that this builder is submitted to must be a
+    ///   protected queue, and the command buffers must have been allocated from a protected
+    ///   command pool, created with
+    ///   [`UnsafeCommandPool::new_protected`](crate::command_buffer::pool::UnsafeCommandPool::new_protected).
+    /// - If `protected` is `false`, the command buffers must not have been allocated from a
+    ///   protected command pool.
+    ///
+    #[inline]
+    pub unsafe fn set_protected(&mut self, protected: bool) {
+        self.protected = protected;
+    }
+
+    /// Returns true if the submission is a protected submission.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
+    /// Returns the number of command buffers to execute.
+    ///
+    /// In other words, this is the number of times `add_command_buffer` has been called.
+    #[inline]
+    pub fn num_command_buffers(&self) -> usize {
+        self.command_buffers.len()
+    }
+
     /// Returns the number of semaphores to signal.
     ///
     /// In other words, this is the number of times `add_signal_semaphore` has been called.
@@ -189,6 +263,31 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     #[inline]
     pub unsafe fn add_signal_semaphore(&mut self, semaphore: &'a Semaphore) {
         self.signal_semaphores.push(semaphore.internal_object());
+        self.signal_values.push(0);
+    }
+
+    /// Adds a timeline semaphore whose counter is set to `value` at the end of the submission.
+    ///
+    /// # Safety
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has finished executing this submission.
+    ///
+    /// - `value` must be greater than the value of the counter when queue execution reaches this
+    ///   submission, and greater than any value it is set to by other pending signal operations
+    ///   that come before this one.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_signal_timeline_semaphore(
+        &mut self,
+        semaphore: &'a TimelineSemaphore,
+        value: u64,
+    ) {
+        self.signal_semaphores.push(semaphore.internal_object());
+        self.signal_values.push(value);
+        self.has_timeline_semaphores = true;
     }
 
     /// Submits the command buffer to the given queue.
@@ -198,12 +297,97 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     ///
     pub fn submit(self, queue: &Queue) -> Result<(), SubmitCommandBufferError> {
         unsafe {
-            let fns = queue.device().fns();
+            let device = queue.device();
+            let fns = device.fns();
             let queue = queue.internal_object_guard();
 
             debug_assert_eq!(self.wait_semaphores.len(), self.destination_stages.len());
 
+            if device.enabled_features().synchronization2 {
+                let wait_semaphore_infos: SmallVec<[_; 16]> = self
+                    .wait_semaphores
+                    .iter()
+                    .zip(&self.wait_values)
+                    .zip(&self.destination_stages)
+                    .map(
+                        |((&semaphore, &value), &stages)| ash::vk::SemaphoreSubmitInfoKHR {
+                            semaphore,
+                            value,
+                            stage_mask: ash::vk::PipelineStageFlags2KHR::from_raw(
+                                stages.as_raw() as u64
+                            ),
+                            ..Default::default()
+                        },
+                    )
+                    .collect();
+                let command_buffer_infos: SmallVec<[_; 4]> = self
+                    .command_buffers
+                    .iter()
+                    .map(|&command_buffer| ash::vk::CommandBufferSubmitInfoKHR {
+                        command_buffer,
+                        ..Default::default()
+                    })
+                    .collect();
+                let signal_semaphore_infos: SmallVec<[_; 16]> = self
+                    .signal_semaphores
+                    .iter()
+                    .zip(&self.signal_values)
+                    .map(|(&semaphore, &value)| ash::vk::SemaphoreSubmitInfoKHR {
+                        semaphore,
+                        value,
+                        stage_mask: ash::vk::PipelineStageFlags2KHR::ALL_COMMANDS,
+                        ..Default::default()
+                    })
+                    .collect();
+
+                let batch = ash::vk::SubmitInfo2KHR {
+                    flags: if self.protected {
+                        ash::vk::SubmitFlagsKHR::PROTECTED
+                    } else {
+                        ash::vk::SubmitFlagsKHR::empty()
+                    },
+                    wait_semaphore_info_count: wait_semaphore_infos.len() as u32,
+                    p_wait_semaphore_infos: wait_semaphore_infos.as_ptr(),
+                    command_buffer_info_count: command_buffer_infos.len() as u32,
+                    p_command_buffer_infos: command_buffer_infos.as_ptr(),
+                    signal_semaphore_info_count: signal_semaphore_infos.len() as u32,
+                    p_signal_semaphore_infos: signal_semaphore_infos.as_ptr(),
+                    ..Default::default()
+                };
+
+                device.check_errors(
+                    fns.khr_synchronization2
+                        .queue_submit2_khr(*queue, 1, &batch, self.fence),
+                )?;
+                return Ok(());
+            }
+
+            let protected_info = ash::vk::ProtectedSubmitInfo {
+                protected_submit: ash::vk::TRUE,
+                ..Default::default()
+            };
+
+            let timeline_infos = ash::vk::TimelineSemaphoreSubmitInfo {
+                p_next: if self.protected {
+                    &protected_info as *const _ as *const _
+                } else {
+                    ptr::null()
+                },
+                wait_semaphore_value_count: self.wait_values.len() as u32,
+                p_wait_semaphore_values: self.wait_values.as_ptr(),
+                signal_semaphore_value_count: self.signal_values.len() as u32,
+                p_signal_semaphore_values: self.signal_values.as_ptr(),
+                ..Default::default()
+            };
+
             let batch = ash::vk::SubmitInfo {
+                p_next: if self.has_timeline_semaphores {
+                    &timeline_infos as *const _ as *const _
+                } else if self.protected {
+                    &protected_info as *const _ as *const _
+                } else {
+                    ptr::null()
+                },
                 wait_semaphore_count: self.wait_semaphores.len() as u32,
                 p_wait_semaphores: self.wait_semaphores.as_ptr(),
                 p_wait_dst_stage_mask: self.destination_stages.as_ptr(),
@@ -214,7 +398,7 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
                 ..Default::default()
             };
 
-            check_errors(fns.v1_0.queue_submit(*queue, 1, &batch, self.fence))?;
+            device.check_errors(fns.v1_0.queue_submit(*queue, 1, &batch, self.fence))?;
             Ok(())
         }
     }
@@ -223,18 +407,29 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     ///
     /// # Panic
     ///
-    /// Panics if both builders have a fence already set.
+    /// - Panics if both builders have a fence already set.
+    /// - Panics if both builders have command buffers, and only one of them is protected.
     // TODO: create multiple batches instead
     pub fn merge(mut self, other: Self) -> Self {
         assert!(
             self.fence == ash::vk::Fence::null() || other.fence == ash::vk::Fence::null(),
             "Can't merge two queue submits that both have a fence"
         );
+        assert!(
+            self.command_buffers.is_empty()
+                || other.command_buffers.is_empty()
+                || self.protected == other.protected,
+            "Can't merge a protected queue submit with an unprotected one"
+        );
 
         self.wait_semaphores.extend(other.wait_semaphores);
+        self.wait_values.extend(other.wait_values);
         self.destination_stages.extend(other.destination_stages); // TODO: meh? will be solved if we submit multiple batches
         self.signal_semaphores.extend(other.signal_semaphores);
+        self.signal_values.extend(other.signal_values);
+        self.has_timeline_semaphores |= other.has_timeline_semaphores;
         self.command_buffers.extend(other.command_buffers);
+        self.protected |= other.protected;
 
         if self.fence == ash::vk::Fence::null() {
             self.fence = other.fence;
@@ -251,7 +446,8 @@ pub enum SubmitCommandBufferError {
     /// Not enough memory.
     OomError(OomError),
 
-    /// The connection to the device has been lost.
+    /// The connection to the device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
     DeviceLost,
 }
 
@@ -29,6 +29,7 @@ use crate::pipeline::graphics::color_blend::LogicOp;
 use crate::pipeline::graphics::depth_stencil::CompareOp;
 use crate::pipeline::graphics::depth_stencil::StencilOp;
 use crate::pipeline::graphics::depth_stencil::StencilOps;
+use crate::pipeline::graphics::fragment_shading_rate::FragmentShadingRateCombinerOp;
 use crate::pipeline::graphics::input_assembly::IndexType;
 use crate::pipeline::graphics::input_assembly::PrimitiveTopology;
 use crate::pipeline::graphics::rasterization::CullMode;
@@ -42,6 +43,7 @@ use crate::pipeline::ComputePipeline;
 use crate::pipeline::DynamicState;
 use crate::pipeline::GraphicsPipeline;
 use crate::pipeline::PipelineBindPoint;
+use crate::pipeline::RayTracingPipeline;
 use crate::range_set::RangeSet;
 use crate::sync::AccessFlags;
 use crate::sync::PipelineMemoryAccess;
@@ -685,6 +687,7 @@ struct CurrentState {
     index_buffer: Option<(Arc<dyn BufferAccess>, IndexType)>,
     pipeline_compute: Option<Arc<ComputePipeline>>,
     pipeline_graphics: Option<Arc<GraphicsPipeline>>,
+    pipeline_ray_tracing: Option<Arc<RayTracingPipeline>>,
     vertex_buffers: FnvHashMap<u32, Arc<dyn BufferAccess>>,
 
     push_constants: RangeSet<u32>,
@@ -701,6 +704,7 @@ struct CurrentState {
     depth_test_enable: Option<bool>,
     depth_write_enable: Option<bool>,
     discard_rectangle: FnvHashMap<u32, Scissor>,
+    fragment_shading_rate: Option<([u32; 2], [FragmentShadingRateCombinerOp; 2])>,
     front_face: Option<FrontFace>,
     line_stipple: Option<LineStipple>,
     line_width: Option<f32>,
@@ -736,7 +740,7 @@ impl CurrentState {
                 DynamicState::DepthWriteEnable => self.depth_write_enable = None,
                 DynamicState::DiscardRectangle => self.discard_rectangle.clear(),
                 DynamicState::ExclusiveScissor => (), // TODO;
-                DynamicState::FragmentShadingRate => (), // TODO:
+                DynamicState::FragmentShadingRate => self.fragment_shading_rate = None,
                 DynamicState::FrontFace => self.front_face = None,
                 DynamicState::LineStipple => self.line_stipple = None,
                 DynamicState::LineWidth => self.line_width = None,
@@ -901,6 +905,12 @@ impl<'a> CommandBufferState<'a> {
         self.current_state.pipeline_graphics.as_ref()
     }
 
+    /// Returns the ray tracing pipeline currently bound, or `None` if nothing has been bound yet.
+    #[inline]
+    pub fn pipeline_ray_tracing(&self) -> Option<&'a Arc<RayTracingPipeline>> {
+        self.current_state.pipeline_ray_tracing.as_ref()
+    }
+
     /// Returns the vertex buffer currently bound to a given binding slot number, or `None` if
     /// nothing has been bound yet.
     #[inline]
@@ -991,6 +1001,13 @@ impl<'a> CommandBufferState<'a> {
         self.current_state.discard_rectangle.get(&num)
     }
 
+    /// Returns the current fragment size and combiner operations of the fragment shading rate,
+    /// or `None` if nothing has been set yet.
+    #[inline]
+    pub fn fragment_shading_rate(&self) -> Option<([u32; 2], [FragmentShadingRateCombinerOp; 2])> {
+        self.current_state.fragment_shading_rate
+    }
+
     /// Returns the current front face, or `None` if nothing has been set yet.
     #[inline]
     pub fn front_face(&self) -> Option<FrontFace> {
@@ -8,6 +8,8 @@
 // according to those terms.
 
 use super::*;
+use crate::acceleration_structure::AccelerationStructure;
+use crate::acceleration_structure::AccelerationStructureBuildGeometryInfo;
 use crate::buffer::BufferAccess;
 use crate::buffer::TypedBufferAccess;
 use crate::command_buffer::synced::builder::KeyTy;
@@ -21,8 +23,10 @@ use crate::command_buffer::sys::UnsafeCommandBufferBuilderDepthStencilImageClear
 use crate::command_buffer::sys::UnsafeCommandBufferBuilderExecuteCommands;
 use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
 use crate::command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
+use crate::command_buffer::sys::UnsafeCommandBufferBuilderPipelineBarrier;
 use crate::command_buffer::CommandBufferExecError;
 use crate::command_buffer::ImageUninitializedSafe;
+use crate::command_buffer::RenderingInfo;
 use crate::command_buffer::SecondaryCommandBuffer;
 use crate::command_buffer::SubpassContents;
 use crate::descriptor_set::layout::DescriptorType;
@@ -40,9 +44,11 @@ use crate::pipeline::graphics::vertex_input::VertexInputState;
 use crate::pipeline::graphics::viewport::Scissor;
 use crate::pipeline::graphics::viewport::Viewport;
 use crate::pipeline::layout::PipelineLayout;
+use crate::pipeline::ray_tracing::ShaderBindingTable;
 use crate::pipeline::ComputePipeline;
 use crate::pipeline::GraphicsPipeline;
 use crate::pipeline::PipelineBindPoint;
+use crate::pipeline::RayTracingPipeline;
 use crate::query::QueryControlFlags;
 use crate::query::QueryPool;
 use crate::query::QueryResultElement;
@@ -53,6 +59,7 @@ use crate::sampler::Filter;
 use crate::shader::DescriptorRequirements;
 use crate::shader::ShaderStages;
 use crate::sync::AccessFlags;
+use crate::sync::DependencyInfo;
 use crate::sync::Event;
 use crate::sync::PipelineMemoryAccess;
 use crate::sync::PipelineStage;
@@ -62,13 +69,65 @@ use crate::SafeDeref;
 use crate::VulkanObject;
 use smallvec::SmallVec;
 use std::borrow::Cow;
-use std::ffi::CStr;
+use std::ffi::CString;
 use std::mem;
 use std::ops::Range;
 use std::ptr;
 use std::sync::{Arc, Mutex};
 
 impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        inverted: bool,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            buffer: Arc<dyn BufferAccess>,
+            inverted: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBeginConditionalRenderingEXT"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_conditional_rendering(self.buffer.as_ref(), self.inverted);
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                buffer: buffer.clone(),
+                inverted,
+            },
+            [(
+                KeyTy::Buffer(buffer),
+                "predicate".into(),
+                Some((
+                    PipelineMemoryAccess {
+                        stages: PipelineStages {
+                            conditional_rendering: true,
+                            ..PipelineStages::none()
+                        },
+                        access: AccessFlags {
+                            conditional_rendering_read: true,
+                            ..AccessFlags::none()
+                        },
+                        exclusive: false,
+                    },
+                    ImageLayout::Undefined,
+                    ImageLayout::Undefined,
+                    ImageUninitializedSafe::Unsafe,
+                )),
+            )],
+        )?;
+
+        Ok(())
+    }
+
     /// Calls `vkCmdBeginQuery` on the builder.
     #[inline]
     pub unsafe fn begin_query(
@@ -104,6 +163,73 @@ impl SyncCommandBufferBuilder {
         .unwrap();
     }
 
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_counter_buffer: u32,
+            counter_buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBeginTransformFeedbackEXT"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_transform_feedback(
+                    self.first_counter_buffer,
+                    self.counter_buffers.iter().map(|buffer| buffer.as_ref()),
+                );
+            }
+        }
+
+        let resources: SmallVec<[_; 4]> = counter_buffers
+            .iter()
+            .enumerate()
+            .map(|(num, buffer)| {
+                (
+                    KeyTy::Buffer(buffer.clone()),
+                    format!(
+                        "Transform feedback counter buffer {}",
+                        first_counter_buffer + num as u32
+                    )
+                    .into(),
+                    Some((
+                        PipelineMemoryAccess {
+                            stages: PipelineStages {
+                                draw_indirect: true,
+                                ..PipelineStages::none()
+                            },
+                            access: AccessFlags {
+                                transform_feedback_counter_read: true,
+                                ..AccessFlags::none()
+                            },
+                            exclusive: false,
+                        },
+                        ImageLayout::Undefined,
+                        ImageLayout::Undefined,
+                        ImageUninitializedSafe::Unsafe,
+                    )),
+                )
+            })
+            .collect();
+
+        self.append_command(
+            Cmd {
+                first_counter_buffer,
+                counter_buffers,
+            },
+            resources,
+        )?;
+
+        Ok(())
+    }
+
     /// Calls `vkBeginRenderPass` on the builder.
     // TODO: it shouldn't be possible to get an error if the framebuffer checked conflicts already
     // TODO: after begin_render_pass has been called, flushing should be forbidden and an error
@@ -193,6 +319,94 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
+    /// Calls `vkCmdBeginRenderingKHR` on the builder.
+    #[inline]
+    pub unsafe fn begin_rendering(
+        &mut self,
+        rendering_info: RenderingInfo,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            rendering_info: RenderingInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBeginRenderingKHR"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_rendering(&self.rendering_info);
+            }
+        }
+
+        let color_attachments = rendering_info
+            .color_attachments
+            .iter()
+            .enumerate()
+            .filter_map(|(index, attachment_info)| {
+                attachment_info
+                    .as_ref()
+                    .map(|attachment_info| (format!("color attachment {}", index), attachment_info))
+            });
+        let depth_attachment = rendering_info
+            .depth_attachment
+            .as_ref()
+            .map(|attachment_info| ("depth attachment".to_owned(), attachment_info));
+        // The same image view may be used for both the depth and the stencil attachment, in which
+        // case it must only be locked once.
+        let stencil_attachment = rendering_info
+            .stencil_attachment
+            .as_ref()
+            .filter(|stencil_info| {
+                rendering_info
+                    .depth_attachment
+                    .as_ref()
+                    .map_or(true, |depth_info| {
+                        depth_info.image_view.internal_object()
+                            != stencil_info.image_view.internal_object()
+                    })
+            })
+            .map(|attachment_info| ("stencil attachment".to_owned(), attachment_info));
+
+        let resources = color_attachments
+            .chain(depth_attachment)
+            .chain(stencil_attachment)
+            .map(|(name, attachment_info)| {
+                (
+                    KeyTy::Image(attachment_info.image_view.image()),
+                    name.into(),
+                    Some((
+                        PipelineMemoryAccess {
+                            stages: PipelineStages {
+                                all_commands: true,
+                                ..PipelineStages::none()
+                            }, // TODO: wrong!
+                            access: AccessFlags {
+                                color_attachment_read: true,
+                                color_attachment_write: true,
+                                depth_stencil_attachment_read: true,
+                                depth_stencil_attachment_write: true,
+                                ..AccessFlags::none()
+                            }, // TODO: suboptimal
+                            exclusive: true, // TODO: suboptimal
+                        },
+                        attachment_info.image_layout,
+                        attachment_info.image_layout,
+                        match attachment_info.load_op == LoadOp::Clear {
+                            true => ImageUninitializedSafe::Safe,
+                            false => ImageUninitializedSafe::Unsafe,
+                        },
+                    )),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        self.append_command(Cmd { rendering_info }, resources)?;
+
+        self.latest_render_pass_enter = Some(self.commands.len() - 1);
+        Ok(())
+    }
+
     /// Starts the process of binding descriptor sets. Returns an intermediate struct which can be
     /// used to add the sets.
     #[inline]
@@ -275,6 +489,97 @@ impl SyncCommandBufferBuilder {
         self.append_command(Cmd { pipeline }, []).unwrap();
     }
 
+    /// Calls `vkCmdBindPipeline` on the builder with a ray tracing pipeline.
+    #[inline]
+    pub unsafe fn bind_pipeline_ray_tracing(&mut self, pipeline: Arc<RayTracingPipeline>) {
+        struct Cmd {
+            pipeline: Arc<RayTracingPipeline>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBindPipeline"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_pipeline_ray_tracing(&self.pipeline);
+            }
+        }
+
+        self.current_state.pipeline_ray_tracing = Some(pipeline.clone());
+        self.append_command(Cmd { pipeline }, []).unwrap();
+    }
+
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    ///
+    /// The buffers are considered to be written by the transform feedback stage from this point
+    /// on.
+    #[inline]
+    pub unsafe fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_binding: u32,
+            buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBindTransformFeedbackBuffersEXT"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_transform_feedback_buffers(
+                    self.first_binding,
+                    self.buffers.iter().map(|buffer| buffer.as_ref()),
+                );
+            }
+        }
+
+        let resources: SmallVec<[_; 4]> = buffers
+            .iter()
+            .enumerate()
+            .map(|(num, buffer)| {
+                (
+                    KeyTy::Buffer(buffer.clone()),
+                    format!(
+                        "Transform feedback buffer binding {}",
+                        first_binding + num as u32
+                    )
+                    .into(),
+                    Some((
+                        PipelineMemoryAccess {
+                            stages: PipelineStages {
+                                transform_feedback: true,
+                                ..PipelineStages::none()
+                            },
+                            access: AccessFlags {
+                                transform_feedback_write: true,
+                                ..AccessFlags::none()
+                            },
+                            exclusive: true,
+                        },
+                        ImageLayout::Undefined,
+                        ImageLayout::Undefined,
+                        ImageUninitializedSafe::Unsafe,
+                    )),
+                )
+            })
+            .collect();
+
+        self.append_command(
+            Cmd {
+                first_binding,
+                buffers,
+            },
+            resources,
+        )?;
+
+        Ok(())
+    }
+
     /// Starts the process of binding vertex buffers. Returns an intermediate struct which can be
     /// used to add the buffers.
     #[inline]
@@ -546,6 +851,114 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder, with a single build.
+    ///
+    /// The geometry buffers are considered to be read, and the destination and scratch buffers to
+    /// be written, by the acceleration structure build stage.
+    #[inline]
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: AccelerationStructureBuildGeometryInfo,
+        destination: Arc<AccelerationStructure>,
+        scratch_buffer: Arc<dyn BufferAccess>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            info: AccelerationStructureBuildGeometryInfo,
+            destination: Arc<AccelerationStructure>,
+            scratch_buffer: Arc<dyn BufferAccess>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBuildAccelerationStructuresKHR"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.build_acceleration_structure(
+                    &self.info,
+                    &self.destination,
+                    self.scratch_buffer.as_ref(),
+                );
+            }
+        }
+
+        let stages = PipelineStages {
+            acceleration_structure_build: true,
+            ..PipelineStages::none()
+        };
+
+        let mut resources: Vec<_> = info
+            .geometries
+            .buffers()
+            .into_iter()
+            .map(|(geometry_index, buffer)| {
+                (
+                    KeyTy::Buffer(buffer.clone()),
+                    format!("geometry {} buffer", geometry_index).into(),
+                    Some((
+                        PipelineMemoryAccess {
+                            stages,
+                            access: AccessFlags {
+                                shader_read: true,
+                                ..AccessFlags::none()
+                            },
+                            exclusive: false,
+                        },
+                        ImageLayout::Undefined,
+                        ImageLayout::Undefined,
+                        ImageUninitializedSafe::Unsafe,
+                    )),
+                )
+            })
+            .collect();
+        resources.push((
+            KeyTy::Buffer(scratch_buffer.clone()),
+            "scratch buffer".into(),
+            Some((
+                PipelineMemoryAccess {
+                    stages,
+                    access: AccessFlags {
+                        acceleration_structure_read: true,
+                        acceleration_structure_write: true,
+                        ..AccessFlags::none()
+                    },
+                    exclusive: true,
+                },
+                ImageLayout::Undefined,
+                ImageLayout::Undefined,
+                ImageUninitializedSafe::Unsafe,
+            )),
+        ));
+        resources.push((
+            KeyTy::Buffer(destination.buffer().clone()),
+            "destination".into(),
+            Some((
+                PipelineMemoryAccess {
+                    stages,
+                    access: AccessFlags {
+                        acceleration_structure_write: true,
+                        ..AccessFlags::none()
+                    },
+                    exclusive: true,
+                },
+                ImageLayout::Undefined,
+                ImageLayout::Undefined,
+                ImageUninitializedSafe::Unsafe,
+            )),
+        ));
+
+        self.append_command(
+            Cmd {
+                info,
+                destination,
+                scratch_buffer,
+            },
+            resources,
+        )?;
+
+        Ok(())
+    }
+
     /// Calls `vkCmdClearAttachments` on the builder.
     ///
     /// Does nothing if the list of attachments or the list of rects is empty, as it would be a
@@ -1125,9 +1538,9 @@ impl SyncCommandBufferBuilder {
     /// The command pool that this command buffer was allocated from must support graphics or
     /// compute operations
     #[inline]
-    pub unsafe fn debug_marker_begin(&mut self, name: &'static CStr, color: [f32; 4]) {
+    pub unsafe fn debug_marker_begin(&mut self, name: CString, color: [f32; 4]) {
         struct Cmd {
-            name: &'static CStr,
+            name: CString,
             color: [f32; 4],
         }
 
@@ -1137,7 +1550,7 @@ impl SyncCommandBufferBuilder {
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.debug_marker_begin(self.name, self.color);
+                out.debug_marker_begin(&self.name, self.color);
             }
         }
 
@@ -1174,9 +1587,9 @@ impl SyncCommandBufferBuilder {
     /// The command pool that this command buffer was allocated from must support graphics or
     /// compute operations
     #[inline]
-    pub unsafe fn debug_marker_insert(&mut self, name: &'static CStr, color: [f32; 4]) {
+    pub unsafe fn debug_marker_insert(&mut self, name: CString, color: [f32; 4]) {
         struct Cmd {
-            name: &'static CStr,
+            name: CString,
             color: [f32; 4],
         }
 
@@ -1186,7 +1599,7 @@ impl SyncCommandBufferBuilder {
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.debug_marker_insert(self.name, self.color);
+                out.debug_marker_insert(&self.name, self.color);
             }
         }
 
@@ -1369,27 +1782,133 @@ impl SyncCommandBufferBuilder {
         .unwrap();
     }
 
-    /// Calls `vkCmdDrawIndirect` on the builder.
+    /// Calls `vkCmdDrawIndirect` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        draw_count: u32,
+        stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+            draw_count: u32,
+            stride: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdDrawIndirect"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indirect(self.indirect_buffer.as_ref(), self.draw_count, self.stride);
+            }
+        }
+
+        let pipeline = self.current_state.pipeline_graphics.as_ref().unwrap();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_set_resources(
+            &mut resources,
+            PipelineBindPoint::Graphics,
+            pipeline.descriptor_requirements(),
+        );
+        self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
+        self.add_indirect_buffer_resources(&mut resources, indirect_buffer.clone());
+
+        self.append_command(
+            Cmd {
+                indirect_buffer,
+                draw_count,
+                stride,
+            },
+            resources,
+        )?;
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawIndexedIndirect` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        draw_count: u32,
+        stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+            draw_count: u32,
+            stride: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdDrawIndexedIndirect"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indexed_indirect(
+                    self.indirect_buffer.as_ref(),
+                    self.draw_count,
+                    self.stride,
+                );
+            }
+        }
+
+        let pipeline = self.current_state.pipeline_graphics.as_ref().unwrap();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_set_resources(
+            &mut resources,
+            PipelineBindPoint::Graphics,
+            pipeline.descriptor_requirements(),
+        );
+        self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
+        self.add_index_buffer_resources(&mut resources);
+        self.add_indirect_buffer_resources(&mut resources, indirect_buffer.clone());
+
+        self.append_command(
+            Cmd {
+                indirect_buffer,
+                draw_count,
+                stride,
+            },
+            resources,
+        )?;
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawIndirectCount` on the builder.
     #[inline]
-    pub unsafe fn draw_indirect(
+    pub unsafe fn draw_indirect_count(
         &mut self,
         indirect_buffer: Arc<dyn BufferAccess>,
-        draw_count: u32,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
         stride: u32,
     ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
             indirect_buffer: Arc<dyn BufferAccess>,
-            draw_count: u32,
+            count_buffer: Arc<dyn BufferAccess>,
+            max_draw_count: u32,
             stride: u32,
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "vkCmdDrawIndirect"
+                "vkCmdDrawIndirectCount"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.draw_indirect(self.indirect_buffer.as_ref(), self.draw_count, self.stride);
+                out.draw_indirect_count(
+                    self.indirect_buffer.as_ref(),
+                    self.count_buffer.as_ref(),
+                    self.max_draw_count,
+                    self.stride,
+                );
             }
         }
 
@@ -1403,11 +1922,13 @@ impl SyncCommandBufferBuilder {
         );
         self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
         self.add_indirect_buffer_resources(&mut resources, indirect_buffer.clone());
+        self.add_indirect_buffer_resources(&mut resources, count_buffer.clone());
 
         self.append_command(
             Cmd {
                 indirect_buffer,
-                draw_count,
+                count_buffer,
+                max_draw_count,
                 stride,
             },
             resources,
@@ -1416,29 +1937,32 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
-    /// Calls `vkCmdDrawIndexedIndirect` on the builder.
+    /// Calls `vkCmdDrawIndexedIndirectCount` on the builder.
     #[inline]
-    pub unsafe fn draw_indexed_indirect(
+    pub unsafe fn draw_indexed_indirect_count(
         &mut self,
         indirect_buffer: Arc<dyn BufferAccess>,
-        draw_count: u32,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
         stride: u32,
     ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
             indirect_buffer: Arc<dyn BufferAccess>,
-            draw_count: u32,
+            count_buffer: Arc<dyn BufferAccess>,
+            max_draw_count: u32,
             stride: u32,
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "vkCmdDrawIndexedIndirect"
+                "vkCmdDrawIndexedIndirectCount"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.draw_indexed_indirect(
+                out.draw_indexed_indirect_count(
                     self.indirect_buffer.as_ref(),
-                    self.draw_count,
+                    self.count_buffer.as_ref(),
+                    self.max_draw_count,
                     self.stride,
                 );
             }
@@ -1455,11 +1979,13 @@ impl SyncCommandBufferBuilder {
         self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
         self.add_index_buffer_resources(&mut resources);
         self.add_indirect_buffer_resources(&mut resources, indirect_buffer.clone());
+        self.add_indirect_buffer_resources(&mut resources, count_buffer.clone());
 
         self.append_command(
             Cmd {
                 indirect_buffer,
-                draw_count,
+                count_buffer,
+                max_draw_count,
                 stride,
             },
             resources,
@@ -1468,6 +1994,24 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        struct Cmd;
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdEndConditionalRenderingEXT"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_conditional_rendering();
+            }
+        }
+
+        self.append_command(Cmd, []).unwrap();
+    }
+
     /// Calls `vkCmdEndQuery` on the builder.
     #[inline]
     pub unsafe fn end_query(&mut self, query_pool: Arc<QueryPool>, query: u32) {
@@ -1509,6 +2053,93 @@ impl SyncCommandBufferBuilder {
         self.latest_render_pass_enter = None;
     }
 
+    /// Calls `vkCmdEndRenderingKHR` on the builder.
+    #[inline]
+    pub unsafe fn end_rendering(&mut self) {
+        struct Cmd;
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdEndRenderingKHR"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_rendering();
+            }
+        }
+
+        self.append_command(Cmd, []).unwrap();
+        debug_assert!(self.latest_render_pass_enter.is_some());
+        self.latest_render_pass_enter = None;
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_counter_buffer: u32,
+            counter_buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdEndTransformFeedbackEXT"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_transform_feedback(
+                    self.first_counter_buffer,
+                    self.counter_buffers.iter().map(|buffer| buffer.as_ref()),
+                );
+            }
+        }
+
+        let resources: SmallVec<[_; 4]> = counter_buffers
+            .iter()
+            .enumerate()
+            .map(|(num, buffer)| {
+                (
+                    KeyTy::Buffer(buffer.clone()),
+                    format!(
+                        "Transform feedback counter buffer {}",
+                        first_counter_buffer + num as u32
+                    )
+                    .into(),
+                    Some((
+                        PipelineMemoryAccess {
+                            stages: PipelineStages {
+                                transform_feedback: true,
+                                ..PipelineStages::none()
+                            },
+                            access: AccessFlags {
+                                transform_feedback_counter_write: true,
+                                ..AccessFlags::none()
+                            },
+                            exclusive: true,
+                        },
+                        ImageLayout::Undefined,
+                        ImageLayout::Undefined,
+                        ImageUninitializedSafe::Unsafe,
+                    )),
+                )
+            })
+            .collect();
+
+        self.append_command(
+            Cmd {
+                first_counter_buffer,
+                counter_buffers,
+            },
+            resources,
+        )?;
+
+        Ok(())
+    }
+
     /// Starts the process of executing secondary command buffers. Returns an intermediate struct
     /// which can be used to add the command buffers.
     #[inline]
@@ -1586,6 +2217,26 @@ impl SyncCommandBufferBuilder {
         self.append_command(Cmd { subpass_contents }, []).unwrap();
     }
 
+    /// Calls `vkCmdPipelineBarrier2KHR` on the builder.
+    #[inline]
+    pub unsafe fn pipeline_barrier2(&mut self, dependency_info: DependencyInfo) {
+        struct Cmd {
+            dependency_info: DependencyInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdPipelineBarrier2KHR"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.pipeline_barrier2(&self.dependency_info);
+            }
+        }
+
+        self.append_command(Cmd { dependency_info }, []).unwrap();
+    }
+
     /// Calls `vkCmdPushConstants` on the builder.
     #[inline]
     pub unsafe fn push_constants<D>(
@@ -2077,6 +2728,67 @@ impl SyncCommandBufferBuilder {
         self.append_command(Cmd { event, stages }, []).unwrap();
     }
 
+    /// Calls `vkCmdSetEvent2KHR` on the builder.
+    #[inline]
+    pub unsafe fn set_event2(&mut self, event: Arc<Event>, dependency_info: DependencyInfo) {
+        struct Cmd {
+            event: Arc<Event>,
+            dependency_info: DependencyInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetEvent2KHR"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_event2(&self.event, &self.dependency_info);
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                event,
+                dependency_info,
+            },
+            [],
+        )
+        .unwrap();
+    }
+
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) {
+        struct Cmd {
+            fragment_size: [u32; 2],
+            combiner_ops: [FragmentShadingRateCombinerOp; 2],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetFragmentShadingRateKHR"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_fragment_shading_rate(self.fragment_size, self.combiner_ops);
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                fragment_size,
+                combiner_ops,
+            },
+            [],
+        )
+        .unwrap();
+        self.current_state.fragment_shading_rate = Some((fragment_size, combiner_ops));
+    }
+
     /// Calls `vkCmdSetFrontFaceEXT` on the builder.
     #[inline]
     pub unsafe fn set_front_face(&mut self, face: FrontFace) {
@@ -2583,6 +3295,68 @@ impl SyncCommandBufferBuilder {
         .unwrap();
     }
 
+    /// Calls `vkCmdTraceRaysKHR` on the builder.
+    #[inline]
+    pub unsafe fn trace_rays(
+        &mut self,
+        shader_binding_table: Arc<ShaderBindingTable>,
+        dimensions: [u32; 3],
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            shader_binding_table: Arc<ShaderBindingTable>,
+            dimensions: [u32; 3],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdTraceRaysKHR"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.trace_rays(&self.shader_binding_table, self.dimensions);
+            }
+        }
+
+        let pipeline = self.current_state.pipeline_ray_tracing.as_ref().unwrap();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_set_resources(
+            &mut resources,
+            PipelineBindPoint::RayTracing,
+            pipeline.descriptor_requirements(),
+        );
+        resources.push((
+            KeyTy::Buffer(shader_binding_table.buffer().clone()),
+            "shader binding table".into(),
+            Some((
+                PipelineMemoryAccess {
+                    stages: PipelineStages {
+                        ray_tracing_shader: true,
+                        ..PipelineStages::none()
+                    },
+                    access: AccessFlags {
+                        shader_read: true,
+                        ..AccessFlags::none()
+                    },
+                    exclusive: false,
+                },
+                ImageLayout::Undefined,
+                ImageLayout::Undefined,
+                ImageUninitializedSafe::Unsafe,
+            )),
+        ));
+
+        self.append_command(
+            Cmd {
+                shader_binding_table,
+                dimensions,
+            },
+            resources,
+        )?;
+
+        Ok(())
+    }
+
     /// Calls `vkCmdUpdateBuffer` on the builder.
     #[inline]
     pub unsafe fn update_buffer<D, Dd>(&mut self, buffer: Arc<dyn BufferAccess>, data: Dd)
@@ -2638,6 +3412,88 @@ impl SyncCommandBufferBuilder {
         .unwrap();
     }
 
+    /// Calls `vkCmdWaitEvents` on the builder, with a single global memory barrier.
+    #[inline]
+    pub unsafe fn wait_events(
+        &mut self,
+        events: impl IntoIterator<Item = Arc<Event>>,
+        source_stages: PipelineStages,
+        source_access: AccessFlags,
+        destination_stages: PipelineStages,
+        destination_access: AccessFlags,
+    ) {
+        struct Cmd {
+            events: SmallVec<[Arc<Event>; 4]>,
+            source_stages: PipelineStages,
+            source_access: AccessFlags,
+            destination_stages: PipelineStages,
+            destination_access: AccessFlags,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdWaitEvents"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_memory_barrier(
+                    self.source_stages,
+                    self.source_access,
+                    self.destination_stages,
+                    self.destination_access,
+                    false,
+                );
+                out.wait_events(self.events.iter().map(|e| e.as_ref()), &barrier);
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                events: events.into_iter().collect(),
+                source_stages,
+                source_access,
+                destination_stages,
+                destination_access,
+            },
+            [],
+        )
+        .unwrap();
+    }
+
+    /// Calls `vkCmdWaitEvents2KHR` on the builder.
+    #[inline]
+    pub unsafe fn wait_events2(
+        &mut self,
+        events: impl IntoIterator<Item = (Arc<Event>, DependencyInfo)>,
+    ) {
+        struct Cmd {
+            events: SmallVec<[(Arc<Event>, DependencyInfo); 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdWaitEvents2KHR"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.wait_events2(
+                    self.events
+                        .iter()
+                        .map(|(event, dependency_info)| (event.as_ref(), dependency_info)),
+                );
+            }
+        }
+
+        self.append_command(
+            Cmd {
+                events: events.into_iter().collect(),
+            },
+            [],
+        )
+        .unwrap();
+    }
+
     /// Calls `vkCmdWriteTimestamp` on the builder.
     #[inline]
     pub unsafe fn write_timestamp(
@@ -2726,6 +3582,10 @@ impl SyncCommandBufferBuilder {
                             ..AccessFlags::none()
                         }
                     }
+                    DescriptorType::AccelerationStructure => AccessFlags {
+                        acceleration_structure_read: true,
+                        ..AccessFlags::none()
+                    },
                 },
                 exclusive: false,
             };
@@ -2826,6 +3686,18 @@ impl SyncCommandBufferBuilder {
                     );
                 }
                 DescriptorBindingResources::Sampler(_) => (),
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    resources.extend(
+                        access
+                            .zip(elements)
+                            .filter_map(|(access, element)| {
+                                element.as_ref().map(|acceleration_structure| {
+                                    (access, acceleration_structure.buffer().clone())
+                                })
+                            })
+                            .map(buffer_resource),
+                    );
+                }
             }
         }
     }
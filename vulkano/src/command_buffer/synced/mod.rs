@@ -651,6 +651,8 @@ mod tests {
                     ty: DescriptorType::Sampler,
                     descriptor_count: 1,
                     variable_count: false,
+                    partially_bound: false,
+                    update_after_bind: false,
                     stages: ShaderStages::all(),
                     immutable_samplers: Vec::new(),
                 })],
@@ -7,6 +7,8 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use crate::acceleration_structure::AccelerationStructure;
+use crate::acceleration_structure::AccelerationStructureBuildGeometryInfo;
 use crate::buffer::BufferAccess;
 use crate::buffer::BufferInner;
 use crate::buffer::TypedBufferAccess;
@@ -15,10 +17,12 @@ use crate::command_buffer::pool::UnsafeCommandPoolAlloc;
 use crate::command_buffer::CommandBufferInheritance;
 use crate::command_buffer::CommandBufferLevel;
 use crate::command_buffer::CommandBufferUsage;
+use crate::command_buffer::RenderingAttachmentInfo;
+use crate::command_buffer::RenderingInfo;
 use crate::command_buffer::SecondaryCommandBuffer;
 use crate::command_buffer::SubpassContents;
 use crate::descriptor_set::sys::UnsafeDescriptorSet;
-use crate::descriptor_set::{DescriptorWriteInfo, WriteDescriptorSet};
+use crate::descriptor_set::WriteDescriptorSet;
 use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::format::ClearValue;
@@ -34,6 +38,7 @@ use crate::pipeline::graphics::color_blend::LogicOp;
 use crate::pipeline::graphics::depth_stencil::CompareOp;
 use crate::pipeline::graphics::depth_stencil::StencilFaces;
 use crate::pipeline::graphics::depth_stencil::StencilOp;
+use crate::pipeline::graphics::fragment_shading_rate::FragmentShadingRateCombinerOp;
 use crate::pipeline::graphics::input_assembly::IndexType;
 use crate::pipeline::graphics::input_assembly::PrimitiveTopology;
 use crate::pipeline::graphics::rasterization::CullMode;
@@ -41,9 +46,11 @@ use crate::pipeline::graphics::rasterization::FrontFace;
 use crate::pipeline::graphics::viewport::Scissor;
 use crate::pipeline::graphics::viewport::Viewport;
 use crate::pipeline::layout::PipelineLayout;
+use crate::pipeline::ray_tracing::ShaderBindingTable;
 use crate::pipeline::ComputePipeline;
 use crate::pipeline::GraphicsPipeline;
 use crate::pipeline::PipelineBindPoint;
+use crate::pipeline::RayTracingPipeline;
 use crate::query::QueriesRange;
 use crate::query::Query;
 use crate::query::QueryControlFlags;
@@ -53,6 +60,7 @@ use crate::render_pass::Framebuffer;
 use crate::sampler::Filter;
 use crate::shader::ShaderStages;
 use crate::sync::AccessFlags;
+use crate::sync::DependencyInfo;
 use crate::sync::Event;
 use crate::sync::PipelineStage;
 use crate::sync::PipelineStages;
@@ -65,6 +73,7 @@ use std::ffi::CStr;
 use std::fmt;
 use std::mem;
 use std::ops::Range;
+use std::ptr;
 use std::sync::Arc;
 
 /// Command buffer being built.
@@ -79,6 +88,7 @@ pub struct UnsafeCommandBufferBuilder {
     command_buffer: ash::vk::CommandBuffer,
     device: Arc<Device>,
     usage: CommandBufferUsage,
+    protected: bool,
 }
 
 impl fmt::Debug for UnsafeCommandBufferBuilder {
@@ -204,6 +214,7 @@ impl UnsafeCommandBufferBuilder {
             command_buffer: pool_alloc.internal_object(),
             device: device.clone(),
             usage,
+            protected: pool_alloc.is_protected(),
         })
     }
 
@@ -218,10 +229,41 @@ impl UnsafeCommandBufferBuilder {
                 command_buffer: self.command_buffer,
                 device: self.device.clone(),
                 usage: self.usage,
+                protected: self.protected,
             })
         }
     }
 
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering<B>(&mut self, buffer: &B, inverted: bool)
+    where
+        B: ?Sized + BufferAccess,
+    {
+        debug_assert!(self.device().enabled_extensions().ext_conditional_rendering);
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage().conditional_rendering);
+        debug_assert_eq!(inner.offset % 4, 0);
+
+        let info = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: inner.buffer.internal_object(),
+            offset: inner.offset,
+            flags: if inverted {
+                ash::vk::ConditionalRenderingFlagsEXT::INVERTED
+            } else {
+                ash::vk::ConditionalRenderingFlagsEXT::empty()
+            },
+            ..Default::default()
+        };
+
+        fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext(cmd, &info);
+    }
+
     /// Calls `vkCmdBeginQuery` on the builder.
     #[inline]
     pub unsafe fn begin_query(&mut self, query: Query, flags: QueryControlFlags) {
@@ -236,6 +278,31 @@ impl UnsafeCommandBufferBuilder {
             .cmd_begin_query(cmd, query.pool().internal_object(), query.index(), flags);
     }
 
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    ///
+    /// Each buffer in `counter_buffers` holds the counter for the transform feedback buffer with
+    /// the same binding, starting with `first_counter_buffer`.
+    #[inline]
+    pub unsafe fn begin_transform_feedback<'a>(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = &'a dyn BufferAccess>,
+    ) {
+        debug_assert!(self.device().enabled_extensions().ext_transform_feedback);
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+
+        let (raw_buffers, offsets) = transform_feedback_counter_buffers(counter_buffers);
+
+        fns.ext_transform_feedback.cmd_begin_transform_feedback_ext(
+            cmd,
+            first_counter_buffer,
+            raw_buffers.len() as u32,
+            raw_buffers.as_ptr(),
+            offsets.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdBeginRenderPass` on the builder.
     #[inline]
     pub unsafe fn begin_render_pass<I>(
@@ -255,35 +322,7 @@ impl UnsafeCommandBufferBuilder {
 
         let raw_clear_values: SmallVec<[_; 12]> = clear_values
             .into_iter()
-            .map(|clear_value| match clear_value {
-                ClearValue::None => ash::vk::ClearValue {
-                    color: ash::vk::ClearColorValue { float32: [0.0; 4] },
-                },
-                ClearValue::Float(val) => ash::vk::ClearValue {
-                    color: ash::vk::ClearColorValue { float32: val },
-                },
-                ClearValue::Int(val) => ash::vk::ClearValue {
-                    color: ash::vk::ClearColorValue { int32: val },
-                },
-                ClearValue::Uint(val) => ash::vk::ClearValue {
-                    color: ash::vk::ClearColorValue { uint32: val },
-                },
-                ClearValue::Depth(val) => ash::vk::ClearValue {
-                    depth_stencil: ash::vk::ClearDepthStencilValue {
-                        depth: val,
-                        stencil: 0,
-                    },
-                },
-                ClearValue::Stencil(val) => ash::vk::ClearValue {
-                    depth_stencil: ash::vk::ClearDepthStencilValue {
-                        depth: 0.0,
-                        stencil: val,
-                    },
-                },
-                ClearValue::DepthStencil((depth, stencil)) => ash::vk::ClearValue {
-                    depth_stencil: ash::vk::ClearDepthStencilValue { depth, stencil },
-                },
-            })
+            .map(ash::vk::ClearValue::from)
             .collect();
 
         // TODO: allow customizing
@@ -314,6 +353,67 @@ impl UnsafeCommandBufferBuilder {
             .cmd_begin_render_pass(cmd, &begin, subpass_contents.into());
     }
 
+    /// Calls `vkCmdBeginRenderingKHR` on the builder.
+    ///
+    /// `render_area_extent` of `rendering_info` must not be `[0, 0]`.
+    #[inline]
+    pub unsafe fn begin_rendering(&mut self, rendering_info: &RenderingInfo) {
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+
+        let to_vulkan =
+            |attachment_info: &RenderingAttachmentInfo| ash::vk::RenderingAttachmentInfoKHR {
+                image_view: attachment_info.image_view.internal_object(),
+                image_layout: attachment_info.image_layout.into(),
+                load_op: attachment_info.load_op.into(),
+                store_op: attachment_info.store_op.into(),
+                clear_value: attachment_info.clear_value.into(),
+                ..Default::default()
+            };
+
+        let color_attachments: SmallVec<[_; 4]> = rendering_info
+            .color_attachments
+            .iter()
+            .map(|attachment_info| match attachment_info {
+                Some(attachment_info) => to_vulkan(attachment_info),
+                None => ash::vk::RenderingAttachmentInfoKHR {
+                    image_view: ash::vk::ImageView::null(),
+                    ..Default::default()
+                },
+            })
+            .collect();
+        let depth_attachment = rendering_info.depth_attachment.as_ref().map(to_vulkan);
+        let stencil_attachment = rendering_info.stencil_attachment.as_ref().map(to_vulkan);
+
+        let info = ash::vk::RenderingInfoKHR {
+            flags: ash::vk::RenderingFlagsKHR::empty(),
+            render_area: ash::vk::Rect2D {
+                offset: ash::vk::Offset2D {
+                    x: rendering_info.render_area_offset[0] as i32,
+                    y: rendering_info.render_area_offset[1] as i32,
+                },
+                extent: ash::vk::Extent2D {
+                    width: rendering_info.render_area_extent[0],
+                    height: rendering_info.render_area_extent[1],
+                },
+            },
+            layer_count: rendering_info.layer_count,
+            view_mask: rendering_info.view_mask,
+            color_attachment_count: color_attachments.len() as u32,
+            p_color_attachments: color_attachments.as_ptr(),
+            p_depth_attachment: depth_attachment
+                .as_ref()
+                .map_or(ptr::null(), |attachment| attachment),
+            p_stencil_attachment: stencil_attachment
+                .as_ref()
+                .map_or(ptr::null(), |attachment| attachment),
+            ..Default::default()
+        };
+
+        fns.khr_dynamic_rendering
+            .cmd_begin_rendering_khr(cmd, &info);
+    }
+
     /// Calls `vkCmdBindDescriptorSets` on the builder.
     ///
     /// Does nothing if the list of descriptor sets is empty, as it would be a no-op and isn't a
@@ -401,6 +501,61 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdBindPipeline` on the builder with a ray tracing pipeline.
+    #[inline]
+    pub unsafe fn bind_pipeline_ray_tracing(&mut self, pipeline: &RayTracingPipeline) {
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        fns.v1_0.cmd_bind_pipeline(
+            cmd,
+            ash::vk::PipelineBindPoint::RAY_TRACING_KHR,
+            pipeline.internal_object(),
+        );
+    }
+
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    ///
+    /// Does nothing if the list of buffers is empty, as it would be a no-op and isn't a valid
+    /// usage of the command anyway.
+    #[inline]
+    pub unsafe fn bind_transform_feedback_buffers<'a>(
+        &mut self,
+        first_binding: u32,
+        buffers: impl IntoIterator<Item = &'a dyn BufferAccess>,
+    ) {
+        debug_assert!(self.device().enabled_extensions().ext_transform_feedback);
+
+        let mut raw_buffers: SmallVec<[ash::vk::Buffer; 4]> = SmallVec::new();
+        let mut offsets: SmallVec<[DeviceSize; 4]> = SmallVec::new();
+        let mut sizes: SmallVec<[DeviceSize; 4]> = SmallVec::new();
+
+        for buffer in buffers {
+            let inner = buffer.inner();
+            debug_assert!(inner.buffer.usage().transform_feedback_buffer);
+            debug_assert_eq!(inner.offset % 4, 0);
+            raw_buffers.push(inner.buffer.internal_object());
+            offsets.push(inner.offset);
+            sizes.push(buffer.size());
+        }
+
+        if raw_buffers.is_empty() {
+            return;
+        }
+
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+
+        fns.ext_transform_feedback
+            .cmd_bind_transform_feedback_buffers_ext(
+                cmd,
+                first_binding,
+                raw_buffers.len() as u32,
+                raw_buffers.as_ptr(),
+                offsets.as_ptr(),
+                sizes.as_ptr(),
+            );
+    }
+
     /// Calls `vkCmdBindVertexBuffers` on the builder.
     ///
     /// Does nothing if the list of buffers is empty, as it would be a no-op and isn't a valid
@@ -687,6 +842,48 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder, to build a single
+    /// acceleration structure.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the scratch buffer or one of the geometry buffers doesn't have the
+    ///   `device_address` usage.
+    #[inline]
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo,
+        destination: &AccelerationStructure,
+        scratch_buffer: &dyn BufferAccess,
+    ) {
+        debug_assert_eq!(destination.ty(), info.geometries.ty());
+
+        let (geometries, ranges) = info
+            .geometries
+            .to_vulkan()
+            .expect("a geometry buffer doesn't have the device_address usage");
+        let info = ash::vk::AccelerationStructureBuildGeometryInfoKHR {
+            ty: info.geometries.ty().into(),
+            flags: info.flags.into(),
+            mode: ash::vk::BuildAccelerationStructureModeKHR::BUILD,
+            dst_acceleration_structure: destination.internal_object(),
+            geometry_count: geometries.len() as u32,
+            p_geometries: geometries.as_ptr(),
+            scratch_data: ash::vk::DeviceOrHostAddressKHR {
+                device_address: scratch_buffer
+                    .raw_device_address()
+                    .expect("the scratch buffer doesn't have the device_address usage")
+                    .get(),
+            },
+            ..Default::default()
+        };
+
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        fns.khr_acceleration_structure
+            .cmd_build_acceleration_structures_khr(cmd, 1, &info, &ranges.as_ptr());
+    }
+
     /// Calls `vkCmdClearAttachments` on the builder.
     ///
     /// Does nothing if the list of attachments or the list of rects is empty, as it would be a
@@ -1252,6 +1449,123 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdDrawIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect_count<B, C>(
+        &mut self,
+        buffer: &B,
+        count_buffer: &C,
+        max_draw_count: u32,
+        stride: u32,
+    ) where
+        B: ?Sized + BufferAccess,
+        C: ?Sized + BufferAccess,
+    {
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+
+        debug_assert!(
+            ((stride % 4) == 0)
+                && stride as usize >= mem::size_of::<ash::vk::DrawIndirectCommand>()
+        );
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage().indirect_buffer);
+
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner.buffer.usage().indirect_buffer);
+        debug_assert_eq!(count_inner.offset % 4, 0);
+
+        if self.device().enabled_extensions().khr_draw_indirect_count {
+            fns.khr_draw_indirect_count.cmd_draw_indirect_count_khr(
+                cmd,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        } else {
+            debug_assert!(self.device().enabled_features().draw_indirect_count);
+            fns.v1_2.cmd_draw_indirect_count(
+                cmd,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        }
+    }
+
+    /// Calls `vkCmdDrawIndexedIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect_count<B, C>(
+        &mut self,
+        buffer: &B,
+        count_buffer: &C,
+        max_draw_count: u32,
+        stride: u32,
+    ) where
+        B: ?Sized + BufferAccess,
+        C: ?Sized + BufferAccess,
+    {
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+
+        debug_assert!(
+            ((stride % 4) == 0)
+                && stride as usize >= mem::size_of::<ash::vk::DrawIndexedIndirectCommand>()
+        );
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage().indirect_buffer);
+
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner.buffer.usage().indirect_buffer);
+        debug_assert_eq!(count_inner.offset % 4, 0);
+
+        if self.device().enabled_extensions().khr_draw_indirect_count {
+            fns.khr_draw_indirect_count
+                .cmd_draw_indexed_indirect_count_khr(
+                    cmd,
+                    inner.buffer.internal_object(),
+                    inner.offset,
+                    count_inner.buffer.internal_object(),
+                    count_inner.offset,
+                    max_draw_count,
+                    stride,
+                );
+        } else {
+            debug_assert!(self.device().enabled_features().draw_indirect_count);
+            fns.v1_2.cmd_draw_indexed_indirect_count(
+                cmd,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        }
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        debug_assert!(self.device().enabled_extensions().ext_conditional_rendering);
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext(cmd);
+    }
+
     /// Calls `vkCmdEndQuery` on the builder.
     #[inline]
     pub unsafe fn end_query(&mut self, query: Query) {
@@ -1269,6 +1583,39 @@ impl UnsafeCommandBufferBuilder {
         fns.v1_0.cmd_end_render_pass(cmd);
     }
 
+    /// Calls `vkCmdEndRenderingKHR` on the builder.
+    #[inline]
+    pub unsafe fn end_rendering(&mut self) {
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        fns.khr_dynamic_rendering.cmd_end_rendering_khr(cmd);
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    ///
+    /// Each buffer in `counter_buffers` receives the counter for the transform feedback buffer
+    /// with the same binding, starting with `first_counter_buffer`.
+    #[inline]
+    pub unsafe fn end_transform_feedback<'a>(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = &'a dyn BufferAccess>,
+    ) {
+        debug_assert!(self.device().enabled_extensions().ext_transform_feedback);
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+
+        let (raw_buffers, offsets) = transform_feedback_counter_buffers(counter_buffers);
+
+        fns.ext_transform_feedback.cmd_end_transform_feedback_ext(
+            cmd,
+            first_counter_buffer,
+            raw_buffers.len() as u32,
+            raw_buffers.as_ptr(),
+            offsets.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdExecuteCommands` on the builder.
     ///
     /// Does nothing if the list of command buffers is empty, as it would be a no-op and isn't a
@@ -1335,18 +1682,48 @@ impl UnsafeCommandBufferBuilder {
         debug_assert!(!command.src_stage_mask.is_empty());
         debug_assert!(!command.dst_stage_mask.is_empty());
 
-        fns.v1_0.cmd_pipeline_barrier(
-            cmd,
-            command.src_stage_mask,
-            command.dst_stage_mask,
-            command.dependency_flags,
-            command.memory_barriers.len() as u32,
-            command.memory_barriers.as_ptr(),
-            command.buffer_barriers.len() as u32,
-            command.buffer_barriers.as_ptr(),
-            command.image_barriers.len() as u32,
-            command.image_barriers.as_ptr(),
-        );
+        if self.device().enabled_features().synchronization2 {
+            let dependency_info = command.to_vulkan_dependency_info();
+            fns.khr_synchronization2
+                .cmd_pipeline_barrier2_khr(cmd, &dependency_info);
+        } else {
+            let (memory_barriers, buffer_barriers, image_barriers) = command.to_vulkan_legacy();
+            fns.v1_0.cmd_pipeline_barrier(
+                cmd,
+                command.src_stage_mask,
+                command.dst_stage_mask,
+                command.dependency_flags,
+                memory_barriers.len() as u32,
+                memory_barriers.as_ptr(),
+                buffer_barriers.len() as u32,
+                buffer_barriers.as_ptr(),
+                image_barriers.len() as u32,
+                image_barriers.as_ptr(),
+            );
+        }
+    }
+
+    /// Calls `vkCmdPipelineBarrier2KHR` on the builder.
+    ///
+    /// # Safety
+    ///
+    /// - The [`synchronization2`](crate::device::Features::synchronization2) feature must be
+    ///   enabled on the device.
+    /// - The buffers and images of the barriers must be alive for at least as long as the command
+    ///   buffer.
+    /// - Queue ownership transfers and image layout transitions must be correct.
+    /// - The accesses of each barrier must be compatible with its stages.
+    #[inline]
+    pub unsafe fn pipeline_barrier2(&mut self, dependency_info: &DependencyInfo) {
+        debug_assert!(self.device().enabled_features().synchronization2);
+
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        let command =
+            UnsafeCommandBufferBuilderPipelineBarrier::from_dependency_info(dependency_info);
+        let dependency_info = command.to_vulkan_dependency_info();
+        fns.khr_synchronization2
+            .cmd_pipeline_barrier2_khr(cmd, &dependency_info);
     }
 
     /// Calls `vkCmdPushConstants` on the builder.
@@ -1393,7 +1770,7 @@ impl UnsafeCommandBufferBuilder {
     ) {
         debug_assert!(self.device().enabled_extensions().khr_push_descriptor);
 
-        let (infos, mut writes): (SmallVec<[_; 8]>, SmallVec<[_; 8]>) = descriptor_writes
+        let (mut infos, mut writes): (SmallVec<[_; 8]>, SmallVec<[_; 8]>) = descriptor_writes
             .into_iter()
             .map(|write| {
                 let descriptor = pipeline_layout.descriptor_set_layouts()[set_num as usize]
@@ -1412,23 +1789,8 @@ impl UnsafeCommandBufferBuilder {
         }
 
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
-            match info {
-                DescriptorWriteInfo::Image(info) => {
-                    write.descriptor_count = info.len() as u32;
-                    write.p_image_info = info.as_ptr();
-                }
-                DescriptorWriteInfo::Buffer(info) => {
-                    write.descriptor_count = info.len() as u32;
-                    write.p_buffer_info = info.as_ptr();
-                }
-                DescriptorWriteInfo::BufferView(info) => {
-                    write.descriptor_count = info.len() as u32;
-                    write.p_texel_buffer_view = info.as_ptr();
-                }
-            }
-
-            debug_assert!(write.descriptor_count != 0);
+        for (info, write) in infos.iter_mut().zip(writes.iter_mut()) {
+            info.set_info(write);
         }
 
         let fns = self.device().fns();
@@ -1666,6 +2028,53 @@ impl UnsafeCommandBufferBuilder {
             .cmd_set_event(cmd, event.internal_object(), stages.into());
     }
 
+    /// Calls `vkCmdSetEvent2KHR` on the builder.
+    ///
+    /// The source stages and accesses of the barriers in `dependency_info` define which
+    /// operations must complete before the event is signaled. The same dependency info must be
+    /// passed to [`wait_events2`](Self::wait_events2) when waiting for the event.
+    ///
+    /// # Safety
+    ///
+    /// - The [`synchronization2`](crate::device::Features::synchronization2) feature must be
+    ///   enabled on the device.
+    /// - Same as [`pipeline_barrier2`](Self::pipeline_barrier2).
+    #[inline]
+    pub unsafe fn set_event2(&mut self, event: &Event, dependency_info: &DependencyInfo) {
+        debug_assert!(self.device().enabled_features().synchronization2);
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        let command =
+            UnsafeCommandBufferBuilderPipelineBarrier::from_dependency_info(dependency_info);
+        let dependency_info = command.to_vulkan_dependency_info();
+        fns.khr_synchronization2
+            .cmd_set_event2_khr(cmd, event.internal_object(), &dependency_info);
+    }
+
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) {
+        debug_assert!(self.device().enabled_extensions().khr_fragment_shading_rate);
+        debug_assert!(
+            self.device()
+                .enabled_features()
+                .pipeline_fragment_shading_rate
+        );
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        let fragment_size = ash::vk::Extent2D {
+            width: fragment_size[0],
+            height: fragment_size[1],
+        };
+        let combiner_ops = [combiner_ops[0].into(), combiner_ops[1].into()];
+        fns.khr_fragment_shading_rate
+            .cmd_set_fragment_shading_rate_khr(cmd, &fragment_size, &combiner_ops);
+    }
+
     /// Calls `vkCmdSetFrontFaceEXT` on the builder.
     #[inline]
     pub unsafe fn set_front_face(&mut self, face: FrontFace) {
@@ -2011,6 +2420,29 @@ impl UnsafeCommandBufferBuilder {
             .cmd_set_viewport_with_count_ext(cmd, viewports.len() as u32, viewports.as_ptr());
     }
 
+    /// Calls `vkCmdTraceRaysKHR` on the builder.
+    #[inline]
+    pub unsafe fn trace_rays(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) {
+        let [raygen, miss, hit, callable] = shader_binding_table.regions();
+
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        fns.khr_ray_tracing_pipeline.cmd_trace_rays_khr(
+            cmd,
+            raygen,
+            miss,
+            hit,
+            callable,
+            dimensions[0],
+            dimensions[1],
+            dimensions[2],
+        );
+    }
+
     /// Calls `vkCmdUpdateBuffer` on the builder.
     #[inline]
     pub unsafe fn update_buffer<B, D>(&mut self, buffer: &B, data: &D)
@@ -2045,6 +2477,91 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdWaitEvents` on the builder.
+    ///
+    /// The stage masks and barriers of `command` are used as the source and destination scopes of
+    /// the wait. Its dependency flags are ignored.
+    #[inline]
+    pub unsafe fn wait_events<'a>(
+        &mut self,
+        events: impl IntoIterator<Item = &'a Event>,
+        command: &UnsafeCommandBufferBuilderPipelineBarrier,
+    ) {
+        let events: SmallVec<[_; 4]> = events.into_iter().map(|e| e.internal_object()).collect();
+
+        if events.is_empty() {
+            return;
+        }
+
+        debug_assert!(!command.src_stage_mask.is_empty());
+        debug_assert!(!command.dst_stage_mask.is_empty());
+
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        let (memory_barriers, buffer_barriers, image_barriers) = command.to_vulkan_legacy();
+        fns.v1_0.cmd_wait_events(
+            cmd,
+            events.len() as u32,
+            events.as_ptr(),
+            command.src_stage_mask,
+            command.dst_stage_mask,
+            memory_barriers.len() as u32,
+            memory_barriers.as_ptr(),
+            buffer_barriers.len() as u32,
+            buffer_barriers.as_ptr(),
+            image_barriers.len() as u32,
+            image_barriers.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdWaitEvents2KHR` on the builder.
+    ///
+    /// Each event is given with its dependency info, which must be the same as the one that was
+    /// passed to [`set_event2`](Self::set_event2) for that event.
+    ///
+    /// # Safety
+    ///
+    /// - The [`synchronization2`](crate::device::Features::synchronization2) feature must be
+    ///   enabled on the device.
+    /// - The events must have been set with `set_event2`, using the same dependency info.
+    /// - Same as [`pipeline_barrier2`](Self::pipeline_barrier2).
+    #[inline]
+    pub unsafe fn wait_events2<'a>(
+        &mut self,
+        events: impl IntoIterator<Item = (&'a Event, &'a DependencyInfo)>,
+    ) {
+        let (events, commands): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) = events
+            .into_iter()
+            .map(|(event, dependency_info)| {
+                (
+                    event.internal_object(),
+                    UnsafeCommandBufferBuilderPipelineBarrier::from_dependency_info(
+                        dependency_info,
+                    ),
+                )
+            })
+            .unzip();
+
+        if events.is_empty() {
+            return;
+        }
+
+        debug_assert!(self.device().enabled_features().synchronization2);
+
+        let fns = self.device().fns();
+        let cmd = self.internal_object();
+        let dependency_infos: SmallVec<[_; 4]> = commands
+            .iter()
+            .map(|command| command.to_vulkan_dependency_info())
+            .collect();
+        fns.khr_synchronization2.cmd_wait_events2_khr(
+            cmd,
+            events.len() as u32,
+            events.as_ptr(),
+            dependency_infos.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdWriteTimestamp` on the builder.
     #[inline]
     pub unsafe fn write_timestamp(&mut self, query: Query, stage: PipelineStage) {
@@ -2123,6 +2640,21 @@ unsafe impl VulkanObject for UnsafeCommandBufferBuilder {
     }
 }
 
+// Returns the raw handles and offsets of a list of transform feedback counter buffers.
+fn transform_feedback_counter_buffers<'a>(
+    counter_buffers: impl IntoIterator<Item = &'a dyn BufferAccess>,
+) -> (SmallVec<[ash::vk::Buffer; 4]>, SmallVec<[DeviceSize; 4]>) {
+    counter_buffers
+        .into_iter()
+        .map(|buffer| {
+            let inner = buffer.inner();
+            debug_assert!(inner.buffer.usage().transform_feedback_counter_buffer);
+            debug_assert_eq!(inner.offset % 4, 0);
+            (inner.buffer.internal_object(), inner.offset)
+        })
+        .unzip()
+}
+
 /// Prototype for a `vkCmdBindVertexBuffers`.
 pub struct UnsafeCommandBufferBuilderBindVertexBuffer {
     // Raw handles of the buffers to bind.
@@ -2257,6 +2789,11 @@ pub struct UnsafeCommandBufferBuilderImageBlit {
 /// Please take a look at the Vulkan specifications for more information. Pipeline barriers are a
 /// complex topic and explaining them in this documentation would be redundant.
 ///
+/// If the [`synchronization2`](crate::device::Features::synchronization2) feature is enabled on
+/// the device, the barrier is recorded with `vkCmdPipelineBarrier2KHR`, and each barrier only
+/// depends on the stages that were given for it. Otherwise, the stages of all the barriers are
+/// combined into a single source and destination stage mask.
+///
 /// > **Note**: We use a builder-like API here so that users can pass multiple buffers or images of
 /// > multiple different types. Doing so with a single function would be very tedious in terms of
 /// > API.
@@ -2264,9 +2801,9 @@ pub struct UnsafeCommandBufferBuilderPipelineBarrier {
     src_stage_mask: ash::vk::PipelineStageFlags,
     dst_stage_mask: ash::vk::PipelineStageFlags,
     dependency_flags: ash::vk::DependencyFlags,
-    memory_barriers: SmallVec<[ash::vk::MemoryBarrier; 2]>,
-    buffer_barriers: SmallVec<[ash::vk::BufferMemoryBarrier; 8]>,
-    image_barriers: SmallVec<[ash::vk::ImageMemoryBarrier; 8]>,
+    memory_barriers: SmallVec<[ash::vk::MemoryBarrier2KHR; 2]>,
+    buffer_barriers: SmallVec<[ash::vk::BufferMemoryBarrier2KHR; 8]>,
+    image_barriers: SmallVec<[ash::vk::ImageMemoryBarrier2KHR; 8]>,
 }
 
 impl UnsafeCommandBufferBuilderPipelineBarrier {
@@ -2319,6 +2856,19 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         destination: PipelineStages,
         by_region: bool,
     ) {
+        self.add_stages(source, destination, by_region);
+
+        // With synchronization2, an execution dependency is a memory barrier without accesses.
+        self.memory_barriers.push(ash::vk::MemoryBarrier2KHR {
+            src_stage_mask: source.into(),
+            dst_stage_mask: destination.into(),
+            ..Default::default()
+        });
+    }
+
+    // Adds the stages of a barrier to the combined stage masks.
+    #[inline]
+    fn add_stages(&mut self, source: PipelineStages, destination: PipelineStages, by_region: bool) {
         if !by_region {
             self.dependency_flags = ash::vk::DependencyFlags::empty();
         }
@@ -2351,10 +2901,12 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         debug_assert!(source_access.is_compatible_with(&source_stage));
         debug_assert!(destination_access.is_compatible_with(&destination_stage));
 
-        self.add_execution_dependency(source_stage, destination_stage, by_region);
+        self.add_stages(source_stage, destination_stage, by_region);
 
-        self.memory_barriers.push(ash::vk::MemoryBarrier {
+        self.memory_barriers.push(ash::vk::MemoryBarrier2KHR {
+            src_stage_mask: source_stage.into(),
             src_access_mask: source_access.into(),
+            dst_stage_mask: destination_stage.into(),
             dst_access_mask: destination_access.into(),
             ..Default::default()
         });
@@ -2392,7 +2944,7 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         debug_assert!(source_access.is_compatible_with(&source_stage));
         debug_assert!(destination_access.is_compatible_with(&destination_stage));
 
-        self.add_execution_dependency(source_stage, destination_stage, by_region);
+        self.add_stages(source_stage, destination_stage, by_region);
 
         debug_assert!(size <= buffer.size());
         let BufferInner {
@@ -2407,8 +2959,10 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
             (ash::vk::QUEUE_FAMILY_IGNORED, ash::vk::QUEUE_FAMILY_IGNORED)
         };
 
-        self.buffer_barriers.push(ash::vk::BufferMemoryBarrier {
+        self.buffer_barriers.push(ash::vk::BufferMemoryBarrier2KHR {
+            src_stage_mask: source_stage.into(),
             src_access_mask: source_access.into(),
+            dst_stage_mask: destination_stage.into(),
             dst_access_mask: destination_access.into(),
             src_queue_family_index: src_queue,
             dst_queue_family_index: dest_queue,
@@ -2455,7 +3009,7 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         debug_assert!(source_access.is_compatible_with(&source_stage));
         debug_assert!(destination_access.is_compatible_with(&destination_stage));
 
-        self.add_execution_dependency(source_stage, destination_stage, by_region);
+        self.add_stages(source_stage, destination_stage, by_region);
 
         debug_assert_ne!(new_layout, ImageLayout::Undefined);
         debug_assert_ne!(new_layout, ImageLayout::Preinitialized);
@@ -2475,8 +3029,10 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         let aspects = image.format().aspects();
         let image = image.inner();
 
-        self.image_barriers.push(ash::vk::ImageMemoryBarrier {
+        self.image_barriers.push(ash::vk::ImageMemoryBarrier2KHR {
+            src_stage_mask: source_stage.into(),
             src_access_mask: source_access.into(),
+            dst_stage_mask: destination_stage.into(),
             dst_access_mask: destination_access.into(),
             old_layout: current_layout.into(),
             new_layout: new_layout.into(),
@@ -2493,6 +3049,179 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
             ..Default::default()
         });
     }
+
+    // Builds the barriers of a `DependencyInfo`. Only the per-barrier stages are filled in, so
+    // the result can only be recorded with the commands of `synchronization2`.
+    fn from_dependency_info(dependency_info: &DependencyInfo) -> Self {
+        let DependencyInfo {
+            memory_barriers,
+            buffer_memory_barriers,
+            image_memory_barriers,
+            by_region,
+        } = dependency_info;
+
+        let queue_family_indices = |queue_family_transfer: Option<(u32, u32)>| {
+            queue_family_transfer
+                .unwrap_or((ash::vk::QUEUE_FAMILY_IGNORED, ash::vk::QUEUE_FAMILY_IGNORED))
+        };
+
+        let memory_barriers = memory_barriers
+            .iter()
+            .map(|barrier| ash::vk::MemoryBarrier2KHR {
+                src_stage_mask: barrier.source_stages.into(),
+                src_access_mask: barrier.source_access.into(),
+                dst_stage_mask: barrier.destination_stages.into(),
+                dst_access_mask: barrier.destination_access.into(),
+                ..Default::default()
+            })
+            .collect();
+        let buffer_barriers = buffer_memory_barriers
+            .iter()
+            .map(|barrier| {
+                debug_assert!(barrier.range.start < barrier.range.end);
+                debug_assert!(barrier.range.end <= barrier.buffer.size());
+
+                let BufferInner { buffer, offset } = barrier.buffer.inner();
+                let (src_queue, dest_queue) = queue_family_indices(barrier.queue_family_transfer);
+
+                ash::vk::BufferMemoryBarrier2KHR {
+                    src_stage_mask: barrier.source_stages.into(),
+                    src_access_mask: barrier.source_access.into(),
+                    dst_stage_mask: barrier.destination_stages.into(),
+                    dst_access_mask: barrier.destination_access.into(),
+                    src_queue_family_index: src_queue,
+                    dst_queue_family_index: dest_queue,
+                    buffer: buffer.internal_object(),
+                    offset: offset + barrier.range.start,
+                    size: barrier.range.end - barrier.range.start,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let image_barriers = image_memory_barriers
+            .iter()
+            .map(|barrier| {
+                debug_assert!(barrier.mip_levels.start < barrier.mip_levels.end);
+                debug_assert!(barrier.mip_levels.end <= barrier.image.mip_levels());
+                debug_assert!(barrier.array_layers.start < barrier.array_layers.end);
+                debug_assert!(
+                    barrier.array_layers.end <= barrier.image.dimensions().array_layers()
+                );
+
+                // TODO: Let user choose
+                let aspects = barrier.image.format().aspects();
+                let image = barrier.image.inner();
+                let (src_queue, dest_queue) = queue_family_indices(barrier.queue_family_transfer);
+
+                ash::vk::ImageMemoryBarrier2KHR {
+                    src_stage_mask: barrier.source_stages.into(),
+                    src_access_mask: barrier.source_access.into(),
+                    dst_stage_mask: barrier.destination_stages.into(),
+                    dst_access_mask: barrier.destination_access.into(),
+                    old_layout: barrier.old_layout.into(),
+                    new_layout: barrier.new_layout.into(),
+                    src_queue_family_index: src_queue,
+                    dst_queue_family_index: dest_queue,
+                    image: image.image.internal_object(),
+                    subresource_range: ash::vk::ImageSubresourceRange {
+                        aspect_mask: aspects.into(),
+                        base_mip_level: barrier.mip_levels.start + image.first_mipmap_level as u32,
+                        level_count: barrier.mip_levels.end - barrier.mip_levels.start,
+                        base_array_layer: barrier.array_layers.start + image.first_layer as u32,
+                        layer_count: barrier.array_layers.end - barrier.array_layers.start,
+                    },
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        UnsafeCommandBufferBuilderPipelineBarrier {
+            src_stage_mask: ash::vk::PipelineStageFlags::empty(),
+            dst_stage_mask: ash::vk::PipelineStageFlags::empty(),
+            dependency_flags: if *by_region {
+                ash::vk::DependencyFlags::BY_REGION
+            } else {
+                ash::vk::DependencyFlags::empty()
+            },
+            memory_barriers,
+            buffer_barriers,
+            image_barriers,
+        }
+    }
+
+    // Returns the barriers as a `DependencyInfoKHR`. The returned value borrows `self`.
+    #[inline]
+    fn to_vulkan_dependency_info(&self) -> ash::vk::DependencyInfoKHR {
+        ash::vk::DependencyInfoKHR {
+            dependency_flags: self.dependency_flags,
+            memory_barrier_count: self.memory_barriers.len() as u32,
+            p_memory_barriers: self.memory_barriers.as_ptr(),
+            buffer_memory_barrier_count: self.buffer_barriers.len() as u32,
+            p_buffer_memory_barriers: self.buffer_barriers.as_ptr(),
+            image_memory_barrier_count: self.image_barriers.len() as u32,
+            p_image_memory_barriers: self.image_barriers.as_ptr(),
+            ..Default::default()
+        }
+    }
+
+    // Returns the barriers in their legacy form, without the per-barrier stages. The barriers
+    // that only contain an execution dependency are left out, as the combined stage masks
+    // already cover them.
+    fn to_vulkan_legacy(
+        &self,
+    ) -> (
+        SmallVec<[ash::vk::MemoryBarrier; 2]>,
+        SmallVec<[ash::vk::BufferMemoryBarrier; 8]>,
+        SmallVec<[ash::vk::ImageMemoryBarrier; 8]>,
+    ) {
+        // Only legacy access flags are ever added, so the values fit in 32 bits.
+        let access =
+            |flags: ash::vk::AccessFlags2KHR| ash::vk::AccessFlags::from_raw(flags.as_raw() as u32);
+
+        let memory_barriers = self
+            .memory_barriers
+            .iter()
+            .filter(|barrier| {
+                !barrier.src_access_mask.is_empty() || !barrier.dst_access_mask.is_empty()
+            })
+            .map(|barrier| ash::vk::MemoryBarrier {
+                src_access_mask: access(barrier.src_access_mask),
+                dst_access_mask: access(barrier.dst_access_mask),
+                ..Default::default()
+            })
+            .collect();
+        let buffer_barriers = self
+            .buffer_barriers
+            .iter()
+            .map(|barrier| ash::vk::BufferMemoryBarrier {
+                src_access_mask: access(barrier.src_access_mask),
+                dst_access_mask: access(barrier.dst_access_mask),
+                src_queue_family_index: barrier.src_queue_family_index,
+                dst_queue_family_index: barrier.dst_queue_family_index,
+                buffer: barrier.buffer,
+                offset: barrier.offset,
+                size: barrier.size,
+                ..Default::default()
+            })
+            .collect();
+        let image_barriers = self
+            .image_barriers
+            .iter()
+            .map(|barrier| ash::vk::ImageMemoryBarrier {
+                src_access_mask: access(barrier.src_access_mask),
+                dst_access_mask: access(barrier.dst_access_mask),
+                old_layout: barrier.old_layout,
+                new_layout: barrier.new_layout,
+                src_queue_family_index: barrier.src_queue_family_index,
+                dst_queue_family_index: barrier.dst_queue_family_index,
+                image: barrier.image,
+                subresource_range: barrier.subresource_range,
+                ..Default::default()
+            })
+            .collect();
+
+        (memory_barriers, buffer_barriers, image_barriers)
+    }
 }
 
 /// Command buffer that has been built.
@@ -2505,6 +3234,7 @@ pub struct UnsafeCommandBuffer {
     command_buffer: ash::vk::CommandBuffer,
     device: Arc<Device>,
     usage: CommandBufferUsage,
+    protected: bool,
 }
 
 impl UnsafeCommandBuffer {
@@ -2512,6 +3242,13 @@ impl UnsafeCommandBuffer {
     pub fn usage(&self) -> CommandBufferUsage {
         self.usage
     }
+
+    /// Returns true if the command buffer was allocated from a protected pool, and can only be
+    /// executed with a protected submission.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandBuffer {
@@ -2529,3 +3266,207 @@ unsafe impl VulkanObject for UnsafeCommandBuffer {
         self.command_buffer
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::UnsafeCommandBufferBuilderPipelineBarrier;
+    use crate::buffer::BufferAccess;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use crate::sync::AccessFlags;
+    use crate::sync::AccessFlags2;
+    use crate::sync::BufferMemoryBarrier2;
+    use crate::sync::DependencyInfo;
+    use crate::sync::PipelineStages;
+    use crate::sync::PipelineStages2;
+
+    #[test]
+    fn barrier_stages_per_barrier() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage::all(),
+            false,
+            [0_u32; 4].iter().copied(),
+        )
+        .unwrap();
+
+        let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+
+        unsafe {
+            barrier.add_buffer_memory_barrier(
+                &*buffer,
+                PipelineStages {
+                    transfer: true,
+                    ..PipelineStages::none()
+                },
+                AccessFlags {
+                    transfer_write: true,
+                    ..AccessFlags::none()
+                },
+                PipelineStages {
+                    transfer: true,
+                    ..PipelineStages::none()
+                },
+                AccessFlags {
+                    transfer_read: true,
+                    ..AccessFlags::none()
+                },
+                true,
+                None,
+                0,
+                buffer.size(),
+            );
+            barrier.add_memory_barrier(
+                PipelineStages {
+                    compute_shader: true,
+                    ..PipelineStages::none()
+                },
+                AccessFlags {
+                    shader_write: true,
+                    ..AccessFlags::none()
+                },
+                PipelineStages {
+                    vertex_input: true,
+                    ..PipelineStages::none()
+                },
+                AccessFlags {
+                    vertex_attribute_read: true,
+                    ..AccessFlags::none()
+                },
+                true,
+            );
+        }
+
+        // With synchronization2, the buffer barrier only waits for the transfer stage.
+        let dependency_info = barrier.to_vulkan_dependency_info();
+        assert_eq!(dependency_info.memory_barrier_count, 1);
+        assert_eq!(dependency_info.buffer_memory_barrier_count, 1);
+        assert_eq!(
+            barrier.buffer_barriers[0].src_stage_mask,
+            ash::vk::PipelineStageFlags2KHR::ALL_TRANSFER
+        );
+        assert_eq!(
+            barrier.buffer_barriers[0].dst_stage_mask,
+            ash::vk::PipelineStageFlags2KHR::ALL_TRANSFER
+        );
+        assert_eq!(
+            barrier.memory_barriers[0].src_stage_mask,
+            ash::vk::PipelineStageFlags2KHR::COMPUTE_SHADER
+        );
+        assert_eq!(
+            barrier.memory_barriers[0].dst_stage_mask,
+            ash::vk::PipelineStageFlags2KHR::VERTEX_INPUT
+        );
+
+        // Without it, every barrier waits for the combined stages.
+        assert_eq!(
+            barrier.src_stage_mask,
+            ash::vk::PipelineStageFlags::TRANSFER | ash::vk::PipelineStageFlags::COMPUTE_SHADER
+        );
+        assert_eq!(
+            barrier.dst_stage_mask,
+            ash::vk::PipelineStageFlags::TRANSFER | ash::vk::PipelineStageFlags::VERTEX_INPUT
+        );
+
+        let (memory_barriers, buffer_barriers, image_barriers) = barrier.to_vulkan_legacy();
+        assert_eq!(memory_barriers.len(), 1);
+        assert_eq!(
+            memory_barriers[0].src_access_mask,
+            ash::vk::AccessFlags::SHADER_WRITE
+        );
+        assert_eq!(buffer_barriers.len(), 1);
+        assert_eq!(
+            buffer_barriers[0].dst_access_mask,
+            ash::vk::AccessFlags::TRANSFER_READ
+        );
+        assert!(image_barriers.is_empty());
+    }
+
+    #[test]
+    fn execution_dependency_legacy() {
+        let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+
+        unsafe {
+            barrier.add_execution_dependency(
+                PipelineStages {
+                    transfer: true,
+                    ..PipelineStages::none()
+                },
+                PipelineStages {
+                    fragment_shader: true,
+                    ..PipelineStages::none()
+                },
+                false,
+            );
+        }
+
+        // The execution dependency is a memory barrier without accesses with synchronization2,
+        // and is only kept in the stage masks without it.
+        assert_eq!(barrier.to_vulkan_dependency_info().memory_barrier_count, 1);
+        assert!(barrier.to_vulkan_legacy().0.is_empty());
+        assert_eq!(barrier.dependency_flags, ash::vk::DependencyFlags::empty());
+    }
+
+    #[test]
+    fn from_dependency_info() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage::all(),
+            false,
+            [0_u32; 4].iter().copied(),
+        )
+        .unwrap();
+
+        let barrier =
+            UnsafeCommandBufferBuilderPipelineBarrier::from_dependency_info(&DependencyInfo {
+                buffer_memory_barriers: vec![BufferMemoryBarrier2 {
+                    source_stages: PipelineStages2 {
+                        copy: true,
+                        ..PipelineStages2::none()
+                    },
+                    source_access: AccessFlags2 {
+                        transfer_write: true,
+                        ..AccessFlags2::none()
+                    },
+                    destination_stages: PipelineStages2 {
+                        copy: true,
+                        ..PipelineStages2::none()
+                    },
+                    destination_access: AccessFlags2 {
+                        transfer_read: true,
+                        ..AccessFlags2::none()
+                    },
+                    range: 4..12,
+                    ..BufferMemoryBarrier2::buffer(buffer.clone())
+                }],
+                ..Default::default()
+            });
+
+        // The stages that only exist in synchronization2 are kept, and the range is relative to
+        // the start of the buffer.
+        let buffer_barrier = &barrier.buffer_barriers[0];
+        assert_eq!(
+            buffer_barrier.src_stage_mask,
+            ash::vk::PipelineStageFlags2KHR::COPY
+        );
+        assert_eq!(
+            buffer_barrier.src_access_mask,
+            ash::vk::AccessFlags2KHR::TRANSFER_WRITE
+        );
+        assert_eq!(
+            buffer_barrier.dst_stage_mask,
+            ash::vk::PipelineStageFlags2KHR::COPY
+        );
+        assert_eq!(
+            buffer_barrier.dst_access_mask,
+            ash::vk::AccessFlags2KHR::TRANSFER_READ
+        );
+        assert_eq!(buffer_barrier.offset, buffer.inner().offset + 4);
+        assert_eq!(buffer_barrier.size, 8);
+        assert_eq!(barrier.dependency_flags, ash::vk::DependencyFlags::empty());
+    }
+}
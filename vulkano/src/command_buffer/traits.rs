@@ -129,6 +129,11 @@ pub unsafe trait PrimaryCommandBuffer: DeviceOwned + Send + Sync {
             assert!(future.queue().unwrap().is_same(&queue));
         }
 
+        // VUID-vkQueueSubmit-queue-06448
+        if self.inner().is_protected() && !queue.is_protected() {
+            return Err(CommandBufferExecError::ProtectedQueueRequired);
+        }
+
         self.lock_submit(&future, &queue)?;
 
         Ok(CommandBufferExecFuture {
@@ -330,20 +335,32 @@ where
     // Implementation of `build_submission`. Doesn't check whenever the future was already flushed.
     // You must make sure to not submit same command buffer multiple times.
     unsafe fn build_submission_impl(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        let protected = self.command_buffer.inner().is_protected();
+
         Ok(match self.previous.build_submission()? {
             SubmitAnyBuilder::Empty => {
                 let mut builder = SubmitCommandBufferBuilder::new();
                 builder.add_command_buffer(self.command_buffer.inner());
+                builder.set_protected(protected);
                 SubmitAnyBuilder::CommandBuffer(builder)
             }
             SubmitAnyBuilder::SemaphoresWait(sem) => {
                 let mut builder: SubmitCommandBufferBuilder = sem.into();
                 builder.add_command_buffer(self.command_buffer.inner());
+                builder.set_protected(protected);
                 SubmitAnyBuilder::CommandBuffer(builder)
             }
             SubmitAnyBuilder::CommandBuffer(mut builder) => {
+                // A protected and an unprotected command buffer can't be part of the same
+                // submission, so the previous one is submitted first.
+                if builder.num_command_buffers() > 0 && builder.is_protected() != protected {
+                    builder.submit(&self.queue)?;
+                    builder = SubmitCommandBufferBuilder::new();
+                }
+
                 // FIXME: add pipeline barrier
                 builder.add_command_buffer(self.command_buffer.inner());
+                builder.set_protected(protected);
                 SubmitAnyBuilder::CommandBuffer(builder)
             }
             SubmitAnyBuilder::QueuePresent(_) | SubmitAnyBuilder::BindSparse(_) => {
@@ -506,6 +523,9 @@ pub enum CommandBufferExecError {
     /// The command buffer or one of the secondary command buffers it executes is already in use by
     /// the GPU and was not created with the "concurrent" flag.
     ExclusiveAlreadyInUse,
+
+    /// The command buffer is protected, but the queue is not a protected queue.
+    ProtectedQueueRequired,
     // TODO: missing entries (eg. wrong queue family, secondary command buffer)
 }
 
@@ -537,6 +557,9 @@ impl fmt::Display for CommandBufferExecError {
                     "the command buffer or one of the secondary command buffers it executes is \
                  already in use was not created with the \"concurrent\" flag"
                 }
+                CommandBufferExecError::ProtectedQueueRequired => {
+                    "the command buffer is protected, but the queue is not a protected queue"
+                }
             }
         )
     }
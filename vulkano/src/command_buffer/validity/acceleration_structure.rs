@@ -0,0 +1,459 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use crate::acceleration_structure::AccelerationStructure;
+use crate::acceleration_structure::AccelerationStructureBuildGeometryInfo;
+use crate::acceleration_structure::AccelerationStructureGeometries;
+use crate::acceleration_structure::AccelerationStructureType;
+use crate::buffer::BufferAccess;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::format::Format;
+use crate::pipeline::graphics::input_assembly::IndexType;
+use crate::DeviceSize;
+use crate::VulkanObject;
+
+/// Checks whether a build acceleration structure command is valid.
+///
+/// # Panic
+///
+/// - Panics if the destination, the scratch buffer or one of the geometry buffers was not created
+///   with `device`.
+///
+pub fn check_build_acceleration_structure(
+    device: &Device,
+    info: &AccelerationStructureBuildGeometryInfo,
+    destination: &AccelerationStructure,
+    scratch_buffer: &dyn BufferAccess,
+) -> Result<(), CheckBuildAccelerationStructureError> {
+    assert_eq!(
+        destination.device().internal_object(),
+        device.internal_object()
+    );
+    assert_eq!(
+        scratch_buffer.device().internal_object(),
+        device.internal_object()
+    );
+
+    if destination.ty() != info.geometries.ty() {
+        return Err(
+            CheckBuildAccelerationStructureError::DestinationTypeMismatch {
+                destination: destination.ty(),
+                geometries: info.geometries.ty(),
+            },
+        );
+    }
+
+    for (geometry_index, buffer) in info.geometries.buffers() {
+        assert_eq!(buffer.device().internal_object(), device.internal_object());
+
+        let usage = buffer.inner().buffer.usage();
+        if !usage.acceleration_structure_build_input_read_only || !usage.device_address {
+            return Err(
+                CheckBuildAccelerationStructureError::GeometryBufferMissingUsage { geometry_index },
+            );
+        }
+    }
+
+    let (geometries, ranges) = match info.geometries.to_vulkan() {
+        Ok(geometries) => geometries,
+        Err(geometry_index) => {
+            return Err(
+                CheckBuildAccelerationStructureError::GeometryBufferMissingUsage { geometry_index },
+            )
+        }
+    };
+
+    // The destination exists, so the extension and the feature are enabled.
+    let sizes = unsafe {
+        AccelerationStructure::build_sizes_unchecked(
+            device,
+            ash::vk::AccelerationStructureBuildTypeKHR::DEVICE,
+            info.geometries.ty(),
+            info.flags,
+            &geometries,
+            &ranges,
+        )
+    };
+
+    if destination.buffer().size() < sizes.acceleration_structure_size {
+        return Err(CheckBuildAccelerationStructureError::DestinationTooSmall {
+            required: sizes.acceleration_structure_size,
+            obtained: destination.buffer().size(),
+        });
+    }
+
+    let scratch_address = match scratch_buffer.raw_device_address() {
+        Ok(address) if scratch_buffer.inner().buffer.usage().storage_buffer => address,
+        _ => return Err(CheckBuildAccelerationStructureError::ScratchBufferMissingUsage),
+    };
+
+    if scratch_buffer.size() < sizes.build_scratch_size {
+        return Err(
+            CheckBuildAccelerationStructureError::ScratchBufferTooSmall {
+                required: sizes.build_scratch_size,
+                obtained: scratch_buffer.size(),
+            },
+        );
+    }
+
+    // The property is always available when the extension is enabled.
+    let scratch_alignment = device
+        .physical_device()
+        .properties()
+        .min_acceleration_structure_scratch_offset_alignment
+        .unwrap_or(1) as DeviceSize;
+    if scratch_address.get() % scratch_alignment != 0 {
+        return Err(
+            CheckBuildAccelerationStructureError::ScratchBufferNotAligned {
+                alignment: scratch_alignment,
+            },
+        );
+    }
+
+    match &info.geometries {
+        AccelerationStructureGeometries::Triangles(triangles) => {
+            for (geometry_index, data) in triangles.iter().enumerate() {
+                if !device
+                    .physical_device()
+                    .format_properties(data.vertex_format)
+                    .buffer_features
+                    .acceleration_structure_vertex_buffer
+                {
+                    return Err(
+                        CheckBuildAccelerationStructureError::UnsupportedVertexFormat {
+                            geometry_index,
+                            format: data.vertex_format,
+                        },
+                    );
+                }
+
+                if let Some((_, IndexType::U8)) = data.index_data {
+                    return Err(CheckBuildAccelerationStructureError::UnsupportedIndexType {
+                        geometry_index,
+                    });
+                }
+            }
+        }
+        AccelerationStructureGeometries::Aabbs(aabbs) => {
+            for (geometry_index, data) in aabbs.iter().enumerate() {
+                check_geometry_alignment(geometry_index, &data.data, 8)?;
+            }
+        }
+        AccelerationStructureGeometries::Instances(instances) => {
+            check_geometry_alignment(0, &instances.data, 16)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn check_geometry_alignment(
+    geometry_index: usize,
+    buffer: &dyn BufferAccess,
+    alignment: DeviceSize,
+) -> Result<(), CheckBuildAccelerationStructureError> {
+    let address = match buffer.raw_device_address() {
+        Ok(address) => address,
+        Err(_) => {
+            return Err(
+                CheckBuildAccelerationStructureError::GeometryBufferMissingUsage { geometry_index },
+            )
+        }
+    };
+
+    if address.get() % alignment != 0 {
+        return Err(
+            CheckBuildAccelerationStructureError::GeometryBufferNotAligned {
+                geometry_index,
+                alignment,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `build_acceleration_structure` command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckBuildAccelerationStructureError {
+    /// The type of the destination doesn't match the type of the geometries.
+    DestinationTypeMismatch {
+        /// The type of the destination.
+        destination: AccelerationStructureType,
+        /// The type of acceleration structure that the geometries build.
+        geometries: AccelerationStructureType,
+    },
+    /// The buffer of the destination is too small for the geometries.
+    DestinationTooSmall {
+        /// The size that the geometries require.
+        required: DeviceSize,
+        /// The size of the buffer of the destination.
+        obtained: DeviceSize,
+    },
+    /// The "storage buffer" and "device address" usages must be enabled on the scratch buffer.
+    ScratchBufferMissingUsage,
+    /// The device address of the scratch buffer is not a multiple of the
+    /// `min_acceleration_structure_scratch_offset_alignment` limit.
+    ScratchBufferNotAligned {
+        /// The required alignment.
+        alignment: DeviceSize,
+    },
+    /// The scratch buffer is too small for the geometries.
+    ScratchBufferTooSmall {
+        /// The size that the geometries require.
+        required: DeviceSize,
+        /// The size of the scratch buffer.
+        obtained: DeviceSize,
+    },
+    /// The "acceleration structure build input read only" and "device address" usages must be
+    /// enabled on the buffers of a geometry.
+    GeometryBufferMissingUsage {
+        /// The index of the geometry.
+        geometry_index: usize,
+    },
+    /// The device address of the buffer of a geometry is not aligned as required by its type.
+    GeometryBufferNotAligned {
+        /// The index of the geometry.
+        geometry_index: usize,
+        /// The required alignment.
+        alignment: DeviceSize,
+    },
+    /// The vertex format of a geometry doesn't support the `acceleration_structure_vertex_buffer`
+    /// buffer feature.
+    UnsupportedVertexFormat {
+        /// The index of the geometry.
+        geometry_index: usize,
+        /// The vertex format of the geometry.
+        format: Format,
+    },
+    /// The indices of a geometry are of type `U8`, which is not supported for acceleration
+    /// structure builds.
+    UnsupportedIndexType {
+        /// The index of the geometry.
+        geometry_index: usize,
+    },
+}
+
+impl error::Error for CheckBuildAccelerationStructureError {}
+
+impl fmt::Display for CheckBuildAccelerationStructureError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::DestinationTypeMismatch { .. } => write!(
+                fmt,
+                "the type of the destination doesn't match the type of the geometries",
+            ),
+            Self::DestinationTooSmall { .. } => write!(
+                fmt,
+                "the buffer of the destination is too small for the geometries",
+            ),
+            Self::ScratchBufferMissingUsage => write!(
+                fmt,
+                "the storage buffer and device address usages must be enabled on the scratch buffer",
+            ),
+            Self::ScratchBufferNotAligned { .. } => write!(
+                fmt,
+                "the device address of the scratch buffer is not aligned to the minimum scratch offset alignment",
+            ),
+            Self::ScratchBufferTooSmall { .. } => {
+                write!(fmt, "the scratch buffer is too small for the geometries")
+            }
+            Self::GeometryBufferMissingUsage { .. } => write!(
+                fmt,
+                "the acceleration structure build input read only and device address usages must be enabled on the buffers of a geometry",
+            ),
+            Self::GeometryBufferNotAligned { .. } => write!(
+                fmt,
+                "the device address of the buffer of a geometry is not aligned as required by its type",
+            ),
+            Self::UnsupportedVertexFormat { .. } => write!(
+                fmt,
+                "the vertex format of a geometry is not supported for acceleration structure builds",
+            ),
+            Self::UnsupportedIndexType { .. } => write!(
+                fmt,
+                "the index type of a geometry is not supported for acceleration structure builds",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acceleration_structure::AccelerationStructureGeometryAabbsData;
+    use crate::acceleration_structure::AccelerationStructureGeometryInstancesData;
+    use crate::acceleration_structure::BuildAccelerationStructureFlags;
+    use crate::acceleration_structure::GeometryFlags;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use std::sync::Arc;
+
+    macro_rules! acceleration_structure_dev_and_queue {
+        () => {
+            gfx_dev_and_queue_with!(
+                [
+                    khr_acceleration_structure,
+                    khr_buffer_device_address,
+                    khr_deferred_host_operations,
+                    ext_descriptor_indexing
+                ],
+                [acceleration_structure, buffer_device_address]
+            )
+        };
+    }
+
+    fn buffer(device: Arc<Device>, usage: BufferUsage, size: DeviceSize) -> Arc<dyn BufferAccess> {
+        CpuAccessibleBuffer::from_iter(device, usage, false, (0..size).map(|_| 0u8)).unwrap()
+    }
+
+    fn geometry_usage() -> BufferUsage {
+        BufferUsage {
+            acceleration_structure_build_input_read_only: true,
+            device_address: true,
+            ..BufferUsage::none()
+        }
+    }
+
+    fn aabbs_info(data: Arc<dyn BufferAccess>) -> AccelerationStructureBuildGeometryInfo {
+        AccelerationStructureBuildGeometryInfo {
+            flags: BuildAccelerationStructureFlags::default(),
+            geometries: AccelerationStructureGeometries::Aabbs(vec![
+                AccelerationStructureGeometryAabbsData {
+                    flags: GeometryFlags::default(),
+                    data,
+                    stride: 24,
+                    primitive_count: 1,
+                },
+            ]),
+        }
+    }
+
+    // Creates a destination and a scratch buffer that are large enough to build `info`.
+    fn destination_and_scratch(
+        device: Arc<Device>,
+        info: &AccelerationStructureBuildGeometryInfo,
+        ty: AccelerationStructureType,
+    ) -> (Arc<AccelerationStructure>, Arc<dyn BufferAccess>) {
+        let sizes = AccelerationStructure::build_sizes(&device, info).unwrap();
+        let storage = buffer(
+            device.clone(),
+            BufferUsage {
+                acceleration_structure_storage: true,
+                ..BufferUsage::none()
+            },
+            sizes.acceleration_structure_size,
+        );
+        let destination = AccelerationStructure::new(storage, ty).unwrap();
+        let scratch = buffer(
+            device,
+            BufferUsage {
+                storage_buffer: true,
+                device_address: true,
+                ..BufferUsage::none()
+            },
+            sizes.build_scratch_size,
+        );
+
+        (destination, scratch)
+    }
+
+    #[test]
+    fn destination_type_mismatch() {
+        let (device, _) = acceleration_structure_dev_and_queue!();
+
+        let data = buffer(device.clone(), geometry_usage(), 64);
+        let info = AccelerationStructureBuildGeometryInfo {
+            flags: BuildAccelerationStructureFlags::default(),
+            geometries: AccelerationStructureGeometries::Instances(
+                AccelerationStructureGeometryInstancesData {
+                    flags: GeometryFlags::default(),
+                    data,
+                    primitive_count: 1,
+                },
+            ),
+        };
+        let (destination, scratch) = destination_and_scratch(
+            device.clone(),
+            &info,
+            AccelerationStructureType::BottomLevel,
+        );
+
+        match check_build_acceleration_structure(&device, &info, &destination, scratch.as_ref()) {
+            Err(CheckBuildAccelerationStructureError::DestinationTypeMismatch {
+                destination: AccelerationStructureType::BottomLevel,
+                geometries: AccelerationStructureType::TopLevel,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn scratch_buffer_missing_usage() {
+        let (device, _) = acceleration_structure_dev_and_queue!();
+
+        let info = aabbs_info(buffer(device.clone(), geometry_usage(), 24));
+        let (destination, scratch) = destination_and_scratch(
+            device.clone(),
+            &info,
+            AccelerationStructureType::BottomLevel,
+        );
+        let scratch_without_usage = buffer(
+            device.clone(),
+            BufferUsage {
+                device_address: true,
+                ..BufferUsage::none()
+            },
+            scratch.size(),
+        );
+
+        match check_build_acceleration_structure(
+            &device,
+            &info,
+            &destination,
+            scratch_without_usage.as_ref(),
+        ) {
+            Err(CheckBuildAccelerationStructureError::ScratchBufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn geometry_buffer_missing_usage() {
+        let (device, _) = acceleration_structure_dev_and_queue!();
+
+        let data = buffer(
+            device.clone(),
+            BufferUsage {
+                storage_buffer: true,
+                device_address: true,
+                ..BufferUsage::none()
+            },
+            24,
+        );
+        let info = aabbs_info(data);
+        let (destination, scratch) = destination_and_scratch(
+            device.clone(),
+            &info,
+            AccelerationStructureType::BottomLevel,
+        );
+
+        match check_build_acceleration_structure(&device, &info, &destination, scratch.as_ref()) {
+            Err(CheckBuildAccelerationStructureError::GeometryBufferMissingUsage {
+                geometry_index: 0,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use crate::buffer::BufferAccess;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::VulkanObject;
+
+/// Checks whether a begin conditional rendering command is valid.
+///
+/// # Panic
+///
+/// - Panics if the buffer not created with `device`.
+///
+pub fn check_begin_conditional_rendering<B>(
+    device: &Device,
+    buffer: &B,
+) -> Result<(), CheckBeginConditionalRenderingError>
+where
+    B: ?Sized + BufferAccess,
+{
+    assert_eq!(
+        buffer.inner().buffer.device().internal_object(),
+        device.internal_object()
+    );
+
+    if !device.enabled_extensions().ext_conditional_rendering {
+        return Err(CheckBeginConditionalRenderingError::ExtensionNotEnabled);
+    }
+
+    if !device.enabled_features().conditional_rendering {
+        return Err(CheckBeginConditionalRenderingError::FeatureNotEnabled);
+    }
+
+    if !buffer.inner().buffer.usage().conditional_rendering {
+        return Err(CheckBeginConditionalRenderingError::BufferMissingUsage);
+    }
+
+    if buffer.inner().offset % 4 != 0 {
+        return Err(CheckBeginConditionalRenderingError::WrongAlignment);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `begin_conditional_rendering` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckBeginConditionalRenderingError {
+    /// The `ext_conditional_rendering` extension must be enabled on the device.
+    ExtensionNotEnabled,
+    /// The `conditional_rendering` feature must be enabled on the device.
+    FeatureNotEnabled,
+    /// The "conditional rendering" usage must be enabled on the buffer.
+    BufferMissingUsage,
+    /// The offset of the predicate must be 4-bytes aligned.
+    WrongAlignment,
+}
+
+impl error::Error for CheckBeginConditionalRenderingError {}
+
+impl fmt::Display for CheckBeginConditionalRenderingError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckBeginConditionalRenderingError::ExtensionNotEnabled => {
+                    "the ext_conditional_rendering extension must be enabled on the device"
+                }
+                CheckBeginConditionalRenderingError::FeatureNotEnabled => {
+                    "the conditional_rendering feature must be enabled on the device"
+                }
+                CheckBeginConditionalRenderingError::BufferMissingUsage => {
+                    "the conditional rendering usage must be enabled on the buffer"
+                }
+                CheckBeginConditionalRenderingError::WrongAlignment => {
+                    "the offset of the predicate is not aligned to 4 bytes"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+
+    #[test]
+    fn missing_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0u32)
+                .unwrap();
+
+        match check_begin_conditional_rendering(&device, buffer.as_ref()) {
+            Err(CheckBeginConditionalRenderingError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -60,8 +60,15 @@ where
         return Err(CheckCopyBufferError::DestinationOutOfBounds);
     }
 
+    // The source and destination can be two slices of the same buffer, so the ranges must be
+    // compared relative to the start of the underlying buffer.
     if source.conflict_key() == destination.conflict_key()
-        && is_overlapping_ranges(source_offset, size, destination_offset, size)
+        && is_overlapping_ranges(
+            source.inner().offset + source_offset,
+            size,
+            destination.inner().offset + destination_offset,
+            size,
+        )
     {
         return Err(CheckCopyBufferError::OverlappingRanges);
     }
@@ -109,3 +116,49 @@ impl fmt::Display for CheckCopyBufferError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::BufferAccess;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+
+    #[test]
+    fn overlapping_slices() {
+        let (device, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_source() | BufferUsage::transfer_destination(),
+            false,
+            0..8u32,
+        )
+        .unwrap();
+
+        let source = buffer.slice::<u32>(0..4).unwrap();
+        let destination = buffer.slice::<u32>(2..6).unwrap();
+
+        match check_copy_buffer(&device, source.as_ref(), destination.as_ref(), 0, 0, 16) {
+            Err(CheckCopyBufferError::OverlappingRanges) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn disjoint_slices() {
+        let (device, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_source() | BufferUsage::transfer_destination(),
+            false,
+            0..8u32,
+        )
+        .unwrap();
+
+        // Both copies start at offset 0 of their slice, but the slices don't overlap.
+        let source = buffer.slice::<u32>(0..4).unwrap();
+        let destination = buffer.slice::<u32>(4..8).unwrap();
+
+        check_copy_buffer(&device, source.as_ref(), destination.as_ref(), 0, 0, 16).unwrap();
+    }
+}
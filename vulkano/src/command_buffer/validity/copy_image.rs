@@ -173,12 +173,14 @@ where
         ImageDimensions::Dim3d { .. } => {}
     }
 
+    // The source and destination can be two subresource ranges of the same image, so the array
+    // layers must be compared relative to the underlying image.
     if source.conflict_key() == destination.conflict_key() {
         if source_mip_level == destination_mip_level
             && is_overlapping_ranges(
-                source_base_array_layer as u64,
+                (source_inner.first_layer as u32 + source_base_array_layer) as u64,
                 layer_count as u64,
-                destination_base_array_layer as u64,
+                (destination_inner.first_layer as u32 + destination_base_array_layer) as u64,
                 layer_count as u64,
             )
             // since both images are the same, we can use any dimensions type
@@ -260,3 +262,72 @@ impl fmt::Display for CheckCopyImageError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Format;
+    use crate::image::immutable::SubImage;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageLayout;
+    use crate::image::ImageUsage;
+    use crate::image::StorageImage;
+
+    #[test]
+    fn sub_images_different_layers() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let image = StorageImage::with_usage(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 2,
+            },
+            Format::R8G8B8A8_UNORM,
+            ImageUsage {
+                transfer_source: true,
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let source = SubImage::new(image.clone(), 0, 1, 0, 1, ImageLayout::General);
+        let destination = SubImage::new(image.clone(), 0, 1, 1, 1, ImageLayout::General);
+
+        check_copy_image(
+            &device,
+            source.as_ref(),
+            [0, 0, 0],
+            0,
+            0,
+            destination.as_ref(),
+            [0, 0, 0],
+            1,
+            0,
+            [32, 32, 1],
+            1,
+        )
+        .unwrap();
+
+        // Copying within the same layer of the two sub-images overlaps.
+        match check_copy_image(
+            &device,
+            source.as_ref(),
+            [0, 0, 0],
+            0,
+            0,
+            destination.as_ref(),
+            [8, 8, 0],
+            0,
+            0,
+            [16, 16, 1],
+            1,
+        ) {
+            Err(CheckCopyImageError::OverlappingRegions) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -14,6 +14,7 @@ use crate::format::Format;
 use crate::format::IncompatiblePixelsType;
 use crate::format::Pixel;
 use crate::image::ImageAccess;
+use crate::image::ImageAspect;
 use crate::image::ImageDimensions;
 use crate::image::SampleCount;
 use crate::DeviceSize;
@@ -31,6 +32,9 @@ pub enum CheckCopyBufferImageTy {
 /// Checks whether a copy buffer-image command is valid. Can check both buffer-to-image copies and
 /// image-to-buffer copies.
 ///
+/// `image_aspect` is the aspect of the image that is copied. For multi-planar formats, this
+/// determines the plane whose format and extent the copy is checked against.
+///
 /// # Panic
 ///
 /// - Panics if the buffer and image were not created with `device`.
@@ -45,6 +49,7 @@ pub fn check_copy_buffer_image<B, I, Px>(
     image_first_layer: u32,
     image_num_layers: u32,
     image_mipmap: u32,
+    image_aspect: ImageAspect,
 ) -> Result<(), CheckCopyBufferImageError>
 where
     I: ?Sized + ImageAccess,
@@ -133,11 +138,80 @@ where
         }
     }
 
-    Px::ensure_accepts(image.format())?;
+    let format = image.format();
+    let [block_width, block_height, block_depth] = format.block_extent();
+
+    // For block-compressed formats, the region must start on a block boundary, and must either
+    // cover a whole number of blocks or extend to the edge of the mip level.
+    if image_offset[0] % block_width != 0
+        || image_offset[1] % block_height != 0
+        || image_offset[2] % block_depth != 0
+    {
+        return Err(CheckCopyBufferImageError::ImageRegionNotAlignedToBlocks);
+    }
 
+    // VUID-vkCmdCopyBufferToImage-imageExtent-00207
+    // VUID-vkCmdCopyBufferToImage-imageExtent-00208
+    // VUID-vkCmdCopyBufferToImage-imageExtent-00209
+    if !(image_size[0] % block_width == 0
+        || image_offset[0] + image_size[0] == image_dimensions.width())
+        || !(image_size[1] % block_height == 0
+            || image_offset[1] + image_size[1] == image_dimensions.height())
+        || !(image_size[2] % block_depth == 0
+            || image_offset[2] + image_size[2] == image_dimensions.depth())
     {
-        let required_len =
-            required_len_for_format::<Px>(image.format(), image_size, image_num_layers);
+        return Err(CheckCopyBufferImageError::ImageRegionNotAlignedToBlocks);
+    }
+
+    // VUID-vkCmdCopyBufferToImage-bufferOffset-00193
+    // VUID-vkCmdCopyBufferToImage-bufferOffset-01558
+    // VUID-vkCmdCopyBufferToImage-bufferOffset-01559
+    // VUID-vkCmdCopyBufferToImage-srcImage-04053
+    // Each plane of a multi-planar format is copied on its own, with the format and the extent
+    // of that plane.
+    let copy_format = if !format.planes().is_empty() {
+        let plane = match image_aspect {
+            ImageAspect::Plane0 => 0,
+            ImageAspect::Plane1 => 1,
+            ImageAspect::Plane2 => 2,
+            _ => return Err(CheckCopyBufferImageError::ImageAspectNotPlane),
+        };
+
+        let plane_format = match format.planes().get(plane) {
+            Some(&plane_format) => plane_format,
+            None => return Err(CheckCopyBufferImageError::ImageAspectNotPlane),
+        };
+
+        let [plane_width, plane_height, _] =
+            format.plane_extent(plane, image_dimensions.width_height_depth());
+
+        if image_offset[0] + image_size[0] > plane_width
+            || image_offset[1] + image_size[1] > plane_height
+        {
+            return Err(CheckCopyBufferImageError::ImageCoordinatesOutOfRange);
+        }
+
+        plane_format
+    } else {
+        format
+    };
+
+    let required_alignment = if format.aspects().depth || format.aspects().stencil {
+        Some(4)
+    } else {
+        copy_format.block_size()
+    };
+
+    if let Some(required_alignment) = required_alignment {
+        if buffer_inner.offset % required_alignment != 0 {
+            return Err(CheckCopyBufferImageError::BufferOffsetNotAligned { required_alignment });
+        }
+    }
+
+    Px::ensure_accepts(copy_format)?;
+
+    {
+        let required_len = required_len_for_format::<Px>(copy_format, image_size, image_num_layers);
         if required_len > buffer.len() {
             return Err(CheckCopyBufferImageError::BufferTooSmall {
                 required_len,
@@ -173,8 +247,101 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::command_buffer::validity::copy_image_buffer::required_len_for_format;
-    use crate::format::Format;
+    use super::*;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+    use crate::device::physical::QueueFamily;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageUsage;
+    use crate::image::StorageImage;
+    use std::sync::Arc;
+
+    fn transfer_image(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        format: Format,
+    ) -> Arc<StorageImage> {
+        StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            format,
+            ImageUsage {
+                transfer_source: true,
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            Some(queue_family),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn region_not_aligned_to_blocks() {
+        let (device, queue) = gfx_dev_and_queue!(texture_compression_bc);
+
+        let image = transfer_image(device.clone(), queue.family(), Format::BC1_RGB_UNORM_BLOCK);
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_source(),
+            false,
+            (0..1024).map(|_| 0u8),
+        )
+        .unwrap();
+
+        match check_copy_buffer_image(
+            &device,
+            buffer.as_ref(),
+            image.as_ref(),
+            CheckCopyBufferImageTy::BufferToImage,
+            [2, 0, 0],
+            [4, 4, 1],
+            0,
+            1,
+            0,
+            ImageAspect::Color,
+        ) {
+            Err(CheckCopyBufferImageError::ImageRegionNotAlignedToBlocks) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn buffer_offset_not_aligned() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = transfer_image(device.clone(), queue.family(), Format::R8G8B8A8_UNORM);
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_source(),
+            false,
+            (0..1024).map(|_| 0u8),
+        )
+        .unwrap();
+        let slice = buffer.slice::<u8>(1..1024).unwrap();
+
+        match check_copy_buffer_image(
+            &device,
+            slice.as_ref(),
+            image.as_ref(),
+            CheckCopyBufferImageTy::BufferToImage,
+            [0, 0, 0],
+            [4, 4, 1],
+            0,
+            1,
+            0,
+            ImageAspect::Color,
+        ) {
+            Err(CheckCopyBufferImageError::BufferOffsetNotAligned {
+                required_alignment: 4,
+            }) => (),
+            _ => panic!(),
+        }
+    }
 
     #[test]
     fn test_required_len_for_format() {
@@ -232,6 +399,17 @@ pub enum CheckCopyBufferImageError {
     UnexpectedMultisampled,
     /// The image coordinates are out of range.
     ImageCoordinatesOutOfRange,
+    /// The image has a block-compressed format, and the image coordinates don't start on a block
+    /// boundary, or don't cover a whole number of blocks without reaching the edge of the image.
+    ImageRegionNotAlignedToBlocks,
+    /// The image has a multi-planar format, and the aspect to copy is not one of its planes.
+    ImageAspectNotPlane,
+    /// The offset of the buffer is not a multiple of the texel block size of the image format, or
+    /// of the plane that is copied for multi-planar formats, or of 4 for depth/stencil formats.
+    BufferOffsetNotAligned {
+        /// Required alignment of the offset in bytes.
+        required_alignment: DeviceSize,
+    },
     /// The type of pixels in the buffer isn't compatible with the image format.
     WrongPixelType(IncompatiblePixelsType),
     /// The buffer is too small for the copy operation.
@@ -274,6 +452,15 @@ impl fmt::Display for CheckCopyBufferImageError {
                 CheckCopyBufferImageError::ImageCoordinatesOutOfRange => {
                     "the image coordinates are out of range"
                 }
+                CheckCopyBufferImageError::ImageRegionNotAlignedToBlocks => {
+                    "the image coordinates are not aligned to the blocks of the image format"
+                }
+                CheckCopyBufferImageError::ImageAspectNotPlane => {
+                    "the image has a multi-planar format, and the aspect to copy is not one of its planes"
+                }
+                CheckCopyBufferImageError::BufferOffsetNotAligned { .. } => {
+                    "the offset of the buffer is not aligned to the texel block size of the image format"
+                }
                 CheckCopyBufferImageError::WrongPixelType(_) => {
                     "the type of pixels in the buffer isn't compatible with the image format"
                 }
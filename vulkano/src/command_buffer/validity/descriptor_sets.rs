@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use crate::acceleration_structure::AccelerationStructure;
 use crate::buffer::BufferAccess;
 use crate::buffer::BufferViewAbstract;
 use crate::command_buffer::synced::CommandBufferState;
@@ -21,15 +22,20 @@ use crate::sampler::Sampler;
 use crate::sampler::SamplerImageViewIncompatibleError;
 use crate::shader::DescriptorRequirements;
 use crate::shader::ShaderScalarType;
+use crate::VulkanObject;
 use std::error;
 use std::fmt;
 use std::sync::Arc;
 
 /// Checks whether descriptor sets are compatible with the pipeline.
+///
+/// `input_attachments` contains the image views that are used as input attachments by the
+/// current subpass, in the order of their input attachment index, if they are known.
 pub(in super::super) fn check_descriptor_sets_validity<'a, P: Pipeline>(
     current_state: CommandBufferState,
     pipeline: &P,
     descriptor_requirements: impl IntoIterator<Item = ((u32, u32), &'a DescriptorRequirements)>,
+    input_attachments: Option<&[Arc<dyn ImageViewAbstract>]>,
 ) -> Result<(), CheckDescriptorSetsValidityError> {
     if pipeline.num_used_descriptor_sets() == 0 {
         return Ok(());
@@ -215,6 +221,27 @@ pub(in super::super) fn check_descriptor_sets_validity<'a, P: Pipeline>(
         let check_image_view = |index: u32, image_view: &Arc<dyn ImageViewAbstract>| {
             check_image_view_common(index, image_view)?;
 
+            if layout_binding.ty == DescriptorType::InputAttachment {
+                if let (Some(input_attachments), Some(input_attachment_index)) =
+                    (input_attachments, reqs.input_attachment_index)
+                {
+                    // The descriptor must refer to the same image subresources as the attachment
+                    // at its input attachment index.
+                    let is_same_subresource = input_attachments
+                        .get((input_attachment_index + index) as usize)
+                        .map_or(false, |attachment| {
+                            attachment.image().inner().image.internal_object()
+                                == image_view.image().inner().image.internal_object()
+                                && attachment.mip_levels() == image_view.mip_levels()
+                                && attachment.array_layers() == image_view.array_layers()
+                        });
+
+                    if !is_same_subresource {
+                        return Err(InvalidDescriptorResource::InputAttachmentNotInSubpass);
+                    }
+                }
+            }
+
             if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
                 check_sampler_common(index, sampler)?;
             }
@@ -265,6 +292,9 @@ pub(in super::super) fn check_descriptor_sets_validity<'a, P: Pipeline>(
             Ok(())
         };
 
+        let check_acceleration_structure =
+            |index: u32, acceleration_structure: &Arc<AccelerationStructure>| Ok(());
+
         let check_none = |index: u32, _: &()| {
             if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
                 check_sampler(index, sampler)?;
@@ -305,6 +335,15 @@ pub(in super::super) fn check_descriptor_sets_validity<'a, P: Pipeline>(
             DescriptorBindingResources::Sampler(elements) => {
                 check_resources(set_num, binding_num, reqs, elements, check_sampler)?;
             }
+            DescriptorBindingResources::AccelerationStructure(elements) => {
+                check_resources(
+                    set_num,
+                    binding_num,
+                    reqs,
+                    elements,
+                    check_acceleration_structure,
+                )?;
+            }
         }
     }
 
@@ -423,6 +462,7 @@ pub enum InvalidDescriptorResource {
         required: ImageViewType,
         obtained: ImageViewType,
     },
+    InputAttachmentNotInSubpass,
     Missing,
     SamplerCompareMismatch {
         required: bool,
@@ -466,6 +506,12 @@ impl fmt::Display for InvalidDescriptorResource {
             Self::ImageViewTypeMismatch { required, obtained } => {
                 write!(fmt, "the bound image view did not have the required type; required {:?}, obtained {:?}", required, obtained)
             }
+            Self::InputAttachmentNotInSubpass => {
+                write!(
+                    fmt,
+                    "the bound image view is not the input attachment of the current subpass at its input attachment index"
+                )
+            }
             Self::Missing => {
                 write!(fmt, "no resource was bound")
             }
@@ -105,7 +105,11 @@ pub(in super::super) fn check_dynamic_state_validity(
                 }
             }
             DynamicState::ExclusiveScissor => todo!(),
-            DynamicState::FragmentShadingRate => todo!(),
+            DynamicState::FragmentShadingRate => {
+                if current_state.fragment_shading_rate().is_none() {
+                    return Err(CheckDynamicStateValidityError::NotSet { dynamic_state });
+                }
+            }
             DynamicState::FrontFace => {
                 if current_state.front_face().is_none() {
                     return Err(CheckDynamicStateValidityError::NotSet { dynamic_state });
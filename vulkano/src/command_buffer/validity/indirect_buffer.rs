@@ -8,11 +8,14 @@
 // according to those terms.
 
 use crate::buffer::BufferAccess;
+use crate::command_buffer::DispatchIndirectCommand;
 use crate::device::Device;
 use crate::device::DeviceOwned;
+use crate::DeviceSize;
 use crate::VulkanObject;
 use std::error;
 use std::fmt;
+use std::mem;
 
 /// Checks whether an indirect buffer can be bound.
 pub fn check_indirect_buffer<Inb>(
@@ -31,6 +34,82 @@ where
         return Err(CheckIndirectBufferError::BufferMissingUsage);
     }
 
+    if buffer.inner().offset % 4 != 0 {
+        return Err(CheckIndirectBufferError::OffsetNotAligned);
+    }
+
+    Ok(())
+}
+
+/// Checks whether an indirect buffer can be used as the source of a dispatch command.
+pub fn check_dispatch_indirect_buffer<Inb>(
+    device: &Device,
+    buffer: &Inb,
+) -> Result<(), CheckIndirectBufferError>
+where
+    Inb: BufferAccess + 'static,
+{
+    check_indirect_buffer(device, buffer)?;
+
+    let required = mem::size_of::<DispatchIndirectCommand>() as DeviceSize;
+
+    if buffer.size() < required {
+        return Err(CheckIndirectBufferError::BufferTooSmall {
+            required,
+            obtained: buffer.size(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks whether the draw count of an indirect draw can be read from a buffer.
+pub fn check_draw_indirect_count(device: &Device) -> Result<(), CheckIndirectBufferError> {
+    if !(device.enabled_extensions().khr_draw_indirect_count
+        || device.enabled_features().draw_indirect_count)
+    {
+        return Err(CheckIndirectBufferError::DrawIndirectCountNotEnabled);
+    }
+
+    Ok(())
+}
+
+/// Checks whether the given number of draws can be performed by an indirect draw command.
+pub fn check_draw_indirect_draw_count(
+    device: &Device,
+    draw_count: u32,
+) -> Result<(), CheckIndirectBufferError> {
+    // VUID-vkCmdDrawIndirect-drawCount-02718
+    if draw_count > 1 && !device.enabled_features().multi_draw_indirect {
+        return Err(CheckIndirectBufferError::MultiDrawIndirectNotEnabled);
+    }
+
+    // VUID-vkCmdDrawIndirect-drawCount-02719
+    check_max_draw_indirect_count(device, draw_count)
+}
+
+/// Checks whether the given number of draws is within the `max_draw_indirect_count` limit.
+///
+/// Unlike [`check_draw_indirect_draw_count`], this doesn't require the `multi_draw_indirect`
+/// feature, which the commands that read the draw count from a buffer don't need.
+pub fn check_max_draw_indirect_count(
+    device: &Device,
+    draw_count: u32,
+) -> Result<(), CheckIndirectBufferError> {
+    let limit = device
+        .physical_device()
+        .properties()
+        .max_draw_indirect_count;
+
+    if draw_count > limit {
+        return Err(
+            CheckIndirectBufferError::MaxDrawIndirectCountLimitExceeded {
+                limit,
+                requested: draw_count,
+            },
+        );
+    }
+
     Ok(())
 }
 
@@ -39,6 +118,21 @@ where
 pub enum CheckIndirectBufferError {
     /// The "indirect buffer" usage must be enabled on the indirect buffer.
     BufferMissingUsage,
+    /// The indirect buffer is too small for the command that reads from it.
+    BufferTooSmall {
+        /// The minimum size in bytes.
+        required: DeviceSize,
+        /// The actual size in bytes.
+        obtained: DeviceSize,
+    },
+    /// The offset of the indirect buffer must be a multiple of 4.
+    OffsetNotAligned,
+    /// The `draw_indirect_count` feature or the `khr_draw_indirect_count` extension must be
+    /// enabled on the device to read the draw count from a buffer.
+    DrawIndirectCountNotEnabled,
+    /// The `multi_draw_indirect` feature must be enabled on the device to perform more than one
+    /// draw with a single indirect draw command.
+    MultiDrawIndirectNotEnabled,
     /// The maximum number of indirect draws has been exceeded.
     MaxDrawIndirectCountLimitExceeded {
         /// The limit that must be fulfilled.
@@ -60,6 +154,18 @@ impl fmt::Display for CheckIndirectBufferError {
                 CheckIndirectBufferError::BufferMissingUsage => {
                     "the indirect buffer usage must be enabled on the indirect buffer"
                 }
+                CheckIndirectBufferError::BufferTooSmall { .. } => {
+                    "the indirect buffer is too small for the command that reads from it"
+                }
+                CheckIndirectBufferError::OffsetNotAligned => {
+                    "the offset of the indirect buffer must be a multiple of 4"
+                }
+                CheckIndirectBufferError::DrawIndirectCountNotEnabled => {
+                    "the draw_indirect_count feature or the khr_draw_indirect_count extension must be enabled"
+                }
+                CheckIndirectBufferError::MultiDrawIndirectNotEnabled => {
+                    "the multi_draw_indirect feature must be enabled to perform more than one draw"
+                }
                 CheckIndirectBufferError::MaxDrawIndirectCountLimitExceeded {
                     limit,
                     requested,
@@ -70,3 +176,98 @@ impl fmt::Display for CheckIndirectBufferError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+
+    #[test]
+    fn missing_usage() {
+        let (device, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage::vertex_buffer(),
+            false,
+            DispatchIndirectCommand { x: 1, y: 1, z: 1 },
+        )
+        .unwrap();
+
+        match check_dispatch_indirect_buffer(&device, buffer.as_ref()) {
+            Err(CheckIndirectBufferError::BufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn too_small() {
+        let (device, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage::indirect_buffer(),
+            false,
+            [1u32, 1],
+        )
+        .unwrap();
+
+        match check_dispatch_indirect_buffer(&device, buffer.as_ref()) {
+            Err(CheckIndirectBufferError::BufferTooSmall {
+                required: 12,
+                obtained: 8,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multi_draw_indirect_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(check_draw_indirect_draw_count(&device, 1).is_ok());
+
+        match check_draw_indirect_draw_count(&device, 2) {
+            Err(CheckIndirectBufferError::MultiDrawIndirectNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn max_draw_indirect_count_without_multi_draw_indirect() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(check_max_draw_indirect_count(&device, 1).is_ok());
+
+        let limit = device
+            .physical_device()
+            .properties()
+            .max_draw_indirect_count;
+
+        if limit >= 2 {
+            assert!(check_max_draw_indirect_count(&device, 2).is_ok());
+        }
+    }
+
+    #[test]
+    fn max_draw_indirect_count_exceeded() {
+        let (device, _) = gfx_dev_and_queue!(multi_draw_indirect);
+        let limit = device
+            .physical_device()
+            .properties()
+            .max_draw_indirect_count;
+
+        if limit == u32::MAX {
+            return;
+        }
+
+        assert!(check_draw_indirect_draw_count(&device, limit).is_ok());
+
+        match check_draw_indirect_draw_count(&device, limit + 1) {
+            Err(CheckIndirectBufferError::MaxDrawIndirectCountLimitExceeded {
+                limit: l,
+                requested,
+            }) if l == limit && requested == limit + 1 => (),
+            _ => panic!(),
+        }
+    }
+}
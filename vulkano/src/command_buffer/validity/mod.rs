@@ -9,11 +9,17 @@
 
 //! Functions that check the validity of commands.
 
+pub use self::acceleration_structure::{
+    check_build_acceleration_structure, CheckBuildAccelerationStructureError,
+};
 pub use self::blit_image::{check_blit_image, CheckBlitImageError};
 pub use self::clear_color_image::{check_clear_color_image, CheckClearColorImageError};
 pub use self::clear_depth_stencil_image::{
     check_clear_depth_stencil_image, CheckClearDepthStencilImageError,
 };
+pub use self::conditional_rendering::{
+    check_begin_conditional_rendering, CheckBeginConditionalRenderingError,
+};
 pub use self::copy_buffer::{check_copy_buffer, CheckCopyBufferError};
 pub use self::copy_image::{check_copy_image, CheckCopyImageError};
 pub use self::copy_image_buffer::{
@@ -25,7 +31,10 @@ pub use self::dispatch::{check_dispatch, CheckDispatchError};
 pub use self::dynamic_state::CheckDynamicStateValidityError;
 pub use self::fill_buffer::{check_fill_buffer, CheckFillBufferError};
 pub use self::index_buffer::CheckIndexBufferError;
-pub use self::indirect_buffer::{check_indirect_buffer, CheckIndirectBufferError};
+pub use self::indirect_buffer::{
+    check_dispatch_indirect_buffer, check_draw_indirect_count, check_draw_indirect_draw_count,
+    check_indirect_buffer, check_max_draw_indirect_count, CheckIndirectBufferError,
+};
 pub use self::pipeline::CheckPipelineError;
 pub use self::push_constants::CheckPushConstantsValidityError;
 pub use self::query::{
@@ -33,6 +42,11 @@ pub use self::query::{
     check_write_timestamp, CheckBeginQueryError, CheckCopyQueryPoolResultsError,
     CheckEndQueryError, CheckResetQueryPoolError, CheckWriteTimestampError,
 };
+pub use self::trace_rays::{check_trace_rays, CheckTraceRaysError};
+pub use self::transform_feedback::{
+    check_bind_transform_feedback_buffers, check_transform_feedback_counter_buffers,
+    CheckTransformFeedbackError,
+};
 pub use self::update_buffer::{check_update_buffer, CheckUpdateBufferError};
 pub use self::vertex_buffers::CheckVertexBufferError;
 pub(super) use {
@@ -40,9 +54,11 @@ pub(super) use {
     vertex_buffers::*,
 };
 
+mod acceleration_structure;
 mod blit_image;
 mod clear_color_image;
 mod clear_depth_stencil_image;
+mod conditional_rendering;
 mod copy_buffer;
 mod copy_image;
 mod copy_image_buffer;
@@ -57,5 +73,7 @@ mod pipeline;
 mod push_constants;
 mod query;
 mod ranges;
+mod trace_rays;
+mod transform_feedback;
 mod update_buffer;
 mod vertex_buffers;
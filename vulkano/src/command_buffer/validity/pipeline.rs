@@ -9,7 +9,7 @@
 
 use crate::{
     command_buffer::synced::CommandBufferState,
-    pipeline::{ComputePipeline, GraphicsPipeline},
+    pipeline::{ComputePipeline, GraphicsPipeline, RayTracingPipeline},
 };
 use std::{error, fmt};
 
@@ -35,6 +35,17 @@ pub(in super::super) fn check_pipeline_graphics(
     Ok(pipeline)
 }
 
+pub(in super::super) fn check_pipeline_ray_tracing(
+    current_state: CommandBufferState,
+) -> Result<&RayTracingPipeline, CheckPipelineError> {
+    let pipeline = match current_state.pipeline_ray_tracing() {
+        Some(x) => x,
+        None => return Err(CheckPipelineError::PipelineNotBound),
+    };
+
+    Ok(pipeline)
+}
+
 /// Error that can happen when checking whether the pipeline is valid.
 #[derive(Debug, Copy, Clone)]
 pub enum CheckPipelineError {
@@ -0,0 +1,166 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use crate::device::Device;
+use crate::pipeline::ray_tracing::ShaderBindingTable;
+use crate::pipeline::RayTracingPipeline;
+
+/// Checks whether a trace rays command is valid.
+pub fn check_trace_rays(
+    device: &Device,
+    shader_binding_table: &ShaderBindingTable,
+    pipeline: &RayTracingPipeline,
+    dimensions: [u32; 3],
+) -> Result<(), CheckTraceRaysError> {
+    if shader_binding_table.pipeline().as_ref() != pipeline {
+        return Err(CheckTraceRaysError::ShaderBindingTablePipelineMismatch);
+    }
+
+    check_dimensions(device, dimensions)
+}
+
+fn check_dimensions(device: &Device, dimensions: [u32; 3]) -> Result<(), CheckTraceRaysError> {
+    let properties = device.physical_device().properties();
+    let count = properties.max_compute_work_group_count;
+    let size = properties.max_compute_work_group_size;
+    let max = [0, 1, 2].map(|i| (count[i] as u64 * size[i] as u64).min(u32::MAX as u64) as u32);
+
+    if dimensions[0] > max[0] || dimensions[1] > max[1] || dimensions[2] > max[2] {
+        return Err(CheckTraceRaysError::UnsupportedDimensions {
+            requested: dimensions,
+            max_supported: max,
+        });
+    }
+
+    let invocations = dimensions.iter().map(|&d| d as u64).product::<u64>();
+    // The property is always available when the extension is enabled.
+    let max_invocations = properties.max_ray_dispatch_invocation_count.unwrap_or(0) as u64;
+
+    if invocations > max_invocations {
+        return Err(CheckTraceRaysError::MaxRayDispatchInvocationCountExceeded {
+            requested: invocations,
+            max: max_invocations,
+        });
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking trace rays command validity.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckTraceRaysError {
+    /// The shader binding table was not created for the currently bound pipeline.
+    ShaderBindingTablePipelineMismatch,
+    /// The dimensions are too large for the device's limits.
+    UnsupportedDimensions {
+        /// The requested dimensions.
+        requested: [u32; 3],
+        /// The actual supported dimensions.
+        max_supported: [u32; 3],
+    },
+    /// The total number of rays is larger than the `max_ray_dispatch_invocation_count` limit.
+    MaxRayDispatchInvocationCountExceeded {
+        /// The requested number of rays.
+        requested: u64,
+        /// The maximum number of rays.
+        max: u64,
+    },
+}
+
+impl error::Error for CheckTraceRaysError {}
+
+impl fmt::Display for CheckTraceRaysError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckTraceRaysError::ShaderBindingTablePipelineMismatch => {
+                    "the shader binding table was not created for the currently bound pipeline"
+                }
+                CheckTraceRaysError::UnsupportedDimensions { .. } => {
+                    "the dimensions are too large for the device's limits"
+                }
+                CheckTraceRaysError::MaxRayDispatchInvocationCountExceeded { .. } => {
+                    "the total number of rays is larger than the maximum ray dispatch invocation count"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_dimensions_checked() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let attempted = [u32::MAX, 1, 1];
+
+        // Just in case the device is some kind of software implementation.
+        let properties = device.physical_device().properties();
+        if properties.max_compute_work_group_count[0] as u64
+            * properties.max_compute_work_group_size[0] as u64
+            >= u32::MAX as u64
+        {
+            return;
+        }
+
+        match check_dimensions(&device, attempted) {
+            Err(CheckTraceRaysError::UnsupportedDimensions { requested, .. }) => {
+                assert_eq!(requested, attempted);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn max_invocations_checked() {
+        let (device, _) = gfx_dev_and_queue_with!(
+            [
+                khr_acceleration_structure,
+                khr_buffer_device_address,
+                khr_deferred_host_operations,
+                ext_descriptor_indexing,
+                khr_spirv_1_4,
+                khr_shader_float_controls,
+                khr_ray_tracing_pipeline
+            ],
+            [ray_tracing_pipeline]
+        );
+
+        let properties = device.physical_device().properties();
+        let max = properties.max_ray_dispatch_invocation_count.unwrap() as u64;
+        let count = properties.max_compute_work_group_count;
+        let size = properties.max_compute_work_group_size;
+        let side = |i: usize| (count[i] as u64 * size[i] as u64).min(u32::MAX as u64);
+
+        // Only possible to exceed if the individual dimensions allow it.
+        if side(0) * side(1) <= max {
+            return;
+        }
+
+        let width = side(0) as u32;
+        let height = (max / side(0) + 1) as u32;
+        match check_dimensions(&device, [width, height, 1]) {
+            Err(CheckTraceRaysError::MaxRayDispatchInvocationCountExceeded {
+                requested, ..
+            }) => {
+                assert_eq!(requested, width as u64 * height as u64);
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::buffer::BufferAccess;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::DeviceSize;
+use crate::VulkanObject;
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+/// Checks whether transform feedback buffers can be bound.
+///
+/// # Panic
+///
+/// - Panics if one of the buffers was not created with `device`.
+///
+pub fn check_bind_transform_feedback_buffers(
+    device: &Device,
+    first_binding: u32,
+    buffers: &[Arc<dyn BufferAccess>],
+) -> Result<(), CheckTransformFeedbackError> {
+    check_transform_feedback_enabled(device)?;
+    check_max_transform_feedback_buffers(device, first_binding, buffers.len())?;
+
+    for buffer in buffers {
+        assert_eq!(
+            buffer.inner().buffer.device().internal_object(),
+            device.internal_object()
+        );
+
+        if !buffer.inner().buffer.usage().transform_feedback_buffer {
+            return Err(CheckTransformFeedbackError::BufferMissingUsage);
+        }
+
+        if buffer.inner().offset % 4 != 0 {
+            return Err(CheckTransformFeedbackError::OffsetNotAligned);
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks whether transform feedback can be begun or ended with the given counter buffers.
+///
+/// # Panic
+///
+/// - Panics if one of the buffers was not created with `device`.
+///
+pub fn check_transform_feedback_counter_buffers(
+    device: &Device,
+    first_counter_buffer: u32,
+    counter_buffers: &[Arc<dyn BufferAccess>],
+) -> Result<(), CheckTransformFeedbackError> {
+    check_transform_feedback_enabled(device)?;
+    check_max_transform_feedback_buffers(device, first_counter_buffer, counter_buffers.len())?;
+
+    for buffer in counter_buffers {
+        assert_eq!(
+            buffer.inner().buffer.device().internal_object(),
+            device.internal_object()
+        );
+
+        if !buffer
+            .inner()
+            .buffer
+            .usage()
+            .transform_feedback_counter_buffer
+        {
+            return Err(CheckTransformFeedbackError::BufferMissingUsage);
+        }
+
+        if buffer.inner().offset % 4 != 0 {
+            return Err(CheckTransformFeedbackError::OffsetNotAligned);
+        }
+
+        if buffer.size() < 4 {
+            return Err(CheckTransformFeedbackError::CounterBufferTooSmall {
+                obtained: buffer.size(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn check_transform_feedback_enabled(device: &Device) -> Result<(), CheckTransformFeedbackError> {
+    if !device.enabled_extensions().ext_transform_feedback {
+        return Err(CheckTransformFeedbackError::ExtensionNotEnabled);
+    }
+
+    if !device.enabled_features().transform_feedback {
+        return Err(CheckTransformFeedbackError::FeatureNotEnabled);
+    }
+
+    Ok(())
+}
+
+fn check_max_transform_feedback_buffers(
+    device: &Device,
+    first: u32,
+    count: usize,
+) -> Result<(), CheckTransformFeedbackError> {
+    let max = device
+        .physical_device()
+        .properties()
+        .max_transform_feedback_buffers
+        .unwrap_or(0);
+    let requested = first + count as u32;
+
+    if requested > max {
+        return Err(
+            CheckTransformFeedbackError::MaxTransformFeedbackBuffersExceeded { max, requested },
+        );
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a transform feedback command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckTransformFeedbackError {
+    /// The `ext_transform_feedback` extension must be enabled on the device.
+    ExtensionNotEnabled,
+    /// The `transform_feedback` feature must be enabled on the device.
+    FeatureNotEnabled,
+    /// The "transform feedback buffer" or "transform feedback counter buffer" usage must be
+    /// enabled on the buffer.
+    BufferMissingUsage,
+    /// The offset of the buffer must be 4-bytes aligned.
+    OffsetNotAligned,
+    /// A counter buffer must be large enough to hold a 32-bit counter.
+    CounterBufferTooSmall {
+        /// The size of the buffer in bytes.
+        obtained: DeviceSize,
+    },
+    /// The maximum number of transform feedback buffers has been exceeded.
+    MaxTransformFeedbackBuffersExceeded {
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was requested.
+        requested: u32,
+    },
+}
+
+impl error::Error for CheckTransformFeedbackError {}
+
+impl fmt::Display for CheckTransformFeedbackError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckTransformFeedbackError::ExtensionNotEnabled => {
+                    "the ext_transform_feedback extension must be enabled on the device"
+                }
+                CheckTransformFeedbackError::FeatureNotEnabled => {
+                    "the transform_feedback feature must be enabled on the device"
+                }
+                CheckTransformFeedbackError::BufferMissingUsage => {
+                    "the buffer is missing the transform feedback usage"
+                }
+                CheckTransformFeedbackError::OffsetNotAligned => {
+                    "the offset of the buffer is not aligned to 4 bytes"
+                }
+                CheckTransformFeedbackError::CounterBufferTooSmall { .. } => {
+                    "the counter buffer is too small to hold a 32-bit counter"
+                }
+                CheckTransformFeedbackError::MaxTransformFeedbackBuffersExceeded { .. } => {
+                    "the maximum number of transform feedback buffers has been exceeded"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::BufferUsage;
+    use crate::buffer::CpuAccessibleBuffer;
+
+    #[test]
+    fn missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        let buffer: Arc<dyn BufferAccess> =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0u32)
+                .unwrap();
+
+        match check_bind_transform_feedback_buffers(&device, 0, &[buffer]) {
+            Err(CheckTransformFeedbackError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+}
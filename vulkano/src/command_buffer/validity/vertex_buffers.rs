@@ -8,6 +8,7 @@
 // according to those terms.
 
 use crate::command_buffer::synced::CommandBufferState;
+use crate::pipeline::graphics::render_pass::PipelineRenderPassType;
 use crate::pipeline::graphics::vertex_input::VertexInputRate;
 use crate::pipeline::GraphicsPipeline;
 use crate::DeviceSize;
@@ -87,13 +88,14 @@ pub(in super::super) fn check_vertex_buffers(
             }
         }
 
-        if pipeline
-            .subpass()
-            .render_pass()
-            .desc()
-            .multiview()
-            .is_some()
-        {
+        let multiview = match pipeline.render_pass() {
+            PipelineRenderPassType::BeginRenderPass(subpass) => {
+                subpass.render_pass().desc().multiview().is_some()
+            }
+            PipelineRenderPassType::BeginRendering(info) => info.view_mask != 0,
+        };
+
+        if multiview {
             let max_instance_index = pipeline
                 .device()
                 .physical_device()
@@ -0,0 +1,323 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Operations on the host that can be deferred.
+//!
+//! Some expensive operations, such as building acceleration structures on the host with
+//! [`AccelerationStructure::build_on_host`](crate::acceleration_structure::AccelerationStructure::build_on_host)
+//! or creating a [`RayTracingPipeline`](crate::pipeline::RayTracingPipeline), can be deferred
+//! when a `DeferredOperation` is passed to them. The work is then done by all the threads that
+//! call [`join`](DeferredOperation::join) on the operation, which allows it to be spread over
+//! multiple CPU threads.
+//!
+//! The functions of vulkano that accept a deferred operation join it on the current thread as
+//! well, and only return once the operation has completed. This guarantees that the parameters
+//! that they borrow stay alive for as long as the implementation can access them. To keep the
+//! work off the main thread, call these functions from a worker thread, and have other threads
+//! join the operation while it is in progress.
+
+use crate::check_errors;
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::Error;
+use crate::OomError;
+use crate::Success;
+use crate::VulkanObject;
+use std::error;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+use std::thread;
+
+/// An operation on the host that has been deferred.
+///
+/// The operation is executed by the threads that call [`join`](DeferredOperation::join). The
+/// object can be shared between threads by wrapping it in an `Arc`.
+///
+/// Dropping a `DeferredOperation` blocks the current thread until the operation has completed,
+/// joining it if necessary.
+#[derive(Debug)]
+pub struct DeferredOperation {
+    handle: ash::vk::DeferredOperationKHR,
+    device: Arc<Device>,
+}
+
+impl DeferredOperation {
+    /// Creates a new `DeferredOperation`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the
+    ///   [`khr_deferred_host_operations`](crate::device::DeviceExtensions::khr_deferred_host_operations)
+    ///   extension is not enabled on the device.
+    pub fn new(device: Arc<Device>) -> Result<Arc<DeferredOperation>, OomError> {
+        assert!(
+            device.enabled_extensions().khr_deferred_host_operations,
+            "the khr_deferred_host_operations extension must be enabled on the device"
+        );
+
+        let handle = unsafe {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            check_errors(
+                fns.khr_deferred_host_operations
+                    .create_deferred_operation_khr(
+                        device.internal_object(),
+                        ptr::null(),
+                        output.as_mut_ptr(),
+                    ),
+            )?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(DeferredOperation { handle, device }))
+    }
+
+    /// Executes a portion of the operation on the current thread.
+    ///
+    /// This can be called from several threads at the same time, up to the number returned by
+    /// [`max_concurrency`](DeferredOperation::max_concurrency).
+    pub fn join(&self) -> Result<DeferredOperationJoinStatus, DeferredOperationError> {
+        let result = unsafe {
+            let fns = self.device.fns();
+            self.device.check_errors(
+                fns.khr_deferred_host_operations
+                    .deferred_operation_join_khr(self.device.internal_object(), self.handle),
+            )?
+        };
+
+        match result {
+            Success::Success => Ok(DeferredOperationJoinStatus::Complete),
+            Success::ThreadDone => Ok(DeferredOperationJoinStatus::ThreadDone),
+            Success::ThreadIdle => Ok(DeferredOperationJoinStatus::ThreadIdle),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the result of the operation, or `None` if it has not completed yet.
+    ///
+    /// If no operation has been deferred to `self`, this returns `Some(Ok(()))`.
+    pub fn result(&self) -> Option<Result<(), DeferredOperationError>> {
+        let result = unsafe {
+            let fns = self.device.fns();
+            fns.khr_deferred_host_operations
+                .get_deferred_operation_result_khr(self.device.internal_object(), self.handle)
+        };
+
+        match result {
+            ash::vk::Result::NOT_READY => None,
+            ash::vk::Result::ERROR_OUT_OF_HOST_MEMORY
+            | ash::vk::Result::ERROR_OUT_OF_DEVICE_MEMORY
+            | ash::vk::Result::ERROR_DEVICE_LOST => Some(
+                self.device
+                    .check_errors(result)
+                    .map(|_| ())
+                    .map_err(Into::into),
+            ),
+            // Any other error comes from the command that was deferred, and may not be known to
+            // `check_errors`.
+            result if result.as_raw() < 0 => Some(Err(DeferredOperationError::OperationFailed)),
+            _ => Some(Ok(())),
+        }
+    }
+
+    /// Waits for the operation to complete, joining it on the current thread as long as there is
+    /// work left for it, then returns its result.
+    pub fn wait(&self) -> Result<(), DeferredOperationError> {
+        if let Some(result) = self.result() {
+            return result;
+        }
+
+        loop {
+            match self.join()? {
+                DeferredOperationJoinStatus::Complete => break,
+                DeferredOperationJoinStatus::ThreadDone => {
+                    // The remaining work is being done by other threads.
+                    loop {
+                        if let Some(result) = self.result() {
+                            return result;
+                        }
+
+                        thread::yield_now();
+                    }
+                }
+                DeferredOperationJoinStatus::ThreadIdle => thread::yield_now(),
+            }
+        }
+
+        self.result().unwrap()
+    }
+
+    /// Returns the maximum number of threads that can usefully call
+    /// [`join`](DeferredOperation::join) on the operation at the same time.
+    ///
+    /// This can change over the course of the operation, so it should be queried again when
+    /// deciding whether to start more threads.
+    #[inline]
+    pub fn max_concurrency(&self) -> u32 {
+        unsafe {
+            let fns = self.device.fns();
+            fns.khr_deferred_host_operations
+                .get_deferred_operation_max_concurrency_khr(
+                    self.device.internal_object(),
+                    self.handle,
+                )
+        }
+    }
+}
+
+unsafe impl DeviceOwned for DeferredOperation {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for DeferredOperation {
+    type Object = ash::vk::DeferredOperationKHR;
+
+    #[inline]
+    fn internal_object(&self) -> ash::vk::DeferredOperationKHR {
+        self.handle
+    }
+}
+
+impl Drop for DeferredOperation {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = self.wait(); // Ignore errors
+
+        unsafe {
+            let fns = self.device.fns();
+            fns.khr_deferred_host_operations
+                .destroy_deferred_operation_khr(
+                    self.device.internal_object(),
+                    self.handle,
+                    ptr::null(),
+                );
+        }
+    }
+}
+
+/// The status of a deferred operation after a call to [`join`](DeferredOperation::join).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeferredOperationJoinStatus {
+    /// The operation has completed.
+    Complete,
+
+    /// There is no more work for the current thread to do, but the operation has not completed
+    /// yet because other threads are still working on it.
+    ThreadDone,
+
+    /// There is currently no work for the current thread to do, but more may become available
+    /// later. `join` should be called again after a short while.
+    ThreadIdle,
+}
+
+/// Error that can be returned by a deferred operation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DeferredOperationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
+    DeviceLost,
+
+    /// The command that was deferred returned an error.
+    OperationFailed,
+}
+
+impl error::Error for DeferredOperationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DeferredOperationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DeferredOperationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DeferredOperationError::OomError(_) => "no memory available",
+                DeferredOperationError::DeviceLost => "the device was lost",
+                DeferredOperationError::OperationFailed => {
+                    "the command that was deferred returned an error"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for DeferredOperationError {
+    #[inline]
+    fn from(err: OomError) -> DeferredOperationError {
+        DeferredOperationError::OomError(err)
+    }
+}
+
+impl From<Error> for DeferredOperationError {
+    #[inline]
+    fn from(err: Error) -> DeferredOperationError {
+        match err {
+            err @ Error::OutOfHostMemory => DeferredOperationError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => DeferredOperationError::OomError(OomError::from(err)),
+            Error::DeviceLost => DeferredOperationError::DeviceLost,
+            _ => DeferredOperationError::OperationFailed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deferred::DeferredOperation;
+    use crate::device::physical::PhysicalDevice;
+    use crate::device::Device;
+    use crate::device::DeviceExtensions;
+    use crate::device::Features;
+
+    #[test]
+    fn wait_unused() {
+        let instance = instance!();
+
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        if !physical.supported_extensions().khr_deferred_host_operations {
+            return;
+        }
+
+        let queue_family = physical.queue_families().next().unwrap();
+        let (device, _) = Device::new(
+            physical,
+            &Features::none(),
+            &DeviceExtensions {
+                khr_deferred_host_operations: true,
+                ..DeviceExtensions::none()
+            },
+            [(queue_family, 0.5)].iter().cloned(),
+        )
+        .unwrap();
+
+        // Nothing has been deferred to the operation, so it has already completed.
+        let operation = DeferredOperation::new(device).unwrap();
+        assert_eq!(operation.result(), Some(Ok(())));
+        operation.wait().unwrap();
+        drop(operation);
+    }
+}
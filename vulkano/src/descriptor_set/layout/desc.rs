@@ -234,6 +234,49 @@ impl DescriptorSetDesc {
         }
     }
 
+    /// Sets whether a descriptor is partially bound.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the binding number refers to an empty binding.
+    pub fn set_partially_bound(&mut self, binding_num: u32, enabled: bool) {
+        match self
+            .descriptors
+            .get_mut(binding_num as usize)
+            .and_then(|b| b.as_mut())
+        {
+            Some(desc) => desc.partially_bound = enabled,
+            None => panic!("descriptor is empty"),
+        }
+    }
+
+    /// Sets whether a descriptor can be updated after the descriptor set has been bound.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the binding number refers to an empty binding.
+    pub fn set_update_after_bind(&mut self, binding_num: u32, enabled: bool) {
+        match self
+            .descriptors
+            .get_mut(binding_num as usize)
+            .and_then(|b| b.as_mut())
+        {
+            Some(desc) => desc.update_after_bind = enabled,
+            None => panic!("descriptor is empty"),
+        }
+    }
+
+    /// Returns whether any descriptor in the set can be updated after the descriptor set has been
+    /// bound. If so, descriptor sets with this layout must be allocated from a pool that was
+    /// created with the update-after-bind flag.
+    #[inline]
+    pub fn is_update_after_bind(&self) -> bool {
+        self.descriptors
+            .iter()
+            .flatten()
+            .any(|desc| desc.update_after_bind)
+    }
+
     /// Returns whether `self` is compatible with `other`.
     ///
     /// "Compatible" in this sense is defined by the Vulkan specification under the section
@@ -289,6 +332,18 @@ pub struct DescriptorDesc {
     /// variable count in a descriptor set, and it must be the last binding.
     pub variable_count: bool,
 
+    /// True if the descriptor does not need to be written to before the descriptor set is bound,
+    /// as long as it is not dynamically used by a shader. Individual array elements of the
+    /// binding may also be left unwritten.
+    pub partially_bound: bool,
+
+    /// True if the descriptor may be written to after the descriptor set has been bound in a
+    /// command buffer, as long as the command buffer has not yet been submitted. The descriptor
+    /// set must then be allocated from a pool created with the update-after-bind flag.
+    ///
+    /// This is not allowed for dynamic buffer and input attachment descriptors.
+    pub update_after_bind: bool,
+
     /// Which shader stages are going to access this descriptor.
     pub stages: ShaderStages,
 
@@ -317,6 +372,7 @@ impl DescriptorDesc {
             image_multisampled,
             image_scalar_type,
             image_view_type,
+            input_attachment_index,
             sampler_compare,
             sampler_no_unnormalized_coordinates,
             sampler_no_ycbcr_conversion,
@@ -366,12 +422,15 @@ impl From<&DescriptorRequirements> for DescriptorDesc {
             DescriptorType::UniformBufferDynamic => DescriptorType::UniformBufferDynamic,
             DescriptorType::StorageBufferDynamic => DescriptorType::StorageBufferDynamic,
             DescriptorType::InputAttachment => DescriptorType::InputAttachment,
+            DescriptorType::AccelerationStructure => DescriptorType::AccelerationStructure,
         };
 
         Self {
             ty,
             descriptor_count: reqs.descriptor_count,
             variable_count: false,
+            partially_bound: false,
+            update_after_bind: false,
             stages: reqs.stages,
             immutable_samplers: Vec::new(),
         }
@@ -393,6 +452,8 @@ pub enum DescriptorType {
     UniformBufferDynamic = ash::vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC.as_raw(),
     StorageBufferDynamic = ash::vk::DescriptorType::STORAGE_BUFFER_DYNAMIC.as_raw(),
     InputAttachment = ash::vk::DescriptorType::INPUT_ATTACHMENT.as_raw(),
+    /// Requires the `khr_acceleration_structure` extension.
+    AccelerationStructure = ash::vk::DescriptorType::ACCELERATION_STRUCTURE_KHR.as_raw(),
 }
 
 impl From<DescriptorType> for ash::vk::DescriptorType {
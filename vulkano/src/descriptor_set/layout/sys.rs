@@ -82,6 +82,15 @@ impl DescriptorSetLayout {
 
             let ty = binding_desc.ty;
 
+            if ty == DescriptorType::AccelerationStructure
+                && !device.enabled_extensions().khr_acceleration_structure
+            {
+                return Err(DescriptorSetLayoutError::ExtensionNotEnabled {
+                    extension: "khr_acceleration_structure",
+                    reason: "binding has the AccelerationStructure descriptor type",
+                });
+            }
+
             if set_desc.is_push_descriptor() {
                 if matches!(
                     ty,
@@ -94,13 +103,9 @@ impl DescriptorSetLayout {
                     return Err(DescriptorSetLayoutError::PushDescriptorVariableCount);
                 }
 
-                // TODO: VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-flags-03003
-                // If VkDescriptorSetLayoutCreateInfo::flags includes
-                // VK_DESCRIPTOR_SET_LAYOUT_CREATE_PUSH_DESCRIPTOR_BIT_KHR,
-                // then all elements of pBindingFlags must not include
-                // VK_DESCRIPTOR_BINDING_UPDATE_AFTER_BIND_BIT,
-                // VK_DESCRIPTOR_BINDING_UPDATE_UNUSED_WHILE_PENDING_BIT, or
-                // VK_DESCRIPTOR_BINDING_VARIABLE_DESCRIPTOR_COUNT_BIT
+                if binding_desc.update_after_bind {
+                    return Err(DescriptorSetLayoutError::PushDescriptorUpdateAfterBind);
+                }
 
                 // TODO: VUID-VkDescriptorSetLayoutCreateInfo-flags-02208
                 // If flags contains VK_DESCRIPTOR_SET_LAYOUT_CREATE_PUSH_DESCRIPTOR_BIT_KHR, then all
@@ -189,6 +194,68 @@ impl DescriptorSetLayout {
                 binding_flags |= ash::vk::DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT;
             }
 
+            if binding_desc.partially_bound {
+                if !device.enabled_features().descriptor_binding_partially_bound {
+                    return Err(DescriptorSetLayoutError::FeatureNotEnabled {
+                        feature: "descriptor_binding_partially_bound",
+                        reason: "binding is partially bound",
+                    });
+                }
+
+                binding_flags |= ash::vk::DescriptorBindingFlags::PARTIALLY_BOUND;
+            }
+
+            if binding_desc.update_after_bind {
+                let features = device.enabled_features();
+                let feature = match ty {
+                    DescriptorType::Sampler
+                    | DescriptorType::CombinedImageSampler
+                    | DescriptorType::SampledImage => (
+                        features.descriptor_binding_sampled_image_update_after_bind,
+                        "descriptor_binding_sampled_image_update_after_bind",
+                    ),
+                    DescriptorType::StorageImage => (
+                        features.descriptor_binding_storage_image_update_after_bind,
+                        "descriptor_binding_storage_image_update_after_bind",
+                    ),
+                    DescriptorType::UniformTexelBuffer => (
+                        features.descriptor_binding_uniform_texel_buffer_update_after_bind,
+                        "descriptor_binding_uniform_texel_buffer_update_after_bind",
+                    ),
+                    DescriptorType::StorageTexelBuffer => (
+                        features.descriptor_binding_storage_texel_buffer_update_after_bind,
+                        "descriptor_binding_storage_texel_buffer_update_after_bind",
+                    ),
+                    DescriptorType::UniformBuffer => (
+                        features.descriptor_binding_uniform_buffer_update_after_bind,
+                        "descriptor_binding_uniform_buffer_update_after_bind",
+                    ),
+                    DescriptorType::StorageBuffer => (
+                        features.descriptor_binding_storage_buffer_update_after_bind,
+                        "descriptor_binding_storage_buffer_update_after_bind",
+                    ),
+                    DescriptorType::AccelerationStructure => (
+                        features.descriptor_binding_acceleration_structure_update_after_bind,
+                        "descriptor_binding_acceleration_structure_update_after_bind",
+                    ),
+                    DescriptorType::UniformBufferDynamic
+                    | DescriptorType::StorageBufferDynamic
+                    | DescriptorType::InputAttachment => {
+                        return Err(DescriptorSetLayoutError::UpdateAfterBindWrongDescriptorType);
+                    }
+                };
+
+                if let (false, feature) = feature {
+                    return Err(DescriptorSetLayoutError::FeatureNotEnabled {
+                        feature,
+                        reason: "binding can be updated after bind",
+                    });
+                }
+
+                binding_flags |= ash::vk::DescriptorBindingFlags::UPDATE_AFTER_BIND;
+                flags |= ash::vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL;
+            }
+
             bindings_vk.push(ash::vk::DescriptorSetLayoutBinding {
                 binding: binding as u32,
                 descriptor_type: ty.into(),
@@ -293,6 +360,13 @@ impl DescriptorSetLayout {
             .unwrap_or(0)
     }
 
+    /// Returns whether any binding in the layout can be updated after bind. Descriptor sets with
+    /// this layout must be allocated from a pool created for update-after-bind layouts.
+    #[inline]
+    pub fn is_update_after_bind(&self) -> bool {
+        self.desc.is_update_after_bind()
+    }
+
     /// Returns the number of binding slots in the set.
     #[inline]
     pub fn num_bindings(&self) -> u32 {
@@ -390,6 +464,13 @@ pub enum DescriptorSetLayoutError {
     /// The layout was being created for push descriptors, but included a variable count binding.
     PushDescriptorVariableCount,
 
+    /// The layout was being created for push descriptors, but included an update-after-bind
+    /// binding.
+    PushDescriptorUpdateAfterBind,
+
+    /// An update-after-bind binding was a dynamic buffer or input attachment descriptor.
+    UpdateAfterBindWrongDescriptorType,
+
     /// Variable count descriptor must be last binding.
     VariableCountDescMustBeLast,
 
@@ -443,9 +524,18 @@ impl std::fmt::Display for DescriptorSetLayoutError {
             Self::PushDescriptorVariableCount => {
                 write!(fmt, "the layout was being created for push descriptors, but included a variable count binding")
             }
+            Self::PushDescriptorUpdateAfterBind => {
+                write!(fmt, "the layout was being created for push descriptors, but included an update-after-bind binding")
+            }
             Self::OomError(_) => {
                 write!(fmt, "out of memory")
             }
+            Self::UpdateAfterBindWrongDescriptorType => {
+                write!(
+                    fmt,
+                    "an update-after-bind binding was a dynamic buffer or input attachment descriptor"
+                )
+            }
             Self::VariableCountDescMustBeLast => {
                 write!(fmt, "variable count descriptor must be last binding")
             }
@@ -464,6 +554,7 @@ mod tests {
     use crate::descriptor_set::layout::DescriptorDesc;
     use crate::descriptor_set::layout::DescriptorSetDesc;
     use crate::descriptor_set::layout::DescriptorSetLayout;
+    use crate::descriptor_set::layout::DescriptorSetLayoutError;
     use crate::descriptor_set::layout::DescriptorType;
     use crate::descriptor_set::pool::DescriptorsCount;
     use crate::shader::ShaderStages;
@@ -483,6 +574,8 @@ mod tests {
             ty: DescriptorType::UniformBuffer,
             descriptor_count: 1,
             variable_count: false,
+            partially_bound: false,
+            update_after_bind: false,
             stages: ShaderStages::all_graphics(),
             immutable_samplers: Vec::new(),
         };
@@ -501,4 +594,50 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn update_after_bind_missing_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorType::SampledImage,
+            descriptor_count: 1,
+            variable_count: false,
+            partially_bound: false,
+            update_after_bind: true,
+            stages: ShaderStages::all_graphics(),
+            immutable_samplers: Vec::new(),
+        };
+
+        match DescriptorSetLayout::new(device, DescriptorSetDesc::new(iter::once(Some(layout)))) {
+            Err(DescriptorSetLayoutError::FeatureNotEnabled {
+                feature: "descriptor_binding_sampled_image_update_after_bind",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn acceleration_structure_missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorType::AccelerationStructure,
+            descriptor_count: 1,
+            variable_count: false,
+            partially_bound: false,
+            update_after_bind: false,
+            stages: ShaderStages::all_graphics(),
+            immutable_samplers: Vec::new(),
+        };
+
+        match DescriptorSetLayout::new(device, DescriptorSetDesc::new(iter::once(Some(layout)))) {
+            Err(DescriptorSetLayoutError::ExtensionNotEnabled {
+                extension: "khr_acceleration_structure",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
 }
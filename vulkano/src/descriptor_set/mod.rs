@@ -78,8 +78,9 @@ use self::layout::DescriptorSetLayout;
 pub use self::persistent::PersistentDescriptorSet;
 pub use self::single_layout_pool::SingleLayoutDescSetPool;
 use self::sys::UnsafeDescriptorSet;
-pub(crate) use self::update::{check_descriptor_write, DescriptorWriteInfo};
+pub(crate) use self::update::check_descriptor_write;
 pub use self::update::{DescriptorSetUpdateError, WriteDescriptorSet, WriteDescriptorSetElements};
+use crate::acceleration_structure::AccelerationStructure;
 use crate::buffer::BufferAccess;
 use crate::buffer::BufferViewAbstract;
 use crate::descriptor_set::layout::DescriptorType;
@@ -190,23 +191,10 @@ impl DescriptorSetInner {
 
         if !write_descriptor_set.is_empty() {
             for (info, write) in descriptor_write_info
-                .iter()
+                .iter_mut()
                 .zip(write_descriptor_set.iter_mut())
             {
-                match info {
-                    DescriptorWriteInfo::Image(info) => {
-                        write.descriptor_count = info.len() as u32;
-                        write.p_image_info = info.as_ptr();
-                    }
-                    DescriptorWriteInfo::Buffer(info) => {
-                        write.descriptor_count = info.len() as u32;
-                        write.p_buffer_info = info.as_ptr();
-                    }
-                    DescriptorWriteInfo::BufferView(info) => {
-                        write.descriptor_count = info.len() as u32;
-                        write.p_texel_buffer_view = info.as_ptr();
-                    }
-                }
+                info.set_info(write);
             }
         }
 
@@ -303,6 +291,9 @@ impl DescriptorSetResources {
                             DescriptorBindingResources::None(smallvec![Some(()); count])
                         }
                     }
+                    DescriptorType::AccelerationStructure => {
+                        DescriptorBindingResources::AccelerationStructure(smallvec![None; count])
+                    }
                 };
                 (binding_num, binding_resources)
             })
@@ -341,6 +332,7 @@ pub enum DescriptorBindingResources {
     ImageView(Elements<Arc<dyn ImageViewAbstract>>),
     ImageViewSampler(Elements<(Arc<dyn ImageViewAbstract>, Arc<Sampler>)>),
     Sampler(Elements<Arc<Sampler>>),
+    AccelerationStructure(Elements<Arc<AccelerationStructure>>),
 }
 
 type Elements<T> = SmallVec<[Option<T>; 1]>;
@@ -399,6 +391,10 @@ impl DescriptorBindingResources {
                 DescriptorBindingResources::Sampler(resources),
                 WriteDescriptorSetElements::Sampler(elements),
             ) => write_resources(first, resources, elements),
+            (
+                DescriptorBindingResources::AccelerationStructure(resources),
+                WriteDescriptorSetElements::AccelerationStructure(elements),
+            ) => write_resources(first, resources, elements),
             _ => panic!(
                 "descriptor write for binding {} has wrong resource type",
                 write.binding(),
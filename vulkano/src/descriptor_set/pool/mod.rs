@@ -112,6 +112,9 @@ macro_rules! descriptors_count {
                     DescriptorType::UniformBufferDynamic => self.uniform_buffer_dynamic += num,
                     DescriptorType::StorageBufferDynamic => self.storage_buffer_dynamic += num,
                     DescriptorType::InputAttachment => self.input_attachment += num,
+                    DescriptorType::AccelerationStructure => {
+                        self.acceleration_structure += num
+                    }
                 };
             }
         }
@@ -228,4 +231,5 @@ descriptors_count! {
     sampler,
     combined_image_sampler,
     input_attachment,
+    acceleration_structure,
 }
@@ -38,6 +38,7 @@ struct Pool {
     pool: UnsafeDescriptorPool,
     remaining_capacity: DescriptorsCount,
     remaining_sets_count: u32,
+    update_after_bind: bool,
 }
 
 impl StdDescriptorPool {
@@ -94,6 +95,10 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
                 continue;
             }
 
+            if pool.update_after_bind != layout.is_update_after_bind() {
+                continue;
+            }
+
             if !(pool.remaining_capacity >= *layout.descriptors_count()) {
                 continue;
             }
@@ -130,7 +135,13 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
         let count = layout.descriptors_count().clone() * 40;
         // Failure to allocate a new pool results in an error for the whole function because
         // there's no way we can recover from that.
-        let mut new_pool = UnsafeDescriptorPool::new(self.device.clone(), &count, 40, true)?;
+        let mut new_pool = UnsafeDescriptorPool::new(
+            self.device.clone(),
+            &count,
+            40,
+            true,
+            layout.is_update_after_bind(),
+        )?;
 
         let alloc = unsafe {
             match new_pool.alloc([DescriptorSetAllocateInfo {
@@ -155,6 +166,7 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
             pool: new_pool,
             remaining_capacity: count - *layout.descriptors_count(),
             remaining_sets_count: 40 - 1,
+            update_after_bind: layout.is_update_after_bind(),
         }));
 
         pools.push(pool_obj.clone());
@@ -221,6 +233,8 @@ mod tests {
             ty: DescriptorType::Sampler,
             descriptor_count: 1,
             variable_count: false,
+            partially_bound: false,
+            update_after_bind: false,
             stages: ShaderStages::all(),
             immutable_samplers: Vec::new(),
         };
@@ -41,6 +41,10 @@ impl UnsafeDescriptorPool {
     /// If `free_descriptor_set_bit` is `true`, then individual descriptor sets can be free'd from
     /// the pool. Otherwise you must reset or destroy the whole pool at once.
     ///
+    /// If `update_after_bind` is `true`, then descriptor sets whose layout contains
+    /// update-after-bind bindings can be allocated from the pool. Such layouts can only be
+    /// allocated from a pool with this flag.
+    ///
     /// # Panic
     ///
     /// - Panics if all the descriptors count are 0.
@@ -51,6 +55,7 @@ impl UnsafeDescriptorPool {
         count: &DescriptorsCount,
         max_sets: u32,
         free_descriptor_set_bit: bool,
+        update_after_bind: bool,
     ) -> Result<UnsafeDescriptorPool, OomError> {
         let fns = device.fns();
 
@@ -95,6 +100,10 @@ impl UnsafeDescriptorPool {
             ash::vk::DescriptorType::COMBINED_IMAGE_SAMPLER
         );
         elem!(input_attachment, ash::vk::DescriptorType::INPUT_ATTACHMENT);
+        elem!(
+            acceleration_structure,
+            ash::vk::DescriptorType::ACCELERATION_STRUCTURE_KHR
+        );
 
         assert!(
             !pool_sizes.is_empty(),
@@ -102,12 +111,18 @@ impl UnsafeDescriptorPool {
         );
 
         let pool = unsafe {
+            let mut flags = ash::vk::DescriptorPoolCreateFlags::empty();
+
+            if free_descriptor_set_bit {
+                flags |= ash::vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET;
+            }
+
+            if update_after_bind {
+                flags |= ash::vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND;
+            }
+
             let infos = ash::vk::DescriptorPoolCreateInfo {
-                flags: if free_descriptor_set_bit {
-                    ash::vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET
-                } else {
-                    ash::vk::DescriptorPoolCreateFlags::empty()
-                },
+                flags,
                 max_sets: max_sets,
                 pool_size_count: pool_sizes.len() as u32,
                 p_pool_sizes: pool_sizes.as_ptr(),
@@ -391,7 +406,7 @@ mod tests {
             ..DescriptorsCount::zero()
         };
 
-        let _ = UnsafeDescriptorPool::new(device, &desc, 10, false).unwrap();
+        let _ = UnsafeDescriptorPool::new(device, &desc, 10, false, false).unwrap();
     }
 
     #[test]
@@ -403,7 +418,7 @@ mod tests {
         };
 
         assert_should_panic!("The maximum number of sets can't be 0", {
-            let _ = UnsafeDescriptorPool::new(device, &desc, 0, false);
+            let _ = UnsafeDescriptorPool::new(device, &desc, 0, false, false);
         });
     }
 
@@ -412,7 +427,7 @@ mod tests {
         let (device, _) = gfx_dev_and_queue!();
 
         assert_should_panic!("All the descriptors count of a pool are 0", {
-            let _ = UnsafeDescriptorPool::new(device, &DescriptorsCount::zero(), 10, false);
+            let _ = UnsafeDescriptorPool::new(device, &DescriptorsCount::zero(), 10, false, false);
         });
     }
 
@@ -424,6 +439,8 @@ mod tests {
             ty: DescriptorType::UniformBuffer,
             descriptor_count: 1,
             variable_count: false,
+            partially_bound: false,
+            update_after_bind: false,
             stages: ShaderStages::all_graphics(),
             immutable_samplers: Vec::new(),
         };
@@ -439,7 +456,7 @@ mod tests {
             ..DescriptorsCount::zero()
         };
 
-        let mut pool = UnsafeDescriptorPool::new(device, &desc, 10, false).unwrap();
+        let mut pool = UnsafeDescriptorPool::new(device, &desc, 10, false, false).unwrap();
         unsafe {
             let sets = pool
                 .alloc([DescriptorSetAllocateInfo {
@@ -460,6 +477,8 @@ mod tests {
             ty: DescriptorType::UniformBuffer,
             descriptor_count: 1,
             variable_count: false,
+            partially_bound: false,
+            update_after_bind: false,
             stages: ShaderStages::all_graphics(),
             immutable_samplers: Vec::new(),
         };
@@ -476,7 +495,7 @@ mod tests {
         assert_should_panic!(
             "Tried to allocate from a pool with a set layout of a different device",
             {
-                let mut pool = UnsafeDescriptorPool::new(device2, &desc, 10, false).unwrap();
+                let mut pool = UnsafeDescriptorPool::new(device2, &desc, 10, false, false).unwrap();
 
                 unsafe {
                     let _ = pool.alloc([DescriptorSetAllocateInfo {
@@ -497,7 +516,7 @@ mod tests {
             ..DescriptorsCount::zero()
         };
 
-        let mut pool = UnsafeDescriptorPool::new(device, &desc, 1, false).unwrap();
+        let mut pool = UnsafeDescriptorPool::new(device, &desc, 1, false, false).unwrap();
         unsafe {
             let sets = pool.alloc(iter::empty()).unwrap();
             assert_eq!(sets.count(), 0);
@@ -108,6 +108,7 @@ impl SingleLayoutDescSetPool {
                 &count,
                 self.set_count as u32,
                 false,
+                self.layout.is_update_after_bind(),
             )?;
 
             let reserve = unsafe {
@@ -10,7 +10,7 @@
 //! Low-level descriptor set.
 
 use crate::descriptor_set::layout::DescriptorSetLayout;
-use crate::descriptor_set::update::{DescriptorWriteInfo, WriteDescriptorSet};
+use crate::descriptor_set::update::WriteDescriptorSet;
 use crate::device::DeviceOwned;
 use crate::VulkanObject;
 use smallvec::SmallVec;
@@ -41,14 +41,15 @@ impl UnsafeDescriptorSet {
     /// - Doesn't keep the resources alive. You have to do that yourself.
     /// - Updating a descriptor set obeys synchronization rules that aren't checked here. Once a
     ///   command buffer contains a pointer/reference to a descriptor set, it is illegal to write
-    ///   to it.
+    ///   to it, unless the written bindings were created with `update_after_bind` and the command
+    ///   buffer has not been submitted yet.
     ///
     pub unsafe fn write<'a>(
         &mut self,
         layout: &DescriptorSetLayout,
         writes: impl IntoIterator<Item = &'a WriteDescriptorSet>,
     ) {
-        let (infos, mut writes): (SmallVec<[_; 8]>, SmallVec<[_; 8]>) = writes
+        let (mut infos, mut writes): (SmallVec<[_; 8]>, SmallVec<[_; 8]>) = writes
             .into_iter()
             .map(|write| {
                 let descriptor_type = layout.descriptor(write.binding()).unwrap().ty;
@@ -67,23 +68,8 @@ impl UnsafeDescriptorSet {
         }
 
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
-            match info {
-                DescriptorWriteInfo::Image(info) => {
-                    write.descriptor_count = info.len() as u32;
-                    write.p_image_info = info.as_ptr();
-                }
-                DescriptorWriteInfo::Buffer(info) => {
-                    write.descriptor_count = info.len() as u32;
-                    write.p_buffer_info = info.as_ptr();
-                }
-                DescriptorWriteInfo::BufferView(info) => {
-                    write.descriptor_count = info.len() as u32;
-                    write.p_texel_buffer_view = info.as_ptr();
-                }
-            }
-
-            debug_assert!(write.descriptor_count != 0);
+        for (info, write) in infos.iter_mut().zip(writes.iter_mut()) {
+            info.set_info(write);
         }
 
         let fns = layout.device().fns();
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use crate::acceleration_structure::{AccelerationStructure, AccelerationStructureType};
 use crate::buffer::{BufferAccess, BufferInner, BufferViewAbstract};
 use crate::descriptor_set::layout::{DescriptorDesc, DescriptorType};
 use crate::descriptor_set::DescriptorSetLayout;
@@ -176,6 +177,31 @@ impl WriteDescriptorSet {
         }
     }
 
+    /// Write a single acceleration structure to array element 0.
+    #[inline]
+    pub fn acceleration_structure(
+        binding: u32,
+        acceleration_structure: Arc<AccelerationStructure>,
+    ) -> Self {
+        Self::acceleration_structure_array(binding, 0, [acceleration_structure])
+    }
+
+    /// Write a number of consecutive acceleration structure elements.
+    #[inline]
+    pub fn acceleration_structure_array(
+        binding: u32,
+        first_array_element: u32,
+        elements: impl IntoIterator<Item = Arc<AccelerationStructure>>,
+    ) -> Self {
+        let elements: SmallVec<_> = elements.into_iter().collect();
+        assert!(!elements.is_empty());
+        Self {
+            binding,
+            first_array_element,
+            elements: WriteDescriptorSetElements::AccelerationStructure(elements),
+        }
+    }
+
     /// Returns the binding number that is updated by this descriptor write.
     #[inline]
     pub fn binding(&self) -> u32 {
@@ -320,6 +346,19 @@ impl WriteDescriptorSet {
                         .collect(),
                 )
             }
+            WriteDescriptorSetElements::AccelerationStructure(elements) => {
+                debug_assert!(matches!(
+                    descriptor_type,
+                    DescriptorType::AccelerationStructure
+                ));
+                DescriptorWriteInfo::AccelerationStructure(
+                    elements
+                        .iter()
+                        .map(|acceleration_structure| acceleration_structure.internal_object())
+                        .collect(),
+                    Default::default(),
+                )
+            }
         }
     }
 
@@ -350,6 +389,7 @@ pub enum WriteDescriptorSetElements {
     ImageView(SmallVec<[Arc<dyn ImageViewAbstract>; 1]>),
     ImageViewSampler(SmallVec<[(Arc<dyn ImageViewAbstract>, Arc<Sampler>); 1]>),
     Sampler(SmallVec<[Arc<Sampler>; 1]>),
+    AccelerationStructure(SmallVec<[Arc<AccelerationStructure>; 1]>),
 }
 
 impl WriteDescriptorSetElements {
@@ -363,6 +403,7 @@ impl WriteDescriptorSetElements {
             Self::ImageView(elements) => elements.len() as u32,
             Self::ImageViewSampler(elements) => elements.len() as u32,
             Self::Sampler(elements) => elements.len() as u32,
+            Self::AccelerationStructure(elements) => elements.len() as u32,
         }
     }
 }
@@ -372,10 +413,16 @@ pub(crate) enum DescriptorWriteInfo {
     Image(SmallVec<[ash::vk::DescriptorImageInfo; 1]>),
     Buffer(SmallVec<[ash::vk::DescriptorBufferInfo; 1]>),
     BufferView(SmallVec<[ash::vk::BufferView; 1]>),
+    AccelerationStructure(
+        SmallVec<[ash::vk::AccelerationStructureKHR; 1]>,
+        ash::vk::WriteDescriptorSetAccelerationStructureKHR,
+    ),
 }
 
 impl DescriptorWriteInfo {
-    fn set_info(&self, write: &mut ash::vk::WriteDescriptorSet) {
+    /// Sets the info pointers of `write` to point into `self`. `self` must not be moved until
+    /// `write` has been used.
+    pub(crate) fn set_info(&mut self, write: &mut ash::vk::WriteDescriptorSet) {
         match self {
             DescriptorWriteInfo::Image(info) => {
                 write.descriptor_count = info.len() as u32;
@@ -389,6 +436,12 @@ impl DescriptorWriteInfo {
                 write.descriptor_count = info.len() as u32;
                 write.p_texel_buffer_view = info.as_ptr();
             }
+            DescriptorWriteInfo::AccelerationStructure(handles, info) => {
+                info.acceleration_structure_count = handles.len() as u32;
+                info.p_acceleration_structures = handles.as_ptr();
+                write.descriptor_count = handles.len() as u32;
+                write.p_next = info as *const _ as *const _;
+            }
         }
 
         debug_assert!(write.descriptor_count != 0);
@@ -882,6 +935,29 @@ pub(crate) fn check_descriptor_write<'a>(
                 })
             }
         },
+        WriteDescriptorSetElements::AccelerationStructure(elements) => match layout_binding.ty {
+            DescriptorType::AccelerationStructure => {
+                for (index, acceleration_structure) in elements.iter().enumerate() {
+                    assert_eq!(
+                        acceleration_structure.device().internal_object(),
+                        layout.device().internal_object(),
+                    );
+
+                    // VUID-VkWriteDescriptorSetAccelerationStructureKHR-pAccelerationStructures-03579
+                    if acceleration_structure.ty() != AccelerationStructureType::TopLevel {
+                        return Err(DescriptorSetUpdateError::AccelerationStructureNotTopLevel {
+                            binding: write.binding(),
+                            index: descriptor_range_start + index as u32,
+                        });
+                    }
+                }
+            }
+            _ => {
+                return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
+                    binding: write.binding(),
+                })
+            }
+        },
     }
 
     Ok(layout_binding)
@@ -889,6 +965,9 @@ pub(crate) fn check_descriptor_write<'a>(
 
 #[derive(Clone, Copy, Debug)]
 pub enum DescriptorSetUpdateError {
+    /// Tried to write an acceleration structure that is not a top-level acceleration structure.
+    AccelerationStructureNotTopLevel { binding: u32, index: u32 },
+
     /// Tried to write more elements than were available in a binding.
     ArrayIndexOutOfBounds {
         /// Binding that is affected.
@@ -959,6 +1038,11 @@ impl std::fmt::Display for DescriptorSetUpdateError {
     #[inline]
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match self {
+            Self::AccelerationStructureNotTopLevel { binding, index } => write!(
+                fmt,
+                "tried to write an acceleration structure to binding {} index {} that is not a top-level acceleration structure",
+                binding, index,
+            ),
             Self::ArrayIndexOutOfBounds {
                 binding,
                 available_count,
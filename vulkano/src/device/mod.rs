@@ -95,11 +95,13 @@ pub use self::features::{FeatureRestriction, FeatureRestrictionError, Features};
 pub use self::properties::Properties;
 pub(crate) use self::properties::PropertiesFfi;
 use crate::check_errors;
+use crate::command_buffer::pool::CommandPoolCreationError;
 use crate::command_buffer::pool::StandardCommandPool;
 use crate::descriptor_set::pool::StdDescriptorPool;
 pub use crate::device::extensions::DeviceExtensions;
 use crate::device::physical::PhysicalDevice;
 use crate::device::physical::QueueFamily;
+use crate::device::physical::TimeDomain;
 pub use crate::extensions::{
     ExtensionRestriction, ExtensionRestrictionError, SupportedExtensionsError,
 };
@@ -108,6 +110,7 @@ use crate::instance::Instance;
 use crate::memory::pool::StdMemoryPool;
 use crate::Error;
 use crate::OomError;
+use crate::Success;
 use crate::SynchronizedVulkanObject;
 use crate::Version;
 use crate::VulkanObject;
@@ -127,6 +130,8 @@ use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::ptr;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
@@ -150,15 +155,20 @@ pub struct Device {
     fns: DeviceFunctions,
     standard_pool: Mutex<Weak<StdMemoryPool>>,
     standard_descriptor_pool: Mutex<Weak<StdDescriptorPool>>,
+    // Indexed by the queue family and whether the pool is protected.
     standard_command_pools:
-        Mutex<HashMap<u32, Weak<StandardCommandPool>, BuildHasherDefault<FnvHasher>>>,
+        Mutex<HashMap<(u32, bool), Weak<StandardCommandPool>, BuildHasherDefault<FnvHasher>>>,
     features: Features,
     extensions: DeviceExtensions,
     active_queue_families: SmallVec<[u32; 8]>,
     allocation_count: Mutex<u32>,
+    custom_border_color_sampler_count: Mutex<u32>,
     fence_pool: Mutex<Vec<ash::vk::Fence>>,
     semaphore_pool: Mutex<Vec<ash::vk::Semaphore>>,
     event_pool: Mutex<Vec<ash::vk::Event>>,
+    lost: AtomicBool,
+    // Queried the first time `calibrated_timestamps` is called.
+    calibrateable_time_domains: Mutex<Option<Vec<TimeDomain>>>,
 }
 
 // The `StandardCommandPool` type doesn't implement Send/Sync, so we have to manually reimplement
@@ -179,7 +189,8 @@ impl Device {
     ///   the family whose queue belongs to and a priority between 0.0 and 1.0 to assign to it.
     ///   A queue with a higher value indicates that the commands will execute faster than on a
     ///   queue with a lower value. Note however that no guarantee can be made on the way the
-    ///   priority value is handled by the implementation.
+    ///   priority value is handled by the implementation. Elements can be either
+    ///   `(QueueFamily, f32)` tuples, or a [`QueueCreateInfo`] to create a protected queue.
     ///
     /// # Panic
     ///
@@ -198,7 +209,8 @@ impl Device {
         queue_families: I,
     ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
     where
-        I: IntoIterator<Item = (QueueFamily<'a>, f32)>,
+        I: IntoIterator,
+        I::Item: Into<QueueCreateInfo<'a>>,
     {
         let instance = physical_device.instance();
         let fns_i = instance.fns();
@@ -240,15 +252,22 @@ impl Device {
 
         // device creation
         let (device, queues) = unsafe {
-            // each element of `queues` is a `(queue_family, priorities)`
-            // each queue family must only have one entry in `queues`
-            let mut queues: Vec<(u32, Vec<f32>)> =
+            // each element of `queues` is a `(queue_family, protected, priorities)`
+            // each queue family must only have one entry in `queues` for each value of `protected`
+            let mut queues: Vec<(u32, bool, Vec<f32>)> =
                 Vec::with_capacity(physical_device.queue_families().len());
 
-            // this variable will contain the queue family ID and queue ID of each requested queue
-            let mut output_queues: SmallVec<[(u32, u32); 8]> = SmallVec::new();
+            // this variable will contain the queue family ID, queue ID and protected flag of each
+            // requested queue
+            let mut output_queues: SmallVec<[(u32, u32, bool); 8]> = SmallVec::new();
+
+            for queue_create_info in queue_families {
+                let QueueCreateInfo {
+                    family: queue_family,
+                    priority,
+                    protected,
+                } = queue_create_info.into();
 
-            for (queue_family, priority) in queue_families {
                 // checking the parameters
                 assert_eq!(
                     queue_family.physical_device().internal_object(),
@@ -257,26 +276,38 @@ impl Device {
                 if priority < 0.0 || priority > 1.0 {
                     return Err(DeviceCreationError::PriorityOutOfRange);
                 }
+                if protected
+                    && (!requested_features.protected_memory || !queue_family.supports_protected())
+                {
+                    return Err(DeviceCreationError::ProtectedQueueNotSupported);
+                }
 
                 // adding to `queues` and `output_queues`
-                if let Some(q) = queues.iter_mut().find(|q| q.0 == queue_family.id()) {
-                    output_queues.push((queue_family.id(), q.1.len() as u32));
-                    q.1.push(priority);
-                    if q.1.len() > queue_family.queues_count() {
+                if let Some(q) = queues
+                    .iter_mut()
+                    .find(|q| q.0 == queue_family.id() && q.1 == protected)
+                {
+                    output_queues.push((queue_family.id(), q.2.len() as u32, protected));
+                    q.2.push(priority);
+                    if q.2.len() > queue_family.queues_count() {
                         return Err(DeviceCreationError::TooManyQueuesForFamily);
                     }
                     continue;
                 }
-                queues.push((queue_family.id(), vec![priority]));
-                output_queues.push((queue_family.id(), 0));
+                queues.push((queue_family.id(), protected, vec![priority]));
+                output_queues.push((queue_family.id(), 0, protected));
             }
 
             // turning `queues` into an array of `vkDeviceQueueCreateInfo` suitable for Vulkan
             let queues = queues
                 .iter()
                 .map(
-                    |&(queue_id, ref priorities)| ash::vk::DeviceQueueCreateInfo {
-                        flags: ash::vk::DeviceQueueCreateFlags::empty(),
+                    |&(queue_id, protected, ref priorities)| ash::vk::DeviceQueueCreateInfo {
+                        flags: if protected {
+                            ash::vk::DeviceQueueCreateFlags::PROTECTED
+                        } else {
+                            ash::vk::DeviceQueueCreateFlags::empty()
+                        },
                         queue_family_index: queue_id,
                         queue_count: priorities.len() as u32,
                         p_queue_priorities: priorities.as_ptr(),
@@ -355,7 +386,7 @@ impl Device {
         });
 
         let mut active_queue_families: SmallVec<[u32; 8]> = SmallVec::new();
-        for (queue_family, _) in queues.iter() {
+        for (queue_family, _, _) in queues.iter() {
             if let None = active_queue_families
                 .iter()
                 .find(|&&qf| qf == *queue_family)
@@ -381,9 +412,12 @@ impl Device {
             extensions: requested_extensions.clone(),
             active_queue_families,
             allocation_count: Mutex::new(0),
+            custom_border_color_sampler_count: Mutex::new(0),
             fence_pool: Mutex::new(Vec::new()),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
+            lost: AtomicBool::new(false),
+            calibrateable_time_domains: Mutex::new(None),
         });
 
         // Iterator for the produced queues.
@@ -423,11 +457,114 @@ impl Device {
     /// of the device (either explicitly or implicitly, for example with a future's destructor)
     /// while this function is waiting.
     ///
-    pub unsafe fn wait(&self) -> Result<(), OomError> {
-        check_errors(self.fns.v1_0.device_wait_idle(self.device))?;
+    pub unsafe fn wait(&self) -> Result<(), DeviceWaitError> {
+        self.check_errors(self.fns.v1_0.device_wait_idle(self.device))?;
         Ok(())
     }
 
+    /// Returns true if the device has been lost.
+    ///
+    /// A device is lost when an error occurs in the implementation, for example when the GPU is
+    /// reset because a command took too long to execute, or when the driver crashes. Once any
+    /// operation has returned a `DeviceLost` error, this returns true for the rest of the
+    /// lifetime of the device. Most operations on a lost device fail, and the only way to
+    /// recover is to drop all objects created from it and create a new device.
+    #[inline]
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Relaxed)
+    }
+
+    /// Checks the result of a Vulkan function that was called on this device, like
+    /// `check_errors`, and marks the device as lost if it returned `VK_ERROR_DEVICE_LOST`.
+    #[inline]
+    pub(crate) fn check_errors(&self, result: ash::vk::Result) -> Result<Success, Error> {
+        let result = check_errors(result);
+
+        if let Err(Error::DeviceLost) = result {
+            self.lost.store(true, Ordering::Relaxed);
+        }
+
+        result
+    }
+
+    /// Takes a timestamp in each of the given time domains, at as close to the same moment as
+    /// possible.
+    ///
+    /// Returns one timestamp for each element of `time_domains`, in the same order, and the
+    /// maximum deviation between the moments at which the timestamps were taken, in nanoseconds.
+    /// Timestamps in the [`Device`](TimeDomain::Device) time domain are in the same units as
+    /// timestamp queries. If `time_domains` is empty, no timestamps are taken and the maximum
+    /// deviation is 0.
+    ///
+    /// The supported time domains are only queried from the physical device the first time this
+    /// is called, so it is cheap enough to call every frame.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `ext_calibrated_timestamps` extension is not enabled on the device.
+    /// - Panics if one of the time domains is not supported, according to
+    ///   [`PhysicalDevice::calibrateable_time_domains`].
+    pub fn calibrated_timestamps(
+        &self,
+        time_domains: impl IntoIterator<Item = TimeDomain>,
+    ) -> Result<(Vec<u64>, u64), OomError> {
+        assert!(
+            self.enabled_extensions().ext_calibrated_timestamps,
+            "the ext_calibrated_timestamps extension must be enabled on the device"
+        );
+
+        let timestamp_infos: SmallVec<[_; 4]> = {
+            let mut supported_time_domains = self.calibrateable_time_domains.lock().unwrap();
+
+            if supported_time_domains.is_none() {
+                *supported_time_domains =
+                    Some(self.physical_device().calibrateable_time_domains()?);
+            }
+
+            let supported_time_domains = supported_time_domains.as_ref().unwrap();
+
+            time_domains
+                .into_iter()
+                .map(|time_domain| {
+                    assert!(
+                        supported_time_domains.contains(&time_domain),
+                        "the time domain {:?} is not supported by the physical device",
+                        time_domain
+                    );
+
+                    ash::vk::CalibratedTimestampInfoEXT {
+                        time_domain: time_domain.into(),
+                        ..Default::default()
+                    }
+                })
+                .collect()
+        };
+
+        // `timestampCount` must be greater than 0.
+        if timestamp_infos.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+
+        let mut timestamps = vec![0; timestamp_infos.len()];
+        let mut max_deviation = 0;
+
+        unsafe {
+            check_errors(
+                self.fns
+                    .ext_calibrated_timestamps
+                    .get_calibrated_timestamps_ext(
+                        self.device,
+                        timestamp_infos.len() as u32,
+                        timestamp_infos.as_ptr(),
+                        timestamps.as_mut_ptr(),
+                        &mut max_deviation,
+                    ),
+            )?;
+        }
+
+        Ok((timestamps, max_deviation))
+    }
+
     /// Returns the instance used to create this device.
     #[inline]
     pub fn instance(&self) -> &Arc<Instance> {
@@ -478,6 +615,22 @@ impl Device {
         new_pool
     }
 
+    /// Replaces the standard memory pool with `pool`. The buffer and image types that allocate
+    /// from the standard pool use `pool` from then on, which allows them to use a pool that was
+    /// created with [`StdMemoryPool::with_block_sizes`].
+    ///
+    /// The device only keeps a weak reference to the pool, as it does for the default pool. Keep
+    /// `pool` alive for as long as it should be used: once it and all the allocations made from
+    /// it have been dropped, a new default pool is created.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `pool` was not created with this device.
+    pub fn set_standard_pool(&self, pool: &Arc<StdMemoryPool>) {
+        assert_eq!(pool.device().internal_object(), self.device);
+        *self.standard_pool.lock().unwrap() = Arc::downgrade(pool);
+    }
+
     /// Returns the standard descriptor pool used by default if you don't provide any other pool.
     pub fn standard_descriptor_pool(me: &Arc<Self>) -> Arc<StdDescriptorPool> {
         let mut pool = me.standard_descriptor_pool.lock().unwrap();
@@ -502,7 +655,7 @@ impl Device {
     pub fn standard_command_pool(me: &Arc<Self>, queue: QueueFamily) -> Arc<StandardCommandPool> {
         let mut standard_command_pools = me.standard_command_pools.lock().unwrap();
 
-        match standard_command_pools.entry(queue.id()) {
+        match standard_command_pools.entry((queue.id(), false)) {
             Entry::Occupied(mut entry) => {
                 if let Some(pool) = entry.get().upgrade() {
                     return pool;
@@ -520,6 +673,53 @@ impl Device {
         }
     }
 
+    /// Returns the standard command buffer pool for protected command buffers, used by
+    /// [`AutoCommandBufferBuilder::primary_protected`](crate::command_buffer::AutoCommandBufferBuilder::primary_protected).
+    ///
+    /// Returns an error if the [`protected_memory`](crate::device::Features::protected_memory)
+    /// feature is not enabled on the device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    ///
+    pub fn standard_protected_command_pool(
+        me: &Arc<Self>,
+        queue: QueueFamily,
+    ) -> Result<Arc<StandardCommandPool>, CommandPoolCreationError> {
+        let mut standard_command_pools = me.standard_command_pools.lock().unwrap();
+
+        match standard_command_pools.entry((queue.id(), true)) {
+            Entry::Occupied(mut entry) => {
+                if let Some(pool) = entry.get().upgrade() {
+                    return Ok(pool);
+                }
+
+                let new_pool = Arc::new(StandardCommandPool::new_protected(me.clone(), queue)?);
+                *entry.get_mut() = Arc::downgrade(&new_pool);
+                Ok(new_pool)
+            }
+            Entry::Vacant(entry) => {
+                let new_pool = Arc::new(StandardCommandPool::new_protected(me.clone(), queue)?);
+                entry.insert(Arc::downgrade(&new_pool));
+                Ok(new_pool)
+            }
+        }
+    }
+
+    /// Returns the number of `DeviceMemory` allocations that currently exist on this device.
+    ///
+    /// Allocating memory fails with
+    /// [`TooManyObjects`](crate::memory::DeviceMemoryAllocError::TooManyObjects) once this
+    /// reaches the
+    /// [`max_memory_allocation_count`](crate::device::Properties::max_memory_allocation_count)
+    /// limit of the physical device. If the `log` feature is enabled, a warning is logged when
+    /// three quarters of the limit are reached.
+    #[inline]
+    pub fn memory_allocation_count(&self) -> u32 {
+        *self.allocation_count.lock().expect("Poisoned mutex")
+    }
+
     /// Used to track the number of allocations on this device.
     ///
     /// To ensure valid usage of the Vulkan API, we cannot call `vkAllocateMemory` when
@@ -531,6 +731,16 @@ impl Device {
         &self.allocation_count
     }
 
+    /// Used to track the number of samplers with a custom border color on this device.
+    ///
+    /// To ensure valid usage of the Vulkan API, we cannot create such a sampler when
+    /// `maxCustomBorderColorSamplers` has been exceeded.
+    ///
+    /// Warning: You should never modify this value, except in `sampler` module
+    pub(crate) fn custom_border_color_sampler_count(&self) -> &Mutex<u32> {
+        &self.custom_border_color_sampler_count
+    }
+
     pub(crate) fn fence_pool(&self) -> &Mutex<Vec<ash::vk::Fence>> {
         &self.fence_pool
     }
@@ -545,6 +755,8 @@ impl Device {
 
     /// Assigns a human-readable name to `object` for debugging purposes.
     ///
+    /// Passing an empty `name` removes any previously assigned name.
+    ///
     /// # Panics
     /// * If `object` is not owned by this device.
     pub fn set_object_name<T: VulkanObject + DeviceOwned>(
@@ -663,11 +875,57 @@ where
     }
 }
 
+/// Parameters to create a queue of a device.
+///
+/// A `(QueueFamily, f32)` tuple converts into an unprotected queue with the given priority.
+#[derive(Clone, Copy, Debug)]
+pub struct QueueCreateInfo<'a> {
+    /// The queue family to create the queue from.
+    pub family: QueueFamily<'a>,
+
+    /// The priority of the queue, between 0.0 and 1.0.
+    ///
+    /// The default value is 0.5.
+    pub priority: f32,
+
+    /// Whether the queue is a protected queue, that can access protected resources.
+    ///
+    /// If set to `true`, the [`protected_memory`](crate::device::Features::protected_memory)
+    /// feature must be enabled on the device, and the queue family must
+    /// [support protected queues](QueueFamily::supports_protected).
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+}
+
+impl<'a> QueueCreateInfo<'a> {
+    /// Returns a `QueueCreateInfo` with the specified `family`.
+    #[inline]
+    pub fn family(family: QueueFamily<'a>) -> Self {
+        Self {
+            family,
+            priority: 0.5,
+            protected: false,
+        }
+    }
+}
+
+impl<'a> From<(QueueFamily<'a>, f32)> for QueueCreateInfo<'a> {
+    #[inline]
+    fn from((family, priority): (QueueFamily<'a>, f32)) -> Self {
+        Self {
+            family,
+            priority,
+            protected: false,
+        }
+    }
+}
+
 /// Iterator that returns the queues produced when creating a device.
 pub struct QueuesIter {
     next_queue: usize,
     device: Arc<Device>,
-    families_and_ids: SmallVec<[(u32, u32); 8]>,
+    families_and_ids: SmallVec<[(u32, u32, bool); 8]>,
 }
 
 unsafe impl DeviceOwned for QueuesIter {
@@ -681,7 +939,7 @@ impl Iterator for QueuesIter {
 
     fn next(&mut self) -> Option<Arc<Queue>> {
         unsafe {
-            let &(family, id) = match self.families_and_ids.get(self.next_queue) {
+            let &(family, id, protected) = match self.families_and_ids.get(self.next_queue) {
                 Some(a) => a,
                 None => return None,
             };
@@ -689,18 +947,35 @@ impl Iterator for QueuesIter {
             self.next_queue += 1;
 
             let mut output = MaybeUninit::uninit();
-            self.device.fns.v1_0.get_device_queue(
-                self.device.device,
-                family,
-                id,
-                output.as_mut_ptr(),
-            );
+
+            // Protected queues can only be retrieved with `vkGetDeviceQueue2`.
+            if protected {
+                let info = ash::vk::DeviceQueueInfo2 {
+                    flags: ash::vk::DeviceQueueCreateFlags::PROTECTED,
+                    queue_family_index: family,
+                    queue_index: id,
+                    ..Default::default()
+                };
+                self.device.fns.v1_1.get_device_queue2(
+                    self.device.device,
+                    &info,
+                    output.as_mut_ptr(),
+                );
+            } else {
+                self.device.fns.v1_0.get_device_queue(
+                    self.device.device,
+                    family,
+                    id,
+                    output.as_mut_ptr(),
+                );
+            }
 
             Some(Arc::new(Queue {
                 queue: Mutex::new(output.assume_init()),
                 device: self.device.clone(),
                 family: family,
                 id: id,
+                protected,
             }))
         }
     }
@@ -732,6 +1007,9 @@ pub enum DeviceCreationError {
     TooManyQueuesForFamily,
     /// The priority of one of the queues is out of the [0.0; 1.0] range.
     PriorityOutOfRange,
+    /// A protected queue was requested, but the `protected_memory` feature was not enabled or
+    /// the queue family doesn't support protected queues.
+    ProtectedQueueNotSupported,
     /// There is no memory available on the host (ie. the CPU, RAM, etc.).
     OutOfHostMemory,
     /// There is no memory available on the device (ie. video memory).
@@ -774,6 +1052,12 @@ impl fmt::Display for DeviceCreationError {
                     "the priority of one of the queues is out of the [0.0; 1.0] range"
                 )
             }
+            DeviceCreationError::ProtectedQueueNotSupported => {
+                write!(
+                    fmt,
+                    "a protected queue was requested, but protected queues are not supported"
+                )
+            }
             DeviceCreationError::ExtensionNotPresent => {
                 write!(fmt,"some of the requested device extensions are not supported by the physical device")
             }
@@ -816,6 +1100,53 @@ impl From<FeatureRestrictionError> for DeviceCreationError {
     }
 }
 
+/// Error that can be returned when waiting for a device or a queue to become idle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DeviceWaitError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
+    DeviceLost,
+}
+
+impl error::Error for DeviceWaitError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DeviceWaitError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DeviceWaitError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DeviceWaitError::OomError(_) => "not enough memory available",
+                DeviceWaitError::DeviceLost => "the connection to the device has been lost",
+            }
+        )
+    }
+}
+
+impl From<Error> for DeviceWaitError {
+    #[inline]
+    fn from(err: Error) -> DeviceWaitError {
+        match err {
+            err @ Error::OutOfHostMemory => DeviceWaitError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => DeviceWaitError::OomError(OomError::from(err)),
+            Error::DeviceLost => DeviceWaitError::DeviceLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
 /// Represents a queue where commands can be submitted.
 // TODO: should use internal synchronization?
 #[derive(Debug)]
@@ -824,6 +1155,7 @@ pub struct Queue {
     device: Arc<Device>,
     family: u32,
     id: u32, // id within family
+    protected: bool,
 }
 
 impl Queue {
@@ -838,6 +1170,7 @@ impl Queue {
     pub fn is_same(&self, other: &Queue) -> bool {
         self.id == other.id
             && self.family == other.family
+            && self.protected == other.protected
             && self.device.internal_object() == other.device.internal_object()
     }
 
@@ -856,15 +1189,21 @@ impl Queue {
         self.id
     }
 
+    /// Returns true if this is a protected queue, which can access protected resources.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
     /// Waits until all work on this queue has finished.
     ///
     /// Just like `Device::wait()`, you shouldn't have to call this function in a typical program.
     #[inline]
-    pub fn wait(&self) -> Result<(), OomError> {
+    pub fn wait(&self) -> Result<(), DeviceWaitError> {
         unsafe {
             let fns = self.device.fns();
             let queue = self.queue.lock().unwrap();
-            check_errors(fns.v1_0.queue_wait_idle(*queue))?;
+            self.device.check_errors(fns.v1_0.queue_wait_idle(*queue))?;
             Ok(())
         }
     }
@@ -896,10 +1235,14 @@ unsafe impl SynchronizedVulkanObject for Queue {
 #[cfg(test)]
 mod tests {
     use crate::device::physical::PhysicalDevice;
+    use crate::device::physical::TimeDomain;
     use crate::device::Device;
     use crate::device::DeviceCreationError;
     use crate::device::DeviceExtensions;
+    use crate::device::QueueCreateInfo;
     use crate::device::{FeatureRestriction, FeatureRestrictionError, Features};
+    use crate::memory::pool::StdMemoryPool;
+    use std::iter;
     use std::sync::Arc;
 
     #[test]
@@ -908,6 +1251,60 @@ mod tests {
         assert!(Arc::get_mut(&mut device).is_some());
     }
 
+    #[test]
+    fn set_standard_pool() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let memory_type = device.physical_device().memory_types().next().unwrap();
+        let pool = StdMemoryPool::with_block_sizes(device.clone(), [(memory_type.id(), 1024)]);
+        device.set_standard_pool(&pool);
+        assert!(Arc::ptr_eq(&Device::standard_pool(&device), &pool));
+
+        // The device doesn't keep the pool alive, and creates a default pool once it's dropped.
+        let weak = Arc::downgrade(&pool);
+        drop(pool);
+        assert!(weak.upgrade().is_none());
+        Device::standard_pool(&device);
+    }
+
+    #[test]
+    fn calibrateable_time_domains() {
+        let (device, _) = gfx_dev_and_queue_with!([ext_calibrated_timestamps], []);
+
+        let time_domains = device
+            .physical_device()
+            .calibrateable_time_domains()
+            .unwrap();
+        let (timestamps, _) = device
+            .calibrated_timestamps(time_domains.iter().copied())
+            .unwrap();
+        assert_eq!(timestamps.len(), time_domains.len());
+
+        // The second call uses the cached time domains.
+        let (timestamps, _) = device
+            .calibrated_timestamps(time_domains.iter().copied())
+            .unwrap();
+        assert_eq!(timestamps.len(), time_domains.len());
+    }
+
+    #[test]
+    fn calibrated_timestamps_empty() {
+        let (device, _) = gfx_dev_and_queue_with!([ext_calibrated_timestamps], []);
+
+        let (timestamps, max_deviation) = device
+            .calibrated_timestamps(iter::empty::<TimeDomain>())
+            .unwrap();
+        assert!(timestamps.is_empty());
+        assert_eq!(max_deviation, 0);
+    }
+
+    #[test]
+    fn not_lost_after_wait() {
+        let (device, queue) = gfx_dev_and_queue!();
+        queue.wait().unwrap();
+        assert!(!device.is_lost());
+    }
+
     #[test]
     fn too_many_queues() {
         let instance = instance!();
@@ -990,4 +1387,28 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn protected_queue_missing_feature() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+
+        match Device::new(
+            physical,
+            &Features::none(),
+            &DeviceExtensions::none(),
+            Some(QueueCreateInfo {
+                protected: true,
+                ..QueueCreateInfo::family(family)
+            }),
+        ) {
+            Err(DeviceCreationError::ProtectedQueueNotSupported) => (), // Success
+            _ => panic!(),
+        };
+    }
 }
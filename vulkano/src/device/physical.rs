@@ -10,14 +10,20 @@
 use crate::device::{DeviceExtensions, Features, FeaturesFfi, Properties, PropertiesFfi};
 use crate::format::Format;
 use crate::image::view::ImageViewType;
-use crate::image::{ImageCreateFlags, ImageTiling, ImageType, ImageUsage, SampleCounts};
+use crate::image::{
+    ImageAspects, ImageCreateFlags, ImageTiling, ImageType, ImageUsage, SampleCount, SampleCounts,
+};
 use crate::instance::{Instance, InstanceCreationError};
 use crate::memory::ExternalMemoryHandleType;
+use crate::shader::ShaderStages;
+use crate::swapchain::CapabilitiesError;
+use crate::swapchain::Surface;
 use crate::sync::PipelineStage;
 use crate::Version;
 use crate::VulkanObject;
 use crate::{check_errors, OomError};
 use crate::{DeviceSize, Error};
+use std::error;
 use std::ffi::CStr;
 use std::fmt;
 use std::hash::Hash;
@@ -422,6 +428,98 @@ impl<'a> PhysicalDevice<'a> {
         &self.info.supported_features
     }
 
+    /// Returns the identity of the driver of this physical device, or `None` if it is not
+    /// available.
+    ///
+    /// This requires the device to support Vulkan 1.2 or the `khr_driver_properties` extension,
+    /// and the instance to support Vulkan 1.1 or `khr_get_physical_device_properties2`. It is
+    /// a shortcut for the `driver_*` and `conformance_version` values of
+    /// [`properties`](PhysicalDevice::properties).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use vulkano::device::physical::{DriverId, PhysicalDevice};
+    ///
+    /// fn is_nvidia_proprietary(physical_device: PhysicalDevice) -> bool {
+    ///     physical_device
+    ///         .driver_properties()
+    ///         .map_or(false, |driver| driver.driver_id == DriverId::NvidiaProprietary)
+    /// }
+    /// ```
+    #[inline]
+    pub fn driver_properties(&self) -> Option<DriverProperties> {
+        let properties = &self.info.properties;
+
+        Some(DriverProperties {
+            driver_id: properties.driver_id?,
+            driver_name: properties.driver_name.clone()?,
+            driver_info: properties.driver_info.clone()?,
+            conformance_version: properties.conformance_version?,
+        })
+    }
+
+    /// Returns the subgroup capabilities of this physical device, or `None` if they are not
+    /// available.
+    ///
+    /// This requires the device to support Vulkan 1.1, and the instance to support Vulkan 1.1 or
+    /// `khr_get_physical_device_properties2`. It is a shortcut for the subgroup values of
+    /// [`properties`](PhysicalDevice::properties), which are named differently depending on the
+    /// Vulkan version.
+    ///
+    /// The minimum and maximum subgroup sizes are only available if the device supports the
+    /// `ext_subgroup_size_control` extension.
+    #[inline]
+    pub fn subgroup_properties(&self) -> Option<SubgroupProperties> {
+        let properties = &self.info.properties;
+
+        Some(SubgroupProperties {
+            subgroup_size: properties.subgroup_size?,
+            supported_stages: properties
+                .supported_stages
+                .or(properties.subgroup_supported_stages)?,
+            supported_operations: properties
+                .supported_operations
+                .or(properties.subgroup_supported_operations)?,
+            quad_operations_in_all_stages: properties
+                .quad_operations_in_all_stages
+                .or(properties.subgroup_quad_operations_in_all_stages)?,
+            min_subgroup_size: properties.min_subgroup_size,
+            max_subgroup_size: properties.max_subgroup_size,
+        })
+    }
+
+    /// Returns the number of nanoseconds it takes for a timestamp value to be incremented by 1.
+    ///
+    /// This is a shortcut for the `timestamp_period` value of
+    /// [`properties`](PhysicalDevice::properties).
+    #[inline]
+    pub fn timestamp_period(&self) -> f32 {
+        self.info.properties.timestamp_period
+    }
+
+    /// Converts a number of timestamp ticks, such as the difference between two timestamp query
+    /// results, into nanoseconds.
+    ///
+    /// The timestamp values should first be masked with
+    /// [`QueueFamily::timestamp_valid_mask`] of the queue family they were written on.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use vulkano::device::physical::QueueFamily;
+    ///
+    /// fn elapsed_nanos(queue_family: QueueFamily, start: u64, end: u64) -> Option<f64> {
+    ///     let mask = queue_family.timestamp_valid_mask()?;
+    ///     let ticks = (end & mask).wrapping_sub(start & mask) & mask;
+    ///     Some(queue_family.physical_device().timestamp_ticks_to_nanos(ticks))
+    /// }
+    /// ```
+    #[inline]
+    pub fn timestamp_ticks_to_nanos(&self, ticks: u64) -> f64 {
+        ticks as f64 * self.timestamp_period() as f64
+    }
+
     /// Retrieves the properties of a format when used by this physical device.
     pub fn format_properties(&self, format: Format) -> FormatProperties {
         let mut format_properties2 = ash::vk::FormatProperties2::default();
@@ -485,6 +583,53 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Returns the first format in `candidates` that supports all of `required_features` when
+    /// used with the given tiling, or `None` if none of them do.
+    pub fn find_supported_format<I>(
+        &self,
+        candidates: I,
+        tiling: ImageTiling,
+        required_features: FormatFeatures,
+    ) -> Option<Format>
+    where
+        I: IntoIterator<Item = Format>,
+    {
+        candidates.into_iter().find(|&format| {
+            let format_properties = self.format_properties(format);
+            let features = match tiling {
+                ImageTiling::Optimal => format_properties.optimal_tiling_features,
+                ImageTiling::Linear => format_properties.linear_tiling_features,
+            };
+
+            features.is_superset_of(&required_features)
+        })
+    }
+
+    /// Returns a depth format that can be used as a depth attachment of an image with optimal
+    /// tiling.
+    ///
+    /// The formats `D32_SFLOAT`, `D32_SFLOAT_S8_UINT`, `D24_UNORM_S8_UINT` and `D16_UNORM` are
+    /// tried in that order. `D16_UNORM` is always supported, so this only returns `None` on
+    /// non-conforming implementations. Use
+    /// [`find_supported_format`](Self::find_supported_format) to choose from a different list,
+    /// for example if a stencil aspect is needed.
+    #[inline]
+    pub fn find_depth_format(&self) -> Option<Format> {
+        self.find_supported_format(
+            [
+                Format::D32_SFLOAT,
+                Format::D32_SFLOAT_S8_UINT,
+                Format::D24_UNORM_S8_UINT,
+                Format::D16_UNORM,
+            ],
+            ImageTiling::Optimal,
+            FormatFeatures {
+                depth_stencil_attachment: true,
+                ..FormatFeatures::default()
+            },
+        )
+    }
+
     /// Returns the properties supported for images with a given image configuration.
     ///
     /// `Some` is returned if the configuration is supported, `None` if it is not.
@@ -618,6 +763,144 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Returns the properties of sparse images with a given image configuration.
+    ///
+    /// One element is returned for each aspect, or each group of aspects that share the same
+    /// properties, of images created with these parameters. The returned list is empty if the
+    /// configuration does not support sparse residency.
+    pub fn sparse_image_format_properties(
+        &self,
+        format: Format,
+        ty: ImageType,
+        samples: SampleCount,
+        usage: ImageUsage,
+        tiling: ImageTiling,
+    ) -> Vec<SparseImageFormatProperties> {
+        unsafe {
+            let fns = self.instance.fns();
+
+            let mut num = 0;
+            fns.v1_0.get_physical_device_sparse_image_format_properties(
+                self.info.handle,
+                format.into(),
+                ty.into(),
+                samples.into(),
+                usage.into(),
+                tiling.into(),
+                &mut num,
+                ptr::null_mut(),
+            );
+
+            let mut properties = Vec::with_capacity(num as usize);
+            fns.v1_0.get_physical_device_sparse_image_format_properties(
+                self.info.handle,
+                format.into(),
+                ty.into(),
+                samples.into(),
+                usage.into(),
+                tiling.into(),
+                &mut num,
+                properties.as_mut_ptr(),
+            );
+            properties.set_len(num as usize);
+
+            properties.into_iter().map(Into::into).collect()
+        }
+    }
+
+    /// Returns the time domains that can be used with
+    /// [`Device::calibrated_timestamps`](crate::device::Device::calibrated_timestamps).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the physical device doesn't support the `ext_calibrated_timestamps`
+    ///   extension.
+    pub fn calibrateable_time_domains(&self) -> Result<Vec<TimeDomain>, OomError> {
+        assert!(
+            self.supported_extensions().ext_calibrated_timestamps,
+            "the ext_calibrated_timestamps extension is not supported by the physical device"
+        );
+
+        unsafe {
+            let fns = self.instance.fns();
+
+            let mut num = 0;
+            check_errors(
+                fns.ext_calibrated_timestamps
+                    .get_physical_device_calibrateable_time_domains_ext(
+                        self.info.handle,
+                        &mut num,
+                        ptr::null_mut(),
+                    ),
+            )?;
+
+            let mut time_domains = Vec::with_capacity(num as usize);
+            check_errors(
+                fns.ext_calibrated_timestamps
+                    .get_physical_device_calibrateable_time_domains_ext(
+                        self.info.handle,
+                        &mut num,
+                        time_domains.as_mut_ptr(),
+                    ),
+            )?;
+            time_domains.set_len(num as usize);
+
+            Ok(time_domains
+                .into_iter()
+                .filter_map(|time_domain| TimeDomain::try_from(time_domain).ok())
+                .collect())
+        }
+    }
+
+    /// Returns the fragment sizes that can be used with a
+    /// [fragment shading rate](crate::pipeline::graphics::fragment_shading_rate), and the
+    /// sample counts that each of them supports.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the physical device doesn't support the `khr_fragment_shading_rate`
+    ///   extension.
+    pub fn fragment_shading_rates(&self) -> Result<Vec<FragmentShadingRate>, OomError> {
+        assert!(
+            self.supported_extensions().khr_fragment_shading_rate,
+            "the khr_fragment_shading_rate extension is not supported by the physical device"
+        );
+
+        unsafe {
+            let fns = self.instance.fns();
+
+            let mut num = 0;
+            check_errors(
+                fns.khr_fragment_shading_rate
+                    .get_physical_device_fragment_shading_rates_khr(
+                        self.info.handle,
+                        &mut num,
+                        ptr::null_mut(),
+                    ),
+            )?;
+
+            let mut fragment_shading_rates =
+                vec![ash::vk::PhysicalDeviceFragmentShadingRateKHR::default(); num as usize];
+            check_errors(
+                fns.khr_fragment_shading_rate
+                    .get_physical_device_fragment_shading_rates_khr(
+                        self.info.handle,
+                        &mut num,
+                        fragment_shading_rates.as_mut_ptr(),
+                    ),
+            )?;
+            fragment_shading_rates.truncate(num as usize);
+
+            Ok(fragment_shading_rates
+                .into_iter()
+                .map(|rate| FragmentShadingRate {
+                    sample_counts: rate.sample_counts.into(),
+                    fragment_size: [rate.fragment_size.width, rate.fragment_size.height],
+                })
+                .collect())
+        }
+    }
+
     /// Builds an iterator that enumerates all the memory types on this physical device.
     #[inline]
     pub fn memory_types(&self) -> impl ExactSizeIterator<Item = MemoryType<'a>> {
@@ -676,6 +959,65 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Queries the current memory budget and usage of each memory heap of this physical device.
+    ///
+    /// Unlike [`MemoryHeap::size`], these values change over time, as memory is allocated by this
+    /// and other processes. They are only an estimate, and should be queried again regularly.
+    ///
+    /// The physical device must support the `ext_memory_budget` extension, and either support
+    /// Vulkan 1.1 or the instance must have `khr_get_physical_device_properties2` enabled.
+    pub fn memory_budget(&self) -> Result<MemoryBudget, MemoryBudgetError> {
+        if !self.supported_extensions().ext_memory_budget {
+            return Err(MemoryBudgetError::ExtensionNotSupported {
+                extension: "ext_memory_budget",
+                reason: "required to query the memory budget",
+            });
+        }
+
+        if !(self.api_version() >= Version::V1_1
+            || self
+                .instance
+                .enabled_extensions()
+                .khr_get_physical_device_properties2)
+        {
+            return Err(MemoryBudgetError::ExtensionNotEnabled {
+                extension: "khr_get_physical_device_properties2",
+                reason: "required to query the memory budget on Vulkan 1.0",
+            });
+        }
+
+        let mut memory_budget_properties =
+            ash::vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut memory_properties2 = ash::vk::PhysicalDeviceMemoryProperties2 {
+            p_next: &mut memory_budget_properties as *mut _ as *mut _,
+            ..Default::default()
+        };
+
+        unsafe {
+            let fns = self.instance.fns();
+
+            if self.api_version() >= Version::V1_1 {
+                fns.v1_1.get_physical_device_memory_properties2(
+                    self.info.handle,
+                    &mut memory_properties2,
+                );
+            } else {
+                fns.khr_get_physical_device_properties2
+                    .get_physical_device_memory_properties2_khr(
+                        self.info.handle,
+                        &mut memory_properties2,
+                    );
+            }
+        }
+
+        let heap_count = memory_properties2.memory_properties.memory_heap_count as usize;
+
+        Ok(MemoryBudget {
+            heap_budget: memory_budget_properties.heap_budget[..heap_count].to_vec(),
+            heap_usage: memory_budget_properties.heap_usage[..heap_count].to_vec(),
+        })
+    }
+
     /// Builds an iterator that enumerates all the queue families on this physical device.
     #[inline]
     pub fn queue_families(&self) -> impl ExactSizeIterator<Item = QueueFamily<'a>> {
@@ -704,6 +1046,94 @@ impl<'a> PhysicalDevice<'a> {
             None
         }
     }
+
+    /// Returns the first queue family that supports all of the operations in `flags`, and that
+    /// can present to `surface` if it is provided.
+    ///
+    /// Families that support graphics or compute operations are considered to support transfer
+    /// operations as well, even if they don't explicitly say so.
+    pub fn find_queue_family<W>(
+        &self,
+        flags: QueueFlags,
+        surface: Option<&Surface<W>>,
+    ) -> Result<Option<QueueFamily<'a>>, CapabilitiesError> {
+        for family in self.queue_families() {
+            if !family.supports_queue_flags(flags) {
+                continue;
+            }
+
+            if let Some(surface) = surface {
+                if !surface.is_supported(family)? {
+                    continue;
+                }
+            }
+
+            return Ok(Some(family));
+        }
+
+        Ok(None)
+    }
+
+    /// Returns a recommended set of queues for a typical graphics application, in the form
+    /// expected by [`Device::new`](crate::device::Device::new).
+    ///
+    /// The first queue is always a graphics queue, which can also present to `surface` if it is
+    /// provided and a family supports both. Otherwise, it is followed by a queue of a family that
+    /// can present to `surface`. Then come a queue of a dedicated compute family and a queue of a
+    /// dedicated transfer family, if the physical device has such families and they weren't
+    /// already chosen. The dedicated queues are given a lower priority than the graphics and
+    /// present queues. Use [`Queue::family`](crate::device::Queue::family) to tell the created
+    /// queues apart.
+    ///
+    /// Each family appears at most once in the list. Returns an empty list if no suitable graphics
+    /// queue family exists, or if `surface` is provided and no queue family can present to it.
+    pub fn recommended_queue_families<W>(
+        &self,
+        surface: Option<&Surface<W>>,
+    ) -> Result<Vec<(QueueFamily<'a>, f32)>, CapabilitiesError> {
+        let graphics_flags = QueueFlags {
+            graphics: true,
+            ..QueueFlags::none()
+        };
+
+        let mut queue_families = match self.find_queue_family(graphics_flags, surface)? {
+            Some(graphics) => vec![(graphics, 1.0)],
+            None => {
+                // No family supports both graphics and presenting, so use separate families.
+                let graphics = match self.find_queue_family(graphics_flags, None::<&Surface<W>>)? {
+                    Some(family) => family,
+                    None => return Ok(Vec::new()),
+                };
+                let present = match self.find_queue_family(QueueFlags::none(), surface)? {
+                    Some(family) => family,
+                    None => return Ok(Vec::new()),
+                };
+
+                vec![(graphics, 1.0), (present, 1.0)]
+            }
+        };
+
+        if let Some(compute) = self
+            .queue_families()
+            .find(|family| family.supports_compute() && !family.supports_graphics())
+        {
+            if !queue_families.iter().any(|&(family, _)| family == compute) {
+                queue_families.push((compute, 0.5));
+            }
+        }
+
+        if let Some(transfer) = self.queue_families().find(|family| {
+            family.explicitly_supports_transfers()
+                && !family.supports_graphics()
+                && !family.supports_compute()
+        }) {
+            if !queue_families.iter().any(|&(family, _)| family == transfer) {
+                queue_families.push((transfer, 0.5));
+            }
+        }
+
+        Ok(queue_families)
+    }
 }
 
 unsafe impl<'a> VulkanObject for PhysicalDevice<'a> {
@@ -754,6 +1184,54 @@ impl TryFrom<ash::vk::PhysicalDeviceType> for PhysicalDeviceType {
     }
 }
 
+/// A time domain that timestamps can be taken in, with
+/// [`Device::calibrated_timestamps`](crate::device::Device::calibrated_timestamps).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum TimeDomain {
+    /// The time domain of the device, which is the same one as timestamp queries that are
+    /// written by command buffers.
+    Device = ash::vk::TimeDomainEXT::DEVICE.as_raw(),
+    /// The `CLOCK_MONOTONIC` time domain of POSIX platforms.
+    ClockMonotonic = ash::vk::TimeDomainEXT::CLOCK_MONOTONIC.as_raw(),
+    /// The `CLOCK_MONOTONIC_RAW` time domain of POSIX platforms.
+    ClockMonotonicRaw = ash::vk::TimeDomainEXT::CLOCK_MONOTONIC_RAW.as_raw(),
+    /// The time domain of `QueryPerformanceCounter` on Windows.
+    QueryPerformanceCounter = ash::vk::TimeDomainEXT::QUERY_PERFORMANCE_COUNTER.as_raw(),
+}
+
+impl From<TimeDomain> for ash::vk::TimeDomainEXT {
+    #[inline]
+    fn from(val: TimeDomain) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+impl TryFrom<ash::vk::TimeDomainEXT> for TimeDomain {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::TimeDomainEXT) -> Result<Self, Self::Error> {
+        match val {
+            ash::vk::TimeDomainEXT::DEVICE => Ok(Self::Device),
+            ash::vk::TimeDomainEXT::CLOCK_MONOTONIC => Ok(Self::ClockMonotonic),
+            ash::vk::TimeDomainEXT::CLOCK_MONOTONIC_RAW => Ok(Self::ClockMonotonicRaw),
+            ash::vk::TimeDomainEXT::QUERY_PERFORMANCE_COUNTER => Ok(Self::QueryPerformanceCounter),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A fragment size that is supported by a physical device for fragment shading rates, returned
+/// by [`PhysicalDevice::fragment_shading_rates`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FragmentShadingRate {
+    /// The sample counts of the framebuffer that the fragment size can be used with.
+    pub sample_counts: SampleCounts,
+    /// The width and height of the fragment, in pixels.
+    pub fragment_size: [u32; 2],
+}
+
 /// Represents a memory type in a physical device.
 #[derive(Debug, Copy, Clone)]
 pub struct MemoryType<'a> {
@@ -824,6 +1302,15 @@ impl<'a> MemoryType<'a> {
     pub fn is_lazily_allocated(&self) -> bool {
         !(self.info.property_flags & ash::vk::MemoryPropertyFlags::LAZILY_ALLOCATED).is_empty()
     }
+
+    /// Returns true if memory of this type is protected memory.
+    ///
+    /// Protected memory can only be accessed by the device, and only by protected queues.
+    /// Memory of this type is never host-visible.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        !(self.info.property_flags & ash::vk::MemoryPropertyFlags::PROTECTED).is_empty()
+    }
 }
 
 /// Represents a memory heap in a physical device.
@@ -867,6 +1354,52 @@ impl<'a> MemoryHeap<'a> {
     }
 }
 
+/// The memory budget and usage of the memory heaps of a physical device, as returned by
+/// [`PhysicalDevice::memory_budget`].
+///
+/// Both vectors are indexed by the id of the memory heap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryBudget {
+    /// An estimate of how much memory the process can allocate from each heap before
+    /// allocations may fail or cause performance degradation.
+    pub heap_budget: Vec<DeviceSize>,
+    /// An estimate of how much memory the process is currently using in each heap.
+    pub heap_usage: Vec<DeviceSize>,
+}
+
+/// Error that can happen when querying the memory budget of a physical device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MemoryBudgetError {
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+    ExtensionNotSupported {
+        extension: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for MemoryBudgetError {}
+
+impl fmt::Display for MemoryBudgetError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::ExtensionNotSupported { extension, reason } => write!(
+                fmt,
+                "the extension {} must be supported by the physical device: {}",
+                extension, reason
+            ),
+        }
+    }
+}
+
 /// Represents a queue family in a physical device.
 ///
 /// A queue family is group of one or multiple queues. All queues of one family have the same
@@ -912,6 +1445,20 @@ impl<'a> QueueFamily<'a> {
         }
     }
 
+    /// If timestamps are supported, returns a mask of the bits of a timestamp value that are
+    /// valid. Other bits are guaranteed to be zero.
+    /// If timestamps are not supported, returns None.
+    #[inline]
+    pub fn timestamp_valid_mask(&self) -> Option<u64> {
+        self.timestamp_valid_bits().map(|bits| {
+            if bits >= 64 {
+                u64::MAX
+            } else {
+                (1 << bits) - 1
+            }
+        })
+    }
+
     /// Returns the minimum granularity supported for image transfers in terms
     /// of `[width, height, depth]`
     #[inline]
@@ -948,11 +1495,38 @@ impl<'a> QueueFamily<'a> {
         !(self.properties.queue_flags & ash::vk::QueueFlags::SPARSE_BINDING).is_empty()
     }
 
+    /// Returns `true` if protected queues can be created from this family.
+    #[inline]
+    pub fn supports_protected(&self) -> bool {
+        !(self.properties.queue_flags & ash::vk::QueueFlags::PROTECTED).is_empty()
+    }
+
     /// Returns `true` if the queues of this family support a particular pipeline stage.
     #[inline]
     pub fn supports_stage(&self, stage: PipelineStage) -> bool {
         !(self.properties.queue_flags & stage.required_queue_flags()).is_empty()
     }
+
+    /// Returns the operations that queues of this family can execute.
+    #[inline]
+    pub fn queue_flags(&self) -> QueueFlags {
+        self.properties.queue_flags.into()
+    }
+
+    /// Returns `true` if queues of this family can execute all of the operations in `flags`.
+    ///
+    /// Transfer operations are considered supported if the family supports graphics or compute
+    /// operations, even if it doesn't explicitly say so.
+    #[inline]
+    pub fn supports_queue_flags(&self, flags: QueueFlags) -> bool {
+        let supported = self.queue_flags();
+
+        (supported.graphics || !flags.graphics)
+            && (supported.compute || !flags.compute)
+            && (supported.transfer || supported.graphics || supported.compute || !flags.transfer)
+            && (supported.sparse_binding || !flags.sparse_binding)
+            && (supported.protected || !flags.protected)
+    }
 }
 
 impl<'a> PartialEq for QueueFamily<'a> {
@@ -964,6 +1538,43 @@ impl<'a> PartialEq for QueueFamily<'a> {
 
 impl<'a> Eq for QueueFamily<'a> {}
 
+/// The operations that the queues of a queue family can execute.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueueFlags {
+    pub graphics: bool,
+    pub compute: bool,
+    pub transfer: bool,
+    pub sparse_binding: bool,
+    pub protected: bool,
+}
+
+impl QueueFlags {
+    /// Builds a `QueueFlags` with all values set to false.
+    #[inline]
+    pub const fn none() -> QueueFlags {
+        QueueFlags {
+            graphics: false,
+            compute: false,
+            transfer: false,
+            sparse_binding: false,
+            protected: false,
+        }
+    }
+}
+
+impl From<ash::vk::QueueFlags> for QueueFlags {
+    #[inline]
+    fn from(val: ash::vk::QueueFlags) -> Self {
+        QueueFlags {
+            graphics: val.intersects(ash::vk::QueueFlags::GRAPHICS),
+            compute: val.intersects(ash::vk::QueueFlags::COMPUTE),
+            transfer: val.intersects(ash::vk::QueueFlags::TRANSFER),
+            sparse_binding: val.intersects(ash::vk::QueueFlags::SPARSE_BINDING),
+            protected: val.intersects(ash::vk::QueueFlags::PROTECTED),
+        }
+    }
+}
+
 /// The version of the Vulkan conformance test that a driver is conformant against.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ConformanceVersion {
@@ -997,8 +1608,41 @@ impl fmt::Display for ConformanceVersion {
     }
 }
 
+/// The identity of the driver of a physical device, as returned by
+/// [`PhysicalDevice::driver_properties`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DriverProperties {
+    /// The vendor and kind of the driver.
+    pub driver_id: DriverId,
+    /// The name of the driver.
+    pub driver_name: String,
+    /// Additional information about the driver, such as its version.
+    pub driver_info: String,
+    /// The version of the Vulkan conformance test that the driver is conformant against.
+    pub conformance_version: ConformanceVersion,
+}
+
+/// The subgroup capabilities of a physical device, as returned by
+/// [`PhysicalDevice::subgroup_properties`].
+#[derive(Clone, Copy, Debug)]
+pub struct SubgroupProperties {
+    /// The default number of invocations in each subgroup.
+    pub subgroup_size: u32,
+    /// The shader stages in which subgroup operations are supported.
+    pub supported_stages: ShaderStages,
+    /// The subgroup operations that are supported.
+    pub supported_operations: SubgroupFeatures,
+    /// Whether quad subgroup operations are supported in all stages, rather than only in
+    /// fragment and compute shaders.
+    pub quad_operations_in_all_stages: bool,
+    /// The minimum subgroup size that can be requested.
+    pub min_subgroup_size: Option<u32>,
+    /// The maximum subgroup size that can be requested.
+    pub max_subgroup_size: Option<u32>,
+}
+
 /// An identifier for the driver of a physical device.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(i32)]
 pub enum DriverId {
     AMDProprietary = ash::vk::DriverId::AMD_PROPRIETARY.as_raw(),
@@ -1242,6 +1886,14 @@ pub struct FormatFeatures {
     pub acceleration_structure_vertex_buffer: bool,
 }
 
+impl FormatFeatures {
+    /// Returns whether all features in `other` are also present in `self`.
+    #[inline]
+    pub fn is_superset_of(&self, other: &FormatFeatures) -> bool {
+        &(self | other) == self
+    }
+}
+
 impl BitOr for &FormatFeatures {
     type Output = FormatFeatures;
 
@@ -1441,3 +2093,103 @@ impl From<ash::vk::ImageFormatProperties> for ImageFormatProperties {
         }
     }
 }
+
+/// The properties that are supported by a physical device for sparse images of a certain type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SparseImageFormatProperties {
+    /// The aspects of the image that the properties apply to.
+    pub aspects: ImageAspects,
+    /// The size of the sparse image block, in texels or compressed texel blocks. Memory is bound
+    /// to sparse images in multiples of this size.
+    pub image_granularity: [u32; 3],
+    /// If true, the image uses a single mip tail region for all array layers, instead of one per
+    /// array layer.
+    pub single_miptail: bool,
+    /// If true, the first mip level whose dimensions are not a multiple of the block size is
+    /// where the mip tail starts.
+    pub aligned_mip_size: bool,
+    /// If true, the image does not use the standard sparse image block dimensions for its
+    /// format.
+    pub nonstandard_block_size: bool,
+}
+
+impl From<ash::vk::SparseImageFormatProperties> for SparseImageFormatProperties {
+    #[inline]
+    fn from(props: ash::vk::SparseImageFormatProperties) -> Self {
+        Self {
+            aspects: props.aspect_mask.into(),
+            image_granularity: [
+                props.image_granularity.width,
+                props.image_granularity.height,
+                props.image_granularity.depth,
+            ],
+            single_miptail: props
+                .flags
+                .intersects(ash::vk::SparseImageFormatFlags::SINGLE_MIPTAIL),
+            aligned_mip_size: props
+                .flags
+                .intersects(ash::vk::SparseImageFormatFlags::ALIGNED_MIP_SIZE),
+            nonstandard_block_size: props
+                .flags
+                .intersects(ash::vk::SparseImageFormatFlags::NONSTANDARD_BLOCK_SIZE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PhysicalDevice;
+    use super::QueueFlags;
+    use crate::swapchain::Surface;
+
+    #[test]
+    fn find_queue_family() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let flags = QueueFlags {
+            graphics: true,
+            ..QueueFlags::none()
+        };
+
+        match physical
+            .find_queue_family(flags, None::<&Surface<()>>)
+            .unwrap()
+        {
+            Some(family) => assert!(family.supports_graphics()),
+            None => assert!(physical
+                .queue_families()
+                .all(|family| !family.supports_graphics())),
+        }
+    }
+
+    #[test]
+    fn recommended_queue_families() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let queue_families = physical
+            .recommended_queue_families(None::<&Surface<()>>)
+            .unwrap();
+
+        let (graphics, priority) = match queue_families.first() {
+            Some(&first) => first,
+            None => return,
+        };
+        assert!(graphics.supports_graphics());
+        assert_eq!(priority, 1.0);
+
+        // Each family is only requested once.
+        for (index, &(family, _)) in queue_families.iter().enumerate() {
+            assert!(queue_families[index + 1..]
+                .iter()
+                .all(|&(other, _)| other != family));
+        }
+    }
+}
@@ -164,6 +164,21 @@ impl Format {
         )
     }
 
+    /// Returns the extent of plane `plane` of an image with this format and the given extent.
+    ///
+    /// With chroma subsampling, the planes other than the first have half the width of the
+    /// image, and for `420` formats half the height as well.
+    #[inline]
+    pub(crate) fn plane_extent(&self, plane: usize, extent: [u32; 3]) -> [u32; 3] {
+        let [width, height, depth] = extent;
+
+        match self.ycbcr_chroma_sampling() {
+            Some(ChromaSampling::Mode422) if plane != 0 => [width / 2, height, depth],
+            Some(ChromaSampling::Mode420) if plane != 0 => [width / 2, height / 2, depth],
+            _ => extent,
+        }
+    }
+
     #[inline]
     pub fn decode_clear_value(&self, value: ClearValue) -> ClearValue {
         let aspects = self.aspects();
@@ -488,6 +503,41 @@ pub enum ClearValue {
     DepthStencil((f32, u32)),
 }
 
+impl From<ClearValue> for ash::vk::ClearValue {
+    #[inline]
+    fn from(val: ClearValue) -> Self {
+        match val {
+            ClearValue::None => Self {
+                color: ash::vk::ClearColorValue { float32: [0.0; 4] },
+            },
+            ClearValue::Float(val) => Self {
+                color: ash::vk::ClearColorValue { float32: val },
+            },
+            ClearValue::Int(val) => Self {
+                color: ash::vk::ClearColorValue { int32: val },
+            },
+            ClearValue::Uint(val) => Self {
+                color: ash::vk::ClearColorValue { uint32: val },
+            },
+            ClearValue::Depth(val) => Self {
+                depth_stencil: ash::vk::ClearDepthStencilValue {
+                    depth: val,
+                    stencil: 0,
+                },
+            },
+            ClearValue::Stencil(val) => Self {
+                depth_stencil: ash::vk::ClearDepthStencilValue {
+                    depth: 0.0,
+                    stencil: val,
+                },
+            },
+            ClearValue::DepthStencil((depth, stencil)) => Self {
+                depth_stencil: ash::vk::ClearDepthStencilValue { depth, stencil },
+            },
+        }
+    }
+}
+
 // TODO: remove all these From implementations once they are no longer needed
 
 impl From<[f32; 1]> for ClearValue {
@@ -65,6 +65,7 @@ use crate::DeviceSize;
 use std::fs::File;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
@@ -214,6 +215,35 @@ impl AttachmentImage {
         AttachmentImage::new_impl(device, dimensions, 1, format, usage, SampleCount::Sample1)
     }
 
+    /// Same as `with_usage`, but also lets you specify the flags of the image, and the formats
+    /// that image views of it are going to be created with.
+    ///
+    /// See [`UnsafeImageBuilder::view_formats`](crate::image::sys::UnsafeImageBuilder::view_formats)
+    /// for the requirements on `view_formats`.
+    #[inline]
+    pub fn with_view_formats<F>(
+        device: Arc<Device>,
+        dimensions: [u32; 2],
+        format: Format,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+        view_formats: F,
+    ) -> Result<Arc<AttachmentImage>, ImageCreationError>
+    where
+        F: IntoIterator<Item = Format>,
+    {
+        AttachmentImage::new_with_view_formats_impl(
+            device,
+            dimensions,
+            1,
+            format,
+            usage,
+            SampleCount::Sample1,
+            flags,
+            view_formats,
+        )
+    }
+
     /// Same as `with_usage`, but creates a multisampled image.
     ///
     /// > **Note**: You can also use this function and pass `1` for the number of samples if you
@@ -428,7 +458,7 @@ impl AttachmentImage {
         AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
     }
 
-    // All constructors dispatch to this one.
+    #[inline]
     fn new_impl(
         device: Arc<Device>,
         dimensions: [u32; 2],
@@ -437,6 +467,32 @@ impl AttachmentImage {
         base_usage: ImageUsage,
         samples: SampleCount,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
+        AttachmentImage::new_with_view_formats_impl(
+            device,
+            dimensions,
+            array_layers,
+            format,
+            base_usage,
+            samples,
+            ImageCreateFlags::none(),
+            iter::empty(),
+        )
+    }
+
+    // All constructors dispatch to this one.
+    fn new_with_view_formats_impl<F>(
+        device: Arc<Device>,
+        dimensions: [u32; 2],
+        array_layers: u32,
+        format: Format,
+        base_usage: ImageUsage,
+        samples: SampleCount,
+        flags: ImageCreateFlags,
+        view_formats: F,
+    ) -> Result<Arc<AttachmentImage>, ImageCreationError>
+    where
+        F: IntoIterator<Item = Format>,
+    {
         // TODO: check dimensions against the max_framebuffer_width/height/layers limits
 
         let aspects = format.aspects();
@@ -452,6 +508,7 @@ impl AttachmentImage {
                 height: dimensions[1],
                 array_layers,
             })
+            .flags(flags)
             .format(format)
             .samples(samples)
             .usage(ImageUsage {
@@ -459,6 +516,7 @@ impl AttachmentImage {
                 depth_stencil_attachment: is_depth,
                 ..base_usage
             })
+            .view_formats(view_formats)
             .build()?;
 
         let mem_reqs = image.memory_requirements();
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use crate::buffer::BufferAccess;
 use crate::buffer::BufferUsage;
 use crate::buffer::CpuAccessibleBuffer;
 use crate::buffer::TypedBufferAccess;
@@ -43,9 +44,11 @@ use crate::sampler::Filter;
 use crate::sync::AccessError;
 use crate::sync::NowFuture;
 use crate::sync::Sharing;
+use crate::DeviceSize;
 use smallvec::SmallVec;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -220,6 +223,7 @@ impl ImmutableImage {
     /// Builds an uninitialized immutable image.
     ///
     /// Returns two things: the image, and a special access that should be used for the initial upload to the image.
+    #[inline]
     pub fn uninitialized<'a, I, M>(
         device: Arc<Device>,
         dimensions: ImageDimensions,
@@ -233,6 +237,40 @@ impl ImmutableImage {
     where
         I: IntoIterator<Item = QueueFamily<'a>>,
         M: Into<MipmapsCount>,
+    {
+        ImmutableImage::uninitialized_with_view_formats(
+            device,
+            dimensions,
+            format,
+            mip_levels,
+            usage,
+            flags,
+            iter::empty(),
+            layout,
+            queue_families,
+        )
+    }
+
+    /// Same as `uninitialized`, but also allows specifying the formats that image views of the
+    /// image are going to be created with.
+    ///
+    /// See [`UnsafeImageBuilder::view_formats`](crate::image::sys::UnsafeImageBuilder::view_formats)
+    /// for the requirements on `view_formats`.
+    pub fn uninitialized_with_view_formats<'a, I, M, F>(
+        device: Arc<Device>,
+        dimensions: ImageDimensions,
+        format: Format,
+        mip_levels: M,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+        view_formats: F,
+        layout: ImageLayout,
+        queue_families: I,
+    ) -> Result<(Arc<ImmutableImage>, Arc<ImmutableImageInitialization>), ImageCreationError>
+    where
+        I: IntoIterator<Item = QueueFamily<'a>>,
+        M: Into<MipmapsCount>,
+        F: IntoIterator<Item = Format>,
     {
         let queue_families = queue_families
             .into_iter()
@@ -250,6 +288,7 @@ impl ImmutableImage {
                 Sharing::Exclusive
             })
             .usage(usage)
+            .view_formats(view_formats)
             .build()?;
 
         let mem_reqs = image.memory_requirements();
@@ -321,6 +360,13 @@ impl ImmutableImage {
     }
 
     /// Construct an ImmutableImage containing a copy of the data in `source`.
+    ///
+    /// If `mip_levels` is more than one level, the other levels are generated by blitting from the
+    /// first one, which requires the format to support blitting. This is not the case for
+    /// block-compressed formats. To upload a compressed image with its mipmaps, create it with
+    /// [`uninitialized`](ImmutableImage::uninitialized) and copy each level with
+    /// [`copy_buffer_to_image_dimensions`](crate::command_buffer::AutoCommandBufferBuilder::copy_buffer_to_image_dimensions).
+    #[inline]
     pub fn from_buffer<B, Px>(
         source: Arc<B>,
         dimensions: ImageDimensions,
@@ -334,6 +380,188 @@ impl ImmutableImage {
         ),
         ImageCreationError,
     >
+    where
+        B: TypedBufferAccess<Content = [Px]> + 'static,
+        Px: Pixel + Send + Sync + Clone + 'static,
+    {
+        ImmutableImage::from_buffer_with_flags(
+            source,
+            dimensions,
+            mip_levels,
+            format,
+            ImageCreateFlags::none(),
+            queue,
+        )
+    }
+
+    /// Construct a cube-compatible ImmutableImage from the contents of `iter`.
+    ///
+    /// The image has six square faces of `size` texels, stored as six array layers in the order
+    /// +X, -X, +Y, -Y, +Z, -Z. `iter` must contain the faces one after the other, in that order.
+    /// Use an image view of type [`ImageViewType::Cube`](crate::image::view::ImageViewType::Cube)
+    /// to sample it as a cubemap.
+    #[inline]
+    pub fn cubemap_from_iter<Px, I>(
+        iter: I,
+        size: u32,
+        mip_levels: MipmapsCount,
+        format: Format,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        Px: Pixel + Send + Sync + Clone + 'static,
+        I: IntoIterator<Item = Px>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let source = CpuAccessibleBuffer::from_iter(
+            queue.device().clone(),
+            BufferUsage::transfer_source(),
+            false,
+            iter,
+        )?;
+        ImmutableImage::cubemap_from_buffer(source, size, mip_levels, format, queue)
+    }
+
+    /// Construct a cube-compatible ImmutableImage containing a copy of the data in `source`.
+    ///
+    /// See [`cubemap_from_iter`](ImmutableImage::cubemap_from_iter) for the expected layout of
+    /// the data. Returns an error if the size of `source` is not the size of six faces.
+    #[inline]
+    pub fn cubemap_from_buffer<B, Px>(
+        source: Arc<B>,
+        size: u32,
+        mip_levels: MipmapsCount,
+        format: Format,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        B: TypedBufferAccess<Content = [Px]> + 'static,
+        Px: Pixel + Send + Sync + Clone + 'static,
+    {
+        ImmutableImage::cubemap_array_from_buffer(source, size, 1, mip_levels, format, queue)
+    }
+
+    /// Construct a cube-compatible ImmutableImage with `cube_count` cubes from the contents of
+    /// `iter`.
+    ///
+    /// The image has `6 * cube_count` array layers. `iter` must contain the cubes one after the
+    /// other, each laid out as described in [`cubemap_from_iter`](ImmutableImage::cubemap_from_iter).
+    /// Use an image view of type
+    /// [`ImageViewType::CubeArray`](crate::image::view::ImageViewType::CubeArray) to sample it as
+    /// a cubemap array, which requires the
+    /// [`image_cube_array`](crate::device::Features::image_cube_array) feature.
+    #[inline]
+    pub fn cubemap_array_from_iter<Px, I>(
+        iter: I,
+        size: u32,
+        cube_count: u32,
+        mip_levels: MipmapsCount,
+        format: Format,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        Px: Pixel + Send + Sync + Clone + 'static,
+        I: IntoIterator<Item = Px>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let source = CpuAccessibleBuffer::from_iter(
+            queue.device().clone(),
+            BufferUsage::transfer_source(),
+            false,
+            iter,
+        )?;
+        ImmutableImage::cubemap_array_from_buffer(
+            source, size, cube_count, mip_levels, format, queue,
+        )
+    }
+
+    /// Construct a cube-compatible ImmutableImage with `cube_count` cubes containing a copy of
+    /// the data in `source`.
+    ///
+    /// See [`cubemap_array_from_iter`](ImmutableImage::cubemap_array_from_iter) for the expected
+    /// layout of the data. Returns an error if the size of `source` is not the size of
+    /// `6 * cube_count` faces.
+    pub fn cubemap_array_from_buffer<B, Px>(
+        source: Arc<B>,
+        size: u32,
+        cube_count: u32,
+        mip_levels: MipmapsCount,
+        format: Format,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
+    where
+        B: TypedBufferAccess<Content = [Px]> + 'static,
+        Px: Pixel + Send + Sync + Clone + 'static,
+    {
+        let array_layers = cube_count * 6;
+
+        // Only the first mip level is copied from `source`, the others are generated.
+        if let Some(block_size) = format.block_size() {
+            let [block_width, block_height, _] = format.block_extent();
+            let expected = ((size + block_width - 1) / block_width) as DeviceSize
+                * ((size + block_height - 1) / block_height) as DeviceSize
+                * array_layers as DeviceSize
+                * block_size;
+
+            if source.size() != expected {
+                return Err(ImageCreationError::SourceSizeMismatch {
+                    size: source.size(),
+                    expected,
+                });
+            }
+        }
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: size,
+            height: size,
+            array_layers,
+        };
+        let flags = ImageCreateFlags {
+            cube_compatible: true,
+            ..ImageCreateFlags::none()
+        };
+
+        ImmutableImage::from_buffer_with_flags(source, dimensions, mip_levels, format, flags, queue)
+    }
+
+    fn from_buffer_with_flags<B, Px>(
+        source: Arc<B>,
+        dimensions: ImageDimensions,
+        mip_levels: MipmapsCount,
+        format: Format,
+        flags: ImageCreateFlags,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
     where
         B: TypedBufferAccess<Content = [Px]> + 'static,
         Px: Pixel + Send + Sync + Clone + 'static,
@@ -345,7 +573,6 @@ impl ImmutableImage {
             sampled: true,
             ..ImageUsage::none()
         };
-        let flags = ImageCreateFlags::none();
         let layout = ImageLayout::ShaderReadOnlyOptimal;
 
         let (image, initializer) = ImmutableImage::uninitialized(
@@ -359,7 +586,14 @@ impl ImmutableImage {
             source.device().active_queue_families(),
         )?;
 
-        let init = SubImage::new(initializer, 0, 1, 0, 1, ImageLayout::ShaderReadOnlyOptimal);
+        let init = SubImage::new(
+            initializer,
+            0,
+            1,
+            0,
+            dimensions.array_layers(),
+            ImageLayout::ShaderReadOnlyOptimal,
+        );
 
         let mut cbb = AutoCommandBufferBuilder::primary(
             source.device().clone(),
@@ -679,3 +913,65 @@ where
         self.inner().hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ImmutableImage;
+    use crate::format::Format;
+    use crate::image::sys::ImageCreationError;
+    use crate::image::ImageAccess;
+    use crate::image::MipmapsCount;
+
+    #[test]
+    fn cubemap_from_iter() {
+        let (_device, queue) = gfx_dev_and_queue!();
+        let (image, _future) = ImmutableImage::cubemap_from_iter(
+            vec![[0_u8; 4]; 6 * 16 * 16],
+            16,
+            MipmapsCount::One,
+            Format::R8G8B8A8_UNORM,
+            queue,
+        )
+        .unwrap();
+
+        assert!(image.inner().image.flags().cube_compatible);
+        assert_eq!(image.dimensions().array_layers(), 6);
+    }
+
+    #[test]
+    fn cubemap_array_from_iter() {
+        let (_device, queue) = gfx_dev_and_queue!();
+        let (image, _future) = ImmutableImage::cubemap_array_from_iter(
+            vec![[0_u8; 4]; 2 * 6 * 16 * 16],
+            16,
+            2,
+            MipmapsCount::One,
+            Format::R8G8B8A8_UNORM,
+            queue,
+        )
+        .unwrap();
+
+        assert!(image.inner().image.flags().cube_compatible);
+        assert_eq!(image.dimensions().array_layers(), 12);
+    }
+
+    #[test]
+    fn cubemap_source_size_mismatch() {
+        let (_device, queue) = gfx_dev_and_queue!();
+        let result = ImmutableImage::cubemap_from_iter(
+            vec![[0_u8; 4]; 5 * 16 * 16],
+            16,
+            MipmapsCount::One,
+            Format::R8G8B8A8_UNORM,
+            queue,
+        );
+
+        assert!(matches!(
+            result,
+            Err(ImageCreationError::SourceSizeMismatch {
+                size: 5120,
+                expected: 6144,
+            })
+        ));
+    }
+}
@@ -52,6 +52,8 @@ pub use self::attachment::AttachmentImage;
 pub use self::immutable::ImmutableImage;
 pub use self::layout::ImageDescriptorLayouts;
 pub use self::layout::ImageLayout;
+pub use self::readback::read_to_vec;
+pub use self::readback::ImageReadError;
 pub use self::storage::StorageImage;
 pub use self::swapchain::SwapchainImage;
 pub use self::sys::ImageCreationError;
@@ -65,6 +67,7 @@ mod aspect;
 pub mod attachment; // TODO: make private
 pub mod immutable; // TODO: make private
 mod layout;
+mod readback;
 mod storage;
 pub mod swapchain; // TODO: make private
 pub mod sys;
@@ -271,6 +274,12 @@ pub struct ImageCreateFlags {
     ///
     /// Requires `mutable_format`.
     pub block_texel_view_compatible: bool,
+    /// The image is a protected image. It can only be bound to protected memory, and can only be
+    /// accessed by protected queues.
+    ///
+    /// Requires the [`protected_memory`](crate::device::Features::protected_memory) feature to
+    /// be enabled.
+    pub protected: bool,
 }
 
 impl ImageCreateFlags {
@@ -289,6 +298,7 @@ impl From<ImageCreateFlags> for ash::vk::ImageCreateFlags {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            protected,
         } = flags;
 
         let mut vk_flags = Self::default();
@@ -313,6 +323,9 @@ impl From<ImageCreateFlags> for ash::vk::ImageCreateFlags {
         if block_texel_view_compatible {
             vk_flags |= ash::vk::ImageCreateFlags::BLOCK_TEXEL_VIEW_COMPATIBLE
         };
+        if protected {
+            vk_flags |= ash::vk::ImageCreateFlags::PROTECTED
+        };
         vk_flags
     }
 }
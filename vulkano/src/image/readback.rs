@@ -0,0 +1,364 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::buffer::BufferUsage;
+use crate::buffer::CpuAccessibleBuffer;
+use crate::command_buffer::AutoCommandBufferBuilder;
+use crate::command_buffer::BuildError;
+use crate::command_buffer::CommandBufferExecError;
+use crate::command_buffer::CommandBufferUsage;
+use crate::command_buffer::CopyBufferImageError;
+use crate::command_buffer::PrimaryCommandBuffer;
+use crate::device::Queue;
+use crate::format::Format;
+use crate::image::ImageAccess;
+use crate::image::ImageAspect;
+use crate::memory::DeviceMemoryAllocError;
+use crate::sync::FlushError;
+use crate::sync::GpuFuture;
+use crate::DeviceSize;
+use crate::OomError;
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+/// Copies the first mip level of `image` to the CPU, and returns its content.
+///
+/// The data of all array layers is returned one after the other, with the texels of each layer
+/// tightly packed: each row of blocks immediately follows the previous one, without any padding.
+/// For block-compressed formats, the returned data contains whole compressed blocks.
+///
+/// For multi-planar formats, each plane is copied with the format and the extent of that plane,
+/// and the planes are returned one after the other, each with all of its array layers. With
+/// chroma subsampling, the planes other than the first are smaller than the image.
+///
+/// This records a copy into a temporary host-visible buffer, submits it to `queue` and blocks
+/// until the GPU has finished executing it. The image must have been created with the
+/// `transfer_source` usage.
+///
+/// Formats with both a depth and a stencil aspect are not supported, and
+/// `ImageReadError::UnsupportedFormat` is returned for them.
+///
+/// This is also available as the [`read_to_vec`](ImageAccess::read_to_vec) method of images.
+pub fn read_to_vec(
+    image: Arc<dyn ImageAccess>,
+    queue: Arc<Queue>,
+) -> Result<Vec<u8>, ImageReadError> {
+    let format = image.format();
+    let aspects = format.aspects();
+
+    if aspects.depth && aspects.stencil {
+        return Err(ImageReadError::UnsupportedFormat);
+    }
+
+    let dimensions = image.dimensions();
+    let extent = dimensions.width_height_depth();
+
+    // The format, the extent and the aspect of each part of the image that is copied on its own.
+    let parts: Vec<(Format, [u32; 3], Option<ImageAspect>)> = if format.planes().is_empty() {
+        vec![(format, extent, None)]
+    } else {
+        let plane_aspects = [
+            ImageAspect::Plane0,
+            ImageAspect::Plane1,
+            ImageAspect::Plane2,
+        ];
+        format
+            .planes()
+            .iter()
+            .zip(plane_aspects)
+            .enumerate()
+            .map(|(plane, (&plane_format, aspect))| {
+                (
+                    plane_format,
+                    format.plane_extent(plane, extent),
+                    Some(aspect),
+                )
+            })
+            .collect()
+    };
+
+    let device = queue.device().clone();
+    let mut builder = AutoCommandBufferBuilder::primary(
+        device.clone(),
+        queue.family(),
+        CommandBufferUsage::OneTimeSubmit,
+    )?;
+    let mut buffers = Vec::with_capacity(parts.len());
+
+    for (part_format, [width, height, depth], aspect) in parts {
+        let block_size = part_format
+            .block_size()
+            .ok_or(ImageReadError::UnsupportedFormat)?;
+        let [block_width, block_height, _] = part_format.block_extent();
+        let num_blocks = ((width + block_width - 1) / block_width) as DeviceSize
+            * ((height + block_height - 1) / block_height) as DeviceSize
+            * depth as DeviceSize
+            * dimensions.array_layers() as DeviceSize;
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_destination(),
+            true,
+            (0..num_blocks * block_size).map(|_| 0u8),
+        )?;
+
+        match aspect {
+            Some(aspect) => builder.copy_image_aspect_to_buffer_dimensions(
+                image.clone(),
+                buffer.clone(),
+                [0, 0, 0],
+                [width, height, depth],
+                0,
+                dimensions.array_layers(),
+                0,
+                aspect,
+            )?,
+            None => builder.copy_image_to_buffer_dimensions(
+                image.clone(),
+                buffer.clone(),
+                [0, 0, 0],
+                [width, height, depth],
+                0,
+                dimensions.array_layers(),
+                0,
+            )?,
+        };
+        buffers.push(buffer);
+    }
+
+    let command_buffer = builder.build()?;
+
+    command_buffer
+        .execute(queue)?
+        .then_signal_fence_and_flush()?
+        .wait(None)?;
+
+    let mut content = Vec::new();
+    for buffer in buffers {
+        content.extend_from_slice(&buffer.read().unwrap());
+    }
+    Ok(content)
+}
+
+/// Error that can happen when reading the content of an image with [`read_to_vec`].
+#[derive(Clone, Debug)]
+pub enum ImageReadError {
+    /// Allocating memory for the temporary buffer failed.
+    AllocError(DeviceMemoryAllocError),
+    /// Building the command buffer failed.
+    BuildError(BuildError),
+    /// Recording the copy command failed.
+    CopyError(CopyBufferImageError),
+    /// Submitting the command buffer failed.
+    ExecError(CommandBufferExecError),
+    /// Flushing or waiting for the submission failed.
+    FlushError(FlushError),
+    /// Not enough memory.
+    OomError(OomError),
+    /// The format of the image is not supported by this function.
+    UnsupportedFormat,
+}
+
+impl error::Error for ImageReadError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::AllocError(ref err) => Some(err),
+            Self::BuildError(ref err) => Some(err),
+            Self::CopyError(ref err) => Some(err),
+            Self::ExecError(ref err) => Some(err),
+            Self::FlushError(ref err) => Some(err),
+            Self::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ImageReadError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                Self::AllocError(_) => "allocating memory for the temporary buffer failed",
+                Self::BuildError(_) => "building the command buffer failed",
+                Self::CopyError(_) => "recording the copy command failed",
+                Self::ExecError(_) => "submitting the command buffer failed",
+                Self::FlushError(_) => "flushing or waiting for the submission failed",
+                Self::OomError(_) => "not enough memory available",
+                Self::UnsupportedFormat => "the format of the image is not supported",
+            }
+        )
+    }
+}
+
+impl From<DeviceMemoryAllocError> for ImageReadError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> Self {
+        Self::AllocError(err)
+    }
+}
+
+impl From<BuildError> for ImageReadError {
+    #[inline]
+    fn from(err: BuildError) -> Self {
+        Self::BuildError(err)
+    }
+}
+
+impl From<CopyBufferImageError> for ImageReadError {
+    #[inline]
+    fn from(err: CopyBufferImageError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+impl From<CommandBufferExecError> for ImageReadError {
+    #[inline]
+    fn from(err: CommandBufferExecError) -> Self {
+        Self::ExecError(err)
+    }
+}
+
+impl From<FlushError> for ImageReadError {
+    #[inline]
+    fn from(err: FlushError) -> Self {
+        Self::FlushError(err)
+    }
+}
+
+impl From<OomError> for ImageReadError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_to_vec;
+    use crate::format::Format;
+    use crate::image::ImageAccess;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageUsage;
+    use crate::image::ImmutableImage;
+    use crate::image::MipmapsCount;
+    use crate::image::StorageImage;
+    use crate::sync::GpuFuture;
+
+    #[test]
+    fn read_back() {
+        let (_device, queue) = gfx_dev_and_queue!();
+
+        // Every texel of the first mip level holds its own coordinates and array layer, so that
+        // texels that end up at the wrong place in the output are detected.
+        let (width, height, array_layers) = (13, 7, 2);
+        let mut data = Vec::new();
+        for layer in 0..array_layers {
+            for y in 0..height {
+                for x in 0..width {
+                    data.extend_from_slice(&[x as u8, y as u8, layer as u8, 255]);
+                }
+            }
+        }
+
+        // The second mip level is generated from the first one and must not be read back.
+        let (image, future) = ImmutableImage::from_iter(
+            data.iter().cloned(),
+            ImageDimensions::Dim2d {
+                width,
+                height,
+                array_layers,
+            },
+            MipmapsCount::Specific(2),
+            Format::R8G8B8A8_UNORM,
+            queue.clone(),
+        )
+        .unwrap();
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let content = read_to_vec(image, queue).unwrap();
+        assert_eq!(content.len(), (width * height * array_layers * 4) as usize);
+
+        // Rows are tightly packed, one layer after the other.
+        let row_pitch = (width * 4) as usize;
+        let texel = |x: u32, y: u32, layer: u32| {
+            let offset = (layer * height + y) as usize * row_pitch + x as usize * 4;
+            &content[offset..offset + 4]
+        };
+        assert_eq!(texel(12, 0, 0), &[12, 0, 0, 255]);
+        assert_eq!(texel(0, 1, 0), &[0, 1, 0, 255]);
+        assert_eq!(texel(5, 6, 1), &[5, 6, 1, 255]);
+        assert_eq!(content, data);
+    }
+
+    #[test]
+    fn read_back_block_compressed() {
+        let (_device, queue) = gfx_dev_and_queue!();
+
+        // An 8x8 `BC1` image is made of 2x2 blocks of 8 bytes each.
+        let data: Vec<u8> = (0..32).collect();
+        let (image, future) = match ImmutableImage::from_iter(
+            data.iter().cloned(),
+            ImageDimensions::Dim2d {
+                width: 8,
+                height: 8,
+                array_layers: 1,
+            },
+            MipmapsCount::One,
+            Format::BC1_RGB_UNORM_BLOCK,
+            queue.clone(),
+        ) {
+            Ok(image) => image,
+            Err(_) => return,
+        };
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(image.clone().read_to_vec(queue).unwrap(), data);
+    }
+
+    #[test]
+    fn read_back_multi_planar() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = match StorageImage::with_usage(
+            device,
+            ImageDimensions::Dim2d {
+                width: 16,
+                height: 16,
+                array_layers: 1,
+            },
+            Format::G8_B8R8_2PLANE_420_UNORM,
+            ImageUsage {
+                transfer_source: true,
+                ..ImageUsage::none()
+            },
+            Default::default(),
+            Some(queue.family()),
+        ) {
+            Ok(image) => image,
+            Err(_) => return,
+        };
+
+        // The 16x16 luma plane with one byte per texel is followed by the 8x8 chroma plane with
+        // two bytes per texel.
+        let content = read_to_vec(image, queue).unwrap();
+        assert_eq!(content.len(), 16 * 16 + 8 * 8 * 2);
+    }
+}
@@ -67,6 +67,7 @@ use smallvec::SmallVec;
 use std::fs::File;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -132,6 +133,7 @@ impl StorageImage {
     }
 
     /// Same as `new`, but allows specifying the usage.
+    #[inline]
     pub fn with_usage<'a, I>(
         device: Arc<Device>,
         dimensions: ImageDimensions,
@@ -142,6 +144,35 @@ impl StorageImage {
     ) -> Result<Arc<StorageImage>, ImageCreationError>
     where
         I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        StorageImage::with_view_formats(
+            device,
+            dimensions,
+            format,
+            usage,
+            flags,
+            iter::empty(),
+            queue_families,
+        )
+    }
+
+    /// Same as `with_usage`, but also allows specifying the formats that image views of the image
+    /// are going to be created with.
+    ///
+    /// See [`UnsafeImageBuilder::view_formats`](crate::image::sys::UnsafeImageBuilder::view_formats)
+    /// for the requirements on `view_formats`.
+    pub fn with_view_formats<'a, I, F>(
+        device: Arc<Device>,
+        dimensions: ImageDimensions,
+        format: Format,
+        usage: ImageUsage,
+        flags: ImageCreateFlags,
+        view_formats: F,
+        queue_families: I,
+    ) -> Result<Arc<StorageImage>, ImageCreationError>
+    where
+        I: IntoIterator<Item = QueueFamily<'a>>,
+        F: IntoIterator<Item = Format>,
     {
         let queue_families = queue_families
             .into_iter()
@@ -158,6 +189,7 @@ impl StorageImage {
                 Sharing::Exclusive
             })
             .usage(usage)
+            .view_formats(view_formats)
             .build()?;
 
         let mem_reqs = image.memory_requirements();
@@ -423,7 +455,11 @@ where
 mod tests {
     use super::StorageImage;
     use crate::format::Format;
+    use crate::image::view::ImageView;
+    use crate::image::ImageAccess;
+    use crate::image::ImageCreateFlags;
     use crate::image::ImageDimensions;
+    use crate::image::ImageUsage;
 
     #[test]
     fn create() {
@@ -440,4 +476,38 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn with_view_formats() {
+        let (device, queue) = gfx_dev_and_queue_with!([khr_image_format_list], []);
+        let image = StorageImage::with_view_formats(
+            device,
+            ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8_UNORM,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags {
+                mutable_format: true,
+                ..ImageCreateFlags::none()
+            },
+            [Format::R8G8B8A8_UNORM, Format::R8G8B8A8_SRGB],
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            image.inner().image.view_formats(),
+            [Format::R8G8B8A8_UNORM, Format::R8G8B8A8_SRGB]
+        );
+        ImageView::start(image)
+            .format(Format::R8G8B8A8_SRGB)
+            .build()
+            .unwrap();
+    }
 }
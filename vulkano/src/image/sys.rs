@@ -14,7 +14,7 @@
 //! that you create must wrap around the types in this module.
 
 use crate::check_errors;
-use crate::device::physical::{FormatFeatures, ImageFormatProperties};
+use crate::device::physical::{FormatFeatures, ImageFormatProperties, SparseImageFormatProperties};
 use crate::device::{Device, DeviceOwned};
 use crate::format::{ChromaSampling, Format, NumericType};
 use crate::image::{
@@ -62,6 +62,7 @@ pub struct UnsafeImage {
     samples: SampleCount,
     tiling: ImageTiling,
     usage: ImageUsage,
+    view_formats: SmallVec<[Format; 4]>,
 
     // `vkDestroyImage` is called only if `needs_destruction` is true.
     needs_destruction: bool,
@@ -83,6 +84,7 @@ impl UnsafeImage {
             sharing: Sharing::Exclusive,
             tiling: ImageTiling::Optimal,
             usage: ImageUsage::none(),
+            view_formats: SmallVec::new(),
         }
     }
 
@@ -120,12 +122,17 @@ impl UnsafeImage {
             samples,
             tiling,
             usage,
+            view_formats: SmallVec::new(),
 
             needs_destruction: false, // TODO: pass as parameter
         }
     }
 
     /// Returns the memory requirements for this image.
+    ///
+    /// If the image is [`protected`](ImageCreateFlags::protected), `memory_type_bits` only
+    /// contains protected memory types, and `prefer_dedicated` is `true`, so that the memory
+    /// pools never place the image in a block that is shared with unprotected resources.
     pub fn memory_requirements(&self) -> MemoryRequirements {
         let image_memory_requirements_info2 = ash::vk::ImageMemoryRequirementsInfo2 {
             image: self.handle,
@@ -178,10 +185,55 @@ impl UnsafeImage {
             }
         }
 
-        MemoryRequirements {
+        let mut requirements = MemoryRequirements {
             prefer_dedicated: memory_dedicated_requirements
                 .map_or(false, |dreqs| dreqs.prefers_dedicated_allocation != 0),
+            require_dedicated: memory_dedicated_requirements
+                .map_or(false, |dreqs| dreqs.requires_dedicated_allocation != 0),
             ..MemoryRequirements::from(memory_requirements2.memory_requirements)
+        };
+
+        // VUID-vkBindImageMemory-None-01901
+        if self.flags.protected {
+            for memory_type in self.device.physical_device().memory_types() {
+                if !memory_type.is_protected() {
+                    requirements.memory_type_bits &= !(1 << memory_type.id());
+                }
+            }
+
+            requirements.prefer_dedicated = true;
+        }
+
+        requirements
+    }
+
+    /// Returns the sparse memory requirements for this image.
+    ///
+    /// One element is returned for each aspect, or each group of aspects that share the same
+    /// requirements. The returned list is empty if the image was not created with the
+    /// `sparse_residency` flag.
+    pub fn sparse_memory_requirements(&self) -> Vec<SparseImageMemoryRequirements> {
+        unsafe {
+            let fns = self.device.fns();
+
+            let mut num = 0;
+            fns.v1_0.get_image_sparse_memory_requirements(
+                self.device.internal_object(),
+                self.handle,
+                &mut num,
+                ptr::null_mut(),
+            );
+
+            let mut requirements = Vec::with_capacity(num as usize);
+            fns.v1_0.get_image_sparse_memory_requirements(
+                self.device.internal_object(),
+                self.handle,
+                &mut num,
+                requirements.as_mut_ptr(),
+            );
+            requirements.set_len(num as usize);
+
+            requirements.into_iter().map(Into::into).collect()
         }
     }
 
@@ -270,6 +322,13 @@ impl UnsafeImage {
         &self.usage
     }
 
+    /// Returns the formats that image views of the image were declared to be created with. If
+    /// this is empty, no list was given when the image was created.
+    #[inline]
+    pub fn view_formats(&self) -> &[Format] {
+        &self.view_formats
+    }
+
     /// Returns a key unique to each `UnsafeImage`. Can be used for the `conflicts_key` method.
     #[inline]
     pub fn key(&self) -> u64 {
@@ -461,6 +520,7 @@ pub struct UnsafeImageBuilder {
     sharing: Sharing<SmallVec<[u32; 4]>>,
     tiling: ImageTiling,
     usage: ImageUsage,
+    view_formats: SmallVec<[Format; 4]>,
 }
 
 impl UnsafeImageBuilder {
@@ -483,6 +543,7 @@ impl UnsafeImageBuilder {
             sharing,
             tiling,
             usage,
+            view_formats,
         } = self;
 
         let physical_device = device.physical_device();
@@ -713,6 +774,56 @@ impl UnsafeImageBuilder {
             }
         }
 
+        /* Check view formats */
+
+        let view_formats_vk: SmallVec<[ash::vk::Format; 4]> =
+            view_formats.iter().map(|&format| format.into()).collect();
+        let mut image_format_list_create_info = if !view_formats.is_empty() {
+            if !(device.api_version() >= Version::V1_2
+                || device.enabled_extensions().khr_image_format_list)
+            {
+                return Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "khr_image_format_list",
+                    reason: "view_formats was not empty",
+                });
+            }
+
+            // VUID-VkImageCreateInfo-flags-04738
+            if !flags.mutable_format && (view_formats.len() != 1 || view_formats[0] != format) {
+                return Err(ImageCreationError::ViewFormatsWithoutMutableFormat);
+            }
+
+            for &view_format in &view_formats {
+                // VUID-VkImageCreateInfo-pNext-02722
+                if !(view_format.compatibility() == format.compatibility()
+                    || (flags.block_texel_view_compatible
+                        && view_format.block_size() == format.block_size()))
+                {
+                    return Err(ImageCreationError::ViewFormatNotCompatible {
+                        format: view_format,
+                    });
+                }
+            }
+
+            Some(ash::vk::ImageFormatListCreateInfo {
+                view_format_count: view_formats_vk.len() as u32,
+                p_view_formats: view_formats_vk.as_ptr(),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        if flags.protected {
+            // VUID-VkImageCreateInfo-flags-01890
+            if !device.enabled_features().protected_memory {
+                return Err(ImageCreationError::FeatureNotEnabled {
+                    feature: "protected_memory",
+                    reason: "flags included `protected`",
+                });
+            }
+        }
+
         // TODO:
         if flags.sparse_binding || flags.sparse_residency || flags.sparse_aliased {
             todo!();
@@ -1031,6 +1142,10 @@ impl UnsafeImageBuilder {
             create_info = create_info.push_next(next);
         }
 
+        if let Some(next) = image_format_list_create_info.as_mut() {
+            create_info = create_info.push_next(next);
+        }
+
         let fns = device.fns();
         let fns_i = device.instance().fns();
 
@@ -1058,6 +1173,7 @@ impl UnsafeImageBuilder {
             samples,
             tiling,
             usage,
+            view_formats,
 
             needs_destruction: true,
         };
@@ -1256,6 +1372,27 @@ impl UnsafeImageBuilder {
         self.usage = usage;
         self
     }
+
+    /// The formats that image views of the image are going to be created with.
+    ///
+    /// If this is not empty, the image can only be viewed with the formats in the list, which can
+    /// allow the implementation to store the image more efficiently than with `mutable_format`
+    /// alone. Each format must be compatible with the format of the image. If `mutable_format` is
+    /// not set in the flags, the list can only contain the format of the image.
+    ///
+    /// If this is not empty, the device must either support API version 1.2 or the
+    /// [`khr_image_format_list`](crate::device::DeviceExtensions::khr_image_format_list)
+    /// extension must be enabled.
+    ///
+    /// The default value is empty.
+    #[inline]
+    pub fn view_formats<I>(mut self, view_formats: I) -> Self
+    where
+        I: IntoIterator<Item = Format>,
+    {
+        self.view_formats = view_formats.into_iter().collect();
+        self
+    }
 }
 
 /// Error that can happen when creating an instance.
@@ -1342,6 +1479,20 @@ pub enum ImageCreationError {
     /// valid.
     SharingInvalidQueueFamilyId { id: u32 },
 
+    /// The size in bytes of the data to initialize the image with did not match the size of the
+    /// image.
+    SourceSizeMismatch {
+        size: DeviceSize,
+        expected: DeviceSize,
+    },
+
+    /// A format in the view formats was not compatible with the format of the image.
+    ViewFormatNotCompatible { format: Format },
+
+    /// The `mutable_format` flag was not set, but the view formats contained a format other than
+    /// the format of the image.
+    ViewFormatsWithoutMutableFormat,
+
     /// A YCbCr format was given, but the specified width and/or height was not a multiple of 2
     /// as required by the format's chroma subsampling.
     YcbcrFormatInvalidDimensions,
@@ -1466,6 +1617,21 @@ impl fmt::Display for ImageCreationError {
             Self::SharingInvalidQueueFamilyId { id } => {
                 write!(fmt, "the sharing mode was set to `Concurrent`, but one of the specified queue family ids was not valid")
             }
+            Self::SourceSizeMismatch { .. } => {
+                write!(
+                    fmt,
+                    "the size of the data to initialize the image with did not match the size of the image"
+                )
+            }
+            Self::ViewFormatNotCompatible { .. } => {
+                write!(
+                    fmt,
+                    "a format in the view formats was not compatible with the format of the image"
+                )
+            }
+            Self::ViewFormatsWithoutMutableFormat => {
+                write!(fmt, "the `mutable_format` flag was not set, but the view formats contained a format other than the format of the image")
+            }
             Self::YcbcrFormatInvalidDimensions => {
                 write!(fmt, "a YCbCr format was given, but the specified width and/or height was not a multiple of 2 as required by the format's chroma subsampling")
             }
@@ -1540,6 +1706,39 @@ pub struct LinearLayout {
     pub depth_pitch: DeviceSize,
 }
 
+/// Describes the sparse memory requirements of an aspect of an image created with the
+/// `sparse_residency` flag.
+///
+/// Obtained by calling `sparse_memory_requirements` on the image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SparseImageMemoryRequirements {
+    /// The properties of the format of the image, for the aspects this applies to.
+    pub format_properties: SparseImageFormatProperties,
+    /// The first mip level that is part of the mip tail region.
+    pub image_mip_tail_first_lod: u32,
+    /// Size in bytes of the mip tail region. If `format_properties.single_miptail` is false,
+    /// this is the size for each array layer.
+    pub image_mip_tail_size: DeviceSize,
+    /// Opaque offset of the mip tail region, to be used when binding memory to it.
+    pub image_mip_tail_offset: DeviceSize,
+    /// Offset stride between the mip tail regions of each array layer. This value is undefined
+    /// if `format_properties.single_miptail` is true.
+    pub image_mip_tail_stride: DeviceSize,
+}
+
+impl From<ash::vk::SparseImageMemoryRequirements> for SparseImageMemoryRequirements {
+    #[inline]
+    fn from(val: ash::vk::SparseImageMemoryRequirements) -> Self {
+        SparseImageMemoryRequirements {
+            format_properties: val.format_properties.into(),
+            image_mip_tail_first_lod: val.image_mip_tail_first_lod,
+            image_mip_tail_size: val.image_mip_tail_size,
+            image_mip_tail_offset: val.image_mip_tail_offset,
+            image_mip_tail_stride: val.image_mip_tail_stride,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ImageCreateFlags;
@@ -1716,4 +1915,97 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn protected_missing_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::start(device)
+            .dimensions(ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            })
+            .flags(ImageCreateFlags {
+                protected: true,
+                ..ImageCreateFlags::none()
+            })
+            .format(Format::R8G8B8A8_UNORM)
+            .usage(ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            })
+            .build();
+
+        match res {
+            Err(ImageCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
+            }) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn protected_memory_requirements() {
+        let (device, _) = gfx_dev_and_queue!(protected_memory);
+
+        let image = UnsafeImage::start(device.clone())
+            .dimensions(ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            })
+            .flags(ImageCreateFlags {
+                protected: true,
+                ..ImageCreateFlags::none()
+            })
+            .format(Format::R8G8B8A8_UNORM)
+            .usage(ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            })
+            .build()
+            .unwrap();
+
+        let requirements = image.memory_requirements();
+        assert!(requirements.prefer_dedicated);
+        assert_ne!(requirements.memory_type_bits, 0);
+
+        for memory_type in device.physical_device().memory_types() {
+            if requirements.memory_type_bits & (1 << memory_type.id()) != 0 {
+                assert!(memory_type.is_protected());
+            }
+        }
+    }
+
+    #[test]
+    fn view_format_not_compatible() {
+        let (device, _) = gfx_dev_and_queue_with!([khr_image_format_list], []);
+
+        let res = UnsafeImage::start(device)
+            .dimensions(ImageDimensions::Dim2d {
+                width: 32,
+                height: 32,
+                array_layers: 1,
+            })
+            .flags(ImageCreateFlags {
+                mutable_format: true,
+                ..ImageCreateFlags::none()
+            })
+            .format(Format::R8G8B8A8_UNORM)
+            .usage(ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            })
+            .view_formats([Format::R8G8B8A8_SRGB, Format::R16_SFLOAT])
+            .build();
+
+        match res {
+            Err(ImageCreationError::ViewFormatNotCompatible {
+                format: Format::R16_SFLOAT,
+            }) => (),
+            _ => panic!(),
+        };
+    }
 }
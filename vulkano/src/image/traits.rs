@@ -8,12 +8,14 @@
 // according to those terms.
 
 use crate::device::physical::FormatFeatures;
+use crate::device::Queue;
 use crate::format::ClearValue;
 use crate::format::Format;
 use crate::image::sys::UnsafeImage;
 use crate::image::ImageDescriptorLayouts;
 use crate::image::ImageDimensions;
 use crate::image::ImageLayout;
+use crate::image::ImageReadError;
 use crate::image::SampleCount;
 use crate::sync::AccessError;
 use crate::SafeDeref;
@@ -117,6 +119,18 @@ pub unsafe trait ImageAccess: Send + Sync {
         })
     }
 
+    /// Copies the first mip level of the image to the CPU, and returns its content.
+    ///
+    /// This blocks until the copy has completed. See [`read_to_vec`](crate::image::read_to_vec)
+    /// for the layout of the returned data.
+    #[inline]
+    fn read_to_vec(self: Arc<Self>, queue: Arc<Queue>) -> Result<Vec<u8>, ImageReadError>
+    where
+        Self: Sized + 'static,
+    {
+        crate::image::read_to_vec(self, queue)
+    }
+
     /// Returns an [`ImageDescriptorLayouts`] structure specifying the image layout to use
     /// in descriptors of various kinds.
     ///
@@ -466,6 +466,11 @@ where
             return Err(ImageViewCreationError::FormatNotCompatible);
         }
 
+        // VUID-VkImageViewCreateInfo-pNext-01585
+        if !image_inner.view_formats().is_empty() && !image_inner.view_formats().contains(&format) {
+            return Err(ImageViewCreationError::FormatNotInViewFormats);
+        }
+
         // VUID-VkImageViewCreateInfo-imageViewType-04973
         if (ty == ImageViewType::Dim1d || ty == ImageViewType::Dim2d || ty == ImageViewType::Dim3d)
             && layer_count != 1
@@ -669,7 +674,9 @@ where
     /// The format of the image view.
     ///
     /// If this is set to a format that is different from the image, the image must be created with
-    /// the `mutable_format` flag.
+    /// the `mutable_format` flag. If the image was created with a list of
+    /// [`view_formats`](crate::image::sys::UnsafeImageBuilder::view_formats), the format must be
+    /// in that list.
     ///
     /// The default value is the format of the image.
     #[inline]
@@ -762,6 +769,9 @@ pub enum ImageViewCreationError {
     /// The requested format was not compatible with the image.
     FormatNotCompatible,
 
+    /// The image was created with a list of view formats, but the requested format was not in it.
+    FormatNotInViewFormats,
+
     /// The given format was not supported by the device.
     FormatNotSupported,
 
@@ -869,6 +879,10 @@ impl fmt::Display for ImageViewCreationError {
                 fmt,
                 "the requested format was not compatible with the image",
             ),
+            Self::FormatNotInViewFormats => write!(
+                fmt,
+                "the image was created with a list of view formats, but the requested format was not in it",
+            ),
             Self::FormatNotSupported => write!(
                 fmt,
                 "the given format was not supported by the device"
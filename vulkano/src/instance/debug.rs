@@ -40,15 +40,23 @@
 use crate::check_errors;
 use crate::instance::Instance;
 use crate::Error;
+use crate::OomError;
 use crate::VulkanObject;
+use ash::vk::Handle;
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::error;
 use std::ffi::CStr;
 use std::fmt;
 use std::mem::MaybeUninit;
-use std::os::raw::c_void;
+use std::os::raw::{c_char, c_void};
 use std::panic;
 use std::ptr;
+use std::slice;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 /// Registration of a callback called by validation layers.
 ///
@@ -57,14 +65,46 @@ use std::sync::Arc;
               to be called"]
 pub struct DebugCallback {
     instance: Arc<Instance>,
-    debug_report_callback: ash::vk::DebugUtilsMessengerEXT,
-    user_callback: Box<Box<dyn Fn(&Message) + Send>>,
+    debug_utils_messenger: ash::vk::DebugUtilsMessengerEXT,
+    user_callback: Box<Box<dyn Fn(&Message) -> bool + Send + panic::RefUnwindSafe>>,
 }
 
 impl DebugCallback {
+    /// Starts building a debug callback, which allows setting more options than `new`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use vulkano::instance::Instance;
+    /// # use std::sync::Arc;
+    /// # let instance: Arc<Instance> = return;
+    /// use vulkano::instance::debug::DebugCallback;
+    ///
+    /// let _callback = DebugCallback::builder(&instance)
+    ///     .ignore_ids(&["VUID-vkCmdDraw-None-02698"])
+    ///     .user_callback(|msg| println!("Debug callback: {:?}", msg.description))
+    ///     .build()
+    ///     .ok();
+    /// ```
+    #[inline]
+    pub fn builder(instance: &Arc<Instance>) -> DebugCallbackBuilder {
+        DebugCallbackBuilder {
+            instance: instance.clone(),
+            severity: MessageSeverity::errors_and_warnings(),
+            ty: MessageType::all(),
+            ignored_ids: HashSet::default(),
+            user_callback: None,
+        }
+    }
+
     /// Initializes a debug callback.
     ///
-    /// Panics generated by calling `user_callback` are ignored.
+    /// Panics generated by calling `user_callback` are ignored. The callback must not make any
+    /// calls to the Vulkan API.
+    ///
+    /// Shortcut for `builder(instance).severity(severity).message_type(ty)
+    /// .user_callback(user_callback).build()`.
+    #[inline]
     pub fn new<F>(
         instance: &Arc<Instance>,
         severity: MessageSeverity,
@@ -74,56 +114,489 @@ impl DebugCallback {
     where
         F: Fn(&Message) + 'static + Send + panic::RefUnwindSafe,
     {
+        DebugCallback::builder(instance)
+            .severity(severity)
+            .message_type(ty)
+            .user_callback(user_callback)
+            .build()
+    }
+
+    /// Initializes a debug callback that can abort the Vulkan call that triggered the message.
+    ///
+    /// Shortcut for `builder(instance).severity(severity).message_type(ty)
+    /// .user_callback_with_abort(user_callback).build()`. See
+    /// [`DebugCallbackBuilder::user_callback_with_abort`].
+    #[inline]
+    pub fn new_with_abort<F>(
+        instance: &Arc<Instance>,
+        severity: MessageSeverity,
+        ty: MessageType,
+        user_callback: F,
+    ) -> Result<DebugCallback, DebugCallbackCreationError>
+    where
+        F: Fn(&Message) -> bool + 'static + Send + panic::RefUnwindSafe,
+    {
+        DebugCallback::builder(instance)
+            .severity(severity)
+            .message_type(ty)
+            .user_callback_with_abort(user_callback)
+            .build()
+    }
+
+    /// Initializes a debug callback that can mutate its captured state.
+    ///
+    /// Shortcut for `builder(instance).severity(severity).message_type(ty)
+    /// .user_callback_mut(user_callback).build()`. See
+    /// [`DebugCallbackBuilder::user_callback_mut`].
+    #[inline]
+    pub fn new_mut<F>(
+        instance: &Arc<Instance>,
+        severity: MessageSeverity,
+        ty: MessageType,
+        user_callback: F,
+    ) -> Result<DebugCallback, DebugCallbackCreationError>
+    where
+        F: FnMut(&Message) + 'static + Send,
+    {
+        DebugCallback::builder(instance)
+            .severity(severity)
+            .message_type(ty)
+            .user_callback_mut(user_callback)
+            .build()
+    }
+
+    /// Initializes a debug callback that sends a copy of every message into a channel.
+    ///
+    /// This is useful to collect messages and inspect them later, for example to check that no
+    /// validation errors were emitted during a test. Sending never blocks, because the channel is
+    /// unbounded. Messages that are sent after the receiver was dropped are discarded.
+    pub fn channel(
+        instance: &Arc<Instance>,
+        severity: MessageSeverity,
+        ty: MessageType,
+    ) -> Result<(DebugCallback, Receiver<OwnedMessage>), DebugCallbackCreationError> {
+        let (sender, receiver) = mpsc::channel();
+        let callback = DebugCallback::new_mut(instance, severity, ty, move |message| {
+            let _ = sender.send(OwnedMessage::from(message));
+        })?;
+
+        Ok((callback, receiver))
+    }
+
+    /// Initializes a debug callback with errors and warnings.
+    ///
+    /// Shortcut for `new(instance, MessageTypes::errors_and_warnings(), user_callback)`.
+    #[inline]
+    pub fn errors_and_warnings<F>(
+        instance: &Arc<Instance>,
+        user_callback: F,
+    ) -> Result<DebugCallback, DebugCallbackCreationError>
+    where
+        F: Fn(&Message) + Send + 'static + panic::RefUnwindSafe,
+    {
+        DebugCallback::new(
+            instance,
+            MessageSeverity::errors_and_warnings(),
+            MessageType::general(),
+            user_callback,
+        )
+    }
+
+    /// Initializes a debug callback that forwards messages to the [`log`](https://docs.rs/log)
+    /// crate.
+    ///
+    /// Errors are logged with `error!`, warnings with `warn!`, information with `debug!` and
+    /// verbose messages with `trace!`. Performance messages are always logged with `warn!`,
+    /// regardless of their severity. The messages are logged with the `vulkano::instance::debug`
+    /// target, and the message ID name is included in the text.
+    ///
+    /// This function is only available if the `log` feature of vulkano is enabled.
+    #[cfg(feature = "log")]
+    pub fn log(
+        instance: &Arc<Instance>,
+        severity: MessageSeverity,
+        ty: MessageType,
+    ) -> Result<DebugCallback, DebugCallbackCreationError> {
+        DebugCallback::new(instance, severity, ty, |msg| {
+            let id = msg.message_id_name.unwrap_or("unknown");
+
+            let level = if msg.ty.performance {
+                log::Level::Warn
+            } else {
+                match msg.severity.highest() {
+                    Some(Severity::Error) => log::Level::Error,
+                    Some(Severity::Warning) => log::Level::Warn,
+                    Some(Severity::Info) => log::Level::Debug,
+                    Some(Severity::Verbose) | None => log::Level::Trace,
+                }
+            };
+
+            log::log!(level, "[{}] {}", id, msg.description);
+        })
+    }
+}
+
+/// Used to create a `DebugCallback` with more options. See `DebugCallback::builder`.
+pub struct DebugCallbackBuilder {
+    instance: Arc<Instance>,
+    severity: MessageSeverity,
+    ty: MessageType,
+    ignored_ids: HashSet<String>,
+    user_callback: Option<Box<dyn Fn(&Message) -> bool + Send + panic::RefUnwindSafe>>,
+}
+
+impl DebugCallbackBuilder {
+    /// Creates the `DebugCallback`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no user callback was provided.
+    pub fn build(self) -> Result<DebugCallback, DebugCallbackCreationError> {
+        let DebugCallbackBuilder {
+            instance,
+            severity,
+            ty,
+            ignored_ids,
+            user_callback,
+        } = self;
+
+        let user_callback = user_callback.expect("no user callback was provided");
+
         if !instance.enabled_extensions().ext_debug_utils {
             return Err(DebugCallbackCreationError::MissingExtension);
         }
 
+        let user_callback = if ignored_ids.is_empty() {
+            user_callback
+        } else {
+            Box::new(move |message: &Message| match message.message_id_name {
+                Some(id) if ignored_ids.contains(id) => false,
+                _ => user_callback(message),
+            })
+        };
+
+        // Note that we need to double-box the callback, because a `*const Fn()` is a fat pointer
+        // that can't be cast to a `*const c_void`.
+        let user_callback = Box::new(user_callback);
+
+        let infos = debug_utils_messenger_create_info(
+            severity,
+            ty,
+            &*user_callback as &Box<_> as *const Box<_> as *const c_void,
+        );
+
+        let fns = instance.fns();
+
+        let debug_utils_messenger = unsafe {
+            let mut output = MaybeUninit::uninit();
+            check_errors(fns.ext_debug_utils.create_debug_utils_messenger_ext(
+                instance.internal_object(),
+                &infos,
+                ptr::null(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        instance.register_debug_callback(
+            debug_utils_messenger.as_raw(),
+            DebugCallbackInfo { severity, ty },
+        );
+
+        Ok(DebugCallback {
+            instance,
+            debug_utils_messenger,
+            user_callback,
+        })
+    }
+
+    /// The severities of the messages that the callback receives.
+    ///
+    /// The default value is [`MessageSeverity::errors_and_warnings()`].
+    #[inline]
+    pub fn severity(mut self, severity: MessageSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// The types of the messages that the callback receives.
+    ///
+    /// The default value is [`MessageType::all()`].
+    #[inline]
+    pub fn message_type(mut self, ty: MessageType) -> Self {
+        self.ty = ty;
+        self
+    }
+
+    /// Adds message ID names, such as the VUIDs of validation messages, that the callback should
+    /// not receive. A message is ignored if its `message_id_name` is exactly equal to one of
+    /// these.
+    ///
+    /// The default value is empty.
+    #[inline]
+    pub fn ignore_ids(mut self, ids: &[&str]) -> Self {
+        self.ignored_ids.extend(ids.iter().map(|&id| id.to_owned()));
+        self
+    }
+
+    /// The function that is called for each message.
+    ///
+    /// Panics generated by calling `user_callback` are ignored. The callback must not make any
+    /// calls to the Vulkan API.
+    ///
+    /// A user callback must be provided, using either this method, `user_callback_mut` or
+    /// `user_callback_with_abort`.
+    #[inline]
+    pub fn user_callback<F>(mut self, user_callback: F) -> Self
+    where
+        F: Fn(&Message) + 'static + Send + panic::RefUnwindSafe,
+    {
+        self.user_callback = Some(Box::new(move |message: &Message| {
+            user_callback(message);
+            false
+        }));
+        self
+    }
+
+    /// Same as `user_callback`, but the function can mutate its captured state.
+    ///
+    /// The Vulkan implementation may call the callback from several threads at once, so the
+    /// closure is wrapped in a `Mutex` and calls to it are serialized. If you don't need
+    /// mutable access, prefer `user_callback`, which doesn't have this overhead.
+    ///
+    /// As with `user_callback`, the callback must not make any calls to the Vulkan API, either
+    /// directly or through vulkano. Doing so while the mutex is held would also deadlock if the
+    /// call triggers another message.
+    #[inline]
+    pub fn user_callback_mut<F>(mut self, user_callback: F) -> Self
+    where
+        F: FnMut(&Message) + 'static + Send,
+    {
+        let user_callback = Mutex::new(user_callback);
+
+        self.user_callback = Some(Box::new(move |message: &Message| {
+            // A panic in a previous call poisons the mutex, but panics are ignored anyway.
+            let mut user_callback = user_callback
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            (*user_callback)(message);
+            false
+        }));
+        self
+    }
+
+    /// Same as `user_callback`, but the function can abort the Vulkan call that triggered the
+    /// message.
+    ///
+    /// If `user_callback` returns `true`, the Vulkan call that triggered the message is aborted,
+    /// and returns `VK_ERROR_VALIDATION_FAILED_EXT`. This is useful in tests, to make any
+    /// validation error fail immediately. Note that vulkano does not expect Vulkan calls to fail
+    /// in this way, and will usually panic as a result.
+    ///
+    /// Panics generated by calling `user_callback` are ignored, and don't abort the call.
+    #[inline]
+    pub fn user_callback_with_abort<F>(mut self, user_callback: F) -> Self
+    where
+        F: Fn(&Message) -> bool + 'static + Send + panic::RefUnwindSafe,
+    {
+        self.user_callback = Some(Box::new(user_callback));
+        self
+    }
+}
+
+impl Drop for DebugCallback {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.instance.fns();
+            fns.ext_debug_utils.destroy_debug_utils_messenger_ext(
+                self.instance.internal_object(),
+                self.debug_utils_messenger,
+                ptr::null(),
+            );
+        }
+        self.instance
+            .unregister_debug_callback(self.debug_utils_messenger.as_raw());
+    }
+}
+
+// Builds the create info of a messenger that calls `user_callback`. It must point to a
+// `Box<dyn Fn(&Message) -> bool>`, which must outlive the messenger.
+pub(crate) fn debug_utils_messenger_create_info(
+    severity: MessageSeverity,
+    ty: MessageType,
+    user_callback: *const c_void,
+) -> ash::vk::DebugUtilsMessengerCreateInfoEXT {
+    ash::vk::DebugUtilsMessengerCreateInfoEXT {
+        flags: ash::vk::DebugUtilsMessengerCreateFlagsEXT::empty(),
+        message_severity: severity.into(),
+        message_type: ty.into(),
+        pfn_user_callback: Some(debug_utils_callback),
+        p_user_data: user_callback as *mut _,
+        ..Default::default()
+    }
+}
+
+unsafe extern "system" fn debug_utils_callback(
+    severity: ash::vk::DebugUtilsMessageSeverityFlagsEXT,
+    ty: ash::vk::DebugUtilsMessageTypeFlagsEXT,
+    callback_data: *const ash::vk::DebugUtilsMessengerCallbackDataEXT,
+    user_data: *mut c_void,
+) -> ash::vk::Bool32 {
+    let user_callback = user_data as *mut Box<dyn Fn()> as *const _;
+    let user_callback: &Box<dyn Fn(&Message) -> bool> = &*user_callback;
+
+    let message_id_name = raw_str((*callback_data).p_message_id_name);
+    let description = raw_str((*callback_data).p_message).unwrap_or_default();
+
+    let queue_labels = raw_slice(
+        (*callback_data).p_queue_labels,
+        (*callback_data).queue_label_count,
+    )
+    .iter()
+    .map(|label| DebugUtilsLabel::from_raw(label))
+    .collect();
+
+    let cmd_buf_labels = raw_slice(
+        (*callback_data).p_cmd_buf_labels,
+        (*callback_data).cmd_buf_label_count,
+    )
+    .iter()
+    .map(|label| DebugUtilsLabel::from_raw(label))
+    .collect();
+
+    let objects = raw_slice((*callback_data).p_objects, (*callback_data).object_count)
+        .iter()
+        .map(|object| DebugUtilsObjectInfo::from_raw(object))
+        .collect();
+
+    let message = Message {
+        severity: MessageSeverity {
+            information: !(severity & ash::vk::DebugUtilsMessageSeverityFlagsEXT::INFO).is_empty(),
+            warning: !(severity & ash::vk::DebugUtilsMessageSeverityFlagsEXT::WARNING).is_empty(),
+            error: !(severity & ash::vk::DebugUtilsMessageSeverityFlagsEXT::ERROR).is_empty(),
+            verbose: !(severity & ash::vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE).is_empty(),
+        },
+        ty: MessageType {
+            general: !(ty & ash::vk::DebugUtilsMessageTypeFlagsEXT::GENERAL).is_empty(),
+            validation: !(ty & ash::vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION).is_empty(),
+            performance: !(ty & ash::vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE).is_empty(),
+        },
+        layer_prefix: None,
+        message_id_name: message_id_name.as_deref(),
+        message_id_number: (*callback_data).message_id_number,
+        description: &description,
+        queue_labels,
+        cmd_buf_labels,
+        objects,
+        raw_severity: severity.as_raw(),
+        raw_type: ty.as_raw(),
+    };
+
+    // Since we box the closure, the type system doesn't detect that the `UnwindSafe`
+    // bound is enforced. Therefore we enforce it manually.
+    let abort = panic::catch_unwind(panic::AssertUnwindSafe(move || user_callback(&message)))
+        .unwrap_or(false);
+
+    if abort {
+        ash::vk::TRUE
+    } else {
+        ash::vk::FALSE
+    }
+}
+
+/// Registration of a callback using the legacy `VK_EXT_debug_report` extension.
+///
+/// This should only be used on implementations that don't support `VK_EXT_debug_utils`, as the
+/// older extension provides less information. Messages are converted into the same [`Message`]
+/// type as used by [`DebugCallback`]:
+///
+/// - `ERROR` becomes an `error` severity of type `validation`.
+/// - `WARNING` becomes a `warning` severity of type `validation`.
+/// - `PERFORMANCE_WARNING` becomes a `warning` severity of type `performance`.
+/// - `INFORMATION` becomes an `information` severity of type `general`.
+/// - `DEBUG` becomes a `verbose` severity of type `general`.
+///
+/// The message code is provided as `message_id_number`, and `message_id_name` is always `None`.
+///
+/// The callback can be called as long as this object is alive.
+#[must_use = "The DebugReportCallback object must be kept alive for as long as you want your \
+              callback to be called"]
+pub struct DebugReportCallback {
+    instance: Arc<Instance>,
+    debug_report_callback: ash::vk::DebugReportCallbackEXT,
+    user_callback: Box<Box<dyn Fn(&Message) + Send>>,
+}
+
+impl DebugReportCallback {
+    /// Initializes a debug report callback.
+    ///
+    /// `VK_EXT_debug_report` can't filter messages by type, so `ty` only determines whether
+    /// performance warnings are reported when `severity` includes warnings.
+    ///
+    /// Panics generated by calling `user_callback` are ignored. The callback must not make any
+    /// calls to the Vulkan API.
+    pub fn new<F>(
+        instance: &Arc<Instance>,
+        severity: MessageSeverity,
+        ty: MessageType,
+        user_callback: F,
+    ) -> Result<DebugReportCallback, DebugCallbackCreationError>
+    where
+        F: Fn(&Message) + 'static + Send + panic::RefUnwindSafe,
+    {
+        if !instance.enabled_extensions().ext_debug_report {
+            return Err(DebugCallbackCreationError::MissingExtension);
+        }
+
         // Note that we need to double-box the callback, because a `*const Fn()` is a fat pointer
         // that can't be cast to a `*const c_void`.
         let user_callback = Box::new(Box::new(user_callback) as Box<_>);
 
         unsafe extern "system" fn callback(
-            severity: ash::vk::DebugUtilsMessageSeverityFlagsEXT,
-            ty: ash::vk::DebugUtilsMessageTypeFlagsEXT,
-            callback_data: *const ash::vk::DebugUtilsMessengerCallbackDataEXT,
+            flags: ash::vk::DebugReportFlagsEXT,
+            _object_type: ash::vk::DebugReportObjectTypeEXT,
+            _object: u64,
+            _location: usize,
+            message_code: i32,
+            p_layer_prefix: *const c_char,
+            p_message: *const c_char,
             user_data: *mut c_void,
         ) -> ash::vk::Bool32 {
             let user_callback = user_data as *mut Box<dyn Fn()> as *const _;
             let user_callback: &Box<dyn Fn(&Message)> = &*user_callback;
 
-            let layer_prefix = (*callback_data)
-                .p_message_id_name
-                .as_ref()
-                .map(|msg_id_name| {
-                    CStr::from_ptr(msg_id_name)
-                        .to_str()
-                        .expect("debug callback message not utf-8")
-                });
+            let layer_prefix = raw_str(p_layer_prefix);
+            let description = raw_str(p_message).unwrap_or_default();
+
+            let performance = flags.intersects(ash::vk::DebugReportFlagsEXT::PERFORMANCE_WARNING);
+            let error = flags.intersects(ash::vk::DebugReportFlagsEXT::ERROR);
+            let warning = flags.intersects(ash::vk::DebugReportFlagsEXT::WARNING) || performance;
 
-            let description = CStr::from_ptr((*callback_data).p_message)
-                .to_str()
-                .expect("debug callback message not utf-8");
+            let severity = MessageSeverity {
+                information: flags.intersects(ash::vk::DebugReportFlagsEXT::INFORMATION),
+                warning,
+                error,
+                verbose: flags.intersects(ash::vk::DebugReportFlagsEXT::DEBUG),
+            };
+            let ty = MessageType {
+                general: !(error || warning),
+                validation: !performance && (error || warning),
+                performance,
+            };
 
             let message = Message {
-                severity: MessageSeverity {
-                    information: !(severity & ash::vk::DebugUtilsMessageSeverityFlagsEXT::INFO)
-                        .is_empty(),
-                    warning: !(severity & ash::vk::DebugUtilsMessageSeverityFlagsEXT::WARNING)
-                        .is_empty(),
-                    error: !(severity & ash::vk::DebugUtilsMessageSeverityFlagsEXT::ERROR)
-                        .is_empty(),
-                    verbose: !(severity & ash::vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE)
-                        .is_empty(),
-                },
-                ty: MessageType {
-                    general: !(ty & ash::vk::DebugUtilsMessageTypeFlagsEXT::GENERAL).is_empty(),
-                    validation: !(ty & ash::vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION)
-                        .is_empty(),
-                    performance: !(ty & ash::vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE)
-                        .is_empty(),
-                },
-                layer_prefix,
-                description,
+                severity,
+                ty,
+                layer_prefix: layer_prefix.as_deref(),
+                message_id_name: None,
+                message_id_number: message_code,
+                description: &description,
+                queue_labels: Vec::new(),
+                cmd_buf_labels: Vec::new(),
+                objects: Vec::new(),
+                raw_severity: ash::vk::DebugUtilsMessageSeverityFlagsEXT::from(severity).as_raw(),
+                raw_type: ash::vk::DebugUtilsMessageTypeFlagsEXT::from(ty).as_raw(),
             };
 
             // Since we box the closure, the type system doesn't detect that the `UnwindSafe`
@@ -135,42 +608,29 @@ impl DebugCallback {
             ash::vk::FALSE
         }
 
-        let severity = {
-            let mut flags = ash::vk::DebugUtilsMessageSeverityFlagsEXT::empty();
+        let flags = {
+            let mut flags = ash::vk::DebugReportFlagsEXT::empty();
             if severity.information {
-                flags |= ash::vk::DebugUtilsMessageSeverityFlagsEXT::INFO;
+                flags |= ash::vk::DebugReportFlagsEXT::INFORMATION;
             }
             if severity.warning {
-                flags |= ash::vk::DebugUtilsMessageSeverityFlagsEXT::WARNING;
+                flags |= ash::vk::DebugReportFlagsEXT::WARNING;
+                if ty.performance {
+                    flags |= ash::vk::DebugReportFlagsEXT::PERFORMANCE_WARNING;
+                }
             }
             if severity.error {
-                flags |= ash::vk::DebugUtilsMessageSeverityFlagsEXT::ERROR;
+                flags |= ash::vk::DebugReportFlagsEXT::ERROR;
             }
             if severity.verbose {
-                flags |= ash::vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE;
-            }
-            flags
-        };
-
-        let ty = {
-            let mut flags = ash::vk::DebugUtilsMessageTypeFlagsEXT::empty();
-            if ty.general {
-                flags |= ash::vk::DebugUtilsMessageTypeFlagsEXT::GENERAL;
-            }
-            if ty.validation {
-                flags |= ash::vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION;
-            }
-            if ty.performance {
-                flags |= ash::vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE;
+                flags |= ash::vk::DebugReportFlagsEXT::DEBUG;
             }
             flags
         };
 
-        let infos = ash::vk::DebugUtilsMessengerCreateInfoEXT {
-            flags: ash::vk::DebugUtilsMessengerCreateFlagsEXT::empty(),
-            message_severity: severity,
-            message_type: ty,
-            pfn_user_callback: Some(callback),
+        let infos = ash::vk::DebugReportCallbackCreateInfoEXT {
+            flags,
+            pfn_callback: Some(callback),
             p_user_data: &*user_callback as &Box<_> as *const Box<_> as *const c_void as *mut _,
             ..Default::default()
         };
@@ -179,7 +639,7 @@ impl DebugCallback {
 
         let debug_report_callback = unsafe {
             let mut output = MaybeUninit::uninit();
-            check_errors(fns.ext_debug_utils.create_debug_utils_messenger_ext(
+            check_errors(fns.ext_debug_report.create_debug_report_callback_ext(
                 instance.internal_object(),
                 &infos,
                 ptr::null(),
@@ -188,57 +648,257 @@ impl DebugCallback {
             output.assume_init()
         };
 
-        Ok(DebugCallback {
+        instance.register_debug_callback(
+            debug_report_callback.as_raw(),
+            DebugCallbackInfo { severity, ty },
+        );
+
+        Ok(DebugReportCallback {
             instance: instance.clone(),
             debug_report_callback,
             user_callback,
         })
     }
-
-    /// Initializes a debug callback with errors and warnings.
-    ///
-    /// Shortcut for `new(instance, MessageTypes::errors_and_warnings(), user_callback)`.
-    #[inline]
-    pub fn errors_and_warnings<F>(
-        instance: &Arc<Instance>,
-        user_callback: F,
-    ) -> Result<DebugCallback, DebugCallbackCreationError>
-    where
-        F: Fn(&Message) + Send + 'static + panic::RefUnwindSafe,
-    {
-        DebugCallback::new(
-            instance,
-            MessageSeverity::errors_and_warnings(),
-            MessageType::general(),
-            user_callback,
-        )
-    }
 }
 
-impl Drop for DebugCallback {
+impl Drop for DebugReportCallback {
     #[inline]
     fn drop(&mut self) {
         unsafe {
             let fns = self.instance.fns();
-            fns.ext_debug_utils.destroy_debug_utils_messenger_ext(
+            fns.ext_debug_report.destroy_debug_report_callback_ext(
                 self.instance.internal_object(),
                 self.debug_report_callback,
                 ptr::null(),
             );
         }
+        self.instance
+            .unregister_debug_callback(self.debug_report_callback.as_raw());
     }
 }
 
+/// A debug callback registered with whichever debugging extension is available.
+#[must_use = "The AnyDebugCallback object must be kept alive for as long as you want your \
+              callback to be called"]
+pub enum AnyDebugCallback {
+    /// The callback was registered using `VK_EXT_debug_utils`.
+    DebugUtils(DebugCallback),
+    /// The callback was registered using `VK_EXT_debug_report`.
+    DebugReport(DebugReportCallback),
+}
+
+impl AnyDebugCallback {
+    /// Initializes a debug callback using `VK_EXT_debug_utils` if it is enabled on the instance,
+    /// and falls back to `VK_EXT_debug_report` otherwise.
+    ///
+    /// Returns `MissingExtension` if neither extension was enabled.
+    pub fn new<F>(
+        instance: &Arc<Instance>,
+        severity: MessageSeverity,
+        ty: MessageType,
+        user_callback: F,
+    ) -> Result<AnyDebugCallback, DebugCallbackCreationError>
+    where
+        F: Fn(&Message) + 'static + Send + panic::RefUnwindSafe,
+    {
+        if instance.enabled_extensions().ext_debug_utils {
+            DebugCallback::new(instance, severity, ty, user_callback)
+                .map(AnyDebugCallback::DebugUtils)
+        } else {
+            DebugReportCallback::new(instance, severity, ty, user_callback)
+                .map(AnyDebugCallback::DebugReport)
+        }
+    }
+}
+
+/// The filters of a debug callback that is registered on an instance.
+///
+/// See [`Instance::debug_callbacks`](crate::instance::Instance::debug_callbacks).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DebugCallbackInfo {
+    /// The severities of the messages that the callback receives.
+    pub severity: MessageSeverity,
+    /// The types of the messages that the callback receives.
+    pub ty: MessageType,
+}
+
 /// A message received by the callback.
+///
+/// Strings are converted lossily: any invalid UTF-8 sent by the implementation is replaced with
+/// `U+FFFD REPLACEMENT CHARACTER`.
 pub struct Message<'a> {
     /// Severity of message.
     pub severity: MessageSeverity,
     /// Type of message,
     pub ty: MessageType,
     /// Prefix of the layer that reported this message or `None` if unknown.
+    ///
+    /// `VK_EXT_debug_utils` doesn't report the layer, so this is always `None` with
+    /// [`DebugCallback`].
     pub layer_prefix: Option<&'a str>,
+    /// Identifier of the message, such as the VUID of a validation error, or `None` if the
+    /// message doesn't have one.
+    pub message_id_name: Option<&'a str>,
+    /// Numeric identifier of the message. May be `0` if the message doesn't have one.
+    pub message_id_number: i32,
     /// Description of the message.
     pub description: &'a str,
+    /// Labels of the queue debug regions that were active when the message was triggered,
+    /// innermost first.
+    pub queue_labels: Vec<DebugUtilsLabel>,
+    /// Labels of the command buffer debug regions that were active when the message was
+    /// triggered, innermost first.
+    pub cmd_buf_labels: Vec<DebugUtilsLabel>,
+    /// Objects related to the message.
+    pub objects: Vec<DebugUtilsObjectInfo>,
+
+    raw_severity: u32,
+    raw_type: u32,
+}
+
+impl<'a> Message<'a> {
+    /// Returns the `VkDebugUtilsMessageSeverityFlagBitsEXT` value of the message, exactly as it
+    /// was received by the callback. Unlike `severity`, this also includes any bits that vulkano
+    /// doesn't know about.
+    ///
+    /// For messages received through `VK_EXT_debug_report`, this is the `VK_EXT_debug_utils`
+    /// equivalent of `severity`.
+    #[inline]
+    pub fn raw_severity(&self) -> u32 {
+        self.raw_severity
+    }
+
+    /// Returns the `VkDebugUtilsMessageTypeFlagsEXT` value of the message, exactly as it was
+    /// received by the callback. Unlike `ty`, this also includes any bits that vulkano doesn't
+    /// know about.
+    ///
+    /// For messages received through `VK_EXT_debug_report`, this is the `VK_EXT_debug_utils`
+    /// equivalent of `ty`.
+    #[inline]
+    pub fn raw_type(&self) -> u32 {
+        self.raw_type
+    }
+}
+
+/// An owned copy of a [`Message`], which can be kept after the callback has returned.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedMessage {
+    /// Severity of message.
+    pub severity: MessageSeverity,
+    /// Type of message.
+    pub ty: MessageType,
+    /// Prefix of the layer that reported this message or `None` if unknown.
+    pub layer_prefix: Option<String>,
+    /// Identifier of the message, such as the VUID of a validation error, or `None` if the
+    /// message doesn't have one.
+    pub message_id_name: Option<String>,
+    /// Numeric identifier of the message. May be `0` if the message doesn't have one.
+    pub message_id_number: i32,
+    /// Description of the message.
+    pub description: String,
+    /// Labels of the queue debug regions that were active when the message was triggered,
+    /// innermost first.
+    pub queue_labels: Vec<DebugUtilsLabel>,
+    /// Labels of the command buffer debug regions that were active when the message was
+    /// triggered, innermost first.
+    pub cmd_buf_labels: Vec<DebugUtilsLabel>,
+    /// Objects related to the message.
+    pub objects: Vec<DebugUtilsObjectInfo>,
+
+    raw_severity: u32,
+    raw_type: u32,
+}
+
+impl OwnedMessage {
+    /// Returns the raw severity flags of the message. See [`Message::raw_severity`].
+    #[inline]
+    pub fn raw_severity(&self) -> u32 {
+        self.raw_severity
+    }
+
+    /// Returns the raw type flags of the message. See [`Message::raw_type`].
+    #[inline]
+    pub fn raw_type(&self) -> u32 {
+        self.raw_type
+    }
+}
+
+impl<'a> From<&Message<'a>> for OwnedMessage {
+    #[inline]
+    fn from(message: &Message<'a>) -> Self {
+        OwnedMessage {
+            severity: message.severity,
+            ty: message.ty,
+            layer_prefix: message.layer_prefix.map(str::to_owned),
+            message_id_name: message.message_id_name.map(str::to_owned),
+            message_id_number: message.message_id_number,
+            description: message.description.to_owned(),
+            queue_labels: message.queue_labels.clone(),
+            cmd_buf_labels: message.cmd_buf_labels.clone(),
+            objects: message.objects.clone(),
+            raw_severity: message.raw_severity,
+            raw_type: message.raw_type,
+        }
+    }
+}
+
+/// A label associated with a queue or command buffer debug region.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugUtilsLabel {
+    /// Name of the label.
+    pub name: String,
+    /// RGBA color associated with the label, or all zeroes if none was given.
+    pub color: [f32; 4],
+}
+
+impl DebugUtilsLabel {
+    unsafe fn from_raw(label: &ash::vk::DebugUtilsLabelEXT) -> DebugUtilsLabel {
+        DebugUtilsLabel {
+            name: raw_string(label.p_label_name).unwrap_or_default(),
+            color: label.color,
+        }
+    }
+}
+
+/// An object referred to by a debug message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugUtilsObjectInfo {
+    /// The type of the object.
+    pub object_type: ash::vk::ObjectType,
+    /// The raw Vulkan handle of the object.
+    pub object_handle: u64,
+    /// The name that was given to the object, if any.
+    pub object_name: Option<String>,
+}
+
+impl DebugUtilsObjectInfo {
+    unsafe fn from_raw(object: &ash::vk::DebugUtilsObjectNameInfoEXT) -> DebugUtilsObjectInfo {
+        DebugUtilsObjectInfo {
+            object_type: object.object_type,
+            object_handle: object.object_handle,
+            object_name: raw_string(object.p_object_name),
+        }
+    }
+}
+
+// Borrows a string coming from Vulkan, which may be null. Drivers and layers are not required to
+// emit valid UTF-8, so invalid sequences are replaced rather than panicking inside the callback.
+unsafe fn raw_str<'a>(ptr: *const c_char) -> Option<Cow<'a, str>> {
+    ptr.as_ref()
+        .map(|ptr| CStr::from_ptr(ptr).to_string_lossy())
+}
+
+// Builds a slice from a pointer and count coming from Vulkan, which may be null if the count is 0.
+unsafe fn raw_slice<'a, T>(ptr: *const T, count: u32) -> &'a [T] {
+    if ptr.is_null() || count == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, count as usize)
+    }
+}
+
+unsafe fn raw_string(ptr: *const c_char) -> Option<String> {
+    raw_str(ptr).map(Cow::into_owned)
 }
 
 /// Severity of message.
@@ -323,6 +983,31 @@ impl MessageSeverity {
             verbose: true,
         }
     }
+
+    /// Returns whether all the fields that are set in `other` are also set in `self`.
+    #[inline]
+    pub const fn contains(&self, other: &MessageSeverity) -> bool {
+        (self.error || !other.error)
+            && (self.warning || !other.warning)
+            && (self.information || !other.information)
+            && (self.verbose || !other.verbose)
+    }
+
+    /// Returns the most severe level that is set, or `None` if no field is set.
+    #[inline]
+    pub const fn highest(&self) -> Option<Severity> {
+        if self.error {
+            Some(Severity::Error)
+        } else if self.warning {
+            Some(Severity::Warning)
+        } else if self.information {
+            Some(Severity::Info)
+        } else if self.verbose {
+            Some(Severity::Verbose)
+        } else {
+            None
+        }
+    }
 }
 
 impl std::ops::BitOr for MessageSeverity {
@@ -337,6 +1022,76 @@ impl std::ops::BitOr for MessageSeverity {
     }
 }
 
+impl std::ops::BitOrAssign for MessageSeverity {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl std::ops::BitAnd for MessageSeverity {
+    type Output = Self;
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        MessageSeverity {
+            error: self.error & rhs.error,
+            warning: self.warning & rhs.warning,
+            information: self.information & rhs.information,
+            verbose: self.verbose & rhs.verbose,
+        }
+    }
+}
+
+impl From<MessageSeverity> for ash::vk::DebugUtilsMessageSeverityFlagsEXT {
+    #[inline]
+    fn from(val: MessageSeverity) -> Self {
+        let mut result = Self::empty();
+        if val.information {
+            result |= Self::INFO;
+        }
+        if val.warning {
+            result |= Self::WARNING;
+        }
+        if val.error {
+            result |= Self::ERROR;
+        }
+        if val.verbose {
+            result |= Self::VERBOSE;
+        }
+        result
+    }
+}
+
+/// A single severity level, as returned by the `highest` field of `MessageSeverity`.
+///
+/// Levels are ordered from least to most severe, so they can be compared to a threshold.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Diagnostic information from the loader and layers. Corresponds to the `verbose` field of
+    /// `MessageSeverity`.
+    Verbose,
+    /// An informational message that may be handy when debugging an application. Corresponds to
+    /// the `information` field of `MessageSeverity`.
+    Info,
+    /// An unexpected use. Corresponds to the `warning` field of `MessageSeverity`.
+    Warning,
+    /// An error that may cause undefined results, including an application crash. Corresponds to
+    /// the `error` field of `MessageSeverity`.
+    Error,
+}
+
+impl From<Severity> for MessageSeverity {
+    #[inline]
+    fn from(val: Severity) -> Self {
+        match val {
+            Severity::Verbose => MessageSeverity::verbose(),
+            Severity::Info => MessageSeverity::information(),
+            Severity::Warning => MessageSeverity::warnings(),
+            Severity::Error => MessageSeverity::errors(),
+        }
+    }
+}
+
 /// Type of message.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct MessageType {
@@ -398,6 +1153,14 @@ impl MessageType {
             performance: false,
         }
     }
+
+    /// Returns whether all the fields that are set in `other` are also set in `self`.
+    #[inline]
+    pub const fn contains(&self, other: &MessageType) -> bool {
+        (self.general || !other.general)
+            && (self.validation || !other.validation)
+            && (self.performance || !other.performance)
+    }
 }
 
 impl std::ops::BitOr for MessageType {
@@ -411,14 +1174,144 @@ impl std::ops::BitOr for MessageType {
     }
 }
 
+impl std::ops::BitOrAssign for MessageType {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl std::ops::BitAnd for MessageType {
+    type Output = Self;
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        MessageType {
+            general: self.general & rhs.general,
+            validation: self.validation & rhs.validation,
+            performance: self.performance & rhs.performance,
+        }
+    }
+}
+
+impl From<MessageType> for ash::vk::DebugUtilsMessageTypeFlagsEXT {
+    #[inline]
+    fn from(val: MessageType) -> Self {
+        let mut result = Self::empty();
+        if val.general {
+            result |= Self::GENERAL;
+        }
+        if val.validation {
+            result |= Self::VALIDATION;
+        }
+        if val.performance {
+            result |= Self::PERFORMANCE;
+        }
+        result
+    }
+}
+
+/// Features of the validation layers to enable or disable, when creating an instance.
+///
+/// The `ext_validation_features` extension must be enabled on the instance to use this. Messages
+/// from these features are reported through the usual debug callbacks.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationFeatures {
+    /// Validation features to enable, in addition to the default ones.
+    pub enabled: Vec<ValidationFeatureEnable>,
+    /// Validation features to disable.
+    pub disabled: Vec<ValidationFeatureDisable>,
+}
+
+/// A validation feature that is disabled by default, and can be enabled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum ValidationFeatureEnable {
+    /// The validation layers will instrument shaders to detect errors that can only be found
+    /// while they are running on the GPU.
+    ///
+    /// Can't be enabled together with `DebugPrintf`.
+    GpuAssisted = ash::vk::ValidationFeatureEnableEXT::GPU_ASSISTED.as_raw(),
+
+    /// The validation layers reserve a descriptor set binding slot for their own use, when
+    /// `GpuAssisted` is enabled. This reduces the number of descriptor sets available to the
+    /// application by one.
+    ///
+    /// `GpuAssisted` must also be enabled.
+    GpuAssistedReserveBindingSlot =
+        ash::vk::ValidationFeatureEnableEXT::GPU_ASSISTED_RESERVE_BINDING_SLOT.as_raw(),
+
+    /// The validation layers will report API usage that is valid but potentially inefficient.
+    BestPractices = ash::vk::ValidationFeatureEnableEXT::BEST_PRACTICES.as_raw(),
+
+    /// The validation layers will forward the output of `debugPrintfEXT` calls in shaders as
+    /// information messages.
+    ///
+    /// Can't be enabled together with `GpuAssisted`.
+    DebugPrintf = ash::vk::ValidationFeatureEnableEXT::DEBUG_PRINTF.as_raw(),
+
+    /// The validation layers will detect hazards caused by missing or incorrect
+    /// synchronization.
+    SynchronizationValidation =
+        ash::vk::ValidationFeatureEnableEXT::SYNCHRONIZATION_VALIDATION.as_raw(),
+}
+
+impl From<ValidationFeatureEnable> for ash::vk::ValidationFeatureEnableEXT {
+    #[inline]
+    fn from(val: ValidationFeatureEnable) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+/// A validation feature that is enabled by default, and can be disabled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum ValidationFeatureDisable {
+    /// Disables all validation checks.
+    All = ash::vk::ValidationFeatureDisableEXT::ALL.as_raw(),
+    /// Disables validation of shaders.
+    Shaders = ash::vk::ValidationFeatureDisableEXT::SHADERS.as_raw(),
+    /// Disables validation of thread safety.
+    ThreadSafety = ash::vk::ValidationFeatureDisableEXT::THREAD_SAFETY.as_raw(),
+    /// Disables validation of the parameters of API calls.
+    ApiParameters = ash::vk::ValidationFeatureDisableEXT::API_PARAMETERS.as_raw(),
+    /// Disables validation of object lifetimes.
+    ObjectLifetimes = ash::vk::ValidationFeatureDisableEXT::OBJECT_LIFETIMES.as_raw(),
+    /// Disables the core validation checks.
+    CoreChecks = ash::vk::ValidationFeatureDisableEXT::CORE_CHECKS.as_raw(),
+    /// Disables the wrapping of handles with unique identifiers.
+    UniqueHandles = ash::vk::ValidationFeatureDisableEXT::UNIQUE_HANDLES.as_raw(),
+    /// Disables the caching of shader validation results.
+    ShaderValidationCache = ash::vk::ValidationFeatureDisableEXT::SHADER_VALIDATION_CACHE.as_raw(),
+}
+
+impl From<ValidationFeatureDisable> for ash::vk::ValidationFeatureDisableEXT {
+    #[inline]
+    fn from(val: ValidationFeatureDisable) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
 /// Error that can happen when creating a debug callback.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DebugCallbackCreationError {
-    /// The `EXT_debug_utils` extension was not enabled.
+    /// The `EXT_debug_utils` extension (or `EXT_debug_report` for `DebugReportCallback`) was not
+    /// enabled.
     MissingExtension,
+    /// Not enough memory.
+    OomError(OomError),
+    /// The implementation returned an error other than running out of memory.
+    CreationFailed,
 }
 
-impl error::Error for DebugCallbackCreationError {}
+impl error::Error for DebugCallbackCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DebugCallbackCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl fmt::Display for DebugCallbackCreationError {
     #[inline]
@@ -428,17 +1321,32 @@ impl fmt::Display for DebugCallbackCreationError {
             "{}",
             match *self {
                 DebugCallbackCreationError::MissingExtension => {
-                    "the `EXT_debug_utils` extension was not enabled"
+                    "the required debugging extension was not enabled"
+                }
+                DebugCallbackCreationError::OomError(_) => "not enough memory available",
+                DebugCallbackCreationError::CreationFailed => {
+                    "the implementation failed to create the debug callback"
                 }
             }
         )
     }
 }
 
+impl From<OomError> for DebugCallbackCreationError {
+    #[inline]
+    fn from(err: OomError) -> DebugCallbackCreationError {
+        DebugCallbackCreationError::OomError(err)
+    }
+}
+
 impl From<Error> for DebugCallbackCreationError {
     #[inline]
     fn from(err: Error) -> DebugCallbackCreationError {
-        panic!("unexpected error: {:?}", err)
+        match err {
+            err @ Error::OutOfHostMemory => DebugCallbackCreationError::OomError(err.into()),
+            err @ Error::OutOfDeviceMemory => DebugCallbackCreationError::OomError(err.into()),
+            _ => DebugCallbackCreationError::CreationFailed,
+        }
     }
 }
 
@@ -458,4 +1366,47 @@ mod tests {
             let _ = callback;
         });
     }
+
+    #[test]
+    fn severity_and_type_ops() {
+        let mut severity = MessageSeverity::errors();
+        severity |= MessageSeverity::warnings();
+        assert_eq!(severity, MessageSeverity::errors_and_warnings());
+        assert!(severity.contains(&MessageSeverity::warnings()));
+        assert!(!severity.contains(&MessageSeverity::verbose()));
+        assert_eq!(
+            severity & MessageSeverity::warnings(),
+            MessageSeverity::warnings()
+        );
+
+        let mut ty = MessageType::general();
+        ty |= MessageType::performance();
+        assert!(ty.contains(&MessageType::performance()));
+        assert!(!ty.contains(&MessageType::all()));
+        assert_eq!(ty & MessageType::validation(), MessageType::none());
+    }
+
+    #[test]
+    fn severity_highest() {
+        assert_eq!(MessageSeverity::none().highest(), None);
+        assert_eq!(MessageSeverity::all().highest(), Some(Severity::Error));
+        assert_eq!(
+            (MessageSeverity::verbose() | MessageSeverity::information()).highest(),
+            Some(Severity::Info)
+        );
+        assert!(Severity::Error > Severity::Warning);
+        assert!(Severity::Warning > Severity::Info);
+        assert!(Severity::Info > Severity::Verbose);
+    }
+
+    #[test]
+    fn debug_report_missing_extension() {
+        let instance = instance!();
+        let severity = MessageSeverity::all();
+        let ty = MessageType::all();
+        match DebugReportCallback::new(&instance, severity, ty, |_| {}) {
+            Err(DebugCallbackCreationError::MissingExtension) => (),
+            _ => panic!(),
+        }
+    }
 }
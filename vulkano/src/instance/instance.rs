@@ -9,8 +9,14 @@
 
 use crate::check_errors;
 use crate::device::physical::{init_physical_devices, PhysicalDeviceInfo};
+use crate::device::{Device, DeviceOwned};
 use crate::extensions::ExtensionRestrictionError;
 use crate::fns::InstanceFunctions;
+use crate::instance::debug;
+use crate::instance::debug::{
+    DebugCallbackInfo, Message, MessageSeverity, MessageType, ValidationFeatureEnable,
+    ValidationFeatures,
+};
 use crate::instance::loader;
 use crate::instance::loader::FunctionPointers;
 use crate::instance::loader::Loader;
@@ -29,9 +35,12 @@ use std::hash::Hash;
 use std::hash::Hasher;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
+use std::os::raw::c_void;
+use std::panic::RefUnwindSafe;
 use std::ptr;
 use std::slice;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 /// An instance of a Vulkan context. This is the main object that should be created by an
 /// application before everything else.
@@ -184,6 +193,13 @@ pub struct Instance {
     extensions: InstanceExtensions,
     layers: SmallVec<[CString; 16]>,
     function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader + Send + Sync>>>,
+
+    // Callback that was attached during creation. Must be kept alive until the instance has been
+    // destroyed.
+    creation_debug_callback: Option<Box<Box<dyn Fn(&Message) -> bool + Send + Sync>>>,
+
+    // The debug callbacks that are currently registered, keyed by their raw handle.
+    debug_callbacks: Mutex<Vec<(u64, DebugCallbackInfo)>>,
 }
 
 // TODO: fix the underlying cause instead
@@ -237,6 +253,8 @@ impl Instance {
             extensions,
             layers,
             OwnedOrRef::Ref(loader::auto_loader()?),
+            None,
+            None,
         )
     }
 
@@ -262,15 +280,59 @@ impl Instance {
             extensions,
             layers,
             OwnedOrRef::Owned(loader),
+            None,
+            None,
         )
     }
 
+    /// Starts building a new instance, which allows setting more options than `new`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use vulkano::instance::debug::{MessageSeverity, MessageType};
+    /// use vulkano::instance::{Instance, InstanceExtensions};
+    /// use vulkano::Version;
+    ///
+    /// let extensions = InstanceExtensions {
+    ///     ext_debug_utils: true,
+    ///     ..InstanceExtensions::none()
+    /// };
+    ///
+    /// let instance = Instance::start()
+    ///     .max_api_version(Version::V1_1)
+    ///     .extensions(&extensions)
+    ///     .debug_callback(MessageSeverity::all(), MessageType::all(), |msg| {
+    ///         println!("Instance creation: {}", msg.description);
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[inline]
+    pub fn start<'a>() -> InstanceBuilder<'a> {
+        InstanceBuilder {
+            app_infos: None,
+            max_api_version: Version::V1_0,
+            extensions: InstanceExtensions::none(),
+            layers: SmallVec::new(),
+            function_pointers: None,
+            debug_callback: None,
+            validation_features: None,
+        }
+    }
+
     fn new_inner(
         app_infos: Option<&ApplicationInfo>,
         max_api_version: Version,
         extensions: &InstanceExtensions,
         layers: SmallVec<[CString; 16]>,
         function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader + Send + Sync>>>,
+        creation_debug_callback: Option<(
+            MessageSeverity,
+            MessageType,
+            Box<Box<dyn Fn(&Message) -> bool + Send + Sync>>,
+        )>,
+        validation_features: Option<ValidationFeatures>,
     ) -> Result<Arc<Instance>, InstanceCreationError> {
         let api_version = std::cmp::min(max_api_version, function_pointers.api_version()?);
 
@@ -349,10 +411,85 @@ impl Instance {
             .map(|extension| extension.as_ptr())
             .collect::<SmallVec<[_; 32]>>();
 
+        // Attaching a messenger that is used only while creating and destroying the instance.
+        let (mut debug_utils_messenger_create_info, creation_debug_callback) =
+            match creation_debug_callback {
+                Some((severity, ty, user_callback)) => {
+                    if !extensions.ext_debug_utils {
+                        return Err(InstanceCreationError::ExtensionNotEnabled {
+                            extension: "ext_debug_utils",
+                            reason: "a debug callback was provided for instance creation",
+                        });
+                    }
+
+                    let create_info = debug::debug_utils_messenger_create_info(
+                        severity,
+                        ty,
+                        &*user_callback as &Box<_> as *const Box<_> as *const c_void,
+                    );
+                    (Some(create_info), Some(user_callback))
+                }
+                None => (None, None),
+            };
+
+        // Enabling or disabling features of the validation layers.
+        if let Some(validation_features) = &validation_features {
+            if !extensions.ext_validation_features {
+                return Err(InstanceCreationError::ExtensionNotEnabled {
+                    extension: "ext_validation_features",
+                    reason: "validation features were provided",
+                });
+            }
+
+            let enabled = &validation_features.enabled;
+
+            // VUID-VkValidationFeaturesEXT-pEnabledValidationFeatures-02967
+            if enabled.contains(&ValidationFeatureEnable::GpuAssistedReserveBindingSlot)
+                && !enabled.contains(&ValidationFeatureEnable::GpuAssisted)
+            {
+                return Err(
+                    InstanceCreationError::ValidationFeatureReserveBindingSlotWithoutGpuAssisted,
+                );
+            }
+
+            // VUID-VkValidationFeaturesEXT-pEnabledValidationFeatures-02968
+            if enabled.contains(&ValidationFeatureEnable::DebugPrintf)
+                && enabled.contains(&ValidationFeatureEnable::GpuAssisted)
+            {
+                return Err(InstanceCreationError::ValidationFeatureDebugPrintfWithGpuAssisted);
+            }
+        }
+
+        let validation_features = validation_features.map(|validation_features| {
+            let enabled: SmallVec<[ash::vk::ValidationFeatureEnableEXT; 5]> = validation_features
+                .enabled
+                .iter()
+                .copied()
+                .map(Into::into)
+                .collect();
+            let disabled: SmallVec<[ash::vk::ValidationFeatureDisableEXT; 8]> = validation_features
+                .disabled
+                .iter()
+                .copied()
+                .map(Into::into)
+                .collect();
+            (enabled, disabled)
+        });
+        let mut validation_features_info =
+            validation_features.as_ref().map(|(enabled, disabled)| {
+                ash::vk::ValidationFeaturesEXT {
+                    enabled_validation_feature_count: enabled.len() as u32,
+                    p_enabled_validation_features: enabled.as_ptr(),
+                    disabled_validation_feature_count: disabled.len() as u32,
+                    p_disabled_validation_features: disabled.as_ptr(),
+                    ..Default::default()
+                }
+            });
+
         // Creating the Vulkan instance.
         let instance = unsafe {
             let mut output = MaybeUninit::uninit();
-            let infos = ash::vk::InstanceCreateInfo {
+            let mut infos = ash::vk::InstanceCreateInfo {
                 flags: ash::vk::InstanceCreateFlags::empty(),
                 p_application_info: if let Some(app) = app_infos.as_ref() {
                     app as *const _
@@ -366,6 +503,16 @@ impl Instance {
                 ..Default::default()
             };
 
+            if let Some(create_info) = debug_utils_messenger_create_info.as_mut() {
+                create_info.p_next = infos.p_next;
+                infos.p_next = create_info as *const _ as *const _;
+            }
+
+            if let Some(validation_features_info) = validation_features_info.as_mut() {
+                validation_features_info.p_next = infos.p_next;
+                infos.p_next = validation_features_info as *const _ as *const _;
+            }
+
             let fns = function_pointers.fns();
             check_errors(
                 fns.v1_0
@@ -391,6 +538,8 @@ impl Instance {
             extensions: extensions.clone(),
             layers,
             function_pointers,
+            creation_debug_callback,
+            debug_callbacks: Mutex::new(Vec::new()),
         };
 
         // Enumerating all physical devices.
@@ -435,6 +584,74 @@ impl Instance {
         &self.extensions
     }
 
+    /// Assigns a human-readable name to `object` for debugging purposes. The name will show up
+    /// in debug messages and in tools such as RenderDoc. Passing an empty `name` removes any
+    /// previously assigned name.
+    ///
+    /// This is a convenience wrapper around
+    /// [`Device::set_object_name`](crate::device::Device::set_object_name) that takes a `&str`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `ext_debug_utils` extension was not enabled on the instance.
+    /// - Panics if `device` was not created from this instance.
+    /// - Panics if `object` is not owned by `device`.
+    /// - Panics if `name` contains a null character.
+    pub fn set_object_name<T>(
+        &self,
+        device: &Device,
+        object: &T,
+        name: &str,
+    ) -> Result<(), OomError>
+    where
+        T: VulkanObject + DeviceOwned,
+    {
+        assert!(
+            self.extensions.ext_debug_utils,
+            "the `ext_debug_utils` extension must be enabled to name objects"
+        );
+        assert_eq!(device.instance().internal_object(), self.instance);
+
+        let name = CString::new(name).expect("object name contains a null character");
+        device.set_object_name(object, &name)
+    }
+
+    /// Returns the number of debug callbacks that are currently registered on the instance.
+    ///
+    /// This includes both [`DebugCallback`](crate::instance::debug::DebugCallback) and
+    /// [`DebugReportCallback`](crate::instance::debug::DebugReportCallback) objects, but not the
+    /// callback given to [`InstanceBuilder::debug_callback`].
+    #[inline]
+    pub fn active_debug_callbacks(&self) -> usize {
+        self.debug_callbacks.lock().unwrap().len()
+    }
+
+    /// Returns the filters of the debug callbacks that are currently registered on the instance,
+    /// in the order they were registered.
+    ///
+    /// This is useful to find callbacks that were dropped earlier than intended.
+    #[inline]
+    pub fn debug_callbacks(&self) -> impl ExactSizeIterator<Item = DebugCallbackInfo> {
+        self.debug_callbacks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|&(_, info)| info)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    pub(crate) fn register_debug_callback(&self, handle: u64, info: DebugCallbackInfo) {
+        self.debug_callbacks.lock().unwrap().push((handle, info));
+    }
+
+    pub(crate) fn unregister_debug_callback(&self, handle: u64) {
+        let mut debug_callbacks = self.debug_callbacks.lock().unwrap();
+        if let Some(index) = debug_callbacks.iter().position(|&(h, _)| h == handle) {
+            debug_callbacks.remove(index);
+        }
+    }
+
     /// Returns the layers that have been enabled on the instance.
     #[doc(hidden)]
     #[inline]
@@ -484,6 +701,143 @@ impl Hash for Instance {
     }
 }
 
+/// Used to create an `Instance` with more options. See `Instance::start`.
+pub struct InstanceBuilder<'a> {
+    app_infos: Option<ApplicationInfo<'a>>,
+    max_api_version: Version,
+    extensions: InstanceExtensions,
+    layers: SmallVec<[CString; 16]>,
+    function_pointers: Option<FunctionPointers<Box<dyn Loader + Send + Sync>>>,
+    debug_callback: Option<(
+        MessageSeverity,
+        MessageType,
+        Box<Box<dyn Fn(&Message) -> bool + Send + Sync>>,
+    )>,
+    validation_features: Option<ValidationFeatures>,
+}
+
+impl<'a> InstanceBuilder<'a> {
+    /// Creates the `Instance`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the version numbers passed in `ApplicationInfo` are too large can't be
+    ///   converted into a Vulkan version number.
+    /// - Panics if the application name or engine name contain a null character.
+    pub fn build(self) -> Result<Arc<Instance>, InstanceCreationError> {
+        let function_pointers = match self.function_pointers {
+            Some(function_pointers) => OwnedOrRef::Owned(function_pointers),
+            None => OwnedOrRef::Ref(loader::auto_loader()?),
+        };
+
+        Instance::new_inner(
+            self.app_infos.as_ref(),
+            self.max_api_version,
+            &self.extensions,
+            self.layers,
+            function_pointers,
+            self.debug_callback,
+            self.validation_features,
+        )
+    }
+
+    /// Information about the application, given to the driver.
+    ///
+    /// The default value is `None`.
+    #[inline]
+    pub fn application_info(mut self, app_infos: ApplicationInfo<'a>) -> Self {
+        self.app_infos = Some(app_infos);
+        self
+    }
+
+    /// The highest Vulkan API version that the application will use.
+    ///
+    /// The default value is [`Version::V1_0`].
+    #[inline]
+    pub fn max_api_version(mut self, max_api_version: Version) -> Self {
+        self.max_api_version = max_api_version;
+        self
+    }
+
+    /// The extensions to enable on the instance.
+    ///
+    /// The default value is [`InstanceExtensions::none()`].
+    #[inline]
+    pub fn extensions(mut self, extensions: &InstanceExtensions) -> Self {
+        self.extensions = extensions.clone();
+        self
+    }
+
+    /// The layers to enable on the instance.
+    ///
+    /// The default value is empty.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if a layer name contains a null character.
+    #[inline]
+    pub fn layers<'l, L>(mut self, layers: L) -> Self
+    where
+        L: IntoIterator<Item = &'l str>,
+    {
+        self.layers = layers
+            .into_iter()
+            .map(|layer| CString::new(layer).unwrap())
+            .collect();
+        self
+    }
+
+    /// The loader to load Vulkan from.
+    ///
+    /// The default is to use the loader returned by [`loader::auto_loader`].
+    #[inline]
+    pub fn loader(mut self, loader: FunctionPointers<Box<dyn Loader + Send + Sync>>) -> Self {
+        self.function_pointers = Some(loader);
+        self
+    }
+
+    /// A callback that receives the messages emitted while the instance is being created and
+    /// destroyed.
+    ///
+    /// A [`DebugCallback`](crate::instance::debug::DebugCallback) can only be created once the
+    /// instance exists, so any messages emitted by `vkCreateInstance` or `vkDestroyInstance`
+    /// will only be seen by this callback. It is not called at any other time.
+    ///
+    /// The `ext_debug_utils` extension must be enabled. Panics generated by calling
+    /// `user_callback` are ignored. The callback must not make any calls to the Vulkan API.
+    ///
+    /// The default value is `None`.
+    #[inline]
+    pub fn debug_callback<F>(
+        mut self,
+        severity: MessageSeverity,
+        ty: MessageType,
+        user_callback: F,
+    ) -> Self
+    where
+        F: Fn(&Message) + 'static + Send + Sync + RefUnwindSafe,
+    {
+        let user_callback = move |message: &Message| {
+            user_callback(message);
+            false
+        };
+        self.debug_callback = Some((severity, ty, Box::new(Box::new(user_callback) as Box<_>)));
+        self
+    }
+
+    /// Features of the validation layers to enable or disable, such as GPU-assisted validation
+    /// or best practices checks.
+    ///
+    /// The `ext_validation_features` extension must be enabled.
+    ///
+    /// The default value is `None`.
+    #[inline]
+    pub fn validation_features(mut self, validation_features: ValidationFeatures) -> Self {
+        self.validation_features = Some(validation_features);
+        self
+    }
+}
+
 // Same as Cow but less annoying.
 enum OwnedOrRef<T: 'static> {
     Owned(T),
@@ -597,6 +951,17 @@ pub enum InstanceCreationError {
     IncompatibleDriver,
     /// A restriction for an extension was not met.
     ExtensionRestrictionNotMet(ExtensionRestrictionError),
+    /// An extension that is needed for one of the provided options was not enabled.
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+    /// `ValidationFeatureEnable::GpuAssistedReserveBindingSlot` was enabled without
+    /// `ValidationFeatureEnable::GpuAssisted`.
+    ValidationFeatureReserveBindingSlotWithoutGpuAssisted,
+    /// `ValidationFeatureEnable::DebugPrintf` and `ValidationFeatureEnable::GpuAssisted` were
+    /// both enabled.
+    ValidationFeatureDebugPrintfWithGpuAssisted,
 }
 
 impl error::Error for InstanceCreationError {
@@ -623,6 +988,23 @@ impl fmt::Display for InstanceCreationError {
             InstanceCreationError::ExtensionNotPresent => write!(fmt, "extension not present"),
             InstanceCreationError::IncompatibleDriver => write!(fmt, "incompatible driver"),
             InstanceCreationError::ExtensionRestrictionNotMet(err) => err.fmt(fmt),
+            InstanceCreationError::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            InstanceCreationError::ValidationFeatureReserveBindingSlotWithoutGpuAssisted => {
+                write!(
+                    fmt,
+                    "the `GpuAssistedReserveBindingSlot` validation feature requires \
+                    `GpuAssisted` to be enabled"
+                )
+            }
+            InstanceCreationError::ValidationFeatureDebugPrintfWithGpuAssisted => write!(
+                fmt,
+                "the `DebugPrintf` and `GpuAssisted` validation features can't be enabled at the \
+                same time"
+            ),
         }
     }
 }
@@ -666,6 +1048,11 @@ impl From<Error> for InstanceCreationError {
 #[cfg(test)]
 mod tests {
     use crate::device::physical::PhysicalDevice;
+    use crate::instance::debug::ValidationFeatureEnable;
+    use crate::instance::debug::ValidationFeatures;
+    use crate::instance::Instance;
+    use crate::instance::InstanceCreationError;
+    use crate::instance::InstanceExtensions;
 
     #[test]
     fn create_instance() {
@@ -689,4 +1076,47 @@ mod tests {
         let by_id = phys.queue_family_by_id(queue_family.id()).unwrap();
         assert_eq!(by_id.id(), queue_family.id());
     }
+
+    #[test]
+    fn validation_features_missing_extension() {
+        let result = Instance::start()
+            .validation_features(ValidationFeatures::default())
+            .build();
+
+        match result {
+            Err(InstanceCreationError::ExtensionNotEnabled {
+                extension: "ext_validation_features",
+                ..
+            }) => (),
+            Err(InstanceCreationError::LoadingError(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn validation_features_invalid_combination() {
+        let extensions = InstanceExtensions {
+            ext_validation_features: true,
+            ..InstanceExtensions::none()
+        };
+
+        let result = Instance::start()
+            .extensions(&extensions)
+            .validation_features(ValidationFeatures {
+                enabled: vec![
+                    ValidationFeatureEnable::GpuAssisted,
+                    ValidationFeatureEnable::DebugPrintf,
+                ],
+                disabled: Vec::new(),
+            })
+            .build();
+
+        match result {
+            Err(InstanceCreationError::ValidationFeatureDebugPrintfWithGpuAssisted) => (),
+            // The extension is provided by the validation layers, which may not be installed.
+            Err(InstanceCreationError::LoadingError(_))
+            | Err(InstanceCreationError::ExtensionRestrictionNotMet(_)) => (),
+            _ => panic!(),
+        }
+    }
 }
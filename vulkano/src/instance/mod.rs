@@ -54,6 +54,7 @@
 pub use self::extensions::InstanceExtensions;
 pub use self::instance::ApplicationInfo;
 pub use self::instance::Instance;
+pub use self::instance::InstanceBuilder;
 pub use self::instance::InstanceCreationError;
 pub use self::layers::layers_list;
 pub use self::layers::LayerProperties;
@@ -75,8 +75,10 @@ pub use version::Version;
 mod tests;
 #[macro_use]
 mod extensions;
+pub mod acceleration_structure;
 pub mod buffer;
 pub mod command_buffer;
+pub mod deferred;
 pub mod descriptor_set;
 pub mod device;
 pub mod format;
@@ -171,6 +173,10 @@ enum Success {
     EventReset = ash::vk::Result::EVENT_RESET.as_raw(),
     Incomplete = ash::vk::Result::INCOMPLETE.as_raw(),
     Suboptimal = ash::vk::Result::SUBOPTIMAL_KHR.as_raw(),
+    ThreadIdle = ash::vk::Result::THREAD_IDLE_KHR.as_raw(),
+    ThreadDone = ash::vk::Result::THREAD_DONE_KHR.as_raw(),
+    OperationDeferred = ash::vk::Result::OPERATION_DEFERRED_KHR.as_raw(),
+    OperationNotDeferred = ash::vk::Result::OPERATION_NOT_DEFERRED_KHR.as_raw(),
 }
 
 /// All possible errors returned by any Vulkan function.
@@ -199,6 +205,8 @@ pub(crate) enum Error {
     ValidationFailed = ash::vk::Result::ERROR_VALIDATION_FAILED_EXT.as_raw(),
     OutOfPoolMemory = ash::vk::Result::ERROR_OUT_OF_POOL_MEMORY_KHR.as_raw(),
     FullscreenExclusiveLost = ash::vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT.as_raw(),
+    InvalidExternalHandle = ash::vk::Result::ERROR_INVALID_EXTERNAL_HANDLE.as_raw(),
+    InvalidOpaqueCaptureAddress = ash::vk::Result::ERROR_INVALID_OPAQUE_CAPTURE_ADDRESS.as_raw(),
 }
 
 /// Checks whether the result returned correctly.
@@ -224,6 +232,10 @@ fn check_errors(result: ash::vk::Result) -> Result<Success, Error> {
         ash::vk::Result::ERROR_SURFACE_LOST_KHR => Err(Error::SurfaceLost),
         ash::vk::Result::ERROR_NATIVE_WINDOW_IN_USE_KHR => Err(Error::NativeWindowInUse),
         ash::vk::Result::SUBOPTIMAL_KHR => Ok(Success::Suboptimal),
+        ash::vk::Result::THREAD_IDLE_KHR => Ok(Success::ThreadIdle),
+        ash::vk::Result::THREAD_DONE_KHR => Ok(Success::ThreadDone),
+        ash::vk::Result::OPERATION_DEFERRED_KHR => Ok(Success::OperationDeferred),
+        ash::vk::Result::OPERATION_NOT_DEFERRED_KHR => Ok(Success::OperationNotDeferred),
         ash::vk::Result::ERROR_OUT_OF_DATE_KHR => Err(Error::OutOfDate),
         ash::vk::Result::ERROR_INCOMPATIBLE_DISPLAY_KHR => Err(Error::IncompatibleDisplay),
         ash::vk::Result::ERROR_VALIDATION_FAILED_EXT => Err(Error::ValidationFailed),
@@ -231,6 +243,10 @@ fn check_errors(result: ash::vk::Result) -> Result<Success, Error> {
         ash::vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => {
             Err(Error::FullscreenExclusiveLost)
         }
+        ash::vk::Result::ERROR_INVALID_EXTERNAL_HANDLE => Err(Error::InvalidExternalHandle),
+        ash::vk::Result::ERROR_INVALID_OPAQUE_CAPTURE_ADDRESS => {
+            Err(Error::InvalidOpaqueCaptureAddress)
+        }
         ash::vk::Result::ERROR_INVALID_SHADER_NV => panic!(
             "Vulkan function returned \
                                                VK_ERROR_INVALID_SHADER_NV"
@@ -46,6 +46,7 @@ use std::os::raw::c_void;
 ))]
 use std::os::unix::io::{FromRawFd, IntoRawFd};
 use std::ptr;
+use std::slice;
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -332,15 +333,35 @@ impl<'a> DeviceMemoryBuilder<'a> {
             builder = builder.push_next(info);
         }
 
+        // Memory that is bound to a buffer with the `device_address` usage must be allocated with
+        // this flag. Memory from the pools is shared between resources, so the flag is set on
+        // every allocation as long as the feature is enabled.
+        let mut flags_info = if self.device.enabled_features().buffer_device_address
+            && (self.device.api_version() >= Version::V1_2
+                || self.device.enabled_extensions().khr_buffer_device_address)
+        {
+            Some(ash::vk::MemoryAllocateFlagsInfo {
+                flags: ash::vk::MemoryAllocateFlags::DEVICE_ADDRESS,
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        if let Some(info) = flags_info.as_mut() {
+            builder = builder.push_next(info);
+        }
+
         let memory = unsafe {
             let physical_device = self.device.physical_device();
+            let max_allocation_count = physical_device.properties().max_memory_allocation_count;
             let mut allocation_count = self
                 .device
                 .allocation_count()
                 .lock()
                 .expect("Poisoned mutex");
 
-            if *allocation_count >= physical_device.properties().max_memory_allocation_count {
+            if *allocation_count >= max_allocation_count {
                 return Err(DeviceMemoryAllocError::TooManyObjects);
             }
             let fns = self.device.fns();
@@ -354,6 +375,18 @@ impl<'a> DeviceMemoryBuilder<'a> {
                 output.as_mut_ptr(),
             ))?;
             *allocation_count += 1;
+
+            // Warn once, when three quarters of the limit are reached.
+            #[cfg(feature = "log")]
+            if *allocation_count == max_allocation_count - max_allocation_count / 4 {
+                log::warn!(
+                    "{} of the {} memory allocations allowed by the device are in use; \
+                    consider allocating larger blocks of memory and sub-allocating from them",
+                    *allocation_count,
+                    max_allocation_count,
+                );
+            }
+
             output.assume_init()
         };
 
@@ -703,6 +736,13 @@ impl Drop for DeviceMemory {
 /// In order to access the content of the allocated memory, you can use the `read_write` method.
 /// This method returns a guard object that derefs to the content.
 ///
+/// The memory stays mapped for as long as the `MappedDeviceMemory` exists, so it can be accessed
+/// any number of times without being mapped again. To manage the accesses yourself, for example
+/// to write to memory every frame without the overhead of `read_write`, use `as_slice` and
+/// `as_mut_slice`. If the memory is not host-coherent, host writes must then be made available
+/// to the device with `flush_range`, and device writes made visible to the host with
+/// `invalidate_range`.
+///
 /// # Example
 ///
 /// ```
@@ -753,7 +793,8 @@ impl MappedDeviceMemory {
     /// Gives access to the content of the memory.
     ///
     /// This function takes care of calling `vkInvalidateMappedMemoryRanges` and
-    /// `vkFlushMappedMemoryRanges` on the given range. You are therefore encouraged to use the
+    /// `vkFlushMappedMemoryRanges` on the given range, extended to a multiple of the
+    /// `non_coherent_atom_size` of the device. You are therefore encouraged to use the
     /// smallest range as possible, and to not call this function multiple times in a row for
     /// several small changes.
     ///
@@ -769,29 +810,14 @@ impl MappedDeviceMemory {
     where
         T: Content,
     {
-        let fns = self.memory.device().fns();
         let pointer = T::ref_from_ptr(
             (self.pointer as usize + range.start as usize) as *mut _,
             (range.end - range.start) as usize,
         )
         .unwrap(); // TODO: error
 
-        if !self.coherent {
-            let range = ash::vk::MappedMemoryRange {
-                memory: self.memory.internal_object(),
-                offset: range.start,
-                size: range.end - range.start,
-                ..Default::default()
-            };
-
-            // TODO: return result instead?
-            check_errors(fns.v1_0.invalidate_mapped_memory_ranges(
-                self.memory.device().internal_object(),
-                1,
-                &range,
-            ))
-            .unwrap();
-        }
+        // TODO: return result instead?
+        self.invalidate_range(range.clone()).unwrap();
 
         CpuAccess {
             pointer: pointer,
@@ -800,6 +826,137 @@ impl MappedDeviceMemory {
             range,
         }
     }
+
+    /// Returns true if the memory is host-coherent, in which case `flush_range` and
+    /// `invalidate_range` do nothing.
+    #[inline]
+    pub fn is_coherent(&self) -> bool {
+        self.coherent
+    }
+
+    /// Returns the content of the given range of the memory as a slice of bytes.
+    ///
+    /// Unlike `read_write`, this doesn't invalidate the range. If the memory is not
+    /// host-coherent, you must call `invalidate_range` after the device has written to it and
+    /// before reading the data.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `range` is out of bounds of the memory.
+    ///
+    /// # Safety
+    ///
+    /// - Accesses are not synchronized. You must ensure that the device doesn't write to the
+    ///   range while the slice exists.
+    #[inline]
+    pub unsafe fn as_slice(&self, range: Range<DeviceSize>) -> &[u8] {
+        assert!(range.start <= range.end && range.end <= self.memory.size());
+        slice::from_raw_parts(
+            (self.pointer as *const u8).add(range.start as usize),
+            (range.end - range.start) as usize,
+        )
+    }
+
+    /// Returns the content of the given range of the memory as a mutable slice of bytes.
+    ///
+    /// Unlike `read_write`, this doesn't flush the range. If the memory is not host-coherent,
+    /// you must call `flush_range` after writing and before the device reads the data.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `range` is out of bounds of the memory.
+    ///
+    /// # Safety
+    ///
+    /// - Accesses are not synchronized. You must ensure that the device doesn't access the range
+    ///   while the slice exists, and that there is no other slice to the same range.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_mut_slice(&self, range: Range<DeviceSize>) -> &mut [u8] {
+        assert!(range.start <= range.end && range.end <= self.memory.size());
+        slice::from_raw_parts_mut(
+            (self.pointer as *mut u8).add(range.start as usize),
+            (range.end - range.start) as usize,
+        )
+    }
+
+    /// Makes host writes to the given range of the memory available to the device, by calling
+    /// `vkFlushMappedMemoryRanges`.
+    ///
+    /// The range is extended to a multiple of the `non_coherent_atom_size` of the device. If the
+    /// memory is host-coherent, this does nothing.
+    ///
+    /// # Safety
+    ///
+    /// - The device must not be accessing the range while it is flushed.
+    #[inline]
+    pub unsafe fn flush_range(&self, range: Range<DeviceSize>) -> Result<(), OomError> {
+        if self.coherent {
+            return Ok(());
+        }
+
+        let fns = self.memory.device().fns();
+        let range = self.non_coherent_range(range);
+        check_errors(fns.v1_0.flush_mapped_memory_ranges(
+            self.memory.device().internal_object(),
+            1,
+            &range,
+        ))?;
+        Ok(())
+    }
+
+    /// Makes device writes to the given range of the memory visible to the host, by calling
+    /// `vkInvalidateMappedMemoryRanges`.
+    ///
+    /// The range is extended to a multiple of the `non_coherent_atom_size` of the device. If the
+    /// memory is host-coherent, this does nothing.
+    ///
+    /// # Safety
+    ///
+    /// - The device must not be accessing the range while it is invalidated.
+    /// - Host writes to the range that have not been flushed are discarded.
+    #[inline]
+    pub unsafe fn invalidate_range(&self, range: Range<DeviceSize>) -> Result<(), OomError> {
+        if self.coherent {
+            return Ok(());
+        }
+
+        let fns = self.memory.device().fns();
+        let range = self.non_coherent_range(range);
+        check_errors(fns.v1_0.invalidate_mapped_memory_ranges(
+            self.memory.device().internal_object(),
+            1,
+            &range,
+        ))?;
+        Ok(())
+    }
+
+    // Builds the range to pass to `vkInvalidateMappedMemoryRanges` or `vkFlushMappedMemoryRanges`
+    // for non-coherent memory. The spec requires the offset to be a multiple of
+    // `nonCoherentAtomSize`, and the size to be either a multiple of it or to reach the end of the
+    // allocation.
+    fn non_coherent_range(&self, range: Range<DeviceSize>) -> ash::vk::MappedMemoryRange {
+        let atom_size = self
+            .memory
+            .device()
+            .physical_device()
+            .properties()
+            .non_coherent_atom_size;
+
+        let start = range.start / atom_size * atom_size;
+        let end = (range.end + atom_size - 1) / atom_size * atom_size;
+
+        ash::vk::MappedMemoryRange {
+            memory: self.memory.internal_object(),
+            offset: start,
+            size: if end >= self.memory.size() {
+                ash::vk::WHOLE_SIZE
+            } else {
+                end - start
+            },
+            ..Default::default()
+        }
+    }
 }
 
 impl AsRef<DeviceMemory> for MappedDeviceMemory {
@@ -1012,24 +1169,8 @@ impl<'a, T: ?Sized + 'a> Drop for CpuAccess<'a, T> {
     #[inline]
     fn drop(&mut self) {
         // If the memory doesn't have the `coherent` flag, we need to flush the data.
-        if !self.coherent {
-            let fns = self.mem.as_ref().device().fns();
-
-            let range = ash::vk::MappedMemoryRange {
-                memory: self.mem.as_ref().internal_object(),
-                offset: self.range.start,
-                size: self.range.end - self.range.start,
-                ..Default::default()
-            };
-
-            unsafe {
-                check_errors(fns.v1_0.flush_mapped_memory_ranges(
-                    self.mem.as_ref().device().internal_object(),
-                    1,
-                    &range,
-                ))
-                .unwrap();
-            }
+        unsafe {
+            self.mem.flush_range(self.range.clone()).unwrap();
         }
     }
 }
@@ -1040,6 +1181,9 @@ pub enum DeviceMemoryAllocError {
     /// Not enough memory available.
     OomError(OomError),
     /// The maximum number of allocations has been exceeded.
+    ///
+    /// This is returned without calling Vulkan when the number of live allocations on the device
+    /// has reached the `max_memory_allocation_count` limit.
     TooManyObjects,
     /// Memory map failed.
     MemoryMapFailed,
@@ -1072,9 +1216,12 @@ impl fmt::Display for DeviceMemoryAllocError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
             DeviceMemoryAllocError::OomError(_) => write!(fmt, "not enough memory available"),
-            DeviceMemoryAllocError::TooManyObjects => {
-                write!(fmt, "the maximum number of allocations has been exceeded")
-            }
+            DeviceMemoryAllocError::TooManyObjects => write!(
+                fmt,
+                "the maximum number of memory allocations of the device \
+                (`max_memory_allocation_count`) has been exceeded; allocate larger blocks of \
+                memory and sub-allocate from them instead",
+            ),
             DeviceMemoryAllocError::MemoryMapFailed => write!(fmt, "memory map failed"),
             DeviceMemoryAllocError::MemoryIndexInvalid => write!(fmt, "memory index invalid"),
             DeviceMemoryAllocError::StructureTypeAlreadyPresent => {
@@ -1191,5 +1338,25 @@ mod tests {
             assert_eq!(*device.allocation_count().lock().unwrap(), 2);
         }
         assert_eq!(*device.allocation_count().lock().unwrap(), 1);
+        assert_eq!(device.memory_allocation_count(), 1);
+    }
+
+    #[test]
+    fn persistent_mapping() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device
+            .physical_device()
+            .memory_types()
+            .filter(|t| t.is_host_visible())
+            .next()
+            .unwrap();
+        let memory = DeviceMemory::alloc_and_map(device.clone(), mem_ty, 256).unwrap();
+
+        unsafe {
+            memory.as_mut_slice(0..256).fill(7);
+            memory.flush_range(0..256).unwrap();
+            memory.invalidate_range(16..32).unwrap();
+            assert!(memory.as_slice(16..32).iter().all(|&byte| byte == 7));
+        }
     }
 }
@@ -127,7 +127,18 @@ pub struct MemoryRequirements {
     ///
     /// > **Note**: As its name says, using a dedicated allocation is an optimization and not a
     /// > requirement.
+    ///
+    /// Setting this to true before passing the requirements to
+    /// [`MemoryPool::alloc_from_requirements`] forces a dedicated allocation to be performed,
+    /// as long as a resource is provided. To force dedicated allocations for the buffer and
+    /// image types, which allocate from the standard pool of the device, use
+    /// [`StdMemoryPool::set_force_dedicated`](crate::memory::pool::StdMemoryPool::set_force_dedicated).
     pub prefer_dedicated: bool,
+
+    /// True if the implementation requires the resource to use a dedicated allocation. This can
+    /// be the case for resources that are imported from or exported to external memory. If the
+    /// `khr_get_memory_requirements2` extension isn't enabled, then this will be false.
+    pub require_dedicated: bool,
 }
 
 impl From<ash::vk::MemoryRequirements> for MemoryRequirements {
@@ -138,6 +149,7 @@ impl From<ash::vk::MemoryRequirements> for MemoryRequirements {
             alignment: val.alignment,
             memory_type_bits: val.memory_type_bits,
             prefer_dedicated: false,
+            require_dedicated: false,
         }
     }
 }
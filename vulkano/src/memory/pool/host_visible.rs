@@ -19,11 +19,16 @@ use std::ops::Range;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+const DEFAULT_MIN_BLOCK_SIZE: DeviceSize = 8 * 1024 * 1024; // 8 MB
+
 /// Memory pool that operates on a given memory type.
 #[derive(Debug)]
 pub struct StdHostVisibleMemoryTypePool {
     device: Arc<Device>,
     memory_type: u32,
+    min_block_size: DeviceSize,
+    // The granularity of flushes and invalidations of the memory, or 1 if it is host-coherent.
+    atom_size: DeviceSize,
     // TODO: obviously very inefficient
     occupied: Mutex<Vec<(Arc<MappedDeviceMemory>, Vec<Range<DeviceSize>>)>>,
 }
@@ -31,6 +36,8 @@ pub struct StdHostVisibleMemoryTypePool {
 impl StdHostVisibleMemoryTypePool {
     /// Creates a new pool that will operate on the given memory type.
     ///
+    /// Blocks of memory are allocated with a size of at least 8 MB.
+    ///
     /// # Panic
     ///
     /// - Panics if the `device` and `memory_type` don't belong to the same physical device.
@@ -38,6 +45,28 @@ impl StdHostVisibleMemoryTypePool {
     ///
     #[inline]
     pub fn new(device: Arc<Device>, memory_type: MemoryType) -> Arc<StdHostVisibleMemoryTypePool> {
+        StdHostVisibleMemoryTypePool::with_min_block_size(
+            device,
+            memory_type,
+            DEFAULT_MIN_BLOCK_SIZE,
+        )
+    }
+
+    /// Creates a new pool that will operate on the given memory type, and that allocates blocks
+    /// of memory with a size of at least `min_block_size`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `device` and `memory_type` don't belong to the same physical device.
+    /// - Panics if the memory type is not host-visible.
+    /// - Panics if `min_block_size` is 0.
+    ///
+    pub fn with_min_block_size(
+        device: Arc<Device>,
+        memory_type: MemoryType,
+        min_block_size: DeviceSize,
+    ) -> Arc<StdHostVisibleMemoryTypePool> {
+        assert!(min_block_size != 0);
         assert_eq!(
             &**device.physical_device().instance() as *const Instance,
             &**memory_type.physical_device().instance() as *const Instance
@@ -48,15 +77,27 @@ impl StdHostVisibleMemoryTypePool {
         );
         assert!(memory_type.is_host_visible());
 
+        let atom_size = if memory_type.is_host_coherent() {
+            1
+        } else {
+            device.physical_device().properties().non_coherent_atom_size
+        };
+
         Arc::new(StdHostVisibleMemoryTypePool {
             device: device.clone(),
             memory_type: memory_type.id(),
+            min_block_size,
+            atom_size,
             occupied: Mutex::new(Vec::new()),
         })
     }
 
     /// Allocates memory from the pool.
     ///
+    /// If the memory type is not host-coherent, the allocation is aligned to the
+    /// `non_coherent_atom_size` of the device, and no other allocation shares an atom with it.
+    /// Flushing or invalidating an allocation therefore never affects its neighbours.
+    ///
     /// # Panic
     ///
     /// - Panics if `size` is 0.
@@ -75,6 +116,11 @@ impl StdHostVisibleMemoryTypePool {
             al * (1 + (val - 1) / al)
         }
 
+        // `non_coherent_atom_size` and the alignment are both powers of two. The range that is
+        // reserved for the allocation is rounded up to the atom size as well.
+        let alignment = cmp::max(alignment, me.atom_size);
+        let reserved_size = align(size, me.atom_size);
+
         // Find a location.
         let mut occupied = me.occupied.lock().unwrap();
 
@@ -85,8 +131,8 @@ impl StdHostVisibleMemoryTypePool {
                 let entry1 = entries[i].clone();
                 let entry1_end = align(entry1.end, alignment);
                 let entry2 = entries[i + 1].clone();
-                if entry1_end + size <= entry2.start {
-                    entries.insert(i + 1, entry1_end..entry1_end + size);
+                if entry1_end + reserved_size <= entry2.start {
+                    entries.insert(i + 1, entry1_end..entry1_end + reserved_size);
                     return Ok(StdHostVisibleMemoryTypePoolAlloc {
                         pool: me.clone(),
                         memory: dev_mem.clone(),
@@ -98,8 +144,8 @@ impl StdHostVisibleMemoryTypePool {
 
             // Try append at the end.
             let last_end = entries.last().map(|e| align(e.end, alignment)).unwrap_or(0);
-            if last_end + size <= (**dev_mem).as_ref().size() {
-                entries.push(last_end..last_end + size);
+            if last_end + reserved_size <= (**dev_mem).as_ref().size() {
+                entries.push(last_end..last_end + reserved_size);
                 return Ok(StdHostVisibleMemoryTypePoolAlloc {
                     pool: me.clone(),
                     memory: dev_mem.clone(),
@@ -111,14 +157,13 @@ impl StdHostVisibleMemoryTypePool {
 
         // We need to allocate a new block.
         let new_block = {
-            const MIN_BLOCK_SIZE: DeviceSize = 8 * 1024 * 1024; // 8 MB
-            let to_alloc = cmp::max(MIN_BLOCK_SIZE, size.next_power_of_two());
+            let to_alloc = cmp::max(me.min_block_size, reserved_size.next_power_of_two());
             let new_block =
                 DeviceMemory::alloc_and_map(me.device.clone(), me.memory_type(), to_alloc)?;
             Arc::new(new_block)
         };
 
-        occupied.push((new_block.clone(), vec![0..size]));
+        occupied.push((new_block.clone(), vec![0..reserved_size]));
         Ok(StdHostVisibleMemoryTypePoolAlloc {
             pool: me.clone(),
             memory: new_block,
@@ -148,10 +148,21 @@ pub unsafe trait MemoryPool: DeviceOwned {
         map: MappingRequirement,
     ) -> Result<Self::Alloc, DeviceMemoryAllocError>;
 
+    /// Returns true if [`alloc_from_requirements`](MemoryPool::alloc_from_requirements) should
+    /// perform a dedicated allocation for every resource, even if the implementation doesn't
+    /// prefer one.
+    ///
+    /// The default implementation returns false.
+    #[inline]
+    fn force_dedicated(&self) -> bool {
+        false
+    }
+
     /// Chooses a memory type and allocates memory from it.
     ///
     /// Contrary to `alloc_generic`, this function may allocate a whole new block of memory
-    /// dedicated to a resource based on `requirements.prefer_dedicated`.
+    /// dedicated to a resource based on `requirements.prefer_dedicated`,
+    /// `requirements.require_dedicated` and [`force_dedicated`](MemoryPool::force_dedicated).
     ///
     /// `filter` can be used to restrict the memory types and to indicate which are preferred.
     /// If `map` is `MappingRequirement::Map`, then non-host-visible memory types will
@@ -174,6 +185,8 @@ pub unsafe trait MemoryPool: DeviceOwned {
     ///
     /// - Panics if no memory type could be found, which can happen if `filter` is too restrictive.
     // TODO: ^ is this a good idea?
+    /// - Panics if `requirements.require_dedicated` is true and `dedicated` is
+    ///   `DedicatedAlloc::None`.
     /// - Panics if `size` is 0.
     /// - Panics if `alignment` is 0.
     ///
@@ -192,7 +205,11 @@ pub unsafe trait MemoryPool: DeviceOwned {
         let mem_ty = choose_allocation_memory_type(self.device(), requirements, filter, map);
 
         // Redirect to `self.alloc_generic` if we don't perform a dedicated allocation.
-        if !requirements.prefer_dedicated && requirements.size <= MAX_POOL_ALLOC {
+        if !requirements.prefer_dedicated
+            && !requirements.require_dedicated
+            && !self.force_dedicated()
+            && requirements.size <= MAX_POOL_ALLOC
+        {
             let alloc = self.alloc_generic(
                 mem_ty,
                 requirements.size,
@@ -203,6 +220,7 @@ pub unsafe trait MemoryPool: DeviceOwned {
             return Ok(alloc.into());
         }
         if let DedicatedAlloc::None = dedicated {
+            assert!(!requirements.require_dedicated);
             let alloc = self.alloc_generic(
                 mem_ty,
                 requirements.size,
@@ -18,11 +18,14 @@ use std::ops::Range;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+const DEFAULT_MIN_BLOCK_SIZE: DeviceSize = 8 * 1024 * 1024; // 8 MB
+
 /// Memory pool that operates on a given memory type.
 #[derive(Debug)]
 pub struct StdNonHostVisibleMemoryTypePool {
     device: Arc<Device>,
     memory_type: u32,
+    min_block_size: DeviceSize,
     // TODO: obviously very inefficient
     occupied: Mutex<Vec<(Arc<DeviceMemory>, Vec<Range<DeviceSize>>)>>,
 }
@@ -30,6 +33,8 @@ pub struct StdNonHostVisibleMemoryTypePool {
 impl StdNonHostVisibleMemoryTypePool {
     /// Creates a new pool that will operate on the given memory type.
     ///
+    /// Blocks of memory are allocated with a size of at least 8 MB.
+    ///
     /// # Panic
     ///
     /// - Panics if the `device` and `memory_type` don't belong to the same physical device.
@@ -39,6 +44,27 @@ impl StdNonHostVisibleMemoryTypePool {
         device: Arc<Device>,
         memory_type: MemoryType,
     ) -> Arc<StdNonHostVisibleMemoryTypePool> {
+        StdNonHostVisibleMemoryTypePool::with_min_block_size(
+            device,
+            memory_type,
+            DEFAULT_MIN_BLOCK_SIZE,
+        )
+    }
+
+    /// Creates a new pool that will operate on the given memory type, and that allocates blocks
+    /// of memory with a size of at least `min_block_size`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `device` and `memory_type` don't belong to the same physical device.
+    /// - Panics if `min_block_size` is 0.
+    ///
+    pub fn with_min_block_size(
+        device: Arc<Device>,
+        memory_type: MemoryType,
+        min_block_size: DeviceSize,
+    ) -> Arc<StdNonHostVisibleMemoryTypePool> {
+        assert!(min_block_size != 0);
         assert_eq!(
             &**device.physical_device().instance() as *const Instance,
             &**memory_type.physical_device().instance() as *const Instance
@@ -51,6 +77,7 @@ impl StdNonHostVisibleMemoryTypePool {
         Arc::new(StdNonHostVisibleMemoryTypePool {
             device: device.clone(),
             memory_type: memory_type.id(),
+            min_block_size,
             occupied: Mutex::new(Vec::new()),
         })
     }
@@ -111,8 +138,7 @@ impl StdNonHostVisibleMemoryTypePool {
 
         // We need to allocate a new block.
         let new_block = {
-            const MIN_BLOCK_SIZE: DeviceSize = 8 * 1024 * 1024; // 8 MB
-            let to_alloc = cmp::max(MIN_BLOCK_SIZE, size.next_power_of_two());
+            let to_alloc = cmp::max(me.min_block_size, size.next_power_of_two());
             let new_block = DeviceMemory::alloc(me.device.clone(), me.memory_type(), to_alloc)?;
             Arc::new(new_block)
         };
@@ -26,6 +26,8 @@ use fnv::FnvHasher;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::hash::BuildHasherDefault;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -36,20 +38,70 @@ pub struct StdMemoryPool {
     // For each memory type index, stores the associated pool.
     pools:
         Mutex<HashMap<(u32, AllocLayout, MappingRequirement), Pool, BuildHasherDefault<FnvHasher>>>,
+
+    // For each memory type index, stores the minimum size of the blocks to allocate, if it was
+    // overridden by the user.
+    block_sizes: HashMap<u32, DeviceSize, BuildHasherDefault<FnvHasher>>,
+
+    // Whether every resource gets a dedicated allocation.
+    force_dedicated: AtomicBool,
 }
 
 impl StdMemoryPool {
     /// Creates a new pool.
     #[inline]
     pub fn new(device: Arc<Device>) -> Arc<StdMemoryPool> {
+        StdMemoryPool::with_block_sizes(device, [])
+    }
+
+    /// Creates a new pool, allocating blocks of at least the given size for each of the given
+    /// memory types, as identified by their id. Memory types that are not in `block_sizes` use
+    /// the default minimum block size of 8 MB.
+    ///
+    /// Allocations that are larger than the block size get a block of their own.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the memory type ids doesn't exist on the physical device.
+    /// - Panics if one of the block sizes is 0.
+    pub fn with_block_sizes<I>(device: Arc<Device>, block_sizes: I) -> Arc<StdMemoryPool>
+    where
+        I: IntoIterator<Item = (u32, DeviceSize)>,
+    {
         let cap = device.physical_device().memory_types().len();
         let hasher = BuildHasherDefault::<FnvHasher>::default();
 
+        let block_sizes = block_sizes
+            .into_iter()
+            .map(|(memory_type, block_size)| {
+                assert!(device
+                    .physical_device()
+                    .memory_type_by_id(memory_type)
+                    .is_some());
+                assert!(block_size != 0);
+                (memory_type, block_size)
+            })
+            .collect();
+
         Arc::new(StdMemoryPool {
             device: device.clone(),
             pools: Mutex::new(HashMap::with_capacity_and_hasher(cap, hasher)),
+            block_sizes,
+            force_dedicated: AtomicBool::new(false),
         })
     }
+
+    /// Sets whether every buffer and image allocated from this pool gets a dedicated allocation,
+    /// even if the implementation doesn't prefer one. This is false by default.
+    ///
+    /// Combined with [`Device::set_standard_pool`], this allows forcing dedicated allocations
+    /// for the buffer and image types, for example for render targets. The setting only applies
+    /// to resources that are allocated after it is changed.
+    #[inline]
+    pub fn set_force_dedicated(&self, force_dedicated: bool) {
+        self.force_dedicated
+            .store(force_dedicated, Ordering::Relaxed);
+    }
 }
 
 fn generic_allocation(
@@ -86,8 +138,17 @@ fn generic_allocation(
         },
 
         Entry::Vacant(entry) => {
+            let min_block_size = mem_pool.block_sizes.get(&memory_type.id()).copied();
+
             if memory_type_host_visible {
-                let pool = StdHostVisibleMemoryTypePool::new(mem_pool.device.clone(), memory_type);
+                let pool = match min_block_size {
+                    Some(min_block_size) => StdHostVisibleMemoryTypePool::with_min_block_size(
+                        mem_pool.device.clone(),
+                        memory_type,
+                        min_block_size,
+                    ),
+                    None => StdHostVisibleMemoryTypePool::new(mem_pool.device.clone(), memory_type),
+                };
                 entry.insert(Pool::HostVisible(pool.clone()));
                 let alloc = StdHostVisibleMemoryTypePool::alloc(&pool, size, alignment)?;
                 let inner = StdMemoryPoolAllocInner::HostVisible(alloc);
@@ -96,8 +157,16 @@ fn generic_allocation(
                     pool: mem_pool.clone(),
                 })
             } else {
-                let pool =
-                    StdNonHostVisibleMemoryTypePool::new(mem_pool.device.clone(), memory_type);
+                let pool = match min_block_size {
+                    Some(min_block_size) => StdNonHostVisibleMemoryTypePool::with_min_block_size(
+                        mem_pool.device.clone(),
+                        memory_type,
+                        min_block_size,
+                    ),
+                    None => {
+                        StdNonHostVisibleMemoryTypePool::new(mem_pool.device.clone(), memory_type)
+                    }
+                };
                 entry.insert(Pool::NonHostVisible(pool.clone()));
                 let alloc = StdNonHostVisibleMemoryTypePool::alloc(&pool, size, alignment)?;
                 let inner = StdMemoryPoolAllocInner::NonHostVisible(alloc);
@@ -123,6 +192,11 @@ unsafe impl MemoryPool for Arc<StdMemoryPool> {
     ) -> Result<StdMemoryPoolAlloc, DeviceMemoryAllocError> {
         generic_allocation(self.clone(), memory_type, size, alignment, layout, map)
     }
+
+    #[inline]
+    fn force_dedicated(&self) -> bool {
+        self.force_dedicated.load(Ordering::Relaxed)
+    }
 }
 
 unsafe impl DeviceOwned for StdMemoryPool {
@@ -185,3 +259,50 @@ enum StdMemoryPoolAllocInner {
     NonHostVisible(StdNonHostVisibleMemoryTypePoolAlloc),
     HostVisible(StdHostVisibleMemoryTypePoolAlloc),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::StdMemoryPool;
+    use crate::buffer::sys::UnsafeBuffer;
+    use crate::buffer::BufferUsage;
+    use crate::memory::pool::AllocFromRequirementsFilter;
+    use crate::memory::pool::AllocLayout;
+    use crate::memory::pool::MappingRequirement;
+    use crate::memory::pool::MemoryPool;
+    use crate::memory::pool::PotentialDedicatedAllocation;
+    use crate::memory::DedicatedAlloc;
+    use crate::memory::ExternalMemoryHandleTypes;
+    use crate::sync::Sharing;
+    use std::iter::Empty;
+
+    #[test]
+    fn force_dedicated() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let (buffer, requirements) = unsafe {
+            UnsafeBuffer::new(
+                device.clone(),
+                128,
+                BufferUsage::all(),
+                Sharing::Exclusive::<Empty<_>>,
+                None,
+                ExternalMemoryHandleTypes::none(),
+            )
+            .unwrap()
+        };
+
+        let pool = StdMemoryPool::new(device);
+        pool.set_force_dedicated(true);
+
+        let alloc = pool
+            .alloc_from_requirements(
+                &requirements,
+                AllocLayout::Linear,
+                MappingRequirement::DoNotMap,
+                DedicatedAlloc::Buffer(&buffer),
+                |_| AllocFromRequirementsFilter::Allowed,
+            )
+            .unwrap();
+        assert!(matches!(alloc, PotentialDedicatedAllocation::Dedicated(_)));
+    }
+}
@@ -116,6 +116,17 @@ impl ComputePipeline {
         Css: SpecializationConstants,
     {
         let spec_descriptors = Css::descriptors();
+        let spec_data_size = mem::size_of_val(specialization_constants);
+
+        for desc in spec_descriptors {
+            if desc.offset as usize + desc.size > spec_data_size {
+                return Err(
+                    ComputePipelineCreationError::SpecializationMapEntryOutOfBounds {
+                        constant_id: desc.constant_id,
+                    },
+                );
+            }
+        }
 
         for (constant_id, reqs) in shader.specialization_constant_requirements() {
             let map_entry = spec_descriptors
@@ -314,6 +325,8 @@ pub enum ComputePipelineCreationError {
     IncompatiblePipelineLayout(PipelineLayoutSupersetError),
     /// The provided specialization constants are not compatible with what the shader expects.
     IncompatibleSpecializationConstants,
+    /// A specialization map entry refers to bytes outside of the specialization data.
+    SpecializationMapEntryOutOfBounds { constant_id: u32 },
 }
 
 impl error::Error for ComputePipelineCreationError {
@@ -324,6 +337,7 @@ impl error::Error for ComputePipelineCreationError {
             ComputePipelineCreationError::PipelineLayoutCreationError(ref err) => Some(err),
             ComputePipelineCreationError::IncompatiblePipelineLayout(ref err) => Some(err),
             ComputePipelineCreationError::IncompatibleSpecializationConstants => None,
+            ComputePipelineCreationError::SpecializationMapEntryOutOfBounds { .. } => None,
         }
     }
 }
@@ -345,6 +359,9 @@ impl fmt::Display for ComputePipelineCreationError {
                 ComputePipelineCreationError::IncompatibleSpecializationConstants => {
                     "the provided specialization constants are not compatible with what the shader expects"
                 }
+                ComputePipelineCreationError::SpecializationMapEntryOutOfBounds { .. } => {
+                    "a specialization map entry refers to bytes outside of the specialization data"
+                }
             }
         )
     }
@@ -394,6 +411,8 @@ mod tests {
     use crate::command_buffer::CommandBufferUsage;
     use crate::descriptor_set::PersistentDescriptorSet;
     use crate::descriptor_set::WriteDescriptorSet;
+    use crate::device::Device;
+    use crate::pipeline::compute::ComputePipelineCreationError;
     use crate::pipeline::ComputePipeline;
     use crate::pipeline::Pipeline;
     use crate::pipeline::PipelineBindPoint;
@@ -402,19 +421,14 @@ mod tests {
     use crate::shader::SpecializationMapEntry;
     use crate::sync::now;
     use crate::sync::GpuFuture;
+    use std::sync::Arc;
 
     // TODO: test for basic creation
     // TODO: test for pipeline layout error
 
-    #[test]
-    fn specialization_constants() {
-        // This test checks whether specialization constants work.
-        // It executes a single compute shader (one invocation) that writes the value of a spec.
-        // constant to a buffer. The buffer content is then checked for the right value.
-
-        let (device, queue) = gfx_dev_and_queue!();
-
-        let module = unsafe {
+    // Writes the value of the specialization constant with ID 83 to the buffer at binding 0.
+    fn spec_constant_module(device: Arc<Device>) -> Arc<ShaderModule> {
+        unsafe {
             /*
             #version 450
 
@@ -451,8 +465,19 @@ mod tests {
                 0, 5, 0, 0, 0, 65, 0, 5, 0, 12, 0, 0, 0, 13, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 62,
                 0, 3, 0, 13, 0, 0, 0, 11, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
             ];
-            ShaderModule::from_bytes(device.clone(), &MODULE).unwrap()
-        };
+            ShaderModule::from_bytes(device, &MODULE).unwrap()
+        }
+    }
+
+    #[test]
+    fn specialization_constants() {
+        // This test checks whether specialization constants work.
+        // It executes a single compute shader (one invocation) that writes the value of a spec.
+        // constant to a buffer. The buffer content is then checked for the right value.
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let module = spec_constant_module(device.clone());
 
         #[derive(Debug, Copy, Clone)]
         #[allow(non_snake_case)]
@@ -521,4 +546,40 @@ mod tests {
         let data_buffer_content = data_buffer.read().unwrap();
         assert_eq!(*data_buffer_content, 0x12345678);
     }
+
+    #[test]
+    fn specialization_map_entry_out_of_bounds() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let module = spec_constant_module(device.clone());
+
+        #[derive(Debug, Copy, Clone)]
+        #[repr(C)]
+        struct SpecConsts {
+            value: i32,
+        }
+        unsafe impl SpecializationConstants for SpecConsts {
+            fn descriptors() -> &'static [SpecializationMapEntry] {
+                static DESCRIPTORS: [SpecializationMapEntry; 1] = [SpecializationMapEntry {
+                    constant_id: 83,
+                    offset: 4,
+                    size: 4,
+                }];
+                &DESCRIPTORS
+            }
+        }
+
+        match ComputePipeline::new(
+            device,
+            module.entry_point("main").unwrap(),
+            &SpecConsts { value: 0 },
+            None,
+            |_| {},
+        ) {
+            Err(ComputePipelineCreationError::SpecializationMapEntryOutOfBounds {
+                constant_id: 83,
+            }) => (),
+            _ => panic!(),
+        }
+    }
 }
@@ -14,6 +14,7 @@
 use crate::check_errors;
 use crate::descriptor_set::layout::{DescriptorSetDesc, DescriptorSetLayout};
 use crate::device::Device;
+use crate::device::DeviceOwned;
 use crate::format::NumericType;
 use crate::pipeline::cache::PipelineCache;
 use crate::pipeline::graphics::color_blend::{
@@ -21,11 +22,13 @@ use crate::pipeline::graphics::color_blend::{
 };
 use crate::pipeline::graphics::depth_stencil::DepthStencilState;
 use crate::pipeline::graphics::discard_rectangle::DiscardRectangleState;
+use crate::pipeline::graphics::fragment_shading_rate::FragmentShadingRateState;
 use crate::pipeline::graphics::input_assembly::{InputAssemblyState, PrimitiveTopology};
 use crate::pipeline::graphics::multisample::MultisampleState;
 use crate::pipeline::graphics::rasterization::{
     CullMode, FrontFace, PolygonMode, RasterizationState,
 };
+use crate::pipeline::graphics::render_pass::PipelineRenderPassType;
 use crate::pipeline::graphics::tessellation::TessellationState;
 use crate::pipeline::graphics::vertex_input::{
     BuffersDefinition, Vertex, VertexDefinition, VertexInputState,
@@ -34,7 +37,6 @@ use crate::pipeline::graphics::viewport::{Scissor, Viewport, ViewportState};
 use crate::pipeline::graphics::{GraphicsPipeline, GraphicsPipelineCreationError};
 use crate::pipeline::layout::{PipelineLayout, PipelineLayoutCreationError, PipelineLayoutPcRange};
 use crate::pipeline::{DynamicState, PartialStateMode, StateMode};
-use crate::render_pass::Subpass;
 use crate::shader::{
     DescriptorRequirements, EntryPoint, ShaderExecution, ShaderStage, SpecializationConstants,
     SpecializationMapEntry,
@@ -52,8 +54,10 @@ use std::u32;
 /// Prototype for a `GraphicsPipeline`.
 #[derive(Debug)]
 pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss> {
-    subpass: Option<Subpass>,
+    render_pass: Option<PipelineRenderPassType>,
     cache: Option<Arc<PipelineCache>>,
+    allow_derivatives: bool,
+    base_pipeline: Option<Arc<GraphicsPipeline>>,
 
     vertex_shader: Option<(EntryPoint<'vs>, Vss)>,
     tessellation_shaders: Option<TessellationShaders<'tcs, 'tes, Tcss, Tess>>,
@@ -65,6 +69,7 @@ pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, T
     tessellation_state: TessellationState,
     viewport_state: ViewportState,
     discard_rectangle_state: DiscardRectangleState,
+    fragment_shading_rate_state: FragmentShadingRateState,
     rasterization_state: RasterizationState,
     multisample_state: MultisampleState,
     depth_stencil_state: DepthStencilState,
@@ -96,8 +101,10 @@ impl
     /// Builds a new empty builder.
     pub(super) fn new() -> Self {
         GraphicsPipelineBuilder {
-            subpass: None,
+            render_pass: None,
             cache: None,
+            allow_derivatives: false,
+            base_pipeline: None,
 
             vertex_shader: None,
             tessellation_shaders: None,
@@ -109,6 +116,7 @@ impl
             tessellation_state: Default::default(),
             viewport_state: Default::default(),
             discard_rectangle_state: Default::default(),
+            fragment_shading_rate_state: Default::default(),
             rasterization_state: Default::default(),
             multisample_state: Default::default(),
             depth_stencil_state: Default::default(),
@@ -244,7 +252,20 @@ where
         // TODO: return errors instead of panicking if missing param
 
         let fns = device.fns();
-        let subpass = self.subpass.take().expect("Missing subpass");
+        let render_pass = self.render_pass.take().expect("Missing render pass");
+
+        // Dynamic rendering state
+        let rendering_color_attachment_formats = match &render_pass {
+            PipelineRenderPassType::BeginRenderPass(_) => Vec::new(),
+            PipelineRenderPassType::BeginRendering(info) => info.to_vulkan_formats(),
+        };
+        let mut rendering_state = match &render_pass {
+            PipelineRenderPassType::BeginRenderPass(_) => None,
+            PipelineRenderPassType::BeginRendering(info) => {
+                Some(info.to_vulkan(&device, &rendering_color_attachment_formats)?)
+            }
+        };
+
         let self_vertex_input_state = self
             .vertex_input_state
             .definition(self.vertex_shader.as_ref().unwrap().0.input_interface())?;
@@ -335,17 +356,15 @@ where
                 _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
             }
 
-            if let Some(multiview) = subpass.render_pass().desc().multiview().as_ref() {
-                if multiview.used_layer_count() > 0
-                    && !device.enabled_features().multiview_tessellation_shader
-                {
-                    return Err(
-                        GraphicsPipelineCreationError::FeatureNotEnabled {
-                            feature: "multiview_tessellation_shader",
-                            reason: "a tessellation shader was provided, and the render pass has multiview enabled with more than zero layers used",
-                        },
-                    );
-                }
+            if render_pass.has_multiview()
+                && !device.enabled_features().multiview_tessellation_shader
+            {
+                return Err(
+                    GraphicsPipelineCreationError::FeatureNotEnabled {
+                        feature: "multiview_tessellation_shader",
+                        reason: "a tessellation shader was provided, and the render pass has multiview enabled with more than zero layers used",
+                    },
+                );
             }
         }
 
@@ -368,17 +387,13 @@ where
                 }
             }
 
-            if let Some(multiview) = subpass.render_pass().desc().multiview().as_ref() {
-                if multiview.used_layer_count() > 0
-                    && !device.enabled_features().multiview_geometry_shader
-                {
-                    return Err(
-                        GraphicsPipelineCreationError::FeatureNotEnabled {
-                            feature: "multiview_geometry_shader",
-                            reason: "a geometry shader was provided, and the render pass has multiview enabled with more than zero layers used",
-                        },
-                    );
-                }
+            if render_pass.has_multiview() && !device.enabled_features().multiview_geometry_shader {
+                return Err(
+                    GraphicsPipelineCreationError::FeatureNotEnabled {
+                        feature: "multiview_geometry_shader",
+                        reason: "a geometry shader was provided, and the render pass has multiview enabled with more than zero layers used",
+                    },
+                );
             }
 
             // TODO: VUID-VkGraphicsPipelineCreateInfo-pStages-00739
@@ -395,9 +410,9 @@ where
                 _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
             }
 
-            // Check that the subpass can accept the output of the fragment shader.
+            // Check that the render pass can accept the output of the fragment shader.
             // TODO: If there is no fragment shader, what should be checked then? The previous stage?
-            if !subpass.is_compatible_with(fragment_shader.0.output_interface()) {
+            if !render_pass.is_compatible_with(fragment_shader.0.output_interface()) {
                 return Err(GraphicsPipelineCreationError::FragmentShaderRenderPassIncompatible);
             }
         } else {
@@ -533,6 +548,16 @@ where
         let specialization: SmallVec<[_; 5]> = stages_info
             .iter()
             .map(|stage_info| {
+                for desc in stage_info.specialization_map_entries {
+                    if desc.offset as usize + desc.size > stage_info.specialization_data.len() {
+                        return Err(
+                            GraphicsPipelineCreationError::SpecializationMapEntryOutOfBounds {
+                                constant_id: desc.constant_id,
+                            },
+                        );
+                    }
+                }
+
                 for (constant_id, reqs) in stage_info
                     .entry_point
                     .specialization_constant_requirements()
@@ -645,14 +670,23 @@ where
             &discard_rectangles,
         )?;
 
+        // Fragment shading rate state
+        let mut fragment_shading_rate_state = self
+            .fragment_shading_rate_state
+            .to_vulkan(&device, &mut dynamic_state_modes)?;
+
         // Rasterization state
         let mut rasterization_line_state = self
             .rasterization_state
             .to_vulkan_line_state(&device, &mut dynamic_state_modes)?;
+        let mut rasterization_depth_clip_state = self
+            .rasterization_state
+            .to_vulkan_depth_clip_state(&device)?;
         let rasterization_state = Some(self.rasterization_state.to_vulkan(
             &device,
             &mut dynamic_state_modes,
             rasterization_line_state.as_mut(),
+            rasterization_depth_clip_state.as_mut(),
         )?);
 
         // Fragment shader state
@@ -661,30 +695,31 @@ where
 
         // Multisample state
         let multisample_state = if has_fragment_shader_state {
-            Some(
-                self.multisample_state
-                    .to_vulkan(&device, &mut dynamic_state_modes, &subpass)?,
-            )
+            Some(self.multisample_state.to_vulkan(
+                &device,
+                &mut dynamic_state_modes,
+                &render_pass,
+            )?)
         } else {
             None
         };
 
         // Depth/stencil state
-        let depth_stencil_state = if has_fragment_shader_state
-            && subpass.subpass_desc().depth_stencil.is_some()
-        {
-            Some(
-                self.depth_stencil_state
-                    .to_vulkan(&device, &mut dynamic_state_modes, &subpass)?,
-            )
-        } else {
-            None
-        };
+        let depth_stencil_state =
+            if has_fragment_shader_state && render_pass.has_depth_stencil_attachment() {
+                Some(self.depth_stencil_state.to_vulkan(
+                    &device,
+                    &mut dynamic_state_modes,
+                    &render_pass,
+                )?)
+            } else {
+                None
+            };
 
         // Color blend state
         let (color_blend_attachments, color_write_enables) = self
             .color_blend_state
-            .to_vulkan_attachments(&device, &mut dynamic_state_modes, &subpass)?;
+            .to_vulkan_attachments(&device, &mut dynamic_state_modes, &render_pass)?;
         let mut color_write = self.color_blend_state.to_vulkan_color_write(
             &device,
             &mut dynamic_state_modes,
@@ -728,10 +763,36 @@ where
         // - ExclusiveScissor (VkPipelineViewportExclusiveScissorStateCreateInfoNV)
         // - FragmentShadingRate (VkPipelineFragmentShadingRateStateCreateInfoKHR)
 
+        // Check the base pipeline, if any.
+        let base_pipeline_handle = if let Some(base_pipeline) = &self.base_pipeline {
+            assert_eq!(
+                base_pipeline.device().internal_object(),
+                device.internal_object()
+            );
+
+            if !base_pipeline.allow_derivatives() {
+                return Err(GraphicsPipelineCreationError::BasePipelineNotDerivable);
+            }
+
+            base_pipeline.internal_object()
+        } else {
+            ash::vk::Pipeline::null()
+        };
+
+        let mut flags = ash::vk::PipelineCreateFlags::empty(); // TODO: some flags are available but none are critical
+
+        if self.allow_derivatives {
+            flags |= ash::vk::PipelineCreateFlags::ALLOW_DERIVATIVES;
+        }
+
+        if self.base_pipeline.is_some() {
+            flags |= ash::vk::PipelineCreateFlags::DERIVATIVE;
+        }
+
         // Finally, create the pipeline itself.
         let handle = unsafe {
             let mut create_info = ash::vk::GraphicsPipelineCreateInfo {
-                flags: ash::vk::PipelineCreateFlags::empty(), // TODO: some flags are available but none are critical
+                flags,
                 stage_count: stages.len() as u32,
                 p_stages: stages.as_ptr(),
                 p_vertex_input_state: vertex_input_state
@@ -771,10 +832,18 @@ where
                     .map(|s| s as *const _)
                     .unwrap_or(ptr::null()),
                 layout: pipeline_layout.internal_object(),
-                render_pass: subpass.render_pass().internal_object(),
-                subpass: subpass.index(),
-                base_pipeline_handle: ash::vk::Pipeline::null(), // TODO:
-                base_pipeline_index: -1,                         // TODO:
+                render_pass: match &render_pass {
+                    PipelineRenderPassType::BeginRenderPass(subpass) => {
+                        subpass.render_pass().internal_object()
+                    }
+                    PipelineRenderPassType::BeginRendering(_) => ash::vk::RenderPass::null(),
+                },
+                subpass: match &render_pass {
+                    PipelineRenderPassType::BeginRenderPass(subpass) => subpass.index(),
+                    PipelineRenderPassType::BeginRendering(_) => 0,
+                },
+                base_pipeline_handle,
+                base_pipeline_index: -1,
                 ..Default::default()
             };
 
@@ -783,6 +852,16 @@ where
                 create_info.p_next = discard_rectangle_state as *const _ as *const _;
             }
 
+            if let Some(fragment_shading_rate_state) = fragment_shading_rate_state.as_mut() {
+                fragment_shading_rate_state.p_next = create_info.p_next;
+                create_info.p_next = fragment_shading_rate_state as *const _ as *const _;
+            }
+
+            if let Some(rendering_state) = rendering_state.as_mut() {
+                rendering_state.p_next = create_info.p_next;
+                create_info.p_next = rendering_state as *const _ as *const _;
+            }
+
             let cache_handle = match self.cache.as_ref() {
                 Some(cache) => cache.internal_object(),
                 None => ash::vk::PipelineCache::null(),
@@ -811,7 +890,7 @@ where
             handle,
             device: device.clone(),
             layout: pipeline_layout,
-            subpass,
+            render_pass,
             shaders: stages_info
                 .iter()
                 .map(|stage_info| (ShaderStage::from(*stage_info.entry_point.execution()), ()))
@@ -836,6 +915,11 @@ where
             } else {
                 None
             },
+            fragment_shading_rate_state: if fragment_shading_rate_state.is_some() {
+                Some(self.fragment_shading_rate_state)
+            } else {
+                None
+            },
             rasterization_state: self.rasterization_state,
             multisample_state: if multisample_state.is_some() {
                 Some(self.multisample_state)
@@ -853,6 +937,7 @@ where
                 None
             },
             dynamic_state: dynamic_state_modes,
+            allow_derivatives: self.allow_derivatives,
         }))
     }
 
@@ -862,8 +947,6 @@ where
 impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
 {
-    // TODO: add pipeline derivate system
-
     /// Sets the vertex shader to use.
     // TODO: correct specialization constants
     #[inline]
@@ -876,8 +959,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         Vss2: SpecializationConstants,
     {
         GraphicsPipelineBuilder {
-            subpass: self.subpass,
+            render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: Some((shader, specialization_constants)),
             tessellation_shaders: self.tessellation_shaders,
@@ -889,6 +974,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -911,8 +997,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         Tess2: SpecializationConstants,
     {
         GraphicsPipelineBuilder {
-            subpass: self.subpass,
+            render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: Some(TessellationShaders {
@@ -927,6 +1015,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -935,6 +1024,31 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     }
 
     /// Sets the geometry shader to use.
+    ///
+    /// The geometry shader is run once for each primitive, and can emit any number of primitives
+    /// in its place. The [`geometry_shader`](crate::device::Features::geometry_shader) feature
+    /// must be enabled on the device, and the input primitive type of the shader must match the
+    /// topology of the input assembly state, or the output of the tessellation shaders.
+    ///
+    /// A geometry shader can choose where each primitive it emits is rasterized:
+    ///
+    /// - Writing `gl_Layer` selects the layer of the framebuffer that the primitive is rendered
+    ///   to, which allows rendering to several layers of an image, such as the faces of a
+    ///   cubemap, in a single draw. The framebuffer must have been created with enough layers:
+    ///   rendering to a layer that doesn't exist is undefined. If the shader doesn't write
+    ///   `gl_Layer`, primitives are rendered to layer 0. With multiview, each view is rendered to
+    ///   the layer of its view index instead, and using a geometry shader requires the
+    ///   `multiview_geometry_shader` feature.
+    /// - Writing `gl_ViewportIndex` selects the viewport and scissor that the primitive is
+    ///   transformed and clipped with. The index must be less than the number of viewports of
+    ///   the viewport state, and using more than one viewport requires the
+    ///   [`multi_viewport`](crate::device::Features::multi_viewport) feature. If the shader
+    ///   doesn't write `gl_ViewportIndex`, viewport 0 is used.
+    ///
+    /// Both values apply to a whole primitive, so the shader should write the same value for
+    /// every vertex of a primitive it emits. Writing them from the vertex or tessellation
+    /// shaders instead requires the `shader_output_layer` and `shader_output_viewport_index`
+    /// features.
     // TODO: correct specialization constants
     #[inline]
     pub fn geometry_shader<'gs2, Gss2>(
@@ -946,8 +1060,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         Gss2: SpecializationConstants,
     {
         GraphicsPipelineBuilder {
-            subpass: self.subpass,
+            render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -959,6 +1075,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -980,8 +1097,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         Fss2: SpecializationConstants,
     {
         GraphicsPipelineBuilder {
-            subpass: self.subpass,
+            render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -993,6 +1112,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -1012,8 +1132,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         T: VertexDefinition,
     {
         GraphicsPipelineBuilder {
-            subpass: self.subpass,
+            render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -1025,6 +1147,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -1072,6 +1195,18 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
+    /// Sets the fragment shading rate state.
+    ///
+    /// The default value is [`FragmentShadingRateState::default()`].
+    #[inline]
+    pub fn fragment_shading_rate_state(
+        mut self,
+        fragment_shading_rate_state: FragmentShadingRateState,
+    ) -> Self {
+        self.fragment_shading_rate_state = fragment_shading_rate_state;
+        self
+    }
+
     /// Sets the rasterization state.
     ///
     /// The default value is [`RasterizationState::default()`].
@@ -1671,12 +1806,18 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
-    /// Sets the render pass subpass to use.
+    /// Sets the render pass that the pipeline will be used with.
+    ///
+    /// This can be either a [`Subpass`](crate::render_pass::Subpass) of a render pass object, or a
+    /// [`PipelineRenderingCreateInfo`](crate::pipeline::graphics::render_pass::PipelineRenderingCreateInfo)
+    /// describing the attachment formats for dynamic rendering.
     #[inline]
-    pub fn render_pass(self, subpass: Subpass) -> Self {
+    pub fn render_pass(self, render_pass: impl Into<PipelineRenderPassType>) -> Self {
         GraphicsPipelineBuilder {
-            subpass: Some(subpass),
+            render_pass: Some(render_pass.into()),
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -1693,6 +1834,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             color_blend_state: self.color_blend_state,
 
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
         }
     }
 
@@ -1706,6 +1848,27 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self.cache = Some(pipeline_cache);
         self
     }
+
+    /// Sets whether other pipelines can be derived from this pipeline with
+    /// [`base_pipeline`](Self::base_pipeline).
+    ///
+    /// The default value is `false`.
+    #[inline]
+    pub fn allow_derivatives(mut self, allow_derivatives: bool) -> Self {
+        self.allow_derivatives = allow_derivatives;
+        self
+    }
+
+    /// Creates the pipeline as a derivative of `base_pipeline`, which may make creating it faster
+    /// if the two pipelines are similar.
+    ///
+    /// `base_pipeline` must have been created with
+    /// [`allow_derivatives`](Self::allow_derivatives) set to `true`.
+    #[inline]
+    pub fn base_pipeline(mut self, base_pipeline: Arc<GraphicsPipeline>) -> Self {
+        self.base_pipeline = Some(base_pipeline);
+        self
+    }
 }
 
 impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss> Clone
@@ -1720,8 +1883,10 @@ where
 {
     fn clone(&self) -> Self {
         GraphicsPipelineBuilder {
-            subpass: self.subpass.clone(),
+            render_pass: self.render_pass.clone(),
             cache: self.cache.clone(),
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline.clone(),
 
             vertex_shader: self.vertex_shader.clone(),
             tessellation_shaders: self.tessellation_shaders.clone(),
@@ -1738,6 +1903,7 @@ where
             color_blend_state: self.color_blend_state.clone(),
 
             discard_rectangle_state: self.discard_rectangle_state.clone(),
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
         }
     }
 }
@@ -21,8 +21,8 @@
 //! formats, the logic operation is applied. For normalized integer formats, the logic operation
 //! will take precedence if it is activated, otherwise the blending operation is applied.
 
-use super::{DynamicState, GraphicsPipelineCreationError};
-use crate::{device::Device, pipeline::StateMode, render_pass::Subpass};
+use super::{render_pass::PipelineRenderPassType, DynamicState, GraphicsPipelineCreationError};
+use crate::{device::Device, pipeline::StateMode};
 use fnv::FnvHashMap;
 use smallvec::SmallVec;
 
@@ -138,7 +138,7 @@ impl ColorBlendState {
         &mut self, // TODO: make non-mut
         device: &Device,
         dynamic_state_modes: &mut FnvHashMap<DynamicState, bool>,
-        subpass: &Subpass,
+        render_pass: &PipelineRenderPassType,
     ) -> Result<
         (
             SmallVec<[ash::vk::PipelineColorBlendAttachmentState; 4]>,
@@ -146,7 +146,7 @@ impl ColorBlendState {
         ),
         GraphicsPipelineCreationError,
     > {
-        let num_atch = subpass.num_color_attachments();
+        let num_atch = render_pass.num_color_attachments();
 
         // If there is one element, duplicate it for all attachments.
         // TODO: this is undocumented and only exists for compatibility with some of the
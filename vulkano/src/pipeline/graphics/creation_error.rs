@@ -22,6 +22,21 @@ use std::u32;
 /// Error that can happen when creating a graphics pipeline.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GraphicsPipelineCreationError {
+    /// The base pipeline was not created with `allow_derivatives` set to `true`.
+    BasePipelineNotDerivable,
+
+    /// The format of a color attachment of a pipeline for dynamic rendering does not support
+    /// being used as a color attachment.
+    ColorAttachmentFormatUsageNotSupported { attachment_index: u32 },
+
+    /// The format of the depth attachment of a pipeline for dynamic rendering does not have a
+    /// depth aspect, or does not support being used as a depth/stencil attachment.
+    DepthAttachmentFormatUsageNotSupported,
+
+    /// The depth and stencil attachments of a pipeline for dynamic rendering were both provided,
+    /// but their formats were not equal.
+    DepthStencilAttachmentFormatMismatch,
+
     /// A device extension that was required for a particular setting on the graphics pipeline was not enabled.
     ExtensionNotEnabled {
         extension: &'static str,
@@ -38,12 +53,22 @@ pub enum GraphicsPipelineCreationError {
     /// expects.
     FragmentShaderRenderPassIncompatible,
 
+    /// A component of the fragment size of the fragment shading rate state was not 1, 2 or 4.
+    FragmentShadingRateInvalidFragmentSize { fragment_size: [u32; 2] },
+
+    /// A combiner operation of the fragment shading rate state was not `Keep` or `Replace`, but
+    /// the `fragment_shading_rate_non_trivial_combiner_ops` property is not supported.
+    FragmentShadingRateNonTrivialCombinerOpsNotSupported,
+
     /// The pipeline layout is not compatible with what the shaders expect.
     IncompatiblePipelineLayout(PipelineLayoutSupersetError),
 
     /// The provided specialization constants are not compatible with what the shader expects.
     IncompatibleSpecializationConstants,
 
+    /// A specialization map entry refers to bytes outside of the specialization data.
+    SpecializationMapEntryOutOfBounds { constant_id: u32 },
+
     /// The vertex definition is not compatible with the input of the vertex shader.
     IncompatibleVertexDefinition(IncompatibleVertexDefinitionError),
 
@@ -54,6 +79,14 @@ pub enum GraphicsPipelineCreationError {
     /// `patch_control_points` was not greater than 0 and less than or equal to the `max_tessellation_patch_size` limit.
     InvalidNumPatchControlPoints,
 
+    /// The maximum number of color attachments has been exceeded.
+    MaxColorAttachmentsExceeded {
+        /// Maximum allowed value.
+        max: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
     /// The maximum number of discard rectangles has been exceeded.
     MaxDiscardRectanglesExceeded {
         /// Maximum allowed value.
@@ -62,6 +95,14 @@ pub enum GraphicsPipelineCreationError {
         obtained: u32,
     },
 
+    /// The highest view index in the view mask exceeds the `max_multiview_view_count` limit.
+    MaxMultiviewViewCountExceeded {
+        /// Number of views implied by the view mask.
+        view_count: u32,
+        /// Maximum allowed value.
+        max: u32,
+    },
+
     /// The maximum value for the instance rate divisor has been exceeded.
     MaxVertexAttribDivisorExceeded {
         /// Index of the faulty binding.
@@ -140,6 +181,10 @@ pub enum GraphicsPipelineCreationError {
     /// The output interface of one shader and the input interface of the next shader do not match.
     ShaderStagesMismatch(ShaderInterfaceMismatchError),
 
+    /// The format of the stencil attachment of a pipeline for dynamic rendering does not have a
+    /// stencil aspect, or does not support being used as a depth/stencil attachment.
+    StencilAttachmentFormatUsageNotSupported,
+
     /// The [`strict_lines`](crate::device::Properties::strict_lines) device property was `false`.
     StrictLinesNotSupported,
 
@@ -196,6 +241,23 @@ impl fmt::Display for GraphicsPipelineCreationError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
+            Self::BasePipelineNotDerivable => write!(
+                fmt,
+                "the base pipeline was not created with allow_derivatives set to true",
+            ),
+            Self::ColorAttachmentFormatUsageNotSupported { attachment_index } => write!(
+                fmt,
+                "the format of color attachment {} does not support being used as a color attachment",
+                attachment_index,
+            ),
+            Self::DepthAttachmentFormatUsageNotSupported => write!(
+                fmt,
+                "the format of the depth attachment does not support being used as a depth attachment",
+            ),
+            Self::DepthStencilAttachmentFormatMismatch => write!(
+                fmt,
+                "the depth and stencil attachments have different formats",
+            ),
             Self::ExtensionNotEnabled { extension, reason } => write!(
                 fmt,
                 "the extension {} must be enabled: {}",
@@ -210,6 +272,15 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 fmt,
                 "the output of the fragment shader is not compatible with what the render pass subpass expects",
             ),
+            Self::FragmentShadingRateInvalidFragmentSize { fragment_size } => write!(
+                fmt,
+                "the fragment size {:?} of the fragment shading rate state is not valid",
+                fragment_size,
+            ),
+            Self::FragmentShadingRateNonTrivialCombinerOpsNotSupported => write!(
+                fmt,
+                "a fragment shading rate combiner operation was not Keep or Replace, but the fragment_shading_rate_non_trivial_combiner_ops property is not supported",
+            ),
             Self::IncompatiblePipelineLayout(_) => write!(
                 fmt,
                 "the pipeline layout is not compatible with what the shaders expect",
@@ -218,6 +289,11 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 fmt,
                 "the provided specialization constants are not compatible with what the shader expects",
             ),
+            Self::SpecializationMapEntryOutOfBounds { constant_id } => write!(
+                fmt,
+                "the specialization map entry for constant {} refers to bytes outside of the specialization data",
+                constant_id,
+            ),
             Self::IncompatibleVertexDefinition(_) => write!(
                 fmt,
                 "the vertex definition is not compatible with the input of the vertex shader",
@@ -230,10 +306,18 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 fmt,
                 "patch_control_points was not greater than 0 and less than or equal to the max_tessellation_patch_size limit",
             ),
+            Self::MaxColorAttachmentsExceeded { .. } => write!(
+                fmt,
+                "the maximum number of color attachments has been exceeded",
+            ),
             Self::MaxDiscardRectanglesExceeded { .. } => write!(
                 fmt,
                 "the maximum number of discard rectangles has been exceeded",
             ),
+            Self::MaxMultiviewViewCountExceeded { .. } => write!(
+                fmt,
+                "the highest view index in the view mask exceeds the max_multiview_view_count limit",
+            ),
             Self::MaxVertexAttribDivisorExceeded { .. } => write!(
                 fmt,
                 "the maximum value for the instance rate divisor has been exceeded",
@@ -286,6 +370,10 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 fmt,
                 "the output interface of one shader and the input interface of the next shader do not match",
             ),
+            Self::StencilAttachmentFormatUsageNotSupported => write!(
+                fmt,
+                "the format of the stencil attachment does not support being used as a stencil attachment",
+            ),
             Self::StrictLinesNotSupported => write!(
                 fmt,
                 "the strict_lines device property was false",
@@ -21,9 +21,9 @@
 //! depth and stencil tests, the value of the stencil buffer at that location can be updated.
 
 use crate::device::Device;
+use crate::pipeline::graphics::render_pass::PipelineRenderPassType;
 use crate::pipeline::graphics::GraphicsPipelineCreationError;
 use crate::pipeline::{DynamicState, StateMode};
-use crate::render_pass::Subpass;
 use fnv::FnvHashMap;
 use std::ops::RangeInclusive;
 use std::u32;
@@ -80,11 +80,11 @@ impl DepthStencilState {
         &self,
         device: &Device,
         dynamic_state_modes: &mut FnvHashMap<DynamicState, bool>,
-        subpass: &Subpass,
+        render_pass: &PipelineRenderPassType,
     ) -> Result<ash::vk::PipelineDepthStencilStateCreateInfo, GraphicsPipelineCreationError> {
         let (depth_test_enable, depth_write_enable, depth_compare_op) =
             if let Some(depth_state) = &self.depth {
-                if !subpass.has_depth() {
+                if !render_pass.has_depth() {
                     return Err(GraphicsPipelineCreationError::NoDepthAttachment);
                 }
 
@@ -102,7 +102,7 @@ impl DepthStencilState {
 
                 let write_enable = match depth_state.write_enable {
                     StateMode::Fixed(write_enable) => {
-                        if write_enable && !subpass.has_writable_depth() {
+                        if write_enable && !render_pass.has_writable_depth() {
                             return Err(GraphicsPipelineCreationError::NoDepthAttachment);
                         }
                         dynamic_state_modes.insert(DynamicState::DepthWriteEnable, false);
@@ -192,7 +192,7 @@ impl DepthStencilState {
         };
 
         let (stencil_test_enable, front, back) = if let Some(stencil_state) = &self.stencil {
-            if !subpass.has_stencil() {
+            if !render_pass.has_stencil() {
                 return Err(GraphicsPipelineCreationError::NoStencilAttachment);
             }
 
@@ -0,0 +1,355 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Controls the rate at which the fragment shader is invoked.
+//!
+//! By default, the fragment shader is invoked once for each pixel (or each sample when sample
+//! shading is used). With a fragment shading rate, a single invocation can cover a larger
+//! fragment of up to 4x4 pixels, which reduces the shading cost in areas where the loss of
+//! detail is not noticeable.
+//!
+//! The rate for a draw call is combined with the rate of the primitive being drawn, and then
+//! with the rate of the shading rate attachment, using the combiner operations.
+
+use crate::device::Device;
+use crate::pipeline::graphics::GraphicsPipelineCreationError;
+use crate::pipeline::{DynamicState, StateMode};
+use fnv::FnvHashMap;
+
+/// The state in a graphics pipeline describing the fragment shading rate.
+#[derive(Clone, Copy, Debug)]
+pub struct FragmentShadingRateState {
+    /// The size of the fragments that are shaded with a single invocation, for the draw calls
+    /// using the pipeline. Each component must be 1, 2 or 4.
+    ///
+    /// If set to `Dynamic`, the fragment size and `combiner_ops` are both set with
+    /// [`set_fragment_shading_rate`] while recording a command buffer, and `combiner_ops` is
+    /// ignored.
+    ///
+    /// If set to `Dynamic` or to a value other than `[1, 1]`, the
+    /// [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// [`set_fragment_shading_rate`]: crate::command_buffer::AutoCommandBufferBuilder::set_fragment_shading_rate
+    pub fragment_size: StateMode<[u32; 2]>,
+
+    /// The operations used to combine the pipeline rate with the rate of the primitive, and the
+    /// result with the rate of the shading rate attachment.
+    ///
+    /// If the first operation is not `Keep`, the
+    /// [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    /// feature must be enabled on the device. If the second operation is not `Keep`, the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    pub combiner_ops: [FragmentShadingRateCombinerOp; 2],
+}
+
+impl FragmentShadingRateState {
+    /// Creates a `FragmentShadingRateState` with a fragment size of `[1, 1]` and the `Keep`
+    /// combiner operations, which doesn't change the shading rate.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            fragment_size: StateMode::Fixed([1, 1]),
+            combiner_ops: [FragmentShadingRateCombinerOp::Keep; 2],
+        }
+    }
+
+    /// Sets the fragment size and combiner operations to dynamic.
+    #[inline]
+    pub fn dynamic(mut self) -> Self {
+        self.fragment_size = StateMode::Dynamic;
+        self
+    }
+
+    pub(crate) fn to_vulkan(
+        &self,
+        device: &Device,
+        dynamic_state_modes: &mut FnvHashMap<DynamicState, bool>,
+    ) -> Result<
+        Option<ash::vk::PipelineFragmentShadingRateStateCreateInfoKHR>,
+        GraphicsPipelineCreationError,
+    > {
+        let fragment_size = match self.fragment_size {
+            StateMode::Fixed(fragment_size) => {
+                dynamic_state_modes.insert(DynamicState::FragmentShadingRate, false);
+
+                if fragment_size == [1, 1]
+                    && self.combiner_ops == [FragmentShadingRateCombinerOp::Keep; 2]
+                {
+                    return Ok(None);
+                }
+
+                if !fragment_size.iter().all(|&size| matches!(size, 1 | 2 | 4)) {
+                    return Err(
+                        GraphicsPipelineCreationError::FragmentShadingRateInvalidFragmentSize {
+                            fragment_size,
+                        },
+                    );
+                }
+
+                fragment_size
+            }
+            StateMode::Dynamic => {
+                dynamic_state_modes.insert(DynamicState::FragmentShadingRate, true);
+                [1, 1]
+            }
+        };
+
+        if !device.enabled_extensions().khr_fragment_shading_rate {
+            return Err(GraphicsPipelineCreationError::ExtensionNotEnabled {
+                extension: "khr_fragment_shading_rate",
+                reason: "FragmentShadingRateState was not the default value",
+            });
+        }
+
+        if (matches!(self.fragment_size, StateMode::Dynamic) || fragment_size != [1, 1])
+            && !device.enabled_features().pipeline_fragment_shading_rate
+        {
+            return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                feature: "pipeline_fragment_shading_rate",
+                reason: "FragmentShadingRateState::fragment_size was dynamic or not [1, 1]",
+            });
+        }
+
+        if matches!(self.fragment_size, StateMode::Fixed(_)) {
+            if self.combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+                && !device.enabled_features().primitive_fragment_shading_rate
+            {
+                return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                    feature: "primitive_fragment_shading_rate",
+                    reason: "FragmentShadingRateState::combiner_ops[0] was not Keep",
+                });
+            }
+
+            if self.combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+                && !device.enabled_features().attachment_fragment_shading_rate
+            {
+                return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                    feature: "attachment_fragment_shading_rate",
+                    reason: "FragmentShadingRateState::combiner_ops[1] was not Keep",
+                });
+            }
+
+            if !device
+                .physical_device()
+                .properties()
+                .fragment_shading_rate_non_trivial_combiner_ops
+                .unwrap_or(false)
+                && self.combiner_ops.iter().any(|op| op.is_non_trivial())
+            {
+                return Err(GraphicsPipelineCreationError::FragmentShadingRateNonTrivialCombinerOpsNotSupported);
+            }
+        }
+
+        Ok(Some(
+            ash::vk::PipelineFragmentShadingRateStateCreateInfoKHR {
+                fragment_size: ash::vk::Extent2D {
+                    width: fragment_size[0],
+                    height: fragment_size[1],
+                },
+                combiner_ops: [self.combiner_ops[0].into(), self.combiner_ops[1].into()],
+                ..Default::default()
+            },
+        ))
+    }
+}
+
+impl Default for FragmentShadingRateState {
+    /// Returns [`FragmentShadingRateState::new`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An operation that combines two fragment shading rates.
+///
+/// The rate that is already determined is called `A`, and the rate that is being combined with
+/// it is called `B`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum FragmentShadingRateCombinerOp {
+    /// The result is `A`.
+    Keep = ash::vk::FragmentShadingRateCombinerOpKHR::KEEP.as_raw(),
+
+    /// The result is `B`.
+    Replace = ash::vk::FragmentShadingRateCombinerOpKHR::REPLACE.as_raw(),
+
+    /// The result is the smaller of `A` and `B`, for each dimension.
+    Min = ash::vk::FragmentShadingRateCombinerOpKHR::MIN.as_raw(),
+
+    /// The result is the larger of `A` and `B`, for each dimension.
+    Max = ash::vk::FragmentShadingRateCombinerOpKHR::MAX.as_raw(),
+
+    /// The result is the product of `A` and `B`, for each dimension.
+    Mul = ash::vk::FragmentShadingRateCombinerOpKHR::MUL.as_raw(),
+}
+
+impl FragmentShadingRateCombinerOp {
+    /// Returns true if the operation is not `Keep` or `Replace`. These operations require the
+    /// `fragment_shading_rate_non_trivial_combiner_ops` property to be supported.
+    #[inline]
+    pub(crate) fn is_non_trivial(&self) -> bool {
+        !matches!(self, Self::Keep | Self::Replace)
+    }
+}
+
+impl From<FragmentShadingRateCombinerOp> for ash::vk::FragmentShadingRateCombinerOpKHR {
+    #[inline]
+    fn from(val: FragmentShadingRateCombinerOp) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Same as `gfx_dev_and_queue_with!`, with the `khr_fragment_shading_rate` extension and the
+    /// given features enabled.
+    macro_rules! fragment_shading_rate_dev_and_queue {
+        ($($feature:ident),*) => {
+            gfx_dev_and_queue_with!(
+                [khr_create_renderpass2, khr_fragment_shading_rate],
+                [$($feature),*]
+            )
+        };
+    }
+
+    #[test]
+    fn default_is_none() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut dynamic_state_modes = FnvHashMap::default();
+        let state = FragmentShadingRateState::new()
+            .to_vulkan(&device, &mut dynamic_state_modes)
+            .unwrap();
+        assert!(state.is_none());
+    }
+
+    #[test]
+    fn invalid_fragment_size() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let state = FragmentShadingRateState {
+            fragment_size: StateMode::Fixed([3, 1]),
+            ..FragmentShadingRateState::new()
+        };
+
+        match state.to_vulkan(&device, &mut FnvHashMap::default()) {
+            Err(GraphicsPipelineCreationError::FragmentShadingRateInvalidFragmentSize {
+                fragment_size: [3, 1],
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let state = FragmentShadingRateState {
+            fragment_size: StateMode::Fixed([2, 2]),
+            ..FragmentShadingRateState::new()
+        };
+
+        match state.to_vulkan(&device, &mut FnvHashMap::default()) {
+            Err(GraphicsPipelineCreationError::ExtensionNotEnabled {
+                extension: "khr_fragment_shading_rate",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn fragment_size_missing_feature() {
+        let (device, _) = fragment_shading_rate_dev_and_queue!();
+
+        let state = FragmentShadingRateState {
+            fragment_size: StateMode::Fixed([2, 2]),
+            ..FragmentShadingRateState::new()
+        };
+
+        match state.to_vulkan(&device, &mut FnvHashMap::default()) {
+            Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                feature: "pipeline_fragment_shading_rate",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+
+        match FragmentShadingRateState::new()
+            .dynamic()
+            .to_vulkan(&device, &mut FnvHashMap::default())
+        {
+            Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                feature: "pipeline_fragment_shading_rate",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn combiner_ops_missing_feature() {
+        let (device, _) = fragment_shading_rate_dev_and_queue!();
+
+        let state = FragmentShadingRateState {
+            combiner_ops: [
+                FragmentShadingRateCombinerOp::Replace,
+                FragmentShadingRateCombinerOp::Keep,
+            ],
+            ..FragmentShadingRateState::new()
+        };
+
+        match state.to_vulkan(&device, &mut FnvHashMap::default()) {
+            Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                feature: "primitive_fragment_shading_rate",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+
+        let state = FragmentShadingRateState {
+            combiner_ops: [
+                FragmentShadingRateCombinerOp::Keep,
+                FragmentShadingRateCombinerOp::Replace,
+            ],
+            ..FragmentShadingRateState::new()
+        };
+
+        match state.to_vulkan(&device, &mut FnvHashMap::default()) {
+            Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                feature: "attachment_fragment_shading_rate",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn combiner_ops_without_pipeline_feature() {
+        let (device, _) = fragment_shading_rate_dev_and_queue!(primitive_fragment_shading_rate);
+
+        let state = FragmentShadingRateState {
+            combiner_ops: [
+                FragmentShadingRateCombinerOp::Replace,
+                FragmentShadingRateCombinerOp::Keep,
+            ],
+            ..FragmentShadingRateState::new()
+        };
+
+        assert!(state
+            .to_vulkan(&device, &mut FnvHashMap::default())
+            .unwrap()
+            .is_some());
+    }
+}
@@ -62,9 +62,11 @@ use crate::device::{Device, DeviceOwned};
 use crate::pipeline::graphics::color_blend::ColorBlendState;
 use crate::pipeline::graphics::depth_stencil::DepthStencilState;
 use crate::pipeline::graphics::discard_rectangle::DiscardRectangleState;
+use crate::pipeline::graphics::fragment_shading_rate::FragmentShadingRateState;
 use crate::pipeline::graphics::input_assembly::InputAssemblyState;
 use crate::pipeline::graphics::multisample::MultisampleState;
 use crate::pipeline::graphics::rasterization::RasterizationState;
+use crate::pipeline::graphics::render_pass::PipelineRenderPassType;
 use crate::pipeline::graphics::tessellation::TessellationState;
 use crate::pipeline::graphics::vertex_input::VertexInputState;
 use crate::pipeline::graphics::viewport::ViewportState;
@@ -85,9 +87,11 @@ pub mod color_blend;
 mod creation_error;
 pub mod depth_stencil;
 pub mod discard_rectangle;
+pub mod fragment_shading_rate;
 pub mod input_assembly;
 pub mod multisample;
 pub mod rasterization;
+pub mod render_pass;
 pub mod tessellation;
 pub mod vertex_input;
 pub mod viewport;
@@ -102,7 +106,7 @@ pub struct GraphicsPipeline {
     handle: ash::vk::Pipeline,
     device: Arc<Device>,
     layout: Arc<PipelineLayout>,
-    subpass: Subpass,
+    render_pass: PipelineRenderPassType,
     // TODO: replace () with an object that describes the shaders in some way.
     shaders: FnvHashMap<ShaderStage, ()>,
     descriptor_requirements: FnvHashMap<(u32, u32), DescriptorRequirements>,
@@ -113,11 +117,13 @@ pub struct GraphicsPipeline {
     tessellation_state: Option<TessellationState>,
     viewport_state: Option<ViewportState>,
     discard_rectangle_state: Option<DiscardRectangleState>,
+    fragment_shading_rate_state: Option<FragmentShadingRateState>,
     rasterization_state: RasterizationState,
     multisample_state: Option<MultisampleState>,
     depth_stencil_state: Option<DepthStencilState>,
     color_blend_state: Option<ColorBlendState>,
     dynamic_state: FnvHashMap<DynamicState, bool>,
+    allow_derivatives: bool,
 }
 
 impl GraphicsPipeline {
@@ -145,10 +151,21 @@ impl GraphicsPipeline {
         &self.device
     }
 
+    /// Returns the render pass this graphics pipeline is rendering to.
+    #[inline]
+    pub fn render_pass(&self) -> &PipelineRenderPassType {
+        &self.render_pass
+    }
+
     /// Returns the subpass this graphics pipeline is rendering to.
+    ///
+    /// Returns `None` if the pipeline was created for dynamic rendering.
     #[inline]
-    pub fn subpass(&self) -> &Subpass {
-        &self.subpass
+    pub fn subpass(&self) -> Option<&Subpass> {
+        match &self.render_pass {
+            PipelineRenderPassType::BeginRenderPass(subpass) => Some(subpass),
+            PipelineRenderPassType::BeginRendering(_) => None,
+        }
     }
 
     /// Returns information about a particular shader.
@@ -202,6 +219,12 @@ impl GraphicsPipeline {
         self.discard_rectangle_state.as_ref()
     }
 
+    /// Returns the fragment shading rate state used to create this pipeline.
+    #[inline]
+    pub fn fragment_shading_rate_state(&self) -> Option<&FragmentShadingRateState> {
+        self.fragment_shading_rate_state.as_ref()
+    }
+
     /// Returns the rasterization state used to create this pipeline.
     #[inline]
     pub fn rasterization_state(&self) -> &RasterizationState {
@@ -238,6 +261,12 @@ impl GraphicsPipeline {
     pub fn dynamic_states(&self) -> impl ExactSizeIterator<Item = (DynamicState, bool)> + '_ {
         self.dynamic_state.iter().map(|(k, v)| (*k, *v))
     }
+
+    /// Returns whether other pipelines can be derived from this pipeline.
+    #[inline]
+    pub fn allow_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
 }
 
 impl Pipeline for GraphicsPipeline {
@@ -12,9 +12,9 @@
 
 use crate::device::Device;
 use crate::image::SampleCount;
+use crate::pipeline::graphics::render_pass::PipelineRenderPassType;
 use crate::pipeline::graphics::GraphicsPipelineCreationError;
 use crate::pipeline::DynamicState;
-use crate::render_pass::Subpass;
 use fnv::FnvHashMap;
 use std::ptr;
 
@@ -78,9 +78,12 @@ impl MultisampleState {
         &self,
         device: &Device,
         dynamic_state_modes: &mut FnvHashMap<DynamicState, bool>,
-        subpass: &Subpass,
+        render_pass: &PipelineRenderPassType,
     ) -> Result<ash::vk::PipelineMultisampleStateCreateInfo, GraphicsPipelineCreationError> {
-        let rasterization_samples = subpass.num_samples().unwrap_or(SampleCount::Sample1).into();
+        let rasterization_samples = render_pass
+            .num_samples()
+            .unwrap_or(SampleCount::Sample1)
+            .into();
 
         let (sample_shading_enable, min_sample_shading) =
             if let Some(min_sample_shading) = self.sample_shading {
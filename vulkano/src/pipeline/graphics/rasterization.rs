@@ -24,6 +24,16 @@ pub struct RasterizationState {
     /// enabled on the device.
     pub depth_clamp_enable: bool,
 
+    /// Controls whether fragments whose depth is outside of the range [0.0, 1.0] are discarded,
+    /// independently of `depth_clamp_enable`.
+    ///
+    /// If set to `None`, depth clipping is enabled when `depth_clamp_enable` is false, and
+    /// disabled when it is true. If set to `Some`, the
+    /// [`ext_depth_clip_enable`](crate::device::DeviceExtensions::ext_depth_clip_enable)
+    /// extension and the [`depth_clip_enable`](crate::device::Features::depth_clip_enable)
+    /// feature must be enabled on the device.
+    pub depth_clip_enable: Option<bool>,
+
     /// If true, all the fragments will be discarded, and the fragment shader will not be run. This
     /// is usually used when your vertex shader has some side effects and you don't need to run the
     /// fragment shader.
@@ -85,12 +95,13 @@ pub struct RasterizationState {
 
 impl RasterizationState {
     /// Creates a `RasterizationState` with depth clamping, discard, depth biasing and line
-    /// stippling disabled, filled polygons, no culling, counterclockwise front face, and the
-    /// default line width and line rasterization mode.
+    /// stippling disabled, default depth clipping, filled polygons, no culling, counterclockwise
+    /// front face, and the default line width and line rasterization mode.
     #[inline]
     pub fn new() -> Self {
         Self {
             depth_clamp_enable: false,
+            depth_clip_enable: None,
             rasterizer_discard_enable: StateMode::Fixed(false),
             polygon_mode: Default::default(),
             cull_mode: StateMode::Fixed(Default::default()),
@@ -270,11 +281,46 @@ impl RasterizationState {
         })
     }
 
+    pub(crate) fn to_vulkan_depth_clip_state(
+        &self,
+        device: &Device,
+    ) -> Result<
+        Option<ash::vk::PipelineRasterizationDepthClipStateCreateInfoEXT>,
+        GraphicsPipelineCreationError,
+    > {
+        Ok(if let Some(depth_clip_enable) = self.depth_clip_enable {
+            if !device.enabled_extensions().ext_depth_clip_enable {
+                return Err(GraphicsPipelineCreationError::ExtensionNotEnabled {
+                    extension: "ext_depth_clip_enable",
+                    reason: "RasterizationState::depth_clip_enable was not None",
+                });
+            }
+
+            if !device.enabled_features().depth_clip_enable {
+                return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                    feature: "depth_clip_enable",
+                    reason: "RasterizationState::depth_clip_enable was not None",
+                });
+            }
+
+            Some(ash::vk::PipelineRasterizationDepthClipStateCreateInfoEXT {
+                flags: ash::vk::PipelineRasterizationDepthClipStateCreateFlagsEXT::empty(),
+                depth_clip_enable: depth_clip_enable as ash::vk::Bool32,
+                ..Default::default()
+            })
+        } else {
+            None
+        })
+    }
+
     pub(crate) fn to_vulkan(
         &self,
         device: &Device,
         dynamic_state_modes: &mut FnvHashMap<DynamicState, bool>,
         rasterization_line_state: Option<&mut ash::vk::PipelineRasterizationLineStateCreateInfoEXT>,
+        rasterization_depth_clip_state: Option<
+            &mut ash::vk::PipelineRasterizationDepthClipStateCreateInfoEXT,
+        >,
     ) -> Result<ash::vk::PipelineRasterizationStateCreateInfo, GraphicsPipelineCreationError> {
         if self.depth_clamp_enable && !device.enabled_features().depth_clamp {
             return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
@@ -355,7 +401,7 @@ impl RasterizationState {
                         reason: "DepthBiasState::enable_dynamic was true",
                     });
                 }
-                dynamic_state_modes.insert(DynamicState::DepthTestEnable, true);
+                dynamic_state_modes.insert(DynamicState::DepthBiasEnable, true);
             } else {
                 dynamic_state_modes.insert(DynamicState::DepthBiasEnable, false);
             }
@@ -419,6 +465,11 @@ impl RasterizationState {
             rasterization_state.p_next = rasterization_line_state as *const _ as *const _;
         }
 
+        if let Some(rasterization_depth_clip_state) = rasterization_depth_clip_state {
+            rasterization_depth_clip_state.p_next = rasterization_state.p_next;
+            rasterization_state.p_next = rasterization_depth_clip_state as *const _ as *const _;
+        }
+
         Ok(rasterization_state)
     }
 }
@@ -604,3 +655,86 @@ pub struct LineStipple {
     /// The bit pattern used in stippled line rasterization.
     pub pattern: u16,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth_clip_missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let state = RasterizationState {
+            depth_clip_enable: Some(false),
+            ..RasterizationState::new()
+        };
+
+        match state.to_vulkan_depth_clip_state(&device) {
+            Err(GraphicsPipelineCreationError::ExtensionNotEnabled {
+                extension: "ext_depth_clip_enable",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn depth_clip_missing_feature() {
+        let (device, _) = gfx_dev_and_queue_with!([ext_depth_clip_enable], []);
+
+        let state = RasterizationState {
+            depth_clip_enable: Some(false),
+            ..RasterizationState::new()
+        };
+
+        match state.to_vulkan_depth_clip_state(&device) {
+            Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                feature: "depth_clip_enable",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn depth_clip_default() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(RasterizationState::new()
+            .to_vulkan_depth_clip_state(&device)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn depth_bias_enable_dynamic() {
+        let (device, _) =
+            gfx_dev_and_queue_with!([ext_extended_dynamic_state2], [extended_dynamic_state2]);
+
+        let state = RasterizationState {
+            depth_bias: Some(DepthBiasState {
+                enable_dynamic: true,
+                bias: StateMode::Fixed(DepthBias {
+                    constant_factor: 1.0,
+                    clamp: 0.0,
+                    slope_factor: 1.0,
+                }),
+            }),
+            ..RasterizationState::new()
+        };
+
+        let mut dynamic_state_modes = FnvHashMap::default();
+        state
+            .to_vulkan(&device, &mut dynamic_state_modes, None, None)
+            .unwrap();
+
+        assert_eq!(
+            dynamic_state_modes.get(&DynamicState::DepthBiasEnable),
+            Some(&true)
+        );
+        assert_eq!(
+            dynamic_state_modes.get(&DynamicState::DepthTestEnable),
+            None
+        );
+    }
+}
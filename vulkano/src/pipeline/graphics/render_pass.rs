@@ -0,0 +1,280 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Configures the render pass that a graphics pipeline is used with.
+//!
+//! A graphics pipeline is either created for a subpass of a render pass object, in which case it
+//! can only be used inside that subpass or a compatible one, or for dynamic rendering, in which
+//! case only the formats of the attachments need to be known when creating the pipeline.
+
+use crate::device::Device;
+use crate::format::Format;
+use crate::image::SampleCount;
+use crate::pipeline::graphics::GraphicsPipelineCreationError;
+use crate::render_pass::Subpass;
+use crate::shader::ShaderInterface;
+
+/// The type of render pass that a graphics pipeline is created for.
+#[derive(Clone, Debug)]
+pub enum PipelineRenderPassType {
+    /// The pipeline is used inside a subpass of a render pass object, which is started with
+    /// `begin_render_pass`.
+    BeginRenderPass(Subpass),
+
+    /// The pipeline is used with dynamic rendering, which is started with `begin_rendering`.
+    BeginRendering(PipelineRenderingCreateInfo),
+}
+
+impl PipelineRenderPassType {
+    /// Returns the number of color attachments.
+    #[inline]
+    pub(crate) fn num_color_attachments(&self) -> u32 {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass.num_color_attachments(),
+            Self::BeginRendering(info) => info.color_attachment_formats.len() as u32,
+        }
+    }
+
+    /// Returns true if there is an attachment with a depth aspect.
+    #[inline]
+    pub(crate) fn has_depth(&self) -> bool {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass.has_depth(),
+            Self::BeginRendering(info) => info.depth_attachment_format.is_some(),
+        }
+    }
+
+    /// Returns true if there is an attachment with a depth aspect that can be written to.
+    #[inline]
+    pub(crate) fn has_writable_depth(&self) -> bool {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass.has_writable_depth(),
+            Self::BeginRendering(info) => info.depth_attachment_format.is_some(),
+        }
+    }
+
+    /// Returns true if there is an attachment with a stencil aspect.
+    #[inline]
+    pub(crate) fn has_stencil(&self) -> bool {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass.has_stencil(),
+            Self::BeginRendering(info) => info.stencil_attachment_format.is_some(),
+        }
+    }
+
+    /// Returns true if there is any depth or stencil attachment.
+    #[inline]
+    pub(crate) fn has_depth_stencil_attachment(&self) -> bool {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass.has_depth_stencil_attachment(),
+            Self::BeginRendering(info) => {
+                info.depth_attachment_format.is_some() || info.stencil_attachment_format.is_some()
+            }
+        }
+    }
+
+    /// Returns the number of samples of the attachments, or `None` if it is not known.
+    ///
+    /// Dynamic rendering currently only supports single-sampled attachments.
+    #[inline]
+    pub(crate) fn num_samples(&self) -> Option<SampleCount> {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass.num_samples(),
+            Self::BeginRendering(_) => Some(SampleCount::Sample1),
+        }
+    }
+
+    /// Returns true if multiview rendering is enabled and renders to at least one view.
+    #[inline]
+    pub(crate) fn has_multiview(&self) -> bool {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass
+                .render_pass()
+                .desc()
+                .multiview()
+                .as_ref()
+                .map_or(false, |multiview| multiview.used_layer_count() > 0),
+            Self::BeginRendering(info) => info.view_mask != 0,
+        }
+    }
+
+    /// Returns true if the color attachments can accept the outputs of a fragment shader with
+    /// the given output interface.
+    pub(crate) fn is_compatible_with(&self, shader_interface: &ShaderInterface) -> bool {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass.is_compatible_with(shader_interface),
+            Self::BeginRendering(info) => shader_interface.elements().iter().all(|element| {
+                assert!(!element.ty.is_64bit); // TODO: implement
+                (element.location..element.location + element.ty.num_locations()).all(|location| {
+                    matches!(
+                        info.color_attachment_formats.get(location as usize),
+                        Some(Some(_))
+                    )
+                })
+            }),
+        }
+    }
+}
+
+impl From<Subpass> for PipelineRenderPassType {
+    #[inline]
+    fn from(subpass: Subpass) -> Self {
+        Self::BeginRenderPass(subpass)
+    }
+}
+
+impl From<PipelineRenderingCreateInfo> for PipelineRenderPassType {
+    #[inline]
+    fn from(info: PipelineRenderingCreateInfo) -> Self {
+        Self::BeginRendering(info)
+    }
+}
+
+/// The attachment formats of a graphics pipeline that is used with dynamic rendering.
+///
+/// Using dynamic rendering requires the
+/// [`dynamic_rendering`](crate::device::Features::dynamic_rendering) feature to be enabled on the
+/// device.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PipelineRenderingCreateInfo {
+    /// If not `0`, multiview rendering is used, and the bits that are set indicate the views that
+    /// are rendered to.
+    ///
+    /// If not `0`, the [`multiview`](crate::device::Features::multiview) feature must be enabled
+    /// on the device.
+    pub view_mask: u32,
+
+    /// The formats of the color attachments, one for each attachment index. `None` indicates
+    /// that no attachment will be bound to that index.
+    pub color_attachment_formats: Vec<Option<Format>>,
+
+    /// The format of the depth attachment, or `None` if there is no depth attachment.
+    pub depth_attachment_format: Option<Format>,
+
+    /// The format of the stencil attachment, or `None` if there is no stencil attachment.
+    ///
+    /// If both this and `depth_attachment_format` are `Some`, they must be equal.
+    pub stencil_attachment_format: Option<Format>,
+}
+
+impl PipelineRenderingCreateInfo {
+    pub(crate) fn to_vulkan_formats(&self) -> Vec<ash::vk::Format> {
+        self.color_attachment_formats
+            .iter()
+            .map(|format| format.map_or(ash::vk::Format::UNDEFINED, Into::into))
+            .collect()
+    }
+
+    pub(crate) fn to_vulkan(
+        &self,
+        device: &Device,
+        color_attachment_formats: &[ash::vk::Format],
+    ) -> Result<ash::vk::PipelineRenderingCreateInfoKHR, GraphicsPipelineCreationError> {
+        if !device.enabled_features().dynamic_rendering {
+            return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                feature: "dynamic_rendering",
+                reason: "the pipeline was created for dynamic rendering",
+            });
+        }
+
+        let properties = device.physical_device().properties();
+
+        if self.view_mask != 0 {
+            if !device.enabled_features().multiview {
+                return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                    feature: "multiview",
+                    reason: "PipelineRenderingCreateInfo::view_mask was not 0",
+                });
+            }
+
+            let view_count = u32::BITS - self.view_mask.leading_zeros();
+            let max = properties.max_multiview_view_count.unwrap_or(0);
+
+            if view_count > max {
+                return Err(
+                    GraphicsPipelineCreationError::MaxMultiviewViewCountExceeded {
+                        view_count,
+                        max,
+                    },
+                );
+            }
+        }
+
+        if self.color_attachment_formats.len() as u32 > properties.max_color_attachments {
+            return Err(GraphicsPipelineCreationError::MaxColorAttachmentsExceeded {
+                max: properties.max_color_attachments,
+                obtained: self.color_attachment_formats.len() as u32,
+            });
+        }
+
+        for (attachment_index, format) in self.color_attachment_formats.iter().enumerate() {
+            if let Some(format) = format {
+                if !device
+                    .physical_device()
+                    .format_properties(*format)
+                    .optimal_tiling_features
+                    .color_attachment
+                {
+                    return Err(
+                        GraphicsPipelineCreationError::ColorAttachmentFormatUsageNotSupported {
+                            attachment_index: attachment_index as u32,
+                        },
+                    );
+                }
+            }
+        }
+
+        if let Some(format) = self.depth_attachment_format {
+            if !format.aspects().depth
+                || !device
+                    .physical_device()
+                    .format_properties(format)
+                    .optimal_tiling_features
+                    .depth_stencil_attachment
+            {
+                return Err(GraphicsPipelineCreationError::DepthAttachmentFormatUsageNotSupported);
+            }
+        }
+
+        if let Some(format) = self.stencil_attachment_format {
+            if !format.aspects().stencil
+                || !device
+                    .physical_device()
+                    .format_properties(format)
+                    .optimal_tiling_features
+                    .depth_stencil_attachment
+            {
+                return Err(
+                    GraphicsPipelineCreationError::StencilAttachmentFormatUsageNotSupported,
+                );
+            }
+        }
+
+        if let (Some(depth_format), Some(stencil_format)) =
+            (self.depth_attachment_format, self.stencil_attachment_format)
+        {
+            if depth_format != stencil_format {
+                return Err(GraphicsPipelineCreationError::DepthStencilAttachmentFormatMismatch);
+            }
+        }
+
+        Ok(ash::vk::PipelineRenderingCreateInfoKHR {
+            view_mask: self.view_mask,
+            color_attachment_count: color_attachment_formats.len() as u32,
+            p_color_attachment_formats: color_attachment_formats.as_ptr(),
+            depth_attachment_format: self
+                .depth_attachment_format
+                .map_or(ash::vk::Format::UNDEFINED, Into::into),
+            stencil_attachment_format: self
+                .stencil_attachment_format
+                .map_or(ash::vk::Format::UNDEFINED, Into::into),
+            ..Default::default()
+        })
+    }
+}
@@ -562,6 +562,7 @@ fn check_desc_against_limits(
     let mut num_sampled_images = Counter::default();
     let mut num_storage_images = Counter::default();
     let mut num_input_attachments = Counter::default();
+    let mut num_acceleration_structures = Counter::default();
 
     for set in descriptor_set_layouts {
         for descriptor in (0..set.num_bindings()).filter_map(|i| set.descriptor(i).map(|d| d)) {
@@ -600,6 +601,10 @@ fn check_desc_against_limits(
                     num_input_attachments
                         .increment(descriptor.descriptor_count, &descriptor.stages);
                 }
+                DescriptorType::AccelerationStructure => {
+                    num_acceleration_structures
+                        .increment(descriptor.descriptor_count, &descriptor.stages);
+                }
             }
         }
     }
@@ -669,6 +674,16 @@ fn check_desc_against_limits(
             },
         );
     }
+    if let Some(limit) = properties.max_per_stage_descriptor_acceleration_structures {
+        if num_acceleration_structures.max_per_stage() > limit {
+            return Err(
+                PipelineLayoutLimitsError::MaxPerStageDescriptorAccelerationStructuresLimitExceeded {
+                    limit,
+                    requested: num_acceleration_structures.max_per_stage(),
+                },
+            );
+        }
+    }
 
     if num_samplers.total > properties.max_descriptor_set_samplers {
         return Err(
@@ -734,6 +749,16 @@ fn check_desc_against_limits(
             },
         );
     }
+    if let Some(limit) = properties.max_descriptor_set_acceleration_structures {
+        if num_acceleration_structures.total > limit {
+            return Err(
+                PipelineLayoutLimitsError::MaxDescriptorSetAccelerationStructuresLimitExceeded {
+                    limit,
+                    requested: num_acceleration_structures.total,
+                },
+            );
+        }
+    }
 
     for &PipelineLayoutPcRange { offset, size, .. } in push_constants_ranges {
         if offset + size > properties.max_push_constants_size {
@@ -885,6 +910,22 @@ pub enum PipelineLayoutLimitsError {
         /// What was requested.
         requested: u32,
     },
+
+    /// The `max_per_stage_descriptor_acceleration_structures()` limit has been exceeded.
+    MaxPerStageDescriptorAccelerationStructuresLimitExceeded {
+        /// The limit that must be fulfilled.
+        limit: u32,
+        /// What was requested.
+        requested: u32,
+    },
+
+    /// The `max_descriptor_set_acceleration_structures()` limit has been exceeded.
+    MaxDescriptorSetAccelerationStructuresLimitExceeded {
+        /// The limit that must be fulfilled.
+        limit: u32,
+        /// What was requested.
+        requested: u32,
+    },
 }
 
 impl error::Error for PipelineLayoutLimitsError {}
@@ -959,6 +1000,12 @@ impl fmt::Display for PipelineLayoutLimitsError {
                 } => {
                     "the `max_descriptor_set_input_attachments()` limit has been exceeded"
                 }
+                PipelineLayoutLimitsError::MaxPerStageDescriptorAccelerationStructuresLimitExceeded {
+                    ..
+                } => "the `max_per_stage_descriptor_acceleration_structures()` limit has been exceeded",
+                PipelineLayoutLimitsError::MaxDescriptorSetAccelerationStructuresLimitExceeded {
+                    ..
+                } => "the `max_descriptor_set_acceleration_structures()` limit has been exceeded",
             }
         )
     }
@@ -20,6 +20,7 @@
 pub use self::compute::ComputePipeline;
 pub use self::graphics::GraphicsPipeline;
 pub use self::layout::PipelineLayout;
+pub use self::ray_tracing::RayTracingPipeline;
 use crate::device::DeviceOwned;
 use std::sync::Arc;
 
@@ -27,6 +28,7 @@ pub mod cache;
 pub mod compute;
 pub mod graphics;
 pub mod layout;
+pub mod ray_tracing;
 
 /// A trait for operations shared between pipeline types.
 pub trait Pipeline: DeviceOwned {
@@ -52,6 +54,7 @@ pub trait Pipeline: DeviceOwned {
 pub enum PipelineBindPoint {
     Compute = ash::vk::PipelineBindPoint::COMPUTE.as_raw(),
     Graphics = ash::vk::PipelineBindPoint::GRAPHICS.as_raw(),
+    RayTracing = ash::vk::PipelineBindPoint::RAY_TRACING_KHR.as_raw(),
 }
 
 impl From<PipelineBindPoint> for ash::vk::PipelineBindPoint {
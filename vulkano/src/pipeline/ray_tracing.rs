@@ -0,0 +1,1086 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A pipeline that traces rays through acceleration structures.
+//!
+//! A ray tracing pipeline is made of a number of shader stages, that are combined into *shader
+//! groups*. Rays are generated by a ray generation shader, and are traced through a top-level
+//! [acceleration structure](crate::acceleration_structure). When a ray hits a geometry, the shaders
+//! of the hit group that belongs to the geometry are invoked. When it hits nothing, a miss shader
+//! is invoked instead.
+//!
+//! Which shader group is used for each of these cases is determined by the
+//! [`ShaderBindingTable`], which is passed to
+//! [`trace_rays`](crate::command_buffer::AutoCommandBufferBuilder::trace_rays) together with the
+//! dimensions of the ray generation.
+//!
+//! Using ray tracing pipelines requires the
+//! [`khr_ray_tracing_pipeline`](crate::device::DeviceExtensions::khr_ray_tracing_pipeline)
+//! extension and the
+//! [`ray_tracing_pipeline`](crate::device::Features::ray_tracing_pipeline) feature.
+
+use crate::buffer::{BufferAccess, BufferUsage, CpuAccessibleBuffer};
+use crate::check_errors;
+use crate::deferred::{DeferredOperation, DeferredOperationError};
+use crate::descriptor_set::layout::{DescriptorSetDesc, DescriptorSetLayout};
+use crate::device::{Device, DeviceOwned};
+use crate::memory::DeviceMemoryAllocError;
+use crate::pipeline::cache::PipelineCache;
+use crate::pipeline::layout::{
+    PipelineLayout, PipelineLayoutCreationError, PipelineLayoutPcRange, PipelineLayoutSupersetError,
+};
+use crate::pipeline::{Pipeline, PipelineBindPoint};
+use crate::shader::{DescriptorRequirements, EntryPoint, ShaderStage};
+use crate::DeviceSize;
+use crate::Error;
+use crate::OomError;
+use crate::Success;
+use crate::VulkanObject;
+use fnv::FnvHashMap;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+
+/// A pipeline object that describes to the Vulkan implementation how it should perform ray
+/// tracing operations.
+pub struct RayTracingPipeline {
+    handle: ash::vk::Pipeline,
+    device: Arc<Device>,
+    layout: Arc<PipelineLayout>,
+    descriptor_requirements: FnvHashMap<(u32, u32), DescriptorRequirements>,
+    num_used_descriptor_sets: u32,
+    stages: Vec<ShaderStage>,
+    groups: Vec<RayTracingShaderGroup>,
+}
+
+impl RayTracingPipeline {
+    /// Builds a new `RayTracingPipeline`.
+    ///
+    /// `groups` refer to the shaders in `stages` by their index. `max_pipeline_ray_recursion_depth`
+    /// is the maximum depth of `traceRayEXT` calls that the shaders will make.
+    ///
+    /// `func` is a closure that is given a mutable reference to the inferred descriptor set
+    /// definitions. This can be used to make changes to the layout before it's created, for example
+    /// to add dynamic buffers or immutable samplers.
+    ///
+    /// If `deferred_operation` is `Some`, the implementation may defer the creation of the
+    /// pipeline to it. The creation is then spread over all the threads that call
+    /// [`join`](DeferredOperation::join) on the operation while it is in progress. The current
+    /// thread joins the operation as well, and this function only returns once the creation has
+    /// completed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `deferred_operation` was not created with `device`.
+    pub fn new<F>(
+        device: Arc<Device>,
+        stages: &[EntryPoint],
+        groups: &[RayTracingShaderGroup],
+        max_pipeline_ray_recursion_depth: u32,
+        cache: Option<Arc<PipelineCache>>,
+        deferred_operation: Option<Arc<DeferredOperation>>,
+        func: F,
+    ) -> Result<Arc<RayTracingPipeline>, RayTracingPipelineCreationError>
+    where
+        F: FnOnce(&mut [DescriptorSetDesc]),
+    {
+        let mut descriptor_requirements: FnvHashMap<(u32, u32), DescriptorRequirements> =
+            HashMap::default();
+
+        for (loc, reqs) in stages
+            .iter()
+            .map(|shader| shader.descriptor_requirements())
+            .flatten()
+        {
+            match descriptor_requirements.entry(loc) {
+                Entry::Occupied(entry) => {
+                    let previous = entry.into_mut();
+                    *previous = previous.intersection(reqs).expect(
+                        "Could not produce an intersection of the shader descriptor requirements",
+                    );
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(reqs.clone());
+                }
+            }
+        }
+
+        let mut descriptor_set_layout_descs = DescriptorSetDesc::from_requirements(
+            descriptor_requirements
+                .iter()
+                .map(|(&loc, reqs)| (loc, reqs)),
+        );
+        func(&mut descriptor_set_layout_descs);
+        let descriptor_set_layouts = descriptor_set_layout_descs
+            .into_iter()
+            .map(|desc| Ok(DescriptorSetLayout::new(device.clone(), desc)?))
+            .collect::<Result<Vec<_>, PipelineLayoutCreationError>>()?;
+
+        let mut range_map = HashMap::new();
+        for stage in stages {
+            if let Some(range) = stage.push_constant_requirements() {
+                match range_map.entry((range.offset, range.size)) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(range.stages);
+                    }
+                    Entry::Occupied(mut entry) => {
+                        *entry.get_mut() = *entry.get() | range.stages;
+                    }
+                }
+            }
+        }
+        let push_constant_ranges: Vec<_> = range_map
+            .iter()
+            .map(|((offset, size), stages)| PipelineLayoutPcRange {
+                offset: *offset,
+                size: *size,
+                stages: *stages,
+            })
+            .collect();
+
+        let layout =
+            PipelineLayout::new(device.clone(), descriptor_set_layouts, push_constant_ranges)?;
+
+        RayTracingPipeline::with_pipeline_layout(
+            device,
+            stages,
+            groups,
+            max_pipeline_ray_recursion_depth,
+            layout,
+            cache,
+            deferred_operation,
+        )
+    }
+
+    /// Builds a new `RayTracingPipeline` with a specific pipeline layout.
+    ///
+    /// An error will be returned if the pipeline layout isn't a superset of what the shaders
+    /// use.
+    ///
+    /// `deferred_operation` is used in the same way as for [`new`](RayTracingPipeline::new).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `deferred_operation` was not created with `device`.
+    pub fn with_pipeline_layout(
+        device: Arc<Device>,
+        stages: &[EntryPoint],
+        groups: &[RayTracingShaderGroup],
+        max_pipeline_ray_recursion_depth: u32,
+        layout: Arc<PipelineLayout>,
+        cache: Option<Arc<PipelineCache>>,
+        deferred_operation: Option<Arc<DeferredOperation>>,
+    ) -> Result<Arc<RayTracingPipeline>, RayTracingPipelineCreationError> {
+        if let Some(deferred_operation) = &deferred_operation {
+            assert_eq!(
+                deferred_operation.device().internal_object(),
+                device.internal_object()
+            );
+        }
+
+        if !device.enabled_extensions().khr_ray_tracing_pipeline {
+            return Err(RayTracingPipelineCreationError::ExtensionNotEnabled {
+                extension: "khr_ray_tracing_pipeline",
+                reason: "tried to create a ray tracing pipeline",
+            });
+        }
+
+        if !device.enabled_features().ray_tracing_pipeline {
+            return Err(RayTracingPipelineCreationError::FeatureNotEnabled {
+                feature: "ray_tracing_pipeline",
+                reason: "tried to create a ray tracing pipeline",
+            });
+        }
+
+        let max = device
+            .physical_device()
+            .properties()
+            .max_ray_recursion_depth
+            .unwrap_or(0);
+
+        if max_pipeline_ray_recursion_depth > max {
+            return Err(
+                RayTracingPipelineCreationError::MaxRayRecursionDepthExceeded {
+                    max,
+                    obtained: max_pipeline_ray_recursion_depth,
+                },
+            );
+        }
+
+        let stage_kinds: Vec<ShaderStage> = stages
+            .iter()
+            .map(|shader| ShaderStage::from(*shader.execution()))
+            .collect();
+
+        for (stage_index, &stage) in stage_kinds.iter().enumerate() {
+            if !matches!(
+                stage,
+                ShaderStage::Raygen
+                    | ShaderStage::AnyHit
+                    | ShaderStage::ClosestHit
+                    | ShaderStage::Miss
+                    | ShaderStage::Intersection
+                    | ShaderStage::Callable
+            ) {
+                return Err(RayTracingPipelineCreationError::InvalidShaderStage {
+                    stage_index: stage_index as u32,
+                });
+            }
+        }
+
+        for (group_index, group) in groups.iter().enumerate() {
+            if !group.is_valid_for(&stage_kinds) {
+                return Err(RayTracingPipelineCreationError::InvalidShaderGroup {
+                    group_index: group_index as u32,
+                });
+            }
+        }
+
+        let mut descriptor_requirements: FnvHashMap<(u32, u32), DescriptorRequirements> =
+            HashMap::default();
+
+        for shader in stages {
+            layout.ensure_compatible_with_shader(
+                shader.descriptor_requirements(),
+                shader.push_constant_requirements(),
+            )?;
+
+            for (loc, reqs) in shader.descriptor_requirements() {
+                match descriptor_requirements.entry(loc) {
+                    Entry::Occupied(entry) => {
+                        let previous = entry.into_mut();
+                        *previous = previous.intersection(reqs).expect("Could not produce an intersection of the shader descriptor requirements");
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(reqs.clone());
+                    }
+                }
+            }
+        }
+
+        let handle = unsafe {
+            let stages_vk: Vec<_> = stages
+                .iter()
+                .zip(&stage_kinds)
+                .map(|(shader, &stage)| ash::vk::PipelineShaderStageCreateInfo {
+                    stage: stage.into(),
+                    module: shader.module().internal_object(),
+                    p_name: shader.name().as_ptr(),
+                    ..Default::default()
+                })
+                .collect();
+            let groups_vk: Vec<_> = groups.iter().map(|group| group.to_vulkan()).collect();
+
+            let infos = ash::vk::RayTracingPipelineCreateInfoKHR {
+                stage_count: stages_vk.len() as u32,
+                p_stages: stages_vk.as_ptr(),
+                group_count: groups_vk.len() as u32,
+                p_groups: groups_vk.as_ptr(),
+                max_pipeline_ray_recursion_depth,
+                layout: layout.internal_object(),
+                ..Default::default()
+            };
+
+            let cache_handle = match cache {
+                Some(ref cache) => cache.internal_object(),
+                None => ash::vk::PipelineCache::null(),
+            };
+            let deferred_operation_handle = match deferred_operation {
+                Some(ref deferred_operation) => deferred_operation.internal_object(),
+                None => ash::vk::DeferredOperationKHR::null(),
+            };
+
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            let result = device.check_errors(
+                fns.khr_ray_tracing_pipeline
+                    .create_ray_tracing_pipelines_khr(
+                        device.internal_object(),
+                        deferred_operation_handle,
+                        cache_handle,
+                        1,
+                        &infos,
+                        ptr::null(),
+                        output.as_mut_ptr(),
+                    ),
+            )?;
+
+            // The parameters must stay alive until a deferred creation has completed, and the
+            // handle is only written once it has.
+            if let (Success::OperationDeferred, Some(deferred_operation)) =
+                (result, &deferred_operation)
+            {
+                deferred_operation.wait()?;
+            }
+
+            output.assume_init()
+        };
+
+        let num_used_descriptor_sets = descriptor_requirements
+            .keys()
+            .map(|loc| loc.0)
+            .max()
+            .map(|x| x + 1)
+            .unwrap_or(0);
+
+        Ok(Arc::new(RayTracingPipeline {
+            handle,
+            device,
+            layout,
+            descriptor_requirements,
+            num_used_descriptor_sets,
+            stages: stage_kinds,
+            groups: groups.to_vec(),
+        }))
+    }
+
+    /// Returns the `Device` this ray tracing pipeline was created with.
+    #[inline]
+    pub fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+
+    /// Returns an iterator over the descriptor requirements for this pipeline.
+    #[inline]
+    pub fn descriptor_requirements(
+        &self,
+    ) -> impl ExactSizeIterator<Item = ((u32, u32), &DescriptorRequirements)> {
+        self.descriptor_requirements
+            .iter()
+            .map(|(loc, reqs)| (*loc, reqs))
+    }
+
+    /// Returns the shader groups of the pipeline.
+    #[inline]
+    pub fn groups(&self) -> &[RayTracingShaderGroup] {
+        &self.groups
+    }
+
+    /// Returns the opaque handles of all the shader groups of the pipeline, as one contiguous
+    /// array of
+    /// [`shader_group_handle_size`](crate::device::Properties::shader_group_handle_size) bytes per
+    /// group.
+    pub fn group_handles(&self) -> Result<Vec<u8>, OomError> {
+        // The property is always available when the extension is enabled.
+        let handle_size = self
+            .device
+            .physical_device()
+            .properties()
+            .shader_group_handle_size
+            .unwrap_or(0) as usize;
+        let mut data = vec![0u8; handle_size * self.groups.len()];
+
+        unsafe {
+            let fns = self.device.fns();
+            check_errors(
+                fns.khr_ray_tracing_pipeline
+                    .get_ray_tracing_shader_group_handles_khr(
+                        self.device.internal_object(),
+                        self.handle,
+                        0,
+                        self.groups.len() as u32,
+                        data.len(),
+                        data.as_mut_ptr() as *mut _,
+                    ),
+            )?;
+        }
+
+        Ok(data)
+    }
+}
+
+impl Pipeline for RayTracingPipeline {
+    #[inline]
+    fn bind_point(&self) -> PipelineBindPoint {
+        PipelineBindPoint::RayTracing
+    }
+
+    #[inline]
+    fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+
+    #[inline]
+    fn num_used_descriptor_sets(&self) -> u32 {
+        self.num_used_descriptor_sets
+    }
+}
+
+impl fmt::Debug for RayTracingPipeline {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan ray tracing pipeline {:?}>", self.handle)
+    }
+}
+
+impl PartialEq for RayTracingPipeline {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.internal_object() == other.internal_object()
+    }
+}
+
+impl Eq for RayTracingPipeline {}
+
+unsafe impl VulkanObject for RayTracingPipeline {
+    type Object = ash::vk::Pipeline;
+
+    #[inline]
+    fn internal_object(&self) -> ash::vk::Pipeline {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for RayTracingPipeline {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.device()
+    }
+}
+
+impl Drop for RayTracingPipeline {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            fns.v1_0
+                .destroy_pipeline(self.device.internal_object(), self.handle, ptr::null());
+        }
+    }
+}
+
+/// A group of shaders in a ray tracing pipeline. The shaders are referred to by their index in
+/// the stages of the pipeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RayTracingShaderGroup {
+    /// A single ray generation, miss or callable shader.
+    General {
+        /// The index of the shader.
+        shader: u32,
+    },
+
+    /// The shaders that are invoked when a ray hits a triangle.
+    TrianglesHit {
+        /// The index of the closest hit shader, if any.
+        closest_hit: Option<u32>,
+        /// The index of the any-hit shader, if any.
+        any_hit: Option<u32>,
+    },
+
+    /// The shaders that are invoked when a ray hits an axis-aligned bounding box.
+    ProceduralHit {
+        /// The index of the intersection shader, that determines whether a ray hits the geometry
+        /// inside the bounding box.
+        intersection: u32,
+        /// The index of the closest hit shader, if any.
+        closest_hit: Option<u32>,
+        /// The index of the any-hit shader, if any.
+        any_hit: Option<u32>,
+    },
+}
+
+impl RayTracingShaderGroup {
+    fn is_valid_for(&self, stages: &[ShaderStage]) -> bool {
+        let is = |index: u32, expected: &[ShaderStage]| {
+            stages
+                .get(index as usize)
+                .map_or(false, |stage| expected.contains(stage))
+        };
+        let is_optional = |index: Option<u32>, expected: &[ShaderStage]| {
+            index.map_or(true, |index| is(index, expected))
+        };
+
+        match *self {
+            Self::General { shader } => is(
+                shader,
+                &[
+                    ShaderStage::Raygen,
+                    ShaderStage::Miss,
+                    ShaderStage::Callable,
+                ],
+            ),
+            Self::TrianglesHit {
+                closest_hit,
+                any_hit,
+            } => {
+                is_optional(closest_hit, &[ShaderStage::ClosestHit])
+                    && is_optional(any_hit, &[ShaderStage::AnyHit])
+            }
+            Self::ProceduralHit {
+                intersection,
+                closest_hit,
+                any_hit,
+            } => {
+                is(intersection, &[ShaderStage::Intersection])
+                    && is_optional(closest_hit, &[ShaderStage::ClosestHit])
+                    && is_optional(any_hit, &[ShaderStage::AnyHit])
+            }
+        }
+    }
+
+    fn to_vulkan(&self) -> ash::vk::RayTracingShaderGroupCreateInfoKHR {
+        let unused = |index: Option<u32>| index.unwrap_or(ash::vk::SHADER_UNUSED_KHR);
+
+        match *self {
+            Self::General { shader } => ash::vk::RayTracingShaderGroupCreateInfoKHR {
+                ty: ash::vk::RayTracingShaderGroupTypeKHR::GENERAL,
+                general_shader: shader,
+                closest_hit_shader: ash::vk::SHADER_UNUSED_KHR,
+                any_hit_shader: ash::vk::SHADER_UNUSED_KHR,
+                intersection_shader: ash::vk::SHADER_UNUSED_KHR,
+                ..Default::default()
+            },
+            Self::TrianglesHit {
+                closest_hit,
+                any_hit,
+            } => ash::vk::RayTracingShaderGroupCreateInfoKHR {
+                ty: ash::vk::RayTracingShaderGroupTypeKHR::TRIANGLES_HIT_GROUP,
+                general_shader: ash::vk::SHADER_UNUSED_KHR,
+                closest_hit_shader: unused(closest_hit),
+                any_hit_shader: unused(any_hit),
+                intersection_shader: ash::vk::SHADER_UNUSED_KHR,
+                ..Default::default()
+            },
+            Self::ProceduralHit {
+                intersection,
+                closest_hit,
+                any_hit,
+            } => ash::vk::RayTracingShaderGroupCreateInfoKHR {
+                ty: ash::vk::RayTracingShaderGroupTypeKHR::PROCEDURAL_HIT_GROUP,
+                general_shader: ash::vk::SHADER_UNUSED_KHR,
+                closest_hit_shader: unused(closest_hit),
+                any_hit_shader: unused(any_hit),
+                intersection_shader: intersection,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Error that can happen when creating a ray tracing pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RayTracingPipelineCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation returned an error other than running out of memory.
+    CreationFailed,
+
+    /// An extension required for ray tracing pipelines wasn't enabled.
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    /// A feature required for ray tracing pipelines wasn't enabled.
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The deferred operation that the creation was deferred to failed.
+    DeferredOperationError(DeferredOperationError),
+
+    /// Error while creating the pipeline layout object.
+    PipelineLayoutCreationError(PipelineLayoutCreationError),
+
+    /// The pipeline layout is not compatible with what the shaders expect.
+    IncompatiblePipelineLayout(PipelineLayoutSupersetError),
+
+    /// A stage is not a ray tracing shader.
+    InvalidShaderStage {
+        /// The index of the stage.
+        stage_index: u32,
+    },
+
+    /// A shader group refers to a stage that doesn't exist, or that has the wrong type for its
+    /// place in the group.
+    InvalidShaderGroup {
+        /// The index of the group.
+        group_index: u32,
+    },
+
+    /// The `max_pipeline_ray_recursion_depth` is greater than the `max_ray_recursion_depth`
+    /// device property.
+    MaxRayRecursionDepthExceeded {
+        /// The `max_ray_recursion_depth` device property.
+        max: u32,
+        /// The requested recursion depth.
+        obtained: u32,
+    },
+}
+
+impl error::Error for RayTracingPipelineCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            Self::DeferredOperationError(ref err) => Some(err),
+            Self::PipelineLayoutCreationError(ref err) => Some(err),
+            Self::IncompatiblePipelineLayout(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RayTracingPipelineCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::CreationFailed => {
+                write!(fmt, "the implementation failed to create the pipeline")
+            }
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::DeferredOperationError(_) => {
+                write!(fmt, "the deferred operation of the creation failed")
+            }
+            Self::PipelineLayoutCreationError(_) => {
+                write!(fmt, "error while creating the pipeline layout object")
+            }
+            Self::IncompatiblePipelineLayout(_) => write!(
+                fmt,
+                "the pipeline layout is not compatible with what the shaders expect"
+            ),
+            Self::InvalidShaderStage { stage_index } => {
+                write!(fmt, "stage {} is not a ray tracing shader", stage_index)
+            }
+            Self::InvalidShaderGroup { group_index } => write!(
+                fmt,
+                "shader group {} refers to a stage that doesn't exist or has the wrong type",
+                group_index
+            ),
+            Self::MaxRayRecursionDepthExceeded { max, obtained } => write!(
+                fmt,
+                "the maximum ray recursion depth was {}, but the device only supports {}",
+                obtained, max
+            ),
+        }
+    }
+}
+
+impl From<OomError> for RayTracingPipelineCreationError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<DeferredOperationError> for RayTracingPipelineCreationError {
+    #[inline]
+    fn from(err: DeferredOperationError) -> Self {
+        Self::DeferredOperationError(err)
+    }
+}
+
+impl From<PipelineLayoutCreationError> for RayTracingPipelineCreationError {
+    #[inline]
+    fn from(err: PipelineLayoutCreationError) -> Self {
+        Self::PipelineLayoutCreationError(err)
+    }
+}
+
+impl From<PipelineLayoutSupersetError> for RayTracingPipelineCreationError {
+    #[inline]
+    fn from(err: PipelineLayoutSupersetError) -> Self {
+        Self::IncompatiblePipelineLayout(err)
+    }
+}
+
+impl From<Error> for RayTracingPipelineCreationError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => Self::CreationFailed,
+        }
+    }
+}
+
+/// A buffer that holds the shader group handles of a ray tracing pipeline, laid out in the
+/// regions that are used by
+/// [`trace_rays`](crate::command_buffer::AutoCommandBufferBuilder::trace_rays).
+pub struct ShaderBindingTable {
+    pipeline: Arc<RayTracingPipeline>,
+    buffer: Arc<dyn BufferAccess>,
+    raygen: ash::vk::StridedDeviceAddressRegionKHR,
+    miss: ash::vk::StridedDeviceAddressRegionKHR,
+    hit: ash::vk::StridedDeviceAddressRegionKHR,
+    callable: ash::vk::StridedDeviceAddressRegionKHR,
+}
+
+impl ShaderBindingTable {
+    /// Creates a shader binding table for `pipeline`, with the given shader groups in each of the
+    /// regions. The groups are referred to by their index in
+    /// [`RayTracingPipeline::groups`].
+    ///
+    /// The buffer of the table requires the
+    /// [`buffer_device_address`](crate::device::Features::buffer_device_address) feature.
+    pub fn new(
+        pipeline: Arc<RayTracingPipeline>,
+        raygen_group: u32,
+        miss_groups: &[u32],
+        hit_groups: &[u32],
+        callable_groups: &[u32],
+    ) -> Result<Arc<ShaderBindingTable>, ShaderBindingTableCreationError> {
+        let device = pipeline.device().clone();
+
+        if !device.enabled_features().buffer_device_address {
+            return Err(ShaderBindingTableCreationError::FeatureNotEnabled {
+                feature: "buffer_device_address",
+                reason: "the shader binding table is accessed through its device address",
+            });
+        }
+
+        let check_group = |group_index: u32, expected: &[ShaderStage]| {
+            let valid = match pipeline.groups.get(group_index as usize) {
+                Some(&RayTracingShaderGroup::General { shader }) => {
+                    expected.contains(&pipeline.stages[shader as usize])
+                }
+                Some(RayTracingShaderGroup::TrianglesHit { .. })
+                | Some(RayTracingShaderGroup::ProceduralHit { .. }) => expected.is_empty(),
+                None => false,
+            };
+
+            if valid {
+                Ok(())
+            } else {
+                Err(ShaderBindingTableCreationError::InvalidGroup { group_index })
+            }
+        };
+
+        check_group(raygen_group, &[ShaderStage::Raygen])?;
+        for &group_index in miss_groups {
+            check_group(group_index, &[ShaderStage::Miss])?;
+        }
+        for &group_index in hit_groups {
+            check_group(group_index, &[])?;
+        }
+        for &group_index in callable_groups {
+            check_group(group_index, &[ShaderStage::Callable])?;
+        }
+
+        // The properties are always available when the extension is enabled.
+        let properties = device.physical_device().properties();
+        let handle_size = properties.shader_group_handle_size.unwrap_or(0) as DeviceSize;
+        let base_alignment = properties.shader_group_base_alignment.unwrap_or(1) as DeviceSize;
+        let regions = ShaderBindingTableRegions::new(
+            handle_size,
+            properties.shader_group_handle_alignment.unwrap_or(1) as DeviceSize,
+            base_alignment,
+            [
+                1,
+                miss_groups.len() as DeviceSize,
+                hit_groups.len() as DeviceSize,
+                callable_groups.len() as DeviceSize,
+            ],
+        );
+
+        // The start of the buffer is not guaranteed to be aligned to the base alignment, so
+        // allocate enough to be able to shift the regions.
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage {
+                shader_binding_table: true,
+                device_address: true,
+                ..BufferUsage::none()
+            },
+            false,
+            (0..regions.size + base_alignment).map(|_| 0u8),
+        )?;
+        // The buffer was created with the `device_address` usage, and is not in use yet.
+        let address = buffer.raw_device_address().unwrap().get();
+        let shift = (base_alignment - address % base_alignment) % base_alignment;
+
+        let handles = pipeline.group_handles()?;
+        {
+            let mut data = buffer.write().unwrap();
+            let raygen_groups = [raygen_group];
+            let groups = [&raygen_groups[..], miss_groups, hit_groups, callable_groups];
+
+            for (region, groups) in regions.regions.iter().zip(groups) {
+                for (index, &group_index) in groups.iter().enumerate() {
+                    let src = (group_index as DeviceSize * handle_size) as usize;
+                    let dst =
+                        (shift + region.offset + index as DeviceSize * region.stride) as usize;
+                    data[dst..dst + handle_size as usize]
+                        .copy_from_slice(&handles[src..src + handle_size as usize]);
+                }
+            }
+        }
+
+        let to_vulkan = |region: &ShaderBindingTableRegion| {
+            if region.size == 0 {
+                ash::vk::StridedDeviceAddressRegionKHR::default()
+            } else {
+                ash::vk::StridedDeviceAddressRegionKHR {
+                    device_address: address + shift + region.offset,
+                    stride: region.stride,
+                    size: region.size,
+                }
+            }
+        };
+
+        Ok(Arc::new(ShaderBindingTable {
+            raygen: to_vulkan(&regions.regions[0]),
+            miss: to_vulkan(&regions.regions[1]),
+            hit: to_vulkan(&regions.regions[2]),
+            callable: to_vulkan(&regions.regions[3]),
+            pipeline,
+            buffer,
+        }))
+    }
+
+    /// Returns the pipeline that the table was created for.
+    #[inline]
+    pub fn pipeline(&self) -> &Arc<RayTracingPipeline> {
+        &self.pipeline
+    }
+
+    /// Returns the buffer that holds the table.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<dyn BufferAccess> {
+        &self.buffer
+    }
+
+    #[inline]
+    pub(crate) fn regions(&self) -> [&ash::vk::StridedDeviceAddressRegionKHR; 4] {
+        [&self.raygen, &self.miss, &self.hit, &self.callable]
+    }
+}
+
+impl fmt::Debug for ShaderBindingTable {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("ShaderBindingTable")
+            .field("pipeline", &self.pipeline)
+            .field("raygen", &self.raygen)
+            .field("miss", &self.miss)
+            .field("hit", &self.hit)
+            .field("callable", &self.callable)
+            .finish()
+    }
+}
+
+// The layout of the raygen, miss, hit and callable regions, relative to the start of the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ShaderBindingTableRegions {
+    regions: [ShaderBindingTableRegion; 4],
+    size: DeviceSize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct ShaderBindingTableRegion {
+    offset: DeviceSize,
+    stride: DeviceSize,
+    size: DeviceSize,
+}
+
+impl ShaderBindingTableRegions {
+    fn new(
+        handle_size: DeviceSize,
+        handle_alignment: DeviceSize,
+        base_alignment: DeviceSize,
+        counts: [DeviceSize; 4],
+    ) -> Self {
+        let align = |value: DeviceSize, alignment: DeviceSize| {
+            (value + alignment - 1) / alignment * alignment
+        };
+
+        let stride = align(handle_size, handle_alignment);
+        let mut regions = [ShaderBindingTableRegion::default(); 4];
+        let mut offset = 0;
+
+        for (index, (region, count)) in regions.iter_mut().zip(counts).enumerate() {
+            // The size of the raygen region must be equal to its stride.
+            let region_stride = if index == 0 {
+                align(stride, base_alignment)
+            } else {
+                stride
+            };
+            let size = align(count * region_stride, base_alignment);
+
+            *region = ShaderBindingTableRegion {
+                offset,
+                stride: region_stride,
+                size,
+            };
+            offset += size;
+        }
+
+        ShaderBindingTableRegions {
+            regions,
+            size: offset,
+        }
+    }
+}
+
+/// Error that can happen when creating a shader binding table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShaderBindingTableCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// Allocating the buffer of the table failed.
+    DeviceMemoryAllocError(DeviceMemoryAllocError),
+
+    /// A feature required for shader binding tables wasn't enabled.
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// A group index doesn't exist in the pipeline, or the group has the wrong type for the
+    /// region it is used in.
+    InvalidGroup {
+        /// The index of the group.
+        group_index: u32,
+    },
+}
+
+impl error::Error for ShaderBindingTableCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            Self::DeviceMemoryAllocError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ShaderBindingTableCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::DeviceMemoryAllocError(_) => {
+                write!(fmt, "allocating the buffer of the table failed")
+            }
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::InvalidGroup { group_index } => write!(
+                fmt,
+                "group {} doesn't exist or has the wrong type for its region",
+                group_index
+            ),
+        }
+    }
+}
+
+impl From<OomError> for ShaderBindingTableCreationError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<DeviceMemoryAllocError> for ShaderBindingTableCreationError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> Self {
+        Self::DeviceMemoryAllocError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RayTracingShaderGroup;
+    use super::ShaderBindingTableRegion;
+    use super::ShaderBindingTableRegions;
+    use crate::shader::ShaderStage;
+
+    #[test]
+    fn table_layout() {
+        // Typical values: 32-byte handles, 64-byte base alignment.
+        let regions = ShaderBindingTableRegions::new(32, 32, 64, [1, 2, 3, 0]);
+
+        assert_eq!(
+            regions.regions,
+            [
+                ShaderBindingTableRegion {
+                    offset: 0,
+                    stride: 64,
+                    size: 64,
+                },
+                ShaderBindingTableRegion {
+                    offset: 64,
+                    stride: 32,
+                    size: 64,
+                },
+                ShaderBindingTableRegion {
+                    offset: 128,
+                    stride: 32,
+                    size: 128,
+                },
+                ShaderBindingTableRegion {
+                    offset: 256,
+                    stride: 32,
+                    size: 0,
+                },
+            ]
+        );
+        assert_eq!(regions.size, 256);
+    }
+
+    #[test]
+    fn table_layout_unaligned_handles() {
+        let regions = ShaderBindingTableRegions::new(20, 8, 64, [1, 3, 1, 1]);
+
+        assert_eq!(regions.regions[0].stride, 64);
+        assert_eq!(regions.regions[1].stride, 24);
+        assert_eq!(regions.regions[1].size, 128);
+        assert_eq!(regions.regions[2].offset, 192);
+        assert_eq!(regions.regions[3].offset, 256);
+        assert_eq!(regions.size, 320);
+    }
+
+    #[test]
+    fn group_validity() {
+        let stages = [
+            ShaderStage::Raygen,
+            ShaderStage::Miss,
+            ShaderStage::ClosestHit,
+            ShaderStage::Intersection,
+        ];
+
+        assert!(RayTracingShaderGroup::General { shader: 0 }.is_valid_for(&stages));
+        assert!(RayTracingShaderGroup::General { shader: 1 }.is_valid_for(&stages));
+        assert!(!RayTracingShaderGroup::General { shader: 2 }.is_valid_for(&stages));
+        assert!(!RayTracingShaderGroup::General { shader: 4 }.is_valid_for(&stages));
+        assert!(RayTracingShaderGroup::TrianglesHit {
+            closest_hit: Some(2),
+            any_hit: None,
+        }
+        .is_valid_for(&stages));
+        assert!(!RayTracingShaderGroup::TrianglesHit {
+            closest_hit: None,
+            any_hit: Some(2),
+        }
+        .is_valid_for(&stages));
+        assert!(RayTracingShaderGroup::ProceduralHit {
+            intersection: 3,
+            closest_hit: Some(2),
+            any_hit: None,
+        }
+        .is_valid_for(&stages));
+    }
+}
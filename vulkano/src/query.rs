@@ -278,16 +278,18 @@ impl<'a> QueriesRange<'a> {
 
         let result = unsafe {
             let fns = self.pool.device.fns();
-            check_errors(fns.v1_0.get_query_pool_results(
-                self.pool.device.internal_object(),
-                self.pool.internal_object(),
-                self.range.start,
-                self.range.end - self.range.start,
-                std::mem::size_of_val(destination),
-                destination.as_mut_ptr() as *mut c_void,
-                stride,
-                ash::vk::QueryResultFlags::from(flags) | T::FLAG,
-            ))?
+            self.pool
+                .device
+                .check_errors(fns.v1_0.get_query_pool_results(
+                    self.pool.device.internal_object(),
+                    self.pool.internal_object(),
+                    self.range.start,
+                    self.range.end - self.range.start,
+                    std::mem::size_of_val(destination),
+                    destination.as_mut_ptr() as *mut c_void,
+                    stride,
+                    ash::vk::QueryResultFlags::from(flags) | T::FLAG,
+                ))?
         };
 
         Ok(match result {
@@ -590,6 +592,44 @@ impl QueryPipelineStatisticFlags {
             || tessellation_control_shader_patches
             || tessellation_evaluation_shader_invocations
     }
+
+    /// Splits the results of a single pipeline statistics query into named fields.
+    ///
+    /// `results` must contain the elements written for the query, in the order they were
+    /// written by [`QueriesRange::get_results`]. Statistics that are not enabled in `self` are
+    /// returned as `None`. Any element after the first [`count`](Self::count) elements, such as
+    /// the availability value, is ignored.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `results` contains less than `self.count()` elements.
+    pub fn parse_results(&self, results: &[u64]) -> QueryPipelineStatistics {
+        assert!(
+            results.len() as DeviceSize >= self.count(),
+            "results contains {} elements, but {} are required",
+            results.len(),
+            self.count(),
+        );
+
+        let mut results = results.iter().copied();
+        let mut next = |enabled: bool| if enabled { results.next() } else { None };
+
+        QueryPipelineStatistics {
+            input_assembly_vertices: next(self.input_assembly_vertices),
+            input_assembly_primitives: next(self.input_assembly_primitives),
+            vertex_shader_invocations: next(self.vertex_shader_invocations),
+            geometry_shader_invocations: next(self.geometry_shader_invocations),
+            geometry_shader_primitives: next(self.geometry_shader_primitives),
+            clipping_invocations: next(self.clipping_invocations),
+            clipping_primitives: next(self.clipping_primitives),
+            fragment_shader_invocations: next(self.fragment_shader_invocations),
+            tessellation_control_shader_patches: next(self.tessellation_control_shader_patches),
+            tessellation_evaluation_shader_invocations: next(
+                self.tessellation_evaluation_shader_invocations,
+            ),
+            compute_shader_invocations: next(self.compute_shader_invocations),
+        }
+    }
 }
 
 impl From<QueryPipelineStatisticFlags> for ash::vk::QueryPipelineStatisticFlags {
@@ -633,6 +673,36 @@ impl From<QueryPipelineStatisticFlags> for ash::vk::QueryPipelineStatisticFlags
     }
 }
 
+/// The results of a pipeline statistics query, as returned by
+/// [`QueryPipelineStatisticFlags::parse_results`].
+///
+/// Each field is `None` if the corresponding statistic was not enabled on the query pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueryPipelineStatistics {
+    /// The number of vertices processed by the input assembly.
+    pub input_assembly_vertices: Option<u64>,
+    /// The number of primitives processed by the input assembly.
+    pub input_assembly_primitives: Option<u64>,
+    /// The number of times a vertex shader was invoked.
+    pub vertex_shader_invocations: Option<u64>,
+    /// The number of times a geometry shader was invoked.
+    pub geometry_shader_invocations: Option<u64>,
+    /// The number of primitives generated by geometry shaders.
+    pub geometry_shader_primitives: Option<u64>,
+    /// The number of times the clipping stage was invoked on a primitive.
+    pub clipping_invocations: Option<u64>,
+    /// The number of primitives that were output by the clipping stage.
+    pub clipping_primitives: Option<u64>,
+    /// The number of times a fragment shader was invoked.
+    pub fragment_shader_invocations: Option<u64>,
+    /// The number of patches processed by a tessellation control shader.
+    pub tessellation_control_shader_patches: Option<u64>,
+    /// The number of times a tessellation evaluation shader was invoked.
+    pub tessellation_evaluation_shader_invocations: Option<u64>,
+    /// The number of times a compute shader was invoked.
+    pub compute_shader_invocations: Option<u64>,
+}
+
 /// Flags to control how the results of a query should be retrieved.
 ///
 /// `VK_QUERY_RESULT_64_BIT` is not included, as it is determined automatically via the
@@ -685,4 +755,18 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn pipeline_statistics_parse_results() {
+        let flags = QueryPipelineStatisticFlags {
+            input_assembly_vertices: true,
+            fragment_shader_invocations: true,
+            ..QueryPipelineStatisticFlags::none()
+        };
+
+        let stats = flags.parse_results(&[12, 34, 1]);
+        assert_eq!(stats.input_assembly_vertices, Some(12));
+        assert_eq!(stats.fragment_shader_invocations, Some(34));
+        assert_eq!(stats.vertex_shader_invocations, None);
+    }
 }
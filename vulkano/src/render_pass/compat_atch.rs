@@ -11,6 +11,7 @@
 //! an image view can be used as a render pass attachment.
 
 use crate::image::view::ImageViewAbstract;
+use crate::image::view::ImageViewType;
 use crate::render_pass::RenderPassDesc;
 use crate::{format::Format, image::SampleCount};
 use std::error;
@@ -55,6 +56,12 @@ where
         return Err(IncompatibleRenderPassAttachmentError::NotIdentitySwizzled);
     }
 
+    // VUID-VkFramebufferCreateInfo-flags-04113
+    // To render to a slice of a 3D image, a 2D or 2D array view of it must be used instead.
+    if image_view.ty() == ImageViewType::Dim3d {
+        return Err(IncompatibleRenderPassAttachmentError::Dim3dView);
+    }
+
     let aspects = image_view.image().format().aspects(); // TODO: should use view format?
 
     for subpass in render_pass_desc.subpasses() {
@@ -123,6 +130,9 @@ pub enum IncompatibleRenderPassAttachmentError {
     /// The image view has a component swizzle that is different from identity.
     NotIdentitySwizzled,
 
+    /// The image view has the `Dim3d` type, which can't be used as an attachment.
+    Dim3dView,
+
     /// The image is used as a color attachment but is missing the color attachment usage.
     MissingColorAttachmentUsage,
 
@@ -153,6 +163,9 @@ impl fmt::Display for IncompatibleRenderPassAttachmentError {
                 IncompatibleRenderPassAttachmentError::NotIdentitySwizzled => {
                     "the image view's component mapping is not identity swizzled"
                 }
+                IncompatibleRenderPassAttachmentError::Dim3dView => {
+                    "the image view has the 3D type, which can't be used as an attachment"
+                }
                 IncompatibleRenderPassAttachmentError::MissingColorAttachmentUsage => {
                     "the image is used as a color attachment but is missing the color attachment usage"
                 }
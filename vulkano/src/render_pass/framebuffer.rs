@@ -233,9 +233,14 @@ impl FramebufferBuilder {
             Err(err) => return Err(FramebufferCreationError::IncompatibleAttachment(err)),
         };
 
-        let image_dimensions = attachment.image().dimensions();
+        // The view covers a single mip level. For 2D views of a 3D image, the array layers of the
+        // view are depth slices of the image, so the layer count is taken from the view either way.
+        let image_dimensions = attachment
+            .image()
+            .dimensions()
+            .mip_level_dimensions(attachment.mip_levels().start)
+            .unwrap();
         let array_layers = attachment.array_layers();
-        debug_assert_eq!(image_dimensions.depth(), 1);
 
         let view_dimensions = [
             image_dimensions.width(),
@@ -486,8 +491,14 @@ mod tests {
     use crate::format::Format;
     use crate::image::attachment::AttachmentImage;
     use crate::image::view::ImageView;
+    use crate::image::view::ImageViewType;
+    use crate::image::ImageCreateFlags;
+    use crate::image::ImageDimensions;
+    use crate::image::ImageUsage;
+    use crate::image::StorageImage;
     use crate::render_pass::Framebuffer;
     use crate::render_pass::FramebufferCreationError;
+    use crate::render_pass::IncompatibleRenderPassAttachmentError;
     use crate::render_pass::RenderPass;
 
     #[test]
@@ -852,4 +863,67 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn slice_of_3d_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let render_pass = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        let image = StorageImage::with_usage(
+            device.clone(),
+            ImageDimensions::Dim3d {
+                width: 64,
+                height: 32,
+                depth: 16,
+            },
+            Format::R8G8B8A8_UNORM,
+            ImageUsage {
+                color_attachment: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags {
+                array_2d_compatible: true,
+                ..ImageCreateFlags::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let view = ImageView::start(image.clone())
+            .ty(ImageViewType::Dim2d)
+            .array_layers(5..6)
+            .build()
+            .unwrap();
+
+        let framebuffer = Framebuffer::start(render_pass.clone())
+            .add(view)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(framebuffer.dimensions(), [64, 32, 1]);
+
+        let view = ImageView::new(image).unwrap();
+
+        match Framebuffer::start(render_pass).add(view) {
+            Err(FramebufferCreationError::IncompatibleAttachment(
+                IncompatibleRenderPassAttachmentError::Dim3dView,
+            )) => (),
+            _ => panic!(),
+        }
+    }
 }
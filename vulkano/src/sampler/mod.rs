@@ -49,6 +49,7 @@ pub mod ycbcr;
 use crate::check_errors;
 use crate::device::Device;
 use crate::device::DeviceOwned;
+use crate::format::Format;
 use crate::image::view::ImageViewType;
 use crate::image::ImageViewAbstract;
 use crate::pipeline::graphics::depth_stencil::CompareOp;
@@ -97,6 +98,7 @@ pub struct Sampler {
 
     border_color: Option<BorderColor>,
     compare: Option<CompareOp>,
+    custom_border_color: bool,
     mag_filter: Filter,
     min_filter: Filter,
     mipmap_mode: SamplerMipmapMode,
@@ -122,6 +124,7 @@ impl Sampler {
             compare: None,
             lod: 0.0..=0.0,
             border_color: BorderColor::FloatTransparentBlack,
+            custom_border_color_format: None,
             unnormalized_coordinates: false,
             reduction_mode: SamplerReductionMode::WeightedAverage,
             sampler_ycbcr_conversion: None,
@@ -197,6 +200,15 @@ impl Sampler {
             }
         }
 
+        // VUID-vkCmdDispatch-None-06480
+        if matches!(
+            self.reduction_mode,
+            SamplerReductionMode::Min | SamplerReductionMode::Max
+        ) && !image_view.format_features().sampled_image_filter_minmax
+        {
+            return Err(SamplerImageViewIncompatibleError::FilterMinmaxNotSupported);
+        }
+
         if self.mag_filter == Filter::Cubic || self.min_filter == Filter::Cubic {
             // VUID-vkCmdDispatch-None-02692
             if !image_view.format_features().sampled_image_filter_cubic {
@@ -237,7 +249,8 @@ impl Sampler {
             match border_color {
                 BorderColor::IntTransparentBlack
                 | BorderColor::IntOpaqueBlack
-                | BorderColor::IntOpaqueWhite => {
+                | BorderColor::IntOpaqueWhite
+                | BorderColor::IntCustom(_) => {
                     // The sampler borderColor is an integer type and the image view
                     // format is not one of the VkFormat integer types or a stencil
                     // component of a depth/stencil format.
@@ -252,7 +265,8 @@ impl Sampler {
                 }
                 BorderColor::FloatTransparentBlack
                 | BorderColor::FloatOpaqueBlack
-                | BorderColor::FloatOpaqueWhite => {
+                | BorderColor::FloatOpaqueWhite
+                | BorderColor::FloatCustom(_) => {
                     // The sampler borderColor is a float type and the image view
                     // format is not one of the VkFormat float types or a depth
                     // component of a depth/stencil format.
@@ -395,6 +409,14 @@ impl Drop for Sampler {
             fns.v1_0
                 .destroy_sampler(self.device.internal_object(), self.handle, ptr::null());
         }
+
+        if self.custom_border_color {
+            *self
+                .device
+                .custom_border_color_sampler_count()
+                .lock()
+                .unwrap() -= 1;
+        }
     }
 }
 
@@ -414,6 +436,7 @@ pub struct SamplerBuilder {
     compare: Option<CompareOp>,
     lod: RangeInclusive<f32>,
     border_color: BorderColor,
+    custom_border_color_format: Option<Format>,
     unnormalized_coordinates: bool,
     reduction_mode: SamplerReductionMode,
     sampler_ycbcr_conversion: Option<Arc<SamplerYcbcrConversion>>,
@@ -435,6 +458,7 @@ impl SamplerBuilder {
             compare,
             lod,
             border_color,
+            custom_border_color_format,
             unnormalized_coordinates,
             reduction_mode,
             sampler_ycbcr_conversion,
@@ -561,6 +585,47 @@ impl SamplerBuilder {
             }
         }
 
+        let mut sampler_custom_border_color_create_info = match border_color {
+            BorderColor::FloatCustom(_) | BorderColor::IntCustom(_) => {
+                if !device.enabled_extensions().ext_custom_border_color {
+                    return Err(SamplerCreationError::ExtensionNotEnabled {
+                        extension: "ext_custom_border_color",
+                        reason: "border_color was a custom color",
+                    });
+                }
+
+                if !device.enabled_features().custom_border_colors {
+                    return Err(SamplerCreationError::FeatureNotEnabled {
+                        feature: "custom_border_colors",
+                        reason: "border_color was a custom color",
+                    });
+                }
+
+                if custom_border_color_format.is_none()
+                    && !device.enabled_features().custom_border_color_without_format
+                {
+                    return Err(SamplerCreationError::FeatureNotEnabled {
+                        feature: "custom_border_color_without_format",
+                        reason: "border_color was a custom color, but custom_border_color_format was `None`",
+                    });
+                }
+
+                let custom_border_color = match border_color {
+                    BorderColor::FloatCustom(float32) => ash::vk::ClearColorValue { float32 },
+                    BorderColor::IntCustom(int32) => ash::vk::ClearColorValue { int32 },
+                    _ => unreachable!(),
+                };
+
+                Some(ash::vk::SamplerCustomBorderColorCreateInfoEXT {
+                    custom_border_color,
+                    format: custom_border_color_format
+                        .map_or(ash::vk::Format::UNDEFINED, Into::into),
+                    ..Default::default()
+                })
+            }
+            _ => None,
+        };
+
         let mut sampler_reduction_mode_create_info =
             if device.enabled_features().sampler_filter_minmax
                 || device.enabled_extensions().ext_sampler_filter_minmax
@@ -685,6 +750,13 @@ impl SamplerBuilder {
             ..Default::default()
         };
 
+        if let Some(sampler_custom_border_color_create_info) =
+            sampler_custom_border_color_create_info.as_mut()
+        {
+            sampler_custom_border_color_create_info.p_next = create_info.p_next;
+            create_info.p_next = sampler_custom_border_color_create_info as *const _ as *const _;
+        }
+
         if let Some(sampler_reduction_mode_create_info) =
             sampler_reduction_mode_create_info.as_mut()
         {
@@ -697,6 +769,27 @@ impl SamplerBuilder {
             create_info.p_next = sampler_ycbcr_conversion_info as *const _ as *const _;
         }
 
+        // The count is held locked until the sampler has been created, so that no other thread
+        // can exceed the limit in the meantime.
+        let mut custom_border_color_sampler_count = if sampler_custom_border_color_create_info
+            .is_some()
+        {
+            let count = device.custom_border_color_sampler_count().lock().unwrap();
+            let maximum = device
+                .physical_device()
+                .properties()
+                .max_custom_border_color_samplers
+                .unwrap_or(0);
+
+            if *count >= maximum {
+                return Err(SamplerCreationError::MaxCustomBorderColorSamplersExceeded { maximum });
+            }
+
+            Some(count)
+        } else {
+            None
+        };
+
         let handle = unsafe {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -709,6 +802,14 @@ impl SamplerBuilder {
             output.assume_init()
         };
 
+        let custom_border_color = custom_border_color_sampler_count.is_some();
+
+        if let Some(count) = custom_border_color_sampler_count.as_mut() {
+            **count += 1;
+        }
+
+        drop(custom_border_color_sampler_count);
+
         Ok(Arc::new(Sampler {
             handle,
             device,
@@ -718,6 +819,7 @@ impl SamplerBuilder {
                 .any(|mode| mode == SamplerAddressMode::ClampToBorder)
                 .then(|| border_color),
             compare,
+            custom_border_color,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -899,6 +1001,20 @@ impl SamplerBuilder {
         self
     }
 
+    /// The format of the image views that the sampler will be used with, if `border_color` is
+    /// [`FloatCustom`](BorderColor::FloatCustom) or [`IntCustom`](BorderColor::IntCustom).
+    ///
+    /// If set to `None`, the
+    /// [`custom_border_color_without_format`](crate::device::Features::custom_border_color_without_format)
+    /// feature must be enabled on the device in order to use a custom border color.
+    ///
+    /// The default value is `None`.
+    #[inline]
+    pub fn custom_border_color_format(mut self, format: Option<Format>) -> Self {
+        self.custom_border_color_format = format;
+        self
+    }
+
     /// Sets whether unnormalized texture coordinates are enabled.
     ///
     /// When a sampler is set to use unnormalized coordinates as input, the texture coordinates are
@@ -1002,6 +1118,12 @@ pub enum SamplerCreationError {
         maximum: f32,
     },
 
+    /// Too many samplers with a custom border color exist simultaneously.
+    MaxCustomBorderColorSamplersExceeded {
+        /// The maximum supported number of samplers.
+        maximum: u32,
+    },
+
     /// The requested mip lod bias exceeds the device's limits.
     MaxSamplerLodBiasExceeded {
         /// The value that was requested.
@@ -1094,6 +1216,9 @@ impl fmt::Display for SamplerCreationError {
             Self::MaxSamplerAnisotropyExceeded { .. } => {
                 write!(fmt, "max_sampler_anisotropy limit exceeded")
             }
+            Self::MaxCustomBorderColorSamplersExceeded { .. } => {
+                write!(fmt, "max_custom_border_color_samplers limit exceeded")
+            }
             Self::MaxSamplerLodBiasExceeded { .. } => write!(fmt, "mip lod bias limit exceeded"),
             Self::SamplerYcbcrConversionAnisotropyEnabled => write!(
                 fmt,
@@ -1399,33 +1524,57 @@ impl From<SamplerAddressMode> for ash::vk::SamplerAddressMode {
 /// Only relevant if you use `ClampToBorder`.
 ///
 /// Using a border color restricts the sampler to either floating-point images or integer images.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BorderColor {
     /// The value `(0.0, 0.0, 0.0, 0.0)`. Can only be used with floating-point images.
-    FloatTransparentBlack = ash::vk::BorderColor::FLOAT_TRANSPARENT_BLACK.as_raw(),
+    FloatTransparentBlack,
 
     /// The value `(0, 0, 0, 0)`. Can only be used with integer images.
-    IntTransparentBlack = ash::vk::BorderColor::INT_TRANSPARENT_BLACK.as_raw(),
+    IntTransparentBlack,
 
     /// The value `(0.0, 0.0, 0.0, 1.0)`. Can only be used with floating-point identity-swizzled
     /// images.
-    FloatOpaqueBlack = ash::vk::BorderColor::FLOAT_OPAQUE_BLACK.as_raw(),
+    FloatOpaqueBlack,
 
     /// The value `(0, 0, 0, 1)`. Can only be used with integer identity-swizzled images.
-    IntOpaqueBlack = ash::vk::BorderColor::INT_OPAQUE_BLACK.as_raw(),
+    IntOpaqueBlack,
 
     /// The value `(1.0, 1.0, 1.0, 1.0)`. Can only be used with floating-point images.
-    FloatOpaqueWhite = ash::vk::BorderColor::FLOAT_OPAQUE_WHITE.as_raw(),
+    FloatOpaqueWhite,
 
     /// The value `(1, 1, 1, 1)`. Can only be used with integer images.
-    IntOpaqueWhite = ash::vk::BorderColor::INT_OPAQUE_WHITE.as_raw(),
+    IntOpaqueWhite,
+
+    /// A custom floating-point value. Can only be used with floating-point images.
+    ///
+    /// The [`ext_custom_border_color`](crate::device::DeviceExtensions::ext_custom_border_color)
+    /// extension and the
+    /// [`custom_border_colors`](crate::device::Features::custom_border_colors) feature must be
+    /// enabled on the device.
+    FloatCustom([f32; 4]),
+
+    /// A custom integer value. Can only be used with integer images.
+    ///
+    /// The [`ext_custom_border_color`](crate::device::DeviceExtensions::ext_custom_border_color)
+    /// extension and the
+    /// [`custom_border_colors`](crate::device::Features::custom_border_colors) feature must be
+    /// enabled on the device.
+    IntCustom([i32; 4]),
 }
 
 impl From<BorderColor> for ash::vk::BorderColor {
     #[inline]
     fn from(val: BorderColor) -> Self {
-        Self::from_raw(val as i32)
+        match val {
+            BorderColor::FloatTransparentBlack => Self::FLOAT_TRANSPARENT_BLACK,
+            BorderColor::IntTransparentBlack => Self::INT_TRANSPARENT_BLACK,
+            BorderColor::FloatOpaqueBlack => Self::FLOAT_OPAQUE_BLACK,
+            BorderColor::IntOpaqueBlack => Self::INT_OPAQUE_BLACK,
+            BorderColor::FloatOpaqueWhite => Self::FLOAT_OPAQUE_WHITE,
+            BorderColor::IntOpaqueWhite => Self::INT_OPAQUE_WHITE,
+            BorderColor::FloatCustom(_) => Self::FLOAT_CUSTOM_EXT,
+            BorderColor::IntCustom(_) => Self::INT_CUSTOM_EXT,
+        }
     }
 }
 
@@ -1489,6 +1638,10 @@ pub enum SamplerImageViewIncompatibleError {
     /// supported by the image view's format features.
     FilterCubicMinmaxNotSupported,
 
+    /// The sampler uses a `Min` or `Max` reduction mode, but this is not supported by the image
+    /// view's format features.
+    FilterMinmaxNotSupported,
+
     /// The sampler uses a linear mipmap mode, but this is not supported by the image view's format
     /// features.
     MipmapModeLinearNotSupported,
@@ -1513,6 +1666,7 @@ impl fmt::Display for SamplerImageViewIncompatibleError {
             Self::FilterLinearNotSupported => write!(fmt, "the sampler uses a linear filter, but this is not supported by the image view's format features"),
             Self::FilterCubicNotSupported => write!(fmt, "the sampler uses a cubic filter, but this is not supported by the image view's format features"),
             Self::FilterCubicMinmaxNotSupported => write!(fmt, "the sampler uses a cubic filter with a `Min` or `Max` reduction mode, but this is not supported by the image view's format features"),
+            Self::FilterMinmaxNotSupported => write!(fmt, "the sampler uses a `Min` or `Max` reduction mode, but this is not supported by the image view's format features"),
             Self::MipmapModeLinearNotSupported => write!(fmt, "the sampler uses a linear mipmap mode, but this is not supported by the image view's format features"),
             Self::UnnormalizedCoordinatesMultipleMipLevels => write!(fmt, "the sampler uses unnormalized coordinates, but the image view has multiple mip levels"),
             Self::UnnormalizedCoordinatesViewTypeNotCompatible => write!(fmt, "the sampler uses unnormalized coordinates, but the image view has a type other than `Dim1d` or `Dim2d`"),
@@ -1525,7 +1679,8 @@ mod tests {
     use crate::{
         pipeline::graphics::depth_stencil::CompareOp,
         sampler::{
-            Filter, Sampler, SamplerAddressMode, SamplerCreationError, SamplerReductionMode,
+            BorderColor, Filter, Sampler, SamplerAddressMode, SamplerCreationError,
+            SamplerReductionMode,
         },
     };
 
@@ -1719,4 +1874,22 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn custom_border_color_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let r = Sampler::start(device)
+            .address_mode(SamplerAddressMode::ClampToBorder)
+            .border_color(BorderColor::FloatCustom([0.25, 0.5, 0.75, 1.0]))
+            .build();
+
+        match r {
+            Err(SamplerCreationError::ExtensionNotEnabled {
+                extension: "ext_custom_border_color",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
 }
@@ -50,6 +50,8 @@
 //!         ty: DescriptorType::CombinedImageSampler,
 //!         descriptor_count: 1,
 //!         variable_count: false,
+//!         partially_bound: false,
+//!         update_after_bind: false,
 //!         stages: ShaderStage::Fragment.into(),
 //!         immutable_samplers: vec![sampler],
 //!     })]),
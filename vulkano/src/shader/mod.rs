@@ -48,6 +48,9 @@ use std::sync::Arc;
 pub mod reflect;
 pub mod spirv;
 
+#[cfg(feature = "shaderc")]
+pub use shaderc::{IncludeType, ResolvedInclude};
+
 use spirv::ExecutionModel;
 
 // Generated by build.rs
@@ -220,6 +223,100 @@ impl ShaderModule {
         )
     }
 
+    /// Compiles GLSL source code to SPIR-V at runtime, and builds a new shader module from it.
+    ///
+    /// `#include` directives are not supported; use `from_glsl_with_includes` if you need them.
+    ///
+    /// Only available if the `shaderc` feature of vulkano is enabled.
+    ///
+    /// # Safety
+    ///
+    /// - The produced SPIR-V code is not validated beyond the minimum needed to extract the
+    ///   information.
+    #[cfg(feature = "shaderc")]
+    pub unsafe fn from_glsl(
+        device: Arc<Device>,
+        source: &str,
+        stage: ShaderStage,
+        entry_point: &str,
+    ) -> Result<Arc<ShaderModule>, ShaderCreationError> {
+        Self::from_glsl_with_includes(device, source, stage, entry_point, |name, _, _, _| {
+            Err(format!(
+                "cannot include `{}`: includes are not supported",
+                name
+            ))
+        })
+    }
+
+    /// As `from_glsl`, but resolves `#include` directives by calling `include_callback`.
+    ///
+    /// The callback receives the requested source name, the type of include, the name of the
+    /// source containing the directive and the current include depth. It must return either the
+    /// resolved name and content of the included source, or an error message.
+    ///
+    /// Only available if the `shaderc` feature of vulkano is enabled.
+    ///
+    /// # Safety
+    ///
+    /// - The produced SPIR-V code is not validated beyond the minimum needed to extract the
+    ///   information.
+    #[cfg(feature = "shaderc")]
+    pub unsafe fn from_glsl_with_includes<F>(
+        device: Arc<Device>,
+        source: &str,
+        stage: ShaderStage,
+        entry_point: &str,
+        include_callback: F,
+    ) -> Result<Arc<ShaderModule>, ShaderCreationError>
+    where
+        F: Fn(&str, IncludeType, &str, usize) -> Result<ResolvedInclude, String>,
+    {
+        let mut compiler = shaderc::Compiler::new().ok_or_else(|| {
+            ShaderCreationError::CompilationError("failed to create GLSL compiler".into())
+        })?;
+        let mut compile_options = shaderc::CompileOptions::new().ok_or_else(|| {
+            ShaderCreationError::CompilationError("failed to initialize compile options".into())
+        })?;
+
+        let vulkan_version = if device.api_version() >= Version::V1_2 {
+            shaderc::EnvVersion::Vulkan1_2
+        } else if device.api_version() >= Version::V1_1 {
+            shaderc::EnvVersion::Vulkan1_1
+        } else {
+            shaderc::EnvVersion::Vulkan1_0
+        };
+        compile_options.set_target_env(shaderc::TargetEnv::Vulkan, vulkan_version as u32);
+        compile_options.set_include_callback(include_callback);
+
+        let kind = match stage {
+            ShaderStage::Vertex => shaderc::ShaderKind::Vertex,
+            ShaderStage::TessellationControl => shaderc::ShaderKind::TessControl,
+            ShaderStage::TessellationEvaluation => shaderc::ShaderKind::TessEvaluation,
+            ShaderStage::Geometry => shaderc::ShaderKind::Geometry,
+            ShaderStage::Fragment => shaderc::ShaderKind::Fragment,
+            ShaderStage::Compute => shaderc::ShaderKind::Compute,
+            ShaderStage::Raygen => shaderc::ShaderKind::RayGeneration,
+            ShaderStage::AnyHit => shaderc::ShaderKind::AnyHit,
+            ShaderStage::ClosestHit => shaderc::ShaderKind::ClosestHit,
+            ShaderStage::Miss => shaderc::ShaderKind::Miss,
+            ShaderStage::Intersection => shaderc::ShaderKind::Intersection,
+            ShaderStage::Callable => shaderc::ShaderKind::Callable,
+        };
+
+        let artifact = compiler
+            .compile_into_spirv(
+                source,
+                kind,
+                // An arbitrary placeholder file name, used in diagnostics and include callbacks
+                "shader.glsl",
+                entry_point,
+                Some(&compile_options),
+            )
+            .map_err(|err| ShaderCreationError::CompilationError(err.to_string()))?;
+
+        Self::from_words(device, artifact.as_binary())
+    }
+
     /// Returns information about the entry point with the provided name. Returns `None` if no entry
     /// point with that name exists in the shader module or if multiple entry points with the same
     /// name exist.
@@ -278,6 +375,11 @@ impl Drop for ShaderModule {
 #[derive(Clone, Debug)]
 pub enum ShaderCreationError {
     OomError(OomError),
+    /// Compiling the shader source code to SPIR-V failed. Contains the compiler's diagnostics.
+    ///
+    /// This is only returned by the functions that are enabled by the `shaderc` feature, but the
+    /// variant always exists, so that enabling the feature doesn't break exhaustive matches.
+    CompilationError(String),
     SpirvCapabilityNotSupported {
         capability: Capability,
         reason: ShaderSupportError,
@@ -298,6 +400,7 @@ impl Error for ShaderCreationError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::OomError(err) => Some(err),
+            Self::CompilationError(_) => None,
             Self::SpirvCapabilityNotSupported { reason, .. } => Some(reason),
             Self::SpirvError(err) => Some(err),
             Self::SpirvExtensionNotSupported { reason, .. } => Some(reason),
@@ -310,6 +413,7 @@ impl Display for ShaderCreationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::CompilationError(msg) => write!(f, "failed to compile the shader: {}", msg),
             Self::SpirvCapabilityNotSupported { capability, .. } => write!(
                 f,
                 "the SPIR-V capability {:?} enabled by the shader is not supported by the device",
@@ -455,6 +559,12 @@ pub enum ShaderExecution {
     Geometry(GeometryShaderExecution),
     Fragment,
     Compute,
+    RayGeneration,
+    AnyHit,
+    ClosestHit,
+    Miss,
+    Intersection,
+    Callable,
 }
 
 /*#[derive(Clone, Copy, Debug)]
@@ -553,6 +663,11 @@ pub struct DescriptorRequirements {
     /// This is `None` for non-image descriptors.
     pub image_view_type: Option<ImageViewType>,
 
+    /// For input attachment bindings, the input attachment index of the first descriptor, as
+    /// given by the `InputAttachmentIndex` decoration. Each following descriptor of an array uses
+    /// the next input attachment of the subpass.
+    pub input_attachment_index: Option<u32>,
+
     /// For sampler bindings, the descriptor indices that require a depth comparison sampler.
     pub sampler_compare: FnvHashSet<u32>,
 
@@ -646,6 +761,7 @@ impl DescriptorRequirements {
             image_multisampled: self.image_multisampled,
             image_scalar_type: self.image_scalar_type.or(other.image_scalar_type),
             image_view_type: self.image_view_type.or(other.image_view_type),
+            input_attachment_index: self.input_attachment_index.or(other.input_attachment_index),
             sampler_compare: &self.sampler_compare | &other.sampler_compare,
             sampler_no_unnormalized_coordinates: &self.sampler_no_unnormalized_coordinates
                 | &other.sampler_no_unnormalized_coordinates,
@@ -1060,6 +1176,12 @@ impl From<ShaderExecution> for ShaderStage {
             ShaderExecution::Geometry(_) => Self::Geometry,
             ShaderExecution::Fragment => Self::Fragment,
             ShaderExecution::Compute => Self::Compute,
+            ShaderExecution::RayGeneration => Self::Raygen,
+            ShaderExecution::AnyHit => Self::AnyHit,
+            ShaderExecution::ClosestHit => Self::ClosestHit,
+            ShaderExecution::Miss => Self::Miss,
+            ShaderExecution::Intersection => Self::Intersection,
+            ShaderExecution::Callable => Self::Callable,
         }
     }
 }
@@ -154,15 +154,14 @@ fn shader_execution(
 
         ExecutionModel::GLCompute => ShaderExecution::Compute,
 
-        ExecutionModel::Kernel
-        | ExecutionModel::TaskNV
-        | ExecutionModel::MeshNV
-        | ExecutionModel::RayGenerationKHR
-        | ExecutionModel::IntersectionKHR
-        | ExecutionModel::AnyHitKHR
-        | ExecutionModel::ClosestHitKHR
-        | ExecutionModel::MissKHR
-        | ExecutionModel::CallableKHR => {
+        ExecutionModel::RayGenerationKHR => ShaderExecution::RayGeneration,
+        ExecutionModel::AnyHitKHR => ShaderExecution::AnyHit,
+        ExecutionModel::ClosestHitKHR => ShaderExecution::ClosestHit,
+        ExecutionModel::MissKHR => ShaderExecution::Miss,
+        ExecutionModel::IntersectionKHR => ShaderExecution::Intersection,
+        ExecutionModel::CallableKHR => ShaderExecution::Callable,
+
+        ExecutionModel::Kernel | ExecutionModel::TaskNV | ExecutionModel::MeshNV => {
             todo!()
         }
     }
@@ -849,6 +848,11 @@ fn descriptor_requirements_of(spirv: &Spirv, variable_id: Id) -> DescriptorVaria
                 Some(image_type)
             }
 
+            &Instruction::TypeAccelerationStructureKHR { .. } => {
+                reqs.descriptor_types = vec![DescriptorType::AccelerationStructure];
+                None
+            }
+
             &Instruction::TypeArray {
                 element_type,
                 length,
@@ -884,6 +888,17 @@ fn descriptor_requirements_of(spirv: &Spirv, variable_id: Id) -> DescriptorVaria
         };
     }
 
+    reqs.input_attachment_index =
+        variable_id_info
+            .iter_decoration()
+            .find_map(|instruction| match instruction {
+                Instruction::Decorate {
+                    decoration: Decoration::InputAttachmentIndex { attachment_index },
+                    ..
+                } => Some(*attachment_index),
+                _ => None,
+            });
+
     DescriptorVariable {
         set: variable_id_info
             .iter_decoration()
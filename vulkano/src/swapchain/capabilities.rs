@@ -49,6 +49,12 @@ pub struct Capabilities {
     /// the `color_attachment` usage is guaranteed to be supported.
     pub supported_usage_flags: ImageUsage,
 
+    /// Whether a swapchain created with this surface can use protected images.
+    ///
+    /// This is always `false` unless the `khr_surface_protected_capabilities` extension is
+    /// enabled on the instance.
+    pub supports_protected: bool,
+
     /// List of formats supported for the swapchain.
     pub supported_formats: Vec<(Format, ColorSpace)>, // TODO: https://github.com/KhronosGroup/Vulkan-Docs/issues/207
 
@@ -166,6 +172,37 @@ impl SupportedPresentModes {
         .into_iter()
         .filter(move |&mode| moved.supports(mode))
     }
+
+    /// Returns the first mode of `preferred` that is in this list of supported modes, or `Fifo`
+    /// if none of them are.
+    ///
+    /// Since `Fifo` is guaranteed to be supported, the returned mode can always be used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::swapchain::{PresentMode, SupportedPresentModes};
+    ///
+    /// let supported = SupportedPresentModes {
+    ///     fifo: true,
+    ///     immediate: true,
+    ///     ..SupportedPresentModes::none()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     supported.choose(&[PresentMode::Mailbox, PresentMode::Immediate]),
+    ///     PresentMode::Immediate,
+    /// );
+    /// assert_eq!(supported.choose(&[PresentMode::Mailbox]), PresentMode::Fifo);
+    /// ```
+    #[inline]
+    pub fn choose(&self, preferred: &[PresentMode]) -> PresentMode {
+        preferred
+            .iter()
+            .copied()
+            .find(|&mode| self.supports(mode))
+            .unwrap_or(PresentMode::Fifo)
+    }
 }
 
 /// A transformation to apply to the image before showing it on the screen.
@@ -308,20 +308,24 @@ pub use self::present_region::PresentRegion;
 pub use self::present_region::RectangleLayer;
 pub use self::surface::CapabilitiesError;
 pub use self::surface::Surface;
+pub use self::surface::SurfaceApi;
 pub use self::surface::SurfaceCreationError;
 pub use self::swapchain::acquire_next_image;
 pub use self::swapchain::acquire_next_image_raw;
 pub use self::swapchain::present;
 pub use self::swapchain::present_incremental;
+pub use self::swapchain::try_acquire_next_image;
 pub use self::swapchain::AcquireError;
 pub use self::swapchain::AcquiredImage;
 pub use self::swapchain::FullscreenExclusive;
 pub use self::swapchain::FullscreenExclusiveError;
+pub use self::swapchain::HdrMetadata;
 pub use self::swapchain::PresentFuture;
 pub use self::swapchain::Swapchain;
 pub use self::swapchain::SwapchainAcquireFuture;
 pub use self::swapchain::SwapchainBuilder;
 pub use self::swapchain::SwapchainCreationError;
+pub use self::swapchain::Win32Monitor;
 use std::sync::atomic::AtomicBool;
 
 mod capabilities;
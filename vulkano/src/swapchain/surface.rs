@@ -36,6 +36,7 @@ pub struct Surface<W> {
     window: W,
     instance: Arc<Instance>,
     surface: ash::vk::SurfaceKHR,
+    api: SurfaceApi,
 
     // If true, a swapchain has been associated to this surface, and that any new swapchain
     // creation should be forbidden.
@@ -45,17 +46,21 @@ pub struct Surface<W> {
 impl<W> Surface<W> {
     /// Creates a `Surface` given the raw handler.
     ///
+    /// `api` must be the windowing API that the surface was created with.
+    ///
     /// Be careful when using it
     ///
     pub unsafe fn from_raw_surface(
         instance: Arc<Instance>,
         surface: ash::vk::SurfaceKHR,
+        api: SurfaceApi,
         win: W,
     ) -> Surface<W> {
         Surface {
             window: win,
             instance,
             surface,
+            api,
             has_swapchain: AtomicBool::new(false),
         }
     }
@@ -123,6 +128,7 @@ impl<W> Surface<W> {
             window: (),
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::DisplayPlane,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -172,6 +178,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::Win32,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -220,6 +227,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::Xcb,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -268,6 +276,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::Xlib,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -316,6 +325,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::Wayland,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -360,6 +370,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::Android,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -405,6 +416,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::Ios,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -450,6 +462,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::MacOs,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -494,6 +507,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::Metal,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -538,6 +552,7 @@ impl<W> Surface<W> {
             window: win,
             instance: instance.clone(),
             surface,
+            api: SurfaceApi::Vi,
             has_swapchain: AtomicBool::new(false),
         }))
     }
@@ -561,6 +576,10 @@ impl<W> Surface<W> {
 
     /// Retrieves the capabilities of a surface when used by a certain device.
     ///
+    /// If the `khr_get_surface_capabilities2` extension is enabled on the instance,
+    /// `vkGetPhysicalDeviceSurfaceCapabilities2KHR` is used, which also allows querying
+    /// protected capabilities.
+    ///
     /// # Notes
     ///
     /// - Capabilities that are not supported in `vk-sys` are silently dropped
@@ -579,7 +598,41 @@ impl<W> Surface<W> {
 
             let fns = self.instance.fns();
 
-            let caps = {
+            let (caps, supports_protected) = if self
+                .instance
+                .enabled_extensions()
+                .khr_get_surface_capabilities2
+            {
+                let surface_info = ash::vk::PhysicalDeviceSurfaceInfo2KHR {
+                    surface: self.surface,
+                    ..Default::default()
+                };
+
+                let mut protected_caps = ash::vk::SurfaceProtectedCapabilitiesKHR::default();
+                let mut caps2 = ash::vk::SurfaceCapabilities2KHR::default();
+
+                if self
+                    .instance
+                    .enabled_extensions()
+                    .khr_surface_protected_capabilities
+                {
+                    caps2.p_next = &mut protected_caps as *mut _ as *mut _;
+                }
+
+                check_errors(
+                    fns.khr_get_surface_capabilities2
+                        .get_physical_device_surface_capabilities2_khr(
+                            device.internal_object(),
+                            &surface_info,
+                            &mut caps2,
+                        ),
+                )?;
+
+                (
+                    caps2.surface_capabilities,
+                    protected_caps.supports_protected != ash::vk::FALSE,
+                )
+            } else {
                 let mut out: MaybeUninit<ash::vk::SurfaceCapabilitiesKHR> = MaybeUninit::uninit();
                 check_errors(
                     fns.khr_surface
@@ -589,7 +642,7 @@ impl<W> Surface<W> {
                             out.as_mut_ptr(),
                         ),
                 )?;
-                out.assume_init()
+                (out.assume_init(), false)
             };
 
             let formats = {
@@ -672,6 +725,7 @@ impl<W> Surface<W> {
                     debug_assert!(usage.color_attachment); // specs say that this must be true
                     usage
                 },
+                supports_protected,
                 supported_formats: formats
                     .into_iter()
                     .filter_map(|f| {
@@ -696,6 +750,37 @@ impl<W> Surface<W> {
     pub fn instance(&self) -> &Arc<Instance> {
         &self.instance
     }
+
+    /// Returns the windowing API that the surface was created with.
+    #[inline]
+    pub fn api(&self) -> SurfaceApi {
+        self.api
+    }
+}
+
+/// The windowing API that a surface was created with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SurfaceApi {
+    /// A display plane, created with `from_display_mode`.
+    DisplayPlane,
+    /// A Win32 window, created with `from_hwnd`.
+    Win32,
+    /// An XCB window, created with `from_xcb`.
+    Xcb,
+    /// An Xlib window, created with `from_xlib`.
+    Xlib,
+    /// A Wayland surface, created with `from_wayland`.
+    Wayland,
+    /// An Android native window, created with `from_anativewindow`.
+    Android,
+    /// An iOS view, created with `from_ios_moltenvk`.
+    Ios,
+    /// A macOS view, created with `from_macos_moltenvk`.
+    MacOs,
+    /// A Metal layer, created with `from_metal_layer`.
+    Metal,
+    /// A `nn::vi::Layer`, created with `from_vi_surface`.
+    Vi,
 }
 
 unsafe impl<W> SurfaceSwapchainLock for Surface<W> {
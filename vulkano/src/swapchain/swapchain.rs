@@ -34,6 +34,7 @@ use crate::swapchain::CompositeAlpha;
 use crate::swapchain::PresentMode;
 use crate::swapchain::PresentRegion;
 use crate::swapchain::Surface;
+use crate::swapchain::SurfaceApi;
 use crate::swapchain::SurfaceSwapchainLock;
 use crate::swapchain::SurfaceTransform;
 use crate::sync::semaphore::SemaphoreError;
@@ -89,6 +90,50 @@ impl From<FullscreenExclusive> for ash::vk::FullScreenExclusiveEXT {
     }
 }
 
+/// HDR metadata of a swapchain, as set by [`Swapchain::set_hdr_metadata`].
+///
+/// Chromaticity coordinates are given in the CIE 1931 xy color space, luminance values in nits
+/// (candela per square meter).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct HdrMetadata {
+    /// The chromaticity of the red primary of the mastering display.
+    pub display_primary_red: [f32; 2],
+    /// The chromaticity of the green primary of the mastering display.
+    pub display_primary_green: [f32; 2],
+    /// The chromaticity of the blue primary of the mastering display.
+    pub display_primary_blue: [f32; 2],
+    /// The chromaticity of the white point of the mastering display.
+    pub white_point: [f32; 2],
+    /// The maximum luminance of the mastering display.
+    pub max_luminance: f32,
+    /// The minimum luminance of the mastering display.
+    pub min_luminance: f32,
+    /// The maximum luminance of any pixel of the content (MaxCLL).
+    pub max_content_light_level: f32,
+    /// The maximum average luminance of any frame of the content (MaxFALL).
+    pub max_frame_average_light_level: f32,
+}
+
+/// A wrapper around a Win32 monitor handle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Win32Monitor(ash::vk::HMONITOR);
+
+impl Win32Monitor {
+    /// Wraps a Win32 monitor handle.
+    ///
+    /// # Safety
+    ///
+    /// - `hmonitor` must be a valid handle as returned by the Win32 API.
+    #[inline]
+    pub unsafe fn new<T>(hmonitor: *const T) -> Self {
+        Self(hmonitor as _)
+    }
+}
+
+// A monitor handle is only an identifier, and is not tied to the thread that obtained it.
+unsafe impl Send for Win32Monitor {}
+unsafe impl Sync for Win32Monitor {}
+
 /// Tries to take ownership of an image in order to draw on it.
 ///
 /// The function returns the index of the image in the array of images that was returned
@@ -142,6 +187,17 @@ pub fn acquire_next_image<W>(
     ))
 }
 
+/// Tries to take ownership of an image in order to draw on it, without blocking.
+///
+/// This is the same as calling [`acquire_next_image`] with a timeout of zero. If no image is
+/// available right away, [`AcquireError::NotReady`] is returned.
+#[inline]
+pub fn try_acquire_next_image<W>(
+    swapchain: Arc<Swapchain<W>>,
+) -> Result<(usize, bool, SwapchainAcquireFuture<W>), AcquireError> {
+    acquire_next_image(swapchain, Some(Duration::from_secs(0)))
+}
+
 /// Presents an image on the screen.
 ///
 /// The parameter is the same index as what `acquire_next_image` returned. The image must
@@ -246,7 +302,9 @@ pub struct Swapchain<W> {
     present_mode: PresentMode,
     fullscreen_exclusive: FullscreenExclusive,
     fullscreen_exclusive_held: AtomicBool,
+    win32_monitor: Option<Win32Monitor>,
     clipped: bool,
+    protected: bool,
 }
 
 struct ImageEntry {
@@ -275,7 +333,9 @@ impl<W> Swapchain<W> {
             composite_alpha: CompositeAlpha::Opaque,
             present_mode: PresentMode::Fifo,
             fullscreen_exclusive: FullscreenExclusive::Default,
+            win32_monitor: None,
             clipped: true,
+            protected: false,
 
             old_swapchain: None,
         }
@@ -303,7 +363,9 @@ impl<W> Swapchain<W> {
             composite_alpha: self.composite_alpha,
             present_mode: self.present_mode,
             fullscreen_exclusive: self.fullscreen_exclusive,
+            win32_monitor: self.win32_monitor,
             clipped: self.clipped,
+            protected: self.protected,
 
             old_swapchain: Some(self.clone()),
         }
@@ -375,12 +437,24 @@ impl<W> Swapchain<W> {
         self.clipped
     }
 
+    /// Returns the value of `protected` that was passed when creating the swapchain.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
+
     /// Returns the value of 'fullscreen_exclusive` that was passed when creating the swapchain.
     #[inline]
     pub fn fullscreen_exclusive(&self) -> FullscreenExclusive {
         self.fullscreen_exclusive
     }
 
+    /// Returns the value of `win32_monitor` that was passed when creating the swapchain.
+    #[inline]
+    pub fn win32_monitor(&self) -> Option<Win32Monitor> {
+        self.win32_monitor
+    }
+
     /// `FullscreenExclusive::AppControlled` must be the active fullscreen exclusivity mode.
     /// Acquire fullscreen exclusivity until either the `release_fullscreen_exclusive` is
     /// called, or if any of the the other `Swapchain` functions return `FullscreenExclusiveLost`.
@@ -447,6 +521,44 @@ impl<W> Swapchain<W> {
         }
     }
 
+    /// Sets the HDR metadata of the swapchain, which describes the mastering display and the
+    /// content of the images, to help the presentation engine map them to the actual display.
+    ///
+    /// The metadata takes effect the next time an image of the swapchain is presented.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `ext_hdr_metadata` extension is not enabled on the device.
+    pub fn set_hdr_metadata(&self, metadata: HdrMetadata) {
+        assert!(
+            self.device.enabled_extensions().ext_hdr_metadata,
+            "the ext_hdr_metadata extension must be enabled on the device"
+        );
+
+        let xy = |[x, y]: [f32; 2]| ash::vk::XYColorEXT { x, y };
+        let metadata = ash::vk::HdrMetadataEXT {
+            display_primary_red: xy(metadata.display_primary_red),
+            display_primary_green: xy(metadata.display_primary_green),
+            display_primary_blue: xy(metadata.display_primary_blue),
+            white_point: xy(metadata.white_point),
+            max_luminance: metadata.max_luminance,
+            min_luminance: metadata.min_luminance,
+            max_content_light_level: metadata.max_content_light_level,
+            max_frame_average_light_level: metadata.max_frame_average_light_level,
+            ..Default::default()
+        };
+
+        unsafe {
+            let fns = self.device.fns();
+            fns.ext_hdr_metadata.set_hdr_metadata_ext(
+                self.device.internal_object(),
+                1,
+                &self.swapchain,
+                &metadata,
+            );
+        }
+    }
+
     // This method is necessary to allow `SwapchainImage`s to signal when they have been
     // transitioned out of their initial `undefined` image layout.
     //
@@ -523,7 +635,9 @@ pub struct SwapchainBuilder<W> {
     composite_alpha: CompositeAlpha,
     present_mode: PresentMode,
     fullscreen_exclusive: FullscreenExclusive,
+    win32_monitor: Option<Win32Monitor>,
     clipped: bool,
+    protected: bool,
 }
 
 impl<W> SwapchainBuilder<W> {
@@ -562,7 +676,9 @@ impl<W> SwapchainBuilder<W> {
             composite_alpha,
             present_mode,
             fullscreen_exclusive,
+            win32_monitor,
             clipped,
+            protected,
         } = self;
 
         assert_eq!(
@@ -647,8 +763,14 @@ impl<W> SwapchainBuilder<W> {
         if !capabilities.present_modes.supports(present_mode) {
             return Err(SwapchainCreationError::UnsupportedPresentMode);
         }
+        if protected && !capabilities.supports_protected {
+            return Err(SwapchainCreationError::UnsupportedProtected);
+        }
 
-        let flags = ImageCreateFlags::none();
+        let flags = ImageCreateFlags {
+            protected,
+            ..ImageCreateFlags::none()
+        };
 
         // check that the physical device supports the swapchain image configuration
         if device
@@ -685,6 +807,7 @@ impl<W> SwapchainBuilder<W> {
         }
 
         let mut surface_full_screen_exclusive_info = None;
+        let mut surface_full_screen_exclusive_win32_info = None;
 
         // TODO: VK_EXT_FULL_SCREEN_EXCLUSIVE requires these extensions, so they should always
         // be enabled if it is. A separate check here is unnecessary; this should be checked at
@@ -699,8 +822,26 @@ impl<W> SwapchainBuilder<W> {
                 .enabled_extensions()
                 .khr_get_surface_capabilities2
         {
+            if fullscreen_exclusive == FullscreenExclusive::AppControlled
+                && surface.api() == SurfaceApi::Win32
+                && win32_monitor.is_none()
+            {
+                return Err(SwapchainCreationError::Win32MonitorMissing);
+            }
+
+            surface_full_screen_exclusive_win32_info = win32_monitor.map(|win32_monitor| {
+                ash::vk::SurfaceFullScreenExclusiveWin32InfoEXT {
+                    hmonitor: win32_monitor.0,
+                    ..Default::default()
+                }
+            });
+
             surface_full_screen_exclusive_info = Some(ash::vk::SurfaceFullScreenExclusiveInfoEXT {
                 full_screen_exclusive: fullscreen_exclusive.into(),
+                p_next: match surface_full_screen_exclusive_win32_info.as_mut() {
+                    Some(some) => some as *mut _ as *mut _,
+                    None => ptr::null_mut(),
+                },
                 ..Default::default()
             });
         }
@@ -745,7 +886,11 @@ impl<W> SwapchainBuilder<W> {
 
             let infos = ash::vk::SwapchainCreateInfoKHR {
                 p_next,
-                flags: ash::vk::SwapchainCreateFlagsKHR::empty(),
+                flags: if protected {
+                    ash::vk::SwapchainCreateFlagsKHR::PROTECTED
+                } else {
+                    ash::vk::SwapchainCreateFlagsKHR::empty()
+                },
                 surface: surface.internal_object(),
                 min_image_count: num_images,
                 image_format: format.into(),
@@ -863,7 +1008,9 @@ impl<W> SwapchainBuilder<W> {
             present_mode,
             fullscreen_exclusive,
             fullscreen_exclusive_held: AtomicBool::new(fullscreen_exclusive_held),
+            win32_monitor,
             clipped,
+            protected,
         });
 
         let swapchain_images = unsafe {
@@ -981,6 +1128,19 @@ impl<W> SwapchainBuilder<W> {
         self
     }
 
+    /// Sets the Win32 monitor on which fullscreen exclusivity should be acquired.
+    ///
+    /// This only has an effect if the `ext_full_screen_exclusive` extension is enabled. On a
+    /// Win32 surface, it must be provided if `fullscreen_exclusive` is `AppControlled`, otherwise
+    /// [`build`](SwapchainBuilder::build) returns `SwapchainCreationError::Win32MonitorMissing`.
+    ///
+    /// The default is `None`.
+    #[inline]
+    pub fn win32_monitor(mut self, win32_monitor: Win32Monitor) -> Self {
+        self.win32_monitor = Some(win32_monitor);
+        self
+    }
+
     /// Sets whether the implementation is allowed to discard rendering operations that affect
     /// regions of the surface which aren't visible. This is important to take into account if
     /// your fragment shader has side-effects or if you want to read back the content of the image
@@ -992,6 +1152,19 @@ impl<W> SwapchainBuilder<W> {
         self.clipped = clipped;
         self
     }
+
+    /// Sets whether the images of the swapchain are protected images. Protected images can only
+    /// be accessed by protected queues, and their content can't be read back by the host.
+    ///
+    /// This requires the surface to support protected swapchains, as indicated by
+    /// [`Capabilities::supports_protected`](crate::swapchain::Capabilities::supports_protected).
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn protected(mut self, protected: bool) -> Self {
+        self.protected = protected;
+        self
+    }
 }
 
 /// Error that can happen when creation a swapchain.
@@ -1035,6 +1208,11 @@ pub enum SwapchainCreationError {
     UnsupportedPresentMode,
     /// The image configuration is not supported by the physical device.
     UnsupportedImageConfiguration,
+    /// A protected swapchain was requested, but the surface doesn't support it.
+    UnsupportedProtected,
+    /// Fullscreen exclusivity is `AppControlled` on a Win32 surface, but no Win32 monitor was
+    /// provided.
+    Win32MonitorMissing,
 }
 
 impl error::Error for SwapchainCreationError {
@@ -1105,6 +1283,12 @@ impl fmt::Display for SwapchainCreationError {
                 SwapchainCreationError::UnsupportedImageConfiguration => {
                     "the requested image configuration is not supported by the physical device"
                 }
+                SwapchainCreationError::UnsupportedProtected => {
+                    "a protected swapchain was requested, but the surface doesn't support it"
+                }
+                SwapchainCreationError::Win32MonitorMissing => {
+                    "fullscreen exclusivity is `AppControlled` on a Win32 surface, but no Win32 monitor was provided"
+                }
             }
         )
     }
@@ -1356,12 +1540,16 @@ pub enum AcquireError {
     /// Not enough memory.
     OomError(OomError),
 
-    /// The connection to the device has been lost.
+    /// The connection to the device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
     DeviceLost,
 
     /// The timeout of the function has been reached before an image was available.
     Timeout,
 
+    /// No image was available, and the function was called with a timeout of zero.
+    NotReady,
+
     /// The surface is no longer accessible and must be recreated.
     SurfaceLost,
 
@@ -1397,6 +1585,7 @@ impl fmt::Display for AcquireError {
                 AcquireError::OomError(_) => "not enough memory",
                 AcquireError::DeviceLost => "the connection to the device has been lost",
                 AcquireError::Timeout => "no image is available for acquiring yet",
+                AcquireError::NotReady => "no image is available for acquiring right now",
                 AcquireError::SurfaceLost => "the surface of this swapchain is no longer valid",
                 AcquireError::OutOfDate => "the swapchain needs to be recreated",
                 AcquireError::FullscreenExclusiveLost => {
@@ -1698,7 +1887,7 @@ pub unsafe fn acquire_next_image_raw<W>(
     };
 
     let mut out = MaybeUninit::uninit();
-    let r = check_errors(
+    let r = swapchain.device.check_errors(
         fns.khr_swapchain.acquire_next_image_khr(
             swapchain.device.internal_object(),
             swapchain.swapchain,
@@ -1717,7 +1906,7 @@ pub unsafe fn acquire_next_image_raw<W>(
     let (id, suboptimal) = match r {
         Success::Success => (out as usize, false),
         Success::Suboptimal => (out as usize, true),
-        Success::NotReady => return Err(AcquireError::Timeout),
+        Success::NotReady => return Err(AcquireError::NotReady),
         Success::Timeout => return Err(AcquireError::Timeout),
         s => panic!("unexpected success value: {:?}", s),
     };
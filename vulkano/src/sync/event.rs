@@ -10,9 +10,12 @@
 use crate::check_errors;
 use crate::device::Device;
 use crate::device::DeviceOwned;
+use crate::Error;
 use crate::OomError;
 use crate::Success;
 use crate::VulkanObject;
+use std::error;
+use std::fmt;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
@@ -94,10 +97,10 @@ impl Event {
 
     /// Returns true if the event is signaled.
     #[inline]
-    pub fn signaled(&self) -> Result<bool, OomError> {
+    pub fn signaled(&self) -> Result<bool, EventStatusError> {
         unsafe {
             let fns = self.device.fns();
-            let result = check_errors(
+            let result = self.device.check_errors(
                 fns.v1_0
                     .get_event_status(self.device.internal_object(), self.event),
             )?;
@@ -192,6 +195,53 @@ impl Drop for Event {
     }
 }
 
+/// Error that can be returned when checking whether an event is signaled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EventStatusError {
+    /// Not enough memory to complete the operation.
+    OomError(OomError),
+
+    /// The device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
+    DeviceLost,
+}
+
+impl error::Error for EventStatusError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            EventStatusError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for EventStatusError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                EventStatusError::OomError(_) => "no memory available",
+                EventStatusError::DeviceLost => "the device was lost",
+            }
+        )
+    }
+}
+
+impl From<Error> for EventStatusError {
+    #[inline]
+    fn from(err: Error) -> EventStatusError {
+        match err {
+            Error::OutOfHostMemory => EventStatusError::OomError(From::from(err)),
+            Error::OutOfDeviceMemory => EventStatusError::OomError(From::from(err)),
+            Error::DeviceLost => EventStatusError::DeviceLost,
+            _ => panic!("Unexpected error value: {}", err as i32),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sync::Event;
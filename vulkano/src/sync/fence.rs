@@ -128,15 +128,17 @@ where
     }
 
     /// Returns true if the fence is signaled.
+    ///
+    /// Returns `FenceWaitError::DeviceLostError` if the device has been lost.
     #[inline]
-    pub fn ready(&self) -> Result<bool, OomError> {
+    pub fn ready(&self) -> Result<bool, FenceWaitError> {
         unsafe {
             if self.signaled.load(Ordering::Relaxed) {
                 return Ok(true);
             }
 
             let fns = self.device.fns();
-            let result = check_errors(
+            let result = self.device.check_errors(
                 fns.v1_0
                     .get_fence_status(self.device.internal_object(), self.fence),
             )?;
@@ -172,7 +174,7 @@ where
             };
 
             let fns = self.device.fns();
-            let r = check_errors(fns.v1_0.wait_for_fences(
+            let r = self.device.check_errors(fns.v1_0.wait_for_fences(
                 self.device.internal_object(),
                 1,
                 &self.fence,
@@ -191,17 +193,48 @@ where
         }
     }
 
-    /// Waits for multiple fences at once.
+    /// Waits for multiple fences at once, until all of them are signaled.
     ///
     /// # Panic
     ///
     /// Panics if not all fences belong to the same device.
+    #[inline]
     pub fn multi_wait<'a, I>(iter: I, timeout: Option<Duration>) -> Result<(), FenceWaitError>
+    where
+        I: IntoIterator<Item = &'a Fence<D>>,
+        D: 'a,
+    {
+        Fence::multi_wait_impl(iter, timeout, true)
+    }
+
+    /// Waits for multiple fences at once, until at least one of them is signaled.
+    ///
+    /// Returns `Ok` if one of the fences is now signaled, or if no fences were passed.
+    /// Returns `Err` if the timeout was reached instead.
+    ///
+    /// # Panic
+    ///
+    /// Panics if not all fences belong to the same device.
+    #[inline]
+    pub fn multi_wait_any<'a, I>(iter: I, timeout: Option<Duration>) -> Result<(), FenceWaitError>
+    where
+        I: IntoIterator<Item = &'a Fence<D>>,
+        D: 'a,
+    {
+        Fence::multi_wait_impl(iter, timeout, false)
+    }
+
+    fn multi_wait_impl<'a, I>(
+        iter: I,
+        timeout: Option<Duration>,
+        wait_all: bool,
+    ) -> Result<(), FenceWaitError>
     where
         I: IntoIterator<Item = &'a Fence<D>>,
         D: 'a,
     {
         let mut device: Option<&Device> = None;
+        let mut any_signaled = false;
 
         let fences: SmallVec<[ash::vk::Fence; 8]> = iter
             .into_iter()
@@ -217,6 +250,7 @@ where
                 };
 
                 if fence.signaled.load(Ordering::Relaxed) {
+                    any_signaled = true;
                     None
                 } else {
                     Some(fence.fence)
@@ -224,6 +258,10 @@ where
             })
             .collect();
 
+        if fences.is_empty() || (any_signaled && !wait_all) {
+            return Ok(());
+        }
+
         let timeout_ns = if let Some(timeout) = timeout {
             timeout
                 .as_secs()
@@ -233,19 +271,20 @@ where
             u64::MAX
         };
 
-        let r = if let Some(device) = device {
-            unsafe {
-                let fns = device.fns();
-                check_errors(fns.v1_0.wait_for_fences(
-                    device.internal_object(),
-                    fences.len() as u32,
-                    fences.as_ptr(),
-                    ash::vk::TRUE,
-                    timeout_ns,
-                ))?
-            }
-        } else {
-            return Ok(());
+        let r = unsafe {
+            let device = device.unwrap();
+            let fns = device.fns();
+            device.check_errors(fns.v1_0.wait_for_fences(
+                device.internal_object(),
+                fences.len() as u32,
+                fences.as_ptr(),
+                if wait_all {
+                    ash::vk::TRUE
+                } else {
+                    ash::vk::FALSE
+                },
+                timeout_ns,
+            ))?
         };
 
         match r {
@@ -363,7 +402,8 @@ pub enum FenceWaitError {
     /// The specified timeout wasn't long enough.
     Timeout,
 
-    /// The device has been lost.
+    /// The device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
     DeviceLostError,
 }
 
@@ -407,6 +447,7 @@ impl From<Error> for FenceWaitError {
 #[cfg(test)]
 mod tests {
     use crate::sync::Fence;
+    use crate::sync::FenceWaitError;
     use crate::VulkanObject;
     use std::time::Duration;
 
@@ -443,6 +484,27 @@ mod tests {
         assert!(!fence.ready().unwrap());
     }
 
+    #[test]
+    fn multiwait_any() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence1 = Fence::alloc_signaled(device.clone()).unwrap();
+        let fence2 = Fence::alloc(device.clone()).unwrap();
+
+        Fence::multi_wait_any(
+            [&fence1, &fence2].iter().cloned(),
+            Some(Duration::new(0, 10)),
+        )
+        .unwrap();
+        assert_eq!(
+            Fence::multi_wait(
+                [&fence1, &fence2].iter().cloned(),
+                Some(Duration::new(0, 10))
+            ),
+            Err(FenceWaitError::Timeout)
+        );
+    }
+
     #[test]
     fn multiwait_different_devices() {
         let (device1, _) = gfx_dev_and_queue!();
@@ -24,6 +24,7 @@ use crate::image::ImageLayout;
 use crate::sync::AccessCheckError;
 use crate::sync::AccessFlags;
 use crate::sync::Fence;
+use crate::sync::FenceWaitError;
 use crate::sync::FlushError;
 use crate::sync::GpuFuture;
 use crate::sync::PipelineStages;
@@ -154,6 +155,22 @@ where
             _ => unreachable!(),
         }
     }
+
+    /// Returns true if the fence has been signaled by the GPU, without blocking.
+    ///
+    /// Returns false if the submission hasn't been flushed yet. Unlike `wait`, this function
+    /// doesn't flush the future.
+    pub fn is_signaled(&self) -> Result<bool, FenceWaitError> {
+        let state = self.state.lock().unwrap();
+
+        match *state {
+            FenceSignalFutureState::Pending(_, _)
+            | FenceSignalFutureState::PartiallyFlushed(_, _) => Ok(false),
+            FenceSignalFutureState::Flushed(_, ref fence) => fence.ready(),
+            FenceSignalFutureState::Cleaned => Ok(true),
+            FenceSignalFutureState::Poisoned => unreachable!(),
+        }
+    }
 }
 
 impl<F> FenceSignalFuture<F>
@@ -514,3 +531,48 @@ where
         (**self).check_image_access(image, layout, exclusive, queue)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::command_buffer::AutoCommandBufferBuilder;
+    use crate::command_buffer::CommandBufferUsage;
+    use crate::command_buffer::PrimaryAutoCommandBuffer;
+    use crate::device::Device;
+    use crate::device::Queue;
+    use crate::sync::now;
+    use crate::sync::GpuFuture;
+    use std::sync::Arc;
+
+    fn empty_command_buffer(device: Arc<Device>, queue: &Queue) -> PrimaryAutoCommandBuffer {
+        AutoCommandBufferBuilder::primary(device, queue.family(), CommandBufferUsage::OneTimeSubmit)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn is_signaled_unflushed() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let command_buffer = empty_command_buffer(device.clone(), &queue);
+
+        let future = now(device)
+            .then_execute(queue, command_buffer)
+            .unwrap()
+            .then_signal_fence();
+        assert!(!future.is_signaled().unwrap());
+    }
+
+    #[test]
+    fn is_signaled_flushed() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let command_buffer = empty_command_buffer(device.clone(), &queue);
+
+        let future = now(device)
+            .then_execute(queue.clone(), command_buffer)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        queue.wait().unwrap();
+        assert!(future.is_signaled().unwrap());
+    }
+}
@@ -480,7 +480,8 @@ pub enum FlushError {
     /// Not enough memory.
     OomError(OomError),
 
-    /// The connection to the device has been lost.
+    /// The connection to the device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
     DeviceLost,
 
     /// The surface is no longer accessible and must be recreated.
@@ -107,6 +107,7 @@ use crate::device::Queue;
 use std::sync::Arc;
 
 pub use self::event::Event;
+pub use self::event::EventStatusError;
 pub use self::fence::Fence;
 pub use self::fence::FenceWaitError;
 pub use self::future::now;
@@ -119,12 +120,21 @@ pub use self::future::JoinFuture;
 pub use self::future::NowFuture;
 pub use self::future::SemaphoreSignalFuture;
 pub use self::pipeline::AccessFlags;
+pub use self::pipeline::AccessFlags2;
+pub use self::pipeline::BufferMemoryBarrier2;
+pub use self::pipeline::DependencyInfo;
+pub use self::pipeline::ImageMemoryBarrier2;
+pub use self::pipeline::MemoryBarrier2;
 pub use self::pipeline::PipelineMemoryAccess;
 pub use self::pipeline::PipelineStage;
 pub use self::pipeline::PipelineStages;
+pub use self::pipeline::PipelineStages2;
 pub use self::semaphore::ExternalSemaphoreHandleType;
 pub use self::semaphore::Semaphore;
 pub use self::semaphore::SemaphoreError;
+pub use self::semaphore::SemaphoreSignalError;
+pub use self::semaphore::SemaphoreWaitError;
+pub use self::semaphore::TimelineSemaphore;
 
 mod event;
 mod fence;
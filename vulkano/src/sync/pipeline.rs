@@ -7,7 +7,13 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use crate::buffer::BufferAccess;
+use crate::image::ImageAccess;
+use crate::image::ImageLayout;
+use crate::DeviceSize;
 use std::ops;
+use std::ops::Range;
+use std::sync::Arc;
 
 macro_rules! pipeline_stages {
     ($($elem:ident, $var:ident => $val:expr, $queue:expr;)+) => (
@@ -40,6 +46,18 @@ macro_rules! pipeline_stages {
             }
         }
 
+        // The flags of `VK_KHR_synchronization2` have the same values as their legacy equivalents.
+        impl From<PipelineStages> for ash::vk::PipelineStageFlags2KHR {
+            #[inline]
+            fn from(val: PipelineStages) -> Self {
+                let mut result = ash::vk::PipelineStageFlags2KHR::empty();
+                $(
+                    if val.$elem { result |= Self::from_raw($val.as_raw() as u64) }
+                )+
+                result
+            }
+        }
+
         impl ops::BitOr for PipelineStages {
             type Output = PipelineStages;
 
@@ -109,6 +127,9 @@ pipeline_stages! {
     all_graphics, AllGraphics => ash::vk::PipelineStageFlags::ALL_GRAPHICS, ash::vk::QueueFlags::GRAPHICS;
     all_commands, AllCommands => ash::vk::PipelineStageFlags::ALL_COMMANDS, ash::vk::QueueFlags::empty();
     ray_tracing_shader, RayTracingShader => ash::vk::PipelineStageFlags::RAY_TRACING_SHADER_KHR, ash::vk::QueueFlags::GRAPHICS | ash::vk::QueueFlags::COMPUTE | ash::vk::QueueFlags::TRANSFER;
+    acceleration_structure_build, AccelerationStructureBuild => ash::vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR, ash::vk::QueueFlags::COMPUTE;
+    conditional_rendering, ConditionalRendering => ash::vk::PipelineStageFlags::CONDITIONAL_RENDERING_EXT, ash::vk::QueueFlags::GRAPHICS | ash::vk::QueueFlags::COMPUTE;
+    transform_feedback, TransformFeedback => ash::vk::PipelineStageFlags::TRANSFORM_FEEDBACK_EXT, ash::vk::QueueFlags::GRAPHICS;
 }
 
 macro_rules! access_flags {
@@ -152,6 +173,18 @@ macro_rules! access_flags {
             }
         }
 
+        // The flags of `VK_KHR_synchronization2` have the same values as their legacy equivalents.
+        impl From<AccessFlags> for ash::vk::AccessFlags2KHR {
+            #[inline]
+            fn from(val: AccessFlags) -> Self {
+                let mut result = ash::vk::AccessFlags2KHR::empty();
+                $(
+                    if val.$elem { result |= Self::from_raw($val.as_raw() as u64) }
+                )+
+                result
+            }
+        }
+
         impl ops::BitOr for AccessFlags {
             type Output = AccessFlags;
 
@@ -194,6 +227,12 @@ access_flags! {
     host_write => ash::vk::AccessFlags::HOST_WRITE,
     memory_read => ash::vk::AccessFlags::MEMORY_READ,
     memory_write => ash::vk::AccessFlags::MEMORY_WRITE,
+    conditional_rendering_read => ash::vk::AccessFlags::CONDITIONAL_RENDERING_READ_EXT,
+    transform_feedback_write => ash::vk::AccessFlags::TRANSFORM_FEEDBACK_WRITE_EXT,
+    transform_feedback_counter_read => ash::vk::AccessFlags::TRANSFORM_FEEDBACK_COUNTER_READ_EXT,
+    transform_feedback_counter_write => ash::vk::AccessFlags::TRANSFORM_FEEDBACK_COUNTER_WRITE_EXT,
+    acceleration_structure_read => ash::vk::AccessFlags::ACCELERATION_STRUCTURE_READ_KHR,
+    acceleration_structure_write => ash::vk::AccessFlags::ACCELERATION_STRUCTURE_WRITE_KHR,
 }
 
 impl AccessFlags {
@@ -217,15 +256,21 @@ impl AccessFlags {
             return false;
         }
 
-        if (self.uniform_read || self.shader_read || self.shader_write)
-            && !stages.vertex_shader
-            && !stages.tessellation_control_shader
-            && !stages.tessellation_evaluation_shader
-            && !stages.geometry_shader
-            && !stages.fragment_shader
-            && !stages.compute_shader
-            && !stages.all_graphics
-        {
+        let any_shader = stages.vertex_shader
+            || stages.tessellation_control_shader
+            || stages.tessellation_evaluation_shader
+            || stages.geometry_shader
+            || stages.fragment_shader
+            || stages.compute_shader
+            || stages.ray_tracing_shader
+            || stages.all_graphics;
+
+        if (self.uniform_read || self.shader_write) && !any_shader {
+            return false;
+        }
+
+        // The geometry of an acceleration structure build is read with `shader_read`.
+        if self.shader_read && !any_shader && !stages.acceleration_structure_build {
             return false;
         }
 
@@ -256,6 +301,31 @@ impl AccessFlags {
             return false;
         }
 
+        if self.conditional_rendering_read && !stages.conditional_rendering {
+            return false;
+        }
+
+        if (self.transform_feedback_write || self.transform_feedback_counter_write)
+            && !stages.transform_feedback
+        {
+            return false;
+        }
+
+        if self.transform_feedback_counter_read
+            && !stages.transform_feedback
+            && !stages.draw_indirect
+        {
+            return false;
+        }
+
+        if self.acceleration_structure_read && !any_shader && !stages.acceleration_structure_build {
+            return false;
+        }
+
+        if self.acceleration_structure_write && !stages.acceleration_structure_build {
+            return false;
+        }
+
         true
     }
 }
@@ -270,3 +340,406 @@ pub struct PipelineMemoryAccess {
     /// Whether the resource needs exclusive (mutable) access or can be shared.
     pub exclusive: bool,
 }
+
+macro_rules! pipeline_stages2 {
+    ($($elem:ident => $val:expr,)+) => (
+        /// A set of pipeline stages, as used by the commands of the
+        /// [`synchronization2`](crate::device::Features::synchronization2) feature.
+        ///
+        /// In addition to the stages of [`PipelineStages`], this contains finer-grained stages
+        /// such as `copy` or `index_input`, which only exist with `synchronization2`.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        #[allow(missing_docs)]
+        pub struct PipelineStages2 {
+            $(
+                pub $elem: bool,
+            )+
+        }
+
+        impl PipelineStages2 {
+            /// Builds a `PipelineStages2` struct with none of the stages set.
+            pub fn none() -> PipelineStages2 {
+                PipelineStages2 {
+                    $(
+                        $elem: false,
+                    )+
+                }
+            }
+        }
+
+        impl From<PipelineStages2> for ash::vk::PipelineStageFlags2KHR {
+            #[inline]
+            fn from(val: PipelineStages2) -> Self {
+                let mut result = ash::vk::PipelineStageFlags2KHR::empty();
+                $(
+                    if val.$elem { result |= $val }
+                )+
+                result
+            }
+        }
+
+        impl From<ash::vk::PipelineStageFlags2KHR> for PipelineStages2 {
+            #[inline]
+            fn from(val: ash::vk::PipelineStageFlags2KHR) -> Self {
+                PipelineStages2 {
+                    $(
+                        $elem: val.intersects($val),
+                    )+
+                }
+            }
+        }
+
+        impl ops::BitOr for PipelineStages2 {
+            type Output = PipelineStages2;
+
+            #[inline]
+            fn bitor(self, rhs: PipelineStages2) -> PipelineStages2 {
+                PipelineStages2 {
+                    $(
+                        $elem: self.$elem || rhs.$elem,
+                    )+
+                }
+            }
+        }
+
+        impl ops::BitOrAssign for PipelineStages2 {
+            #[inline]
+            fn bitor_assign(&mut self, rhs: PipelineStages2) {
+                $(
+                    self.$elem = self.$elem || rhs.$elem;
+                )+
+            }
+        }
+    );
+}
+
+pipeline_stages2! {
+    top_of_pipe => ash::vk::PipelineStageFlags2KHR::TOP_OF_PIPE,
+    draw_indirect => ash::vk::PipelineStageFlags2KHR::DRAW_INDIRECT,
+    vertex_input => ash::vk::PipelineStageFlags2KHR::VERTEX_INPUT,
+    vertex_shader => ash::vk::PipelineStageFlags2KHR::VERTEX_SHADER,
+    tessellation_control_shader => ash::vk::PipelineStageFlags2KHR::TESSELLATION_CONTROL_SHADER,
+    tessellation_evaluation_shader => ash::vk::PipelineStageFlags2KHR::TESSELLATION_EVALUATION_SHADER,
+    geometry_shader => ash::vk::PipelineStageFlags2KHR::GEOMETRY_SHADER,
+    fragment_shader => ash::vk::PipelineStageFlags2KHR::FRAGMENT_SHADER,
+    early_fragment_tests => ash::vk::PipelineStageFlags2KHR::EARLY_FRAGMENT_TESTS,
+    late_fragment_tests => ash::vk::PipelineStageFlags2KHR::LATE_FRAGMENT_TESTS,
+    color_attachment_output => ash::vk::PipelineStageFlags2KHR::COLOR_ATTACHMENT_OUTPUT,
+    compute_shader => ash::vk::PipelineStageFlags2KHR::COMPUTE_SHADER,
+    all_transfer => ash::vk::PipelineStageFlags2KHR::ALL_TRANSFER,
+    bottom_of_pipe => ash::vk::PipelineStageFlags2KHR::BOTTOM_OF_PIPE,
+    host => ash::vk::PipelineStageFlags2KHR::HOST,
+    all_graphics => ash::vk::PipelineStageFlags2KHR::ALL_GRAPHICS,
+    all_commands => ash::vk::PipelineStageFlags2KHR::ALL_COMMANDS,
+    copy => ash::vk::PipelineStageFlags2KHR::COPY,
+    resolve => ash::vk::PipelineStageFlags2KHR::RESOLVE,
+    blit => ash::vk::PipelineStageFlags2KHR::BLIT,
+    clear => ash::vk::PipelineStageFlags2KHR::CLEAR,
+    index_input => ash::vk::PipelineStageFlags2KHR::INDEX_INPUT,
+    vertex_attribute_input => ash::vk::PipelineStageFlags2KHR::VERTEX_ATTRIBUTE_INPUT,
+    pre_rasterization_shaders => ash::vk::PipelineStageFlags2KHR::PRE_RASTERIZATION_SHADERS,
+    ray_tracing_shader => ash::vk::PipelineStageFlags2KHR::RAY_TRACING_SHADER,
+    acceleration_structure_build => ash::vk::PipelineStageFlags2KHR::ACCELERATION_STRUCTURE_BUILD,
+    fragment_shading_rate_attachment => ash::vk::PipelineStageFlags2KHR::FRAGMENT_SHADING_RATE_ATTACHMENT,
+    conditional_rendering => ash::vk::PipelineStageFlags2KHR::CONDITIONAL_RENDERING_EXT,
+    transform_feedback => ash::vk::PipelineStageFlags2KHR::TRANSFORM_FEEDBACK_EXT,
+}
+
+impl From<PipelineStages> for PipelineStages2 {
+    #[inline]
+    fn from(val: PipelineStages) -> Self {
+        ash::vk::PipelineStageFlags2KHR::from(val).into()
+    }
+}
+
+macro_rules! access_flags2 {
+    ($($elem:ident => $val:expr,)+) => (
+        /// A set of memory access types, as used by the commands of the
+        /// [`synchronization2`](crate::device::Features::synchronization2) feature.
+        ///
+        /// In addition to the accesses of [`AccessFlags`], this contains finer-grained accesses
+        /// such as `shader_sampled_read`, which only exist with `synchronization2`.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        #[allow(missing_docs)]
+        pub struct AccessFlags2 {
+            $(
+                pub $elem: bool,
+            )+
+        }
+
+        impl AccessFlags2 {
+            /// Builds an `AccessFlags2` struct with all bits set.
+            pub fn all() -> AccessFlags2 {
+                AccessFlags2 {
+                    $(
+                        $elem: true,
+                    )+
+                }
+            }
+
+            /// Builds an `AccessFlags2` struct with none of the bits set.
+            pub fn none() -> AccessFlags2 {
+                AccessFlags2 {
+                    $(
+                        $elem: false,
+                    )+
+                }
+            }
+        }
+
+        impl From<AccessFlags2> for ash::vk::AccessFlags2KHR {
+            #[inline]
+            fn from(val: AccessFlags2) -> Self {
+                let mut result = ash::vk::AccessFlags2KHR::empty();
+                $(
+                    if val.$elem { result |= $val }
+                )+
+                result
+            }
+        }
+
+        impl From<ash::vk::AccessFlags2KHR> for AccessFlags2 {
+            #[inline]
+            fn from(val: ash::vk::AccessFlags2KHR) -> Self {
+                AccessFlags2 {
+                    $(
+                        $elem: val.intersects($val),
+                    )+
+                }
+            }
+        }
+
+        impl ops::BitOr for AccessFlags2 {
+            type Output = AccessFlags2;
+
+            #[inline]
+            fn bitor(self, rhs: AccessFlags2) -> AccessFlags2 {
+                AccessFlags2 {
+                    $(
+                        $elem: self.$elem || rhs.$elem,
+                    )+
+                }
+            }
+        }
+
+        impl ops::BitOrAssign for AccessFlags2 {
+            #[inline]
+            fn bitor_assign(&mut self, rhs: AccessFlags2) {
+                $(
+                    self.$elem = self.$elem || rhs.$elem;
+                )+
+            }
+        }
+    );
+}
+
+access_flags2! {
+    indirect_command_read => ash::vk::AccessFlags2KHR::INDIRECT_COMMAND_READ,
+    index_read => ash::vk::AccessFlags2KHR::INDEX_READ,
+    vertex_attribute_read => ash::vk::AccessFlags2KHR::VERTEX_ATTRIBUTE_READ,
+    uniform_read => ash::vk::AccessFlags2KHR::UNIFORM_READ,
+    input_attachment_read => ash::vk::AccessFlags2KHR::INPUT_ATTACHMENT_READ,
+    shader_read => ash::vk::AccessFlags2KHR::SHADER_READ,
+    shader_write => ash::vk::AccessFlags2KHR::SHADER_WRITE,
+    color_attachment_read => ash::vk::AccessFlags2KHR::COLOR_ATTACHMENT_READ,
+    color_attachment_write => ash::vk::AccessFlags2KHR::COLOR_ATTACHMENT_WRITE,
+    depth_stencil_attachment_read => ash::vk::AccessFlags2KHR::DEPTH_STENCIL_ATTACHMENT_READ,
+    depth_stencil_attachment_write => ash::vk::AccessFlags2KHR::DEPTH_STENCIL_ATTACHMENT_WRITE,
+    transfer_read => ash::vk::AccessFlags2KHR::TRANSFER_READ,
+    transfer_write => ash::vk::AccessFlags2KHR::TRANSFER_WRITE,
+    host_read => ash::vk::AccessFlags2KHR::HOST_READ,
+    host_write => ash::vk::AccessFlags2KHR::HOST_WRITE,
+    memory_read => ash::vk::AccessFlags2KHR::MEMORY_READ,
+    memory_write => ash::vk::AccessFlags2KHR::MEMORY_WRITE,
+    shader_sampled_read => ash::vk::AccessFlags2KHR::SHADER_SAMPLED_READ,
+    shader_storage_read => ash::vk::AccessFlags2KHR::SHADER_STORAGE_READ,
+    shader_storage_write => ash::vk::AccessFlags2KHR::SHADER_STORAGE_WRITE,
+    acceleration_structure_read => ash::vk::AccessFlags2KHR::ACCELERATION_STRUCTURE_READ,
+    acceleration_structure_write => ash::vk::AccessFlags2KHR::ACCELERATION_STRUCTURE_WRITE,
+    fragment_shading_rate_attachment_read => ash::vk::AccessFlags2KHR::FRAGMENT_SHADING_RATE_ATTACHMENT_READ,
+    conditional_rendering_read => ash::vk::AccessFlags2KHR::CONDITIONAL_RENDERING_READ_EXT,
+    transform_feedback_write => ash::vk::AccessFlags2KHR::TRANSFORM_FEEDBACK_WRITE_EXT,
+    transform_feedback_counter_read => ash::vk::AccessFlags2KHR::TRANSFORM_FEEDBACK_COUNTER_READ_EXT,
+    transform_feedback_counter_write => ash::vk::AccessFlags2KHR::TRANSFORM_FEEDBACK_COUNTER_WRITE_EXT,
+}
+
+impl From<AccessFlags> for AccessFlags2 {
+    #[inline]
+    fn from(val: AccessFlags) -> Self {
+        ash::vk::AccessFlags2KHR::from(val).into()
+    }
+}
+
+/// The dependencies of a [`pipeline_barrier2`], [`set_event2`] or [`wait_events2`] command.
+///
+/// Unlike the legacy commands, each barrier has its own source and destination stages.
+///
+/// [`pipeline_barrier2`]: crate::command_buffer::AutoCommandBufferBuilder::pipeline_barrier2
+/// [`set_event2`]: crate::command_buffer::AutoCommandBufferBuilder::set_event2
+/// [`wait_events2`]: crate::command_buffer::AutoCommandBufferBuilder::wait_events2
+#[derive(Clone, Default)]
+pub struct DependencyInfo {
+    /// Barriers that apply to all memory.
+    ///
+    /// The default value is empty.
+    pub memory_barriers: Vec<MemoryBarrier2>,
+
+    /// Barriers that apply to a range of a buffer.
+    ///
+    /// The default value is empty.
+    pub buffer_memory_barriers: Vec<BufferMemoryBarrier2>,
+
+    /// Barriers that apply to a subresource range of an image.
+    ///
+    /// The default value is empty.
+    pub image_memory_barriers: Vec<ImageMemoryBarrier2>,
+
+    /// If true, the dependencies are framebuffer-local.
+    ///
+    /// The default value is `false`.
+    pub by_region: bool,
+}
+
+/// A memory barrier that applies to all memory.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryBarrier2 {
+    /// The stages of the previous commands that must complete before the barrier.
+    ///
+    /// The default value is [`PipelineStages2::none()`].
+    pub source_stages: PipelineStages2,
+
+    /// The memory accesses of `source_stages` that are made available.
+    ///
+    /// The default value is [`AccessFlags2::none()`].
+    pub source_access: AccessFlags2,
+
+    /// The stages of the following commands that wait for the barrier.
+    ///
+    /// The default value is [`PipelineStages2::none()`].
+    pub destination_stages: PipelineStages2,
+
+    /// The memory accesses of `destination_stages` that the memory is made visible to.
+    ///
+    /// The default value is [`AccessFlags2::none()`].
+    pub destination_access: AccessFlags2,
+}
+
+impl Default for MemoryBarrier2 {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            source_stages: PipelineStages2::none(),
+            source_access: AccessFlags2::none(),
+            destination_stages: PipelineStages2::none(),
+            destination_access: AccessFlags2::none(),
+        }
+    }
+}
+
+/// A memory barrier that applies to a range of a buffer.
+#[derive(Clone)]
+pub struct BufferMemoryBarrier2 {
+    /// The stages of the previous commands that must complete before the barrier.
+    pub source_stages: PipelineStages2,
+
+    /// The memory accesses of `source_stages` that are made available.
+    pub source_access: AccessFlags2,
+
+    /// The stages of the following commands that wait for the barrier.
+    pub destination_stages: PipelineStages2,
+
+    /// The memory accesses of `destination_stages` that the memory is made visible to.
+    pub destination_access: AccessFlags2,
+
+    /// The source and destination queue family indices, if the barrier transfers the ownership
+    /// of the buffer between queue families.
+    pub queue_family_transfer: Option<(u32, u32)>,
+
+    /// The buffer that the barrier applies to.
+    pub buffer: Arc<dyn BufferAccess>,
+
+    /// The range of bytes of `buffer` that the barrier applies to.
+    pub range: Range<DeviceSize>,
+}
+
+impl BufferMemoryBarrier2 {
+    /// Returns a `BufferMemoryBarrier2` for the whole of `buffer`, without any stages, accesses
+    /// or queue family transfer.
+    #[inline]
+    pub fn buffer(buffer: Arc<dyn BufferAccess>) -> Self {
+        let range = 0..buffer.size();
+
+        Self {
+            source_stages: PipelineStages2::none(),
+            source_access: AccessFlags2::none(),
+            destination_stages: PipelineStages2::none(),
+            destination_access: AccessFlags2::none(),
+            queue_family_transfer: None,
+            buffer,
+            range,
+        }
+    }
+}
+
+/// A memory barrier that applies to a subresource range of an image.
+#[derive(Clone)]
+pub struct ImageMemoryBarrier2 {
+    /// The stages of the previous commands that must complete before the barrier.
+    pub source_stages: PipelineStages2,
+
+    /// The memory accesses of `source_stages` that are made available.
+    pub source_access: AccessFlags2,
+
+    /// The stages of the following commands that wait for the barrier.
+    pub destination_stages: PipelineStages2,
+
+    /// The memory accesses of `destination_stages` that the memory is made visible to.
+    pub destination_access: AccessFlags2,
+
+    /// The layout that the image is in before the barrier.
+    pub old_layout: ImageLayout,
+
+    /// The layout that the image is transitioned to by the barrier. If it is equal to
+    /// `old_layout`, no layout transition happens.
+    pub new_layout: ImageLayout,
+
+    /// The source and destination queue family indices, if the barrier transfers the ownership
+    /// of the image between queue families.
+    pub queue_family_transfer: Option<(u32, u32)>,
+
+    /// The image that the barrier applies to.
+    pub image: Arc<dyn ImageAccess>,
+
+    /// The mip levels of `image` that the barrier applies to.
+    pub mip_levels: Range<u32>,
+
+    /// The array layers of `image` that the barrier applies to.
+    pub array_layers: Range<u32>,
+}
+
+impl ImageMemoryBarrier2 {
+    /// Returns an `ImageMemoryBarrier2` for all the mip levels and array layers of `image`,
+    /// without any stages, accesses or queue family transfer.
+    ///
+    /// Both `old_layout` and `new_layout` are set to the initial layout requirement of the image,
+    /// so that no layout transition happens.
+    #[inline]
+    pub fn image(image: Arc<dyn ImageAccess>) -> Self {
+        let layout = image.initial_layout_requirement();
+        let mip_levels = 0..image.mip_levels();
+        let array_layers = 0..image.dimensions().array_layers();
+
+        Self {
+            source_stages: PipelineStages2::none(),
+            source_access: AccessFlags2::none(),
+            destination_stages: PipelineStages2::none(),
+            destination_access: AccessFlags2::none(),
+            old_layout: layout,
+            new_layout: layout,
+            queue_family_transfer: None,
+            image,
+            mip_levels,
+            array_layers,
+        }
+    }
+}
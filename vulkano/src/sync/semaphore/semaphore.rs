@@ -13,6 +13,7 @@ use crate::device::DeviceOwned;
 use crate::Error;
 use crate::OomError;
 use crate::SafeDeref;
+use crate::Version;
 use crate::VulkanObject;
 use std::fmt;
 #[cfg(any(
@@ -31,7 +32,7 @@ use std::mem::MaybeUninit;
     target_os = "netbsd",
     target_os = "openbsd"
 ))]
-use std::os::unix::io::FromRawFd;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
 use std::ptr;
 use std::sync::Arc;
 
@@ -49,10 +50,10 @@ where
     semaphore: ash::vk::Semaphore,
     device: D,
     must_put_in_pool: bool,
+    export_handle_types: ash::vk::ExternalSemaphoreHandleTypeFlags,
 }
 
 // TODO: Add support for VkExportSemaphoreWin32HandleInfoKHR
-// TODO: Add suport for importable semaphores
 pub struct SemaphoreBuilder<'a, D = Arc<Device>>
 where
     D: SafeDeref<Target = Device>,
@@ -111,6 +112,11 @@ where
             Err(SemaphoreError::MissingExtension(
                 "khr_external_semaphore_capabilities",
             ))
+        } else if self.export_info.is_some()
+            && !(self.device.api_version() >= Version::V1_1
+                || self.device.enabled_extensions().khr_external_semaphore)
+        {
+            Err(SemaphoreError::MissingExtension("khr_external_semaphore"))
         } else {
             let mut builder = self.create_builder;
             if let Some(export_info) = self.export_info.as_mut() {
@@ -133,6 +139,11 @@ where
                 device: self.device,
                 semaphore,
                 must_put_in_pool: self.must_put_in_pool,
+                export_handle_types: self
+                    .export_info
+                    .map_or(ash::vk::ExternalSemaphoreHandleTypeFlags::empty(), |info| {
+                        info.handle_types
+                    }),
             })
         }
     }
@@ -155,6 +166,8 @@ where
                 device,
                 semaphore: raw_sem,
                 must_put_in_pool: true,
+                // Semaphores of the pool are never created with export info.
+                export_handle_types: ash::vk::ExternalSemaphoreHandleTypeFlags::empty(),
             }),
             None => {
                 // Pool is empty, alloc new semaphore
@@ -184,6 +197,10 @@ where
             .build()
     }
 
+    /// Exports the semaphore into an opaque Unix file descriptor.
+    ///
+    /// The semaphore must have been created with `opaque_fd` in its export info.
+    #[inline]
     #[cfg(any(
         target_os = "linux",
         target_os = "dragonflybsd",
@@ -192,15 +209,51 @@ where
         target_os = "openbsd"
     ))]
     pub fn export_opaque_fd(&self) -> Result<File, SemaphoreError> {
-        let fns = self.device.fns();
+        self.export_fd(ExternalSemaphoreHandleType::posix())
+    }
+
+    /// Exports the semaphore into a Unix file descriptor of the given handle type. The caller
+    /// takes ownership of the returned file.
+    ///
+    /// The semaphore must have been created with `handle_type` in its export info. For
+    /// `sync_fd`, the semaphore must also be signaled, or have a signal operation pending.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` does not contain exactly one of `opaque_fd` and `sync_fd`.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonflybsd",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub fn export_fd(
+        &self,
+        handle_type: ExternalSemaphoreHandleType,
+    ) -> Result<File, SemaphoreError> {
+        let handle_type = ash::vk::ExternalSemaphoreHandleTypeFlags::from(handle_type);
+        assert!(
+            handle_type == ash::vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD
+                || handle_type == ash::vk::ExternalSemaphoreHandleTypeFlags::SYNC_FD
+        );
+
+        if !self.device.enabled_extensions().khr_external_semaphore_fd {
+            return Err(SemaphoreError::MissingExtension(
+                "khr_external_semaphore_fd",
+            ));
+        }
+
+        if !self.export_handle_types.contains(handle_type) {
+            return Err(SemaphoreError::HandleTypeNotExportable);
+        }
 
-        assert!(self.device.enabled_extensions().khr_external_semaphore);
-        assert!(self.device.enabled_extensions().khr_external_semaphore_fd);
+        let fns = self.device.fns();
 
         let fd = unsafe {
             let info = ash::vk::SemaphoreGetFdInfoKHR {
                 semaphore: self.semaphore,
-                handle_type: ash::vk::ExternalSemaphoreHandleTypeFlagsKHR::OPAQUE_FD,
+                handle_type,
                 ..Default::default()
             };
 
@@ -215,6 +268,75 @@ where
         let file = unsafe { File::from_raw_fd(fd) };
         Ok(file)
     }
+
+    /// Imports a payload from a Unix file descriptor of the given handle type into the
+    /// semaphore. On success, the implementation takes ownership of `file`.
+    ///
+    /// If `temporary` is true, the imported payload only replaces the semaphore's own payload
+    /// until the next wait operation on the semaphore. Importing a `sync_fd` is always temporary.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` does not contain exactly one of `opaque_fd` and `sync_fd`.
+    ///
+    /// # Safety
+    ///
+    /// - The semaphore must not be in use by any pending queue operation.
+    /// - `file` must be a valid exported payload of `handle_type`, compatible with this
+    ///   semaphore's device.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonflybsd",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub unsafe fn import_fd(
+        &self,
+        handle_type: ExternalSemaphoreHandleType,
+        file: File,
+        temporary: bool,
+    ) -> Result<(), SemaphoreError> {
+        let handle_type = ash::vk::ExternalSemaphoreHandleTypeFlags::from(handle_type);
+        assert!(
+            handle_type == ash::vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD
+                || handle_type == ash::vk::ExternalSemaphoreHandleTypeFlags::SYNC_FD
+        );
+
+        if !self.device.enabled_extensions().khr_external_semaphore_fd {
+            return Err(SemaphoreError::MissingExtension(
+                "khr_external_semaphore_fd",
+            ));
+        }
+
+        let flags =
+            if temporary || handle_type == ash::vk::ExternalSemaphoreHandleTypeFlags::SYNC_FD {
+                ash::vk::SemaphoreImportFlags::TEMPORARY
+            } else {
+                ash::vk::SemaphoreImportFlags::empty()
+            };
+
+        let fd = file.into_raw_fd();
+        let info = ash::vk::ImportSemaphoreFdInfoKHR {
+            semaphore: self.semaphore,
+            flags,
+            handle_type,
+            fd,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        if let Err(err) = check_errors(
+            fns.khr_external_semaphore_fd
+                .import_semaphore_fd_khr(self.device.internal_object(), &info),
+        ) {
+            // Ownership of the file descriptor is only transferred on success.
+            drop(File::from_raw_fd(fd));
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
 }
 
 unsafe impl DeviceOwned for Semaphore {
@@ -264,6 +386,15 @@ pub enum SemaphoreError {
     OomError(OomError),
     /// An extensions is missing.
     MissingExtension(&'static str),
+    /// A feature is missing.
+    MissingFeature(&'static str),
+    /// The external handle to import was not valid.
+    InvalidExternalHandle,
+    /// The semaphore was not created with the requested handle type in its export info.
+    HandleTypeNotExportable,
+    /// The device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
+    DeviceLost,
 }
 
 impl fmt::Display for SemaphoreError {
@@ -273,6 +404,17 @@ impl fmt::Display for SemaphoreError {
             SemaphoreError::MissingExtension(s) => {
                 write!(fmt, "Missing the following extension: {}", s)
             }
+            SemaphoreError::MissingFeature(s) => {
+                write!(fmt, "Missing the following feature: {}", s)
+            }
+            SemaphoreError::InvalidExternalHandle => {
+                write!(fmt, "the external handle to import was not valid")
+            }
+            SemaphoreError::HandleTypeNotExportable => write!(
+                fmt,
+                "the semaphore was not created with the requested handle type in its export info"
+            ),
+            SemaphoreError::DeviceLost => write!(fmt, "the device was lost"),
         }
     }
 }
@@ -284,6 +426,8 @@ impl From<Error> for SemaphoreError {
             e @ Error::OutOfHostMemory | e @ Error::OutOfDeviceMemory => {
                 SemaphoreError::OomError(e.into())
             }
+            Error::InvalidExternalHandle => SemaphoreError::InvalidExternalHandle,
+            Error::DeviceLost => SemaphoreError::DeviceLost,
             _ => panic!("unexpected error: {:?}", err),
         }
     }
@@ -334,6 +478,49 @@ mod tests {
         assert_eq!(sem2.internal_object(), sem1_internal_obj);
     }
 
+    #[test]
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonflybsd",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    fn semaphore_export_fd_missing_extension() {
+        use crate::sync::ExternalSemaphoreHandleType;
+        use crate::sync::SemaphoreError;
+
+        let (device, _) = gfx_dev_and_queue!();
+        let sem = Semaphore::alloc(device).unwrap();
+
+        match sem.export_fd(ExternalSemaphoreHandleType::posix()) {
+            Err(SemaphoreError::MissingExtension("khr_external_semaphore_fd")) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonflybsd",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    fn semaphore_export_fd_not_exportable() {
+        use crate::sync::ExternalSemaphoreHandleType;
+        use crate::sync::SemaphoreError;
+
+        let (device, _) =
+            gfx_dev_and_queue_with!([khr_external_semaphore, khr_external_semaphore_fd], []);
+        let sem = Semaphore::alloc(device).unwrap();
+
+        match sem.export_fd(ExternalSemaphoreHandleType::posix()) {
+            Err(SemaphoreError::HandleTypeNotExportable) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     #[cfg(any(
         target_os = "linux",
@@ -0,0 +1,400 @@
+// Copyright (c) 2021 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::device::Device;
+use crate::device::DeviceOwned;
+use crate::sync::semaphore::SemaphoreError;
+use crate::Error;
+use crate::OomError;
+use crate::Success;
+use crate::Version;
+use crate::VulkanObject;
+use std::error;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A semaphore whose state is a monotonically increasing 64-bit counter.
+///
+/// Unlike a binary `Semaphore`, a timeline semaphore can be signaled and waited upon from both
+/// the GPU and the CPU. A wait operation for a value completes once the counter is greater than
+/// or equal to that value, so the same semaphore can be waited upon any number of times.
+///
+/// Using timeline semaphores requires the `timeline_semaphore` feature to be enabled on the
+/// device.
+#[derive(Debug)]
+pub struct TimelineSemaphore {
+    semaphore: ash::vk::Semaphore,
+    device: Arc<Device>,
+}
+
+impl TimelineSemaphore {
+    /// Builds a new timeline semaphore, whose counter starts at `initial_value`.
+    pub fn new(
+        device: Arc<Device>,
+        initial_value: u64,
+    ) -> Result<TimelineSemaphore, SemaphoreError> {
+        if !device.enabled_features().timeline_semaphore {
+            return Err(SemaphoreError::MissingFeature("timeline_semaphore"));
+        }
+
+        let semaphore = unsafe {
+            let type_infos = ash::vk::SemaphoreTypeCreateInfo {
+                semaphore_type: ash::vk::SemaphoreType::TIMELINE,
+                initial_value,
+                ..Default::default()
+            };
+
+            let infos = ash::vk::SemaphoreCreateInfo {
+                p_next: &type_infos as *const _ as *const _,
+                flags: ash::vk::SemaphoreCreateFlags::empty(),
+                ..Default::default()
+            };
+
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            device.check_errors(fns.v1_0.create_semaphore(
+                device.internal_object(),
+                &infos,
+                ptr::null(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(TimelineSemaphore { semaphore, device })
+    }
+
+    /// Returns the current value of the counter.
+    #[inline]
+    pub fn value(&self) -> Result<u64, SemaphoreError> {
+        Ok(self.counter_value()?)
+    }
+
+    fn counter_value(&self) -> Result<u64, Error> {
+        unsafe {
+            let fns = self.device.fns();
+            let mut output = MaybeUninit::uninit();
+
+            if self.device.api_version() >= Version::V1_2 {
+                self.device
+                    .check_errors(fns.v1_2.get_semaphore_counter_value(
+                        self.device.internal_object(),
+                        self.semaphore,
+                        output.as_mut_ptr(),
+                    ))?;
+            } else {
+                self.device.check_errors(
+                    fns.khr_timeline_semaphore.get_semaphore_counter_value_khr(
+                        self.device.internal_object(),
+                        self.semaphore,
+                        output.as_mut_ptr(),
+                    ),
+                )?;
+            }
+
+            Ok(output.assume_init())
+        }
+    }
+
+    /// Sets the counter to `value` from the CPU.
+    ///
+    /// `value` must be greater than the current value of the counter, and the difference must
+    /// not exceed the
+    /// [`max_timeline_semaphore_value_difference`](crate::device::Properties::max_timeline_semaphore_value_difference)
+    /// property.
+    pub fn signal(&self, value: u64) -> Result<(), SemaphoreSignalError> {
+        let current = self.counter_value()?;
+
+        if value <= current {
+            return Err(SemaphoreSignalError::ValueNotGreater { value, current });
+        }
+
+        let max = self
+            .device
+            .physical_device()
+            .properties()
+            .max_timeline_semaphore_value_difference
+            .unwrap();
+
+        if value - current > max {
+            return Err(SemaphoreSignalError::ValueDifferenceTooLarge {
+                difference: value - current,
+                max,
+            });
+        }
+
+        unsafe {
+            let infos = ash::vk::SemaphoreSignalInfo {
+                semaphore: self.semaphore,
+                value,
+                ..Default::default()
+            };
+
+            let fns = self.device.fns();
+
+            if self.device.api_version() >= Version::V1_2 {
+                self.device.check_errors(
+                    fns.v1_2
+                        .signal_semaphore(self.device.internal_object(), &infos),
+                )?;
+            } else {
+                self.device.check_errors(
+                    fns.khr_timeline_semaphore
+                        .signal_semaphore_khr(self.device.internal_object(), &infos),
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Waits until the counter is greater than or equal to `value`, or at least until the
+    /// timeout duration has elapsed.
+    ///
+    /// Returns `Ok` if the counter has reached `value`. Returns `Err` if the timeout was reached
+    /// instead.
+    ///
+    /// If you pass a duration of 0, then the function will return without blocking.
+    pub fn wait(&self, value: u64, timeout: Option<Duration>) -> Result<(), SemaphoreWaitError> {
+        unsafe {
+            let timeout_ns = if let Some(timeout) = timeout {
+                timeout
+                    .as_secs()
+                    .saturating_mul(1_000_000_000)
+                    .saturating_add(timeout.subsec_nanos() as u64)
+            } else {
+                u64::MAX
+            };
+
+            let infos = ash::vk::SemaphoreWaitInfo {
+                flags: ash::vk::SemaphoreWaitFlags::empty(),
+                semaphore_count: 1,
+                p_semaphores: &self.semaphore,
+                p_values: &value,
+                ..Default::default()
+            };
+
+            let fns = self.device.fns();
+
+            let r = if self.device.api_version() >= Version::V1_2 {
+                self.device.check_errors(fns.v1_2.wait_semaphores(
+                    self.device.internal_object(),
+                    &infos,
+                    timeout_ns,
+                ))?
+            } else {
+                self.device
+                    .check_errors(fns.khr_timeline_semaphore.wait_semaphores_khr(
+                        self.device.internal_object(),
+                        &infos,
+                        timeout_ns,
+                    ))?
+            };
+
+            match r {
+                Success::Success => Ok(()),
+                Success::Timeout => Err(SemaphoreWaitError::Timeout),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+unsafe impl DeviceOwned for TimelineSemaphore {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for TimelineSemaphore {
+    type Object = ash::vk::Semaphore;
+
+    #[inline]
+    fn internal_object(&self) -> ash::vk::Semaphore {
+        self.semaphore
+    }
+}
+
+impl Drop for TimelineSemaphore {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            fns.v1_0
+                .destroy_semaphore(self.device.internal_object(), self.semaphore, ptr::null());
+        }
+    }
+}
+
+/// Error that can be returned when signaling a timeline semaphore from the CPU.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SemaphoreSignalError {
+    /// Not enough memory to signal the semaphore.
+    OomError(OomError),
+
+    /// The device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
+    DeviceLost,
+
+    /// The value is not greater than the current value of the counter.
+    ValueNotGreater { value: u64, current: u64 },
+
+    /// The difference between the value and the current value of the counter is greater than
+    /// the `max_timeline_semaphore_value_difference` property.
+    ValueDifferenceTooLarge { difference: u64, max: u64 },
+}
+
+impl error::Error for SemaphoreSignalError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            SemaphoreSignalError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SemaphoreSignalError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            SemaphoreSignalError::OomError(_) => write!(fmt, "no memory available"),
+            SemaphoreSignalError::DeviceLost => write!(fmt, "the device was lost"),
+            SemaphoreSignalError::ValueNotGreater { value, current } => write!(
+                fmt,
+                "the value {} is not greater than the current value {} of the counter",
+                value, current,
+            ),
+            SemaphoreSignalError::ValueDifferenceTooLarge { difference, max } => write!(
+                fmt,
+                "the value is {} greater than the current value of the counter, but the maximum \
+                difference is {}",
+                difference, max,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for SemaphoreSignalError {
+    #[inline]
+    fn from(err: OomError) -> SemaphoreSignalError {
+        SemaphoreSignalError::OomError(err)
+    }
+}
+
+impl From<Error> for SemaphoreSignalError {
+    #[inline]
+    fn from(err: Error) -> SemaphoreSignalError {
+        match err {
+            Error::OutOfHostMemory => SemaphoreSignalError::OomError(From::from(err)),
+            Error::OutOfDeviceMemory => SemaphoreSignalError::OomError(From::from(err)),
+            Error::DeviceLost => SemaphoreSignalError::DeviceLost,
+            _ => panic!("Unexpected error value: {}", err as i32),
+        }
+    }
+}
+
+/// Error that can be returned when waiting on a timeline semaphore.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SemaphoreWaitError {
+    /// Not enough memory to complete the wait.
+    OomError(OomError),
+
+    /// The specified timeout wasn't long enough.
+    Timeout,
+
+    /// The device has been lost. See
+    /// [`Device::is_lost`](crate::device::Device::is_lost).
+    DeviceLostError,
+}
+
+impl error::Error for SemaphoreWaitError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            SemaphoreWaitError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SemaphoreWaitError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                SemaphoreWaitError::OomError(_) => "no memory available",
+                SemaphoreWaitError::Timeout => "the timeout has been reached",
+                SemaphoreWaitError::DeviceLostError => "the device was lost",
+            }
+        )
+    }
+}
+
+impl From<Error> for SemaphoreWaitError {
+    #[inline]
+    fn from(err: Error) -> SemaphoreWaitError {
+        match err {
+            Error::OutOfHostMemory => SemaphoreWaitError::OomError(From::from(err)),
+            Error::OutOfDeviceMemory => SemaphoreWaitError::OomError(From::from(err)),
+            Error::DeviceLost => SemaphoreWaitError::DeviceLostError,
+            _ => panic!("Unexpected error value: {}", err as i32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sync::SemaphoreError;
+    use crate::sync::SemaphoreSignalError;
+    use crate::sync::TimelineSemaphore;
+    use std::time::Duration;
+
+    #[test]
+    fn timeline_semaphore_missing_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match TimelineSemaphore::new(device, 0) {
+            Err(SemaphoreError::MissingFeature("timeline_semaphore")) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn timeline_semaphore_signal_wait() {
+        let (device, _) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let semaphore = TimelineSemaphore::new(device, 1).unwrap();
+        assert_eq!(semaphore.value().unwrap(), 1);
+        semaphore.signal(5).unwrap();
+        assert_eq!(semaphore.value().unwrap(), 5);
+        semaphore.wait(3, Some(Duration::from_secs(0))).unwrap();
+    }
+
+    #[test]
+    fn timeline_semaphore_signal_not_greater() {
+        let (device, _) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let semaphore = TimelineSemaphore::new(device, 5).unwrap();
+
+        match semaphore.signal(5) {
+            Err(SemaphoreSignalError::ValueNotGreater {
+                value: 5,
+                current: 5,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -72,6 +72,61 @@ macro_rules! gfx_dev_and_queue {
     });
 }
 
+/// Creates a device and a queue for graphics operations, with the given extensions and
+/// features enabled, or returns if they are not supported.
+macro_rules! gfx_dev_and_queue_with {
+    ([$($extension:ident),*], [$($feature:ident),*]) => {{
+        use crate::device::physical::PhysicalDevice;
+        use crate::device::Device;
+        use crate::device::DeviceExtensions;
+        use crate::device::Features;
+
+        let instance = instance!();
+
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let queue_family = match physical.queue_families().find(|q| q.supports_graphics()) {
+            Some(q) => q,
+            None => return,
+        };
+
+        let extensions = DeviceExtensions {
+            $(
+                $extension: true,
+            )*
+            ..DeviceExtensions::none()
+        };
+
+        let features = Features {
+            $(
+                $feature: true,
+            )*
+            ..Features::none()
+        };
+
+        if !physical.supported_extensions().is_superset_of(&extensions)
+            || !physical.supported_features().is_superset_of(&features)
+        {
+            return;
+        }
+
+        let (device, mut queues) = match Device::new(
+            physical,
+            &features,
+            &extensions,
+            std::iter::once((queue_family, 0.5)),
+        ) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+
+        (device, queues.next().unwrap())
+    }};
+}
+
 macro_rules! assert_should_panic {
     ($msg:expr, $code:block) => {{
         let res = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $code));